@@ -1,6 +1,7 @@
 //! https://tc39.es/ecma262/#sec-abstract-operations
 
 pub mod operations_on_bjects;
+pub mod operations_on_iterator_objects;
 pub mod ordinary_object_internal_methods_and_internal_slots;
 pub mod testing_and_comparison_operations;
 pub mod type_conversion;
@@ -1,20 +1,799 @@
 use crate::{
-  language_types::{undefined::JsUndefined, Value},
-  specification_types::completion_record::Completion,
+  agent::current_realm,
+  executable_code_and_execution_contexts::realm::Realm,
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  language_types::{
+    boolean::JsBoolean,
+    object::{InternalSlot, JsObject, PropertyKey},
+    symbol::JsSymbol,
+    Value,
+  },
+  ordinary_and_exotic_objects_behaviours::array_exotic_objects::array_create,
+  specification_types::property_descriptor::PropertyDescriptor,
+  text_processing::string_objects::string_get_own_property,
 };
 
+/// https://tc39.es/ecma262/#sec-get-o-p
+pub fn get(o: &JsObject, p: &PropertyKey) -> Result<Value, Value> {
+  // 1. Assert: IsPropertyKey(P) is true.
+  // 2. Return ? O.[[Get]](P, O).
+  o.get(p, &Value::from(o.clone()))
+}
+
+/// https://tc39.es/ecma262/#sec-set-o-p-v-throw
+pub fn set(
+  o: &JsObject,
+  p: &PropertyKey,
+  v: Value,
+  throw: bool,
+) -> Result<(), Value> {
+  // 1. Let success be ? O.[[Set]](P, V, O).
+  let success = o.set(p, v, &Value::from(o.clone()))?;
+  // 2. If success is false and Throw is true, throw a TypeError exception.
+  if !success && throw {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      "Cannot assign to read only property",
+    ));
+  }
+  // 3. Return unused.
+  Ok(())
+}
+
+/// https://tc39.es/ecma262/#sec-getv
+pub fn get_v(v: &Value, p: &PropertyKey) -> Result<Value, Value> {
+  // 1. Assert: IsPropertyKey(P) is true.
+  // 2. Let O be ? ToObject(V).
+  // 3. Return ? O.[[Get]](P, V).
+  // The wrapper object isn't observable, so a String primitive answers its
+  // own properties directly and defers the rest to %String.prototype%.
+  if let Value::String(s) = v {
+    if let PropertyKey::String(key) = p {
+      if key == "length" {
+        return Ok(Value::from(s.len() as f64));
+      }
+    }
+    if let Some(desc) = string_get_own_property(s, p) {
+      return Ok(desc.value.unwrap_or_else(Value::undefined));
+    }
+    return current_realm().intrinsic("String.prototype").get(p, v);
+  }
+  let o = v.to_object()?;
+  o.get(p, v)
+}
+
+/// https://tc39.es/ecma262/#sec-createdataproperty
+pub fn create_data_property(
+  o: &JsObject,
+  p: &PropertyKey,
+  v: Value,
+) -> Result<bool, Value> {
+  // 1. Let newDesc be the PropertyDescriptor { [[Value]]: V, [[Writable]]: true,
+  //    [[Enumerable]]: true, [[Configurable]]: true }.
+  let new_desc = PropertyDescriptor {
+    value: Some(v),
+    writable: Some(JsBoolean::True),
+    get: None,
+    set: None,
+    enumerable: Some(JsBoolean::True),
+    configurable: Some(JsBoolean::True),
+  };
+  // 2. Return ? O.[[DefineOwnProperty]](P, newDesc).
+  o.define_own_property(p, new_desc)
+}
+
+/// https://tc39.es/ecma262/#sec-createdatapropertyorthrow
+pub fn create_data_property_or_throw(
+  o: &JsObject,
+  p: &PropertyKey,
+  v: Value,
+) -> Result<(), Value> {
+  // 1. Let success be ? CreateDataProperty(O, P, V).
+  // 2. If success is false, throw a TypeError exception.
+  if !create_data_property(o, p, v)? {
+    return Err(throw_error(ErrorKind::TypeError, "Cannot define property"));
+  }
+  // 3. Return unused.
+  Ok(())
+}
+
+/// https://tc39.es/ecma262/#sec-definepropertyorthrow
+pub fn define_property_or_throw(
+  o: &JsObject,
+  p: &PropertyKey,
+  desc: PropertyDescriptor,
+) -> Result<(), Value> {
+  // 1. Let success be ? O.[[DefineOwnProperty]](P, desc).
+  // 2. If success is false, throw a TypeError exception.
+  // 3. Return unused.
+  if o.define_own_property(p, desc)? {
+    Ok(())
+  } else {
+    Err(throw_error(ErrorKind::TypeError, "Cannot define property"))
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-createnonenumerabledatapropertyorthrow
+pub fn create_non_enumerable_data_property_or_throw(
+  o: &JsObject,
+  p: &PropertyKey,
+  v: Value,
+) {
+  // 1. Assert: O is an ordinary, extensible object with no non-configurable properties.
+  // 2. Let newDesc be the PropertyDescriptor { [[Value]]: V, [[Writable]]: true,
+  //    [[Enumerable]]: false, [[Configurable]]: true }.
+  let new_desc = PropertyDescriptor {
+    value: Some(v),
+    writable: Some(JsBoolean::True),
+    get: None,
+    set: None,
+    enumerable: Some(JsBoolean::False),
+    configurable: Some(JsBoolean::True),
+  };
+  // 3. Perform ! DefinePropertyOrThrow(O, P, newDesc).
+  define_property_or_throw(o, p, new_desc)
+    .expect("O is an ordinary extensible object");
+  // 4. Return unused.
+}
+
+/// https://tc39.es/ecma262/#sec-deletepropertyorthrow
+pub fn delete_property_or_throw(
+  o: &JsObject,
+  p: &PropertyKey,
+) -> Result<(), Value> {
+  // 1. Let success be ? O.[[Delete]](P).
+  // 2. If success is false, throw a TypeError exception.
+  // 3. Return unused.
+  if o.delete(p)? {
+    Ok(())
+  } else {
+    Err(throw_error(
+      ErrorKind::TypeError,
+      "Cannot delete a non-configurable property",
+    ))
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-hasproperty
+pub fn has_property(o: &JsObject, p: &PropertyKey) -> Result<bool, Value> {
+  // 1. Return ? O.[[HasProperty]](P).
+  o.has_property(p)
+}
+
+/// https://tc39.es/ecma262/#sec-hasownproperty
+pub fn has_own_property(o: &JsObject, p: &PropertyKey) -> Result<bool, Value> {
+  // 1. Let desc be ? O.[[GetOwnProperty]](P).
+  // 2. If desc is undefined, return false.
+  // 3. Return true.
+  Ok(o.get_own_property(p)?.is_some())
+}
+
+/// https://tc39.es/ecma262/#sec-getmethod
+pub fn get_method(v: &Value, p: &PropertyKey) -> Result<Option<Value>, Value> {
+  // 1. Let func be ? GetV(V, P).
+  match get_v(v, p)? {
+    // 2. If func is either undefined or null, return undefined.
+    Value::Undefined(_) | Value::Null(_) => Ok(None),
+    // 3. If IsCallable(func) is false, throw a TypeError exception.
+    func if !func.is_callable() => Err(throw_error(
+      ErrorKind::TypeError,
+      &format!("{} is not a function", func.type_name()),
+    )),
+    // 4. Return func.
+    func => Ok(Some(func)),
+  }
+}
+
 /// https://tc39.es/ecma262/#sec-call
 pub fn call(
   f: &Value,
-  _v: &Value,
-  _arguments_list: &[Value],
-) -> Result<Completion, Completion> {
+  v: &Value,
+  arguments_list: &[Value],
+) -> Result<Value, Value> {
   // 1. If argumentsList is not present, set argumentsList to a new empty List.
   // 2. If IsCallable(F) is false, throw a TypeError exception.
-  if !f.is_callable() {
-    return Err(Completion::throw(Value::Undefined(JsUndefined))); // TODO
-  }
+  let f = match f {
+    Value::Object(f) if f.get_call().is_some() => f,
+    _ => {
+      return Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("{} is not a function", f.type_name()),
+      ));
+    }
+  };
   // 3. Return ? F.[[Call]](V, argumentsList).
-  // f.call(v, arguments_list)?
-  todo!()
+  f.call(v, arguments_list)
+}
+
+/// https://tc39.es/ecma262/#sec-construct
+pub fn construct(
+  f: &Value,
+  arguments_list: &[Value],
+  new_target: Option<&Value>,
+) -> Result<JsObject, Value> {
+  // 1. If newTarget is not present, set newTarget to F.
+  let new_target = new_target.unwrap_or(f);
+  // 2. If argumentsList is not present, set argumentsList to a new empty List.
+  // 3. Assert: IsConstructor(F) is true.
+  // 4. Assert: IsConstructor(newTarget) is true.
+  match (f, new_target) {
+    (Value::Object(f), Value::Object(new_target))
+      if f.get_construct().is_some()
+        && new_target.get_construct().is_some() =>
+    {
+      // 5. Return ? F.[[Construct]](argumentsList, newTarget).
+      f.construct(arguments_list, new_target)
+    }
+    _ => {
+      let not_constructor = if f.is_constructor() { new_target } else { f };
+      Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("{} is not a constructor", not_constructor.type_name()),
+      ))
+    }
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-invoke
+pub fn invoke(
+  v: &Value,
+  p: &PropertyKey,
+  arguments_list: &[Value],
+) -> Result<Value, Value> {
+  // 1. If argumentsList is not present, set argumentsList to a new empty List.
+  // 2. Let func be ? GetV(V, P).
+  let func = get_v(v, p)?;
+  // 3. Return ? Call(func, V, argumentsList).
+  call(&func, v, arguments_list)
+}
+
+/// https://tc39.es/ecma262/#sec-createarrayfromlist
+pub fn create_array_from_list(elements: &[Value]) -> JsObject {
+  // 1. Let array be ! ArrayCreate(0).
+  let array = array_create(0.0, None).expect("0 is a valid array length");
+  // 2. Let n be 0.
+  // 3. For each element e of elements, do
+  for (n, e) in elements.iter().enumerate() {
+    // a. Perform ! CreateDataPropertyOrThrow(array, ! ToString(𝔽(n)), e).
+    // b. Set n to n + 1.
+    create_data_property(&array, &n.to_string().into(), e.clone())
+      .expect("array elements are definable");
+  }
+  // 4. Return array.
+  array
+}
+
+/// https://tc39.es/ecma262/#sec-lengthofarraylike
+pub fn length_of_array_like(obj: &JsObject) -> Result<f64, Value> {
+  // 1. Return ℝ(? ToLength(? Get(obj, "length"))).
+  get(obj, &"length".into())?.to_length()
+}
+
+/// The `kind` of EnumerableOwnPropertyNames.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyKind {
+  Key,
+  Value,
+  KeyValue,
+}
+
+/// https://tc39.es/ecma262/#sec-enumerableownpropertynames
+pub fn enumerable_own_property_names(
+  o: &JsObject,
+  kind: PropertyKind,
+) -> Result<Vec<Value>, Value> {
+  // 1. Let ownKeys be ? O.[[OwnPropertyKeys]]().
+  let own_keys = o.own_property_keys()?;
+  // 2. Let results be a new empty List.
+  let mut results = Vec::new();
+  // 3. For each element key of ownKeys, do
+  for key in own_keys {
+    // a. If Type(key) is String, then
+    if let PropertyKey::String(_) = key {
+      // i. Let desc be ? O.[[GetOwnProperty]](key).
+      // ii. If desc is not undefined and desc.[[Enumerable]] is true, then
+      match o.get_own_property(&key)? {
+        Some(desc) if desc.enumerable == Some(JsBoolean::True) => {}
+        _ => continue,
+      }
+      // 1. If kind is key, append key to results.
+      if kind == PropertyKind::Key {
+        results.push(key.into());
+        continue;
+      }
+      // 2. Else,
+      //   a. Let value be ? Get(O, key).
+      let value = get(o, &key)?;
+      //   b. If kind is value, append value to results.
+      //   c. Else,
+      //     i. Assert: kind is key+value.
+      //     ii. Let entry be CreateArrayFromList(« key, value »).
+      //     iii. Append entry to results.
+      if kind == PropertyKind::Value {
+        results.push(value);
+      } else {
+        results.push(create_array_from_list(&[key.into(), value]).into());
+      }
+    }
+  }
+  // 4. Return results.
+  Ok(results)
+}
+
+/// The `level` of SetIntegrityLevel and TestIntegrityLevel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityLevel {
+  Sealed,
+  Frozen,
+}
+
+/// https://tc39.es/ecma262/#sec-setintegritylevel
+pub fn set_integrity_level(
+  o: &JsObject,
+  level: IntegrityLevel,
+) -> Result<bool, Value> {
+  // 1. Let status be ? O.[[PreventExtensions]]().
+  // 2. If status is false, return false.
+  if !o.prevent_extensions()? {
+    return Ok(false);
+  }
+  // 3. Let keys be ? O.[[OwnPropertyKeys]]().
+  let keys = o.own_property_keys()?;
+  let non_configurable = PropertyDescriptor {
+    value: None,
+    writable: None,
+    get: None,
+    set: None,
+    enumerable: None,
+    configurable: Some(JsBoolean::False),
+  };
+  for k in keys {
+    let desc = match level {
+      // 4. If level is sealed, then
+      //   a. For each element k of keys, do
+      //     i. Perform ? DefinePropertyOrThrow(O, k, PropertyDescriptor {
+      //        [[Configurable]]: false }).
+      IntegrityLevel::Sealed => non_configurable.clone(),
+      // 5. Else,
+      //   a. Assert: level is frozen.
+      //   b. For each element k of keys, do
+      //     i. Let currentDesc be ? O.[[GetOwnProperty]](k).
+      //     ii. If currentDesc is not undefined, then
+      //       1. If IsAccessorDescriptor(currentDesc) is true, then
+      //         a. Let desc be the PropertyDescriptor { [[Configurable]]: false }.
+      //       2. Else,
+      //         a. Let desc be the PropertyDescriptor { [[Configurable]]: false,
+      //            [[Writable]]: false }.
+      //       3. Perform ? DefinePropertyOrThrow(O, k, desc).
+      IntegrityLevel::Frozen => match o.get_own_property(&k)? {
+        None => continue,
+        Some(current_desc) if current_desc.is_accessor_descriptor() => {
+          non_configurable.clone()
+        }
+        Some(_) => PropertyDescriptor {
+          writable: Some(JsBoolean::False),
+          ..non_configurable.clone()
+        },
+      },
+    };
+    define_property_or_throw(o, &k, desc)?;
+  }
+  // 6. Return true.
+  Ok(true)
+}
+
+/// https://tc39.es/ecma262/#sec-testintegritylevel
+pub fn test_integrity_level(
+  o: &JsObject,
+  level: IntegrityLevel,
+) -> Result<bool, Value> {
+  // 1. Let extensible be ? IsExtensible(O).
+  // 2. If extensible is true, return false.
+  // 3. NOTE: If the object is extensible, none of its properties are examined.
+  if o.is_extensible()? {
+    return Ok(false);
+  }
+  // 4. Let keys be ? O.[[OwnPropertyKeys]]().
+  // 5. For each element k of keys, do
+  for k in o.own_property_keys()? {
+    // a. Let currentDesc be ? O.[[GetOwnProperty]](k).
+    // b. If currentDesc is not undefined, then
+    if let Some(current_desc) = o.get_own_property(&k)? {
+      // i. If currentDesc.[[Configurable]] is true, return false.
+      if current_desc.configurable == Some(JsBoolean::True) {
+        return Ok(false);
+      }
+      // ii. If level is frozen and IsDataDescriptor(currentDesc) is true, then
+      //   1. If currentDesc.[[Writable]] is true, return false.
+      if level == IntegrityLevel::Frozen
+        && current_desc.is_data_descriptor()
+        && current_desc.writable == Some(JsBoolean::True)
+      {
+        return Ok(false);
+      }
+    }
+  }
+  // 6. Return true.
+  Ok(true)
+}
+
+/// https://tc39.es/ecma262/#sec-speciesconstructor
+pub fn species_constructor(
+  o: &JsObject,
+  default_constructor: &JsObject,
+) -> Result<JsObject, Value> {
+  // 1. Let C be ? Get(O, "constructor").
+  let c = match get(o, &"constructor".into())? {
+    // 2. If C is undefined, return defaultConstructor.
+    Value::Undefined(_) => return Ok(default_constructor.clone()),
+    Value::Object(c) => c,
+    // 3. If Type(C) is not Object, throw a TypeError exception.
+    c => {
+      return Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("constructor is a {}, not an object", c.type_name()),
+      ))
+    }
+  };
+  // 4. Let S be ? Get(C, @@species).
+  match get(&c, &JsSymbol::species().into())? {
+    // 5. If S is either undefined or null, return defaultConstructor.
+    Value::Undefined(_) | Value::Null(_) => Ok(default_constructor.clone()),
+    // 6. If IsConstructor(S) is true, return S.
+    Value::Object(s) if s.get_construct().is_some() => Ok(s),
+    // 7. Throw a TypeError exception.
+    s => Err(throw_error(
+      ErrorKind::TypeError,
+      &format!("@@species is a {}, not a constructor", s.type_name()),
+    )),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-getfunctionrealm
+pub fn get_function_realm(obj: &JsObject) -> Result<Realm, Value> {
+  // 1. If obj has a [[Realm]] internal slot, then
+  //   a. Return obj.[[Realm]].
+  if let Some(InternalSlot::Realm(realm)) = obj.get_internal_slot("Realm") {
+    return Ok(realm);
+  }
+  // 2. If obj is a bound function exotic object, then
+  //   a. Let target be obj.[[BoundTargetFunction]].
+  //   b. Return ? GetFunctionRealm(target).
+  if let Some(InternalSlot::Value(Value::Object(target))) =
+    obj.get_internal_slot("BoundTargetFunction")
+  {
+    return get_function_realm(&target);
+  }
+  // 3. If obj is a Proxy exotic object, then
+  if let Some(InternalSlot::Value(handler)) =
+    obj.get_internal_slot("ProxyHandler")
+  {
+    // a. If obj.[[ProxyHandler]] is null, throw a TypeError exception.
+    if let Value::Null(_) = handler {
+      return Err(throw_error(
+        ErrorKind::TypeError,
+        "Cannot perform operation on a revoked proxy",
+      ));
+    }
+    // b. Let proxyTarget be obj.[[ProxyTarget]].
+    // c. Return ? GetFunctionRealm(proxyTarget).
+    if let Some(InternalSlot::Value(Value::Object(target))) =
+      obj.get_internal_slot("ProxyTarget")
+    {
+      return get_function_realm(&target);
+    }
+  }
+  // 4. Return the current Realm Record.
+  Ok(current_realm())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::ordinary_object_internal_methods_and_internal_slots::{
+      ordinary_object_create, ORDINARY_INTERNAL_METHODS,
+    },
+    abstract_operations::testing_and_comparison_operations::require_object_coercible,
+    agent::{pop_execution_context, push_execution_context},
+    executable_code_and_execution_contexts::{
+      execution_context::ExecutionContext,
+      realm::{create_realm, initialize_host_defined_realm},
+    },
+    helpers::Either,
+    language_types::{null::JsNull, object::InternalMethods},
+    test_helpers::error_name,
+  };
+
+  use ORDINARY_INTERNAL_METHODS as ORDINARY;
+
+  /// Returns `this`.
+  static FUNCTION: InternalMethods = InternalMethods {
+    call: Some(|_, this, _| Ok(this.clone())),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+  static CONSTRUCTOR: InternalMethods = InternalMethods {
+    call: Some(|_, _, _| Ok(Value::undefined())),
+    construct: Some(|_, _, _| {
+      Ok(JsObject::new(Either::B(JsNull), true, &ORDINARY))
+    }),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+  #[test]
+  fn construct_stub() {
+    let f = Value::from(JsObject::new(Either::B(JsNull), true, &CONSTRUCTOR));
+    assert!(f.is_constructor());
+    let o = construct(&f, &[], None).unwrap();
+    assert!(o.get_construct().is_none());
+    assert!(!JsObject::equals(
+      &o,
+      &construct(&f, &[], Some(&f)).unwrap()
+    ));
+  }
+
+  #[test]
+  fn construct_non_constructor() {
+    initialize_host_defined_realm().unwrap();
+    let o = Value::from(JsObject::new(Either::B(JsNull), true, &ORDINARY));
+    assert!(!o.is_constructor());
+    assert!(construct(&o, &[], None).is_err());
+    assert!(construct(&Value::from(1.0), &[], None).is_err());
+    let f = Value::from(JsObject::new(Either::B(JsNull), true, &CONSTRUCTOR));
+    assert!(construct(&f, &[], Some(&o)).is_err());
+    pop_execution_context();
+  }
+
+  fn define(o: &JsObject, key: impl Into<PropertyKey>, value: Value) {
+    o.get_properties_mut().insert(
+      key.into(),
+      PropertyDescriptor {
+        value: Some(value),
+        writable: Some(JsBoolean::True),
+        get: None,
+        set: None,
+        enumerable: Some(JsBoolean::True),
+        configurable: Some(JsBoolean::True),
+      },
+    );
+  }
+
+  fn is_object(value: &Value, o: &JsObject) -> bool {
+    matches!(value, Value::Object(v) if JsObject::equals(v, o))
+  }
+
+  #[test]
+  fn invoke_own_method() {
+    let o = JsObject::new(Either::B(JsNull), true, &ORDINARY);
+    let f = JsObject::new(Either::B(JsNull), true, &FUNCTION);
+    define(&o, "f", f.into());
+    let this = invoke(&o.clone().into(), &"f".into(), &[]).unwrap();
+    assert!(is_object(&this, &o));
+  }
+
+  #[test]
+  fn invoke_inherited_method() {
+    initialize_host_defined_realm().unwrap();
+    let proto = JsObject::new(Either::B(JsNull), true, &ORDINARY);
+    let f = JsObject::new(Either::B(JsNull), true, &FUNCTION);
+    define(&proto, "f", f.into());
+    let o = JsObject::new(Either::A(proto.clone()), true, &ORDINARY);
+    let this = invoke(&o.clone().into(), &"f".into(), &[]).unwrap();
+    assert!(is_object(&this, &o));
+    assert!(invoke(&o.into(), &"g".into(), &[]).is_err());
+    assert!(invoke(&Value::undefined(), &"f".into(), &[]).is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn own_and_inherited_properties() {
+    let proto = JsObject::new(Either::B(JsNull), true, &ORDINARY);
+    define(&proto, "inherited", Value::null());
+    let o = JsObject::new(Either::A(proto), true, &ORDINARY);
+    define(&o, "own", Value::null());
+    assert!(has_own_property(&o, &"own".into()).unwrap());
+    assert!(!has_own_property(&o, &"inherited".into()).unwrap());
+    assert!(has_property(&o, &"own".into()).unwrap());
+    assert!(has_property(&o, &"inherited".into()).unwrap());
+    assert!(!has_property(&o, &"missing".into()).unwrap());
+    // [[Delete]] only removes the own property
+    assert!(o.delete(&"inherited".into()).unwrap());
+    assert!(o.delete(&"own".into()).unwrap());
+    assert!(!has_property(&o, &"own".into()).unwrap());
+    assert!(has_property(&o, &"inherited".into()).unwrap());
+  }
+
+  #[test]
+  fn species_constructor_default() {
+    let default = JsObject::new(Either::B(JsNull), true, &CONSTRUCTOR);
+    let o = JsObject::new(Either::B(JsNull), true, &ORDINARY);
+    let c = species_constructor(&o, &default).unwrap();
+    assert!(JsObject::equals(&c, &default));
+
+    let constructor = JsObject::new(Either::B(JsNull), true, &CONSTRUCTOR);
+    define(&o, "constructor", constructor.clone().into());
+    let c = species_constructor(&o, &default).unwrap();
+    assert!(JsObject::equals(&c, &default));
+
+    define(&constructor, JsSymbol::species(), Value::null());
+    let c = species_constructor(&o, &default).unwrap();
+    assert!(JsObject::equals(&c, &default));
+  }
+
+  #[test]
+  fn species_constructor_override() {
+    initialize_host_defined_realm().unwrap();
+    let default = JsObject::new(Either::B(JsNull), true, &CONSTRUCTOR);
+    let species = JsObject::new(Either::B(JsNull), true, &CONSTRUCTOR);
+    let constructor = JsObject::new(Either::B(JsNull), true, &CONSTRUCTOR);
+    define(&constructor, JsSymbol::species(), species.clone().into());
+    let o = JsObject::new(Either::B(JsNull), true, &ORDINARY);
+    define(&o, "constructor", constructor.clone().into());
+    let c = species_constructor(&o, &default).unwrap();
+    assert!(JsObject::equals(&c, &species));
+
+    define(&constructor, JsSymbol::species(), Value::from(1.0));
+    assert!(species_constructor(&o, &default).is_err());
+    define(&o, "constructor", Value::from(1.0));
+    assert!(species_constructor(&o, &default).is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn get_set_and_delete() {
+    initialize_host_defined_realm().unwrap();
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    set(&o, &"a".into(), Value::from(1.0), true).unwrap();
+    assert!(matches!(get(&o, &"a".into()), Ok(Value::Number(n)) if *n == 1.0));
+    assert!(matches!(get(&o, &"b".into()), Ok(Value::Undefined(_))));
+    assert!(has_property(&o, &"a".into()).unwrap());
+
+    define_property_or_throw(
+      &o,
+      &"fixed".into(),
+      PropertyDescriptor {
+        value: Some(Value::from(2.0)),
+        writable: Some(JsBoolean::False),
+        get: None,
+        set: None,
+        enumerable: Some(JsBoolean::True),
+        configurable: Some(JsBoolean::False),
+      },
+    )
+    .unwrap();
+    // the failure is silent unless Throw is true
+    set(&o, &"fixed".into(), Value::from(3.0), false).unwrap();
+    assert!(set(&o, &"fixed".into(), Value::from(3.0), true).is_err());
+    assert!(
+      matches!(get(&o, &"fixed".into()), Ok(Value::Number(n)) if *n == 2.0)
+    );
+
+    delete_property_or_throw(&o, &"a".into()).unwrap();
+    assert!(!has_own_property(&o, &"a".into()).unwrap());
+    // deleting a missing property succeeds
+    delete_property_or_throw(&o, &"a".into()).unwrap();
+    assert!(delete_property_or_throw(&o, &"fixed".into()).is_err());
+    assert!(has_own_property(&o, &"fixed".into()).unwrap());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn or_throw_on_non_extensible() {
+    initialize_host_defined_realm().unwrap();
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    create_data_property_or_throw(&o, &"a".into(), Value::from(1.0)).unwrap();
+    assert!(o.prevent_extensions().unwrap());
+
+    assert!(!create_data_property(&o, &"b".into(), Value::null()).unwrap());
+    let error = create_data_property_or_throw(&o, &"b".into(), Value::null())
+      .unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    let desc = PropertyDescriptor {
+      value: Some(Value::null()),
+      writable: None,
+      get: None,
+      set: None,
+      enumerable: None,
+      configurable: None,
+    };
+    let error =
+      define_property_or_throw(&o, &"b".into(), desc.clone()).unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    assert!(!has_own_property(&o, &"b".into()).unwrap());
+    // existing properties can still be redefined
+    define_property_or_throw(&o, &"a".into(), desc).unwrap();
+    assert!(matches!(get(&o, &"a".into()), Ok(Value::Null(_))));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn array_like() {
+    initialize_host_defined_realm().unwrap();
+    let elements = [Value::from(1.0), Value::from(2.0), Value::from(3.0)];
+    let array = create_array_from_list(&elements);
+    assert_eq!(length_of_array_like(&array).unwrap(), 3.0);
+    assert!(
+      matches!(get(&array, &"2".into()), Ok(Value::Number(n)) if *n == 3.0)
+    );
+    assert_eq!(
+      length_of_array_like(&create_array_from_list(&[])).unwrap(),
+      0.0
+    );
+
+    // any object with a "length" is array-like
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    assert_eq!(length_of_array_like(&o).unwrap(), 0.0);
+    define(&o, "length", Value::from("2.5"));
+    assert_eq!(length_of_array_like(&o).unwrap(), 2.0);
+    define(&o, "length", Value::from(-1.0));
+    assert_eq!(length_of_array_like(&o).unwrap(), 0.0);
+    pop_execution_context();
+  }
+
+  #[test]
+  fn call_non_callable() {
+    initialize_host_defined_realm().unwrap();
+    let o = Value::from(JsObject::new(Either::B(JsNull), true, &ORDINARY));
+    for f in [o, Value::undefined(), Value::from(1.0)] {
+      // a TypeError object is thrown, not a message or undefined
+      let error = match call(&f, &Value::undefined(), &[]) {
+        Err(Value::Object(error)) => error,
+        result => panic!("expect a thrown error, got {:?}", result),
+      };
+      assert!(error.has_internal_slot("ErrorData"));
+      let name = get(&error, &"name".into()).unwrap().to_string().unwrap();
+      assert_eq!(name, "TypeError");
+    }
+    pop_execution_context();
+  }
+
+  #[test]
+  fn function_realm() {
+    let realm = create_realm();
+    let f = JsObject::new(Either::B(JsNull), true, &FUNCTION);
+    f.set_internal_slot("Realm", InternalSlot::Realm(realm.clone()));
+    assert!(Realm::equals(&get_function_realm(&f).unwrap(), &realm));
+
+    let bound = JsObject::new(Either::B(JsNull), true, &FUNCTION);
+    bound
+      .set_internal_slot("BoundTargetFunction", InternalSlot::Value(f.into()));
+    assert!(Realm::equals(&get_function_realm(&bound).unwrap(), &realm));
+
+    let current = create_realm();
+    push_execution_context(ExecutionContext {
+      function: None,
+      realm: current.clone(),
+      variable_environment: None,
+      lexical_environment: None,
+    });
+    let o = JsObject::new(Either::B(JsNull), true, &ORDINARY);
+    assert!(Realm::equals(&get_function_realm(&o).unwrap(), &current));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn primitive_properties() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let abc = Value::from("abc");
+    assert!(get_v(&Value::undefined(), &"x".into()).is_err());
+    assert!(require_object_coercible(&Value::null()).is_err());
+    assert!(
+      matches!(get_v(&abc, &"length".into()), Ok(Value::Number(n)) if *n == 3.0)
+    );
+    assert!(
+      matches!(get_v(&abc, &"1".into()), Ok(Value::String(s)) if s == "b")
+    );
+    assert!(matches!(get_v(&abc, &"3".into()), Ok(Value::Undefined(_))));
+
+    // Methods come from %String.prototype% and see the primitive as `this`.
+    let string_prototype = realm.intrinsic("String.prototype");
+    let f = JsObject::new(Either::B(JsNull), true, &FUNCTION);
+    define(&string_prototype, "self", f.into());
+    let this = invoke(&abc, &"self".into(), &[]).unwrap();
+    assert!(matches!(this, Value::String(s) if s == "abc"));
+    let c = invoke(&abc, &"charAt".into(), &[2.0.into()]).unwrap();
+    assert!(matches!(c, Value::String(s) if s == "c"));
+    pop_execution_context();
+  }
 }
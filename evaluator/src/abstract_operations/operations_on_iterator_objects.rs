@@ -0,0 +1,283 @@
+//! https://tc39.es/ecma262/#sec-operations-on-iterator-objects
+
+use crate::{
+  abstract_operations::operations_on_bjects::{call, get, get_method, get_v},
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  language_types::{
+    boolean::JsBoolean, object::JsObject, symbol::JsSymbol, Value,
+  },
+};
+
+/// https://tc39.es/ecma262/#sec-iterator-records
+#[derive(Debug, Clone)]
+pub struct IteratorRecord {
+  pub iterator: JsObject,
+  pub next_method: Value,
+  pub done: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IteratorKind {
+  Sync,
+  Async,
+}
+
+/// https://tc39.es/ecma262/#sec-getiteratorfrommethod
+pub fn get_iterator_from_method(
+  obj: &Value,
+  method: &Value,
+) -> Result<IteratorRecord, Value> {
+  // 1. Let iterator be ? Call(method, obj).
+  // 2. If Type(iterator) is not Object, throw a TypeError exception.
+  let iterator = match call(method, obj, &[])? {
+    Value::Object(iterator) => iterator,
+    _ => {
+      return Err(throw_error(
+        ErrorKind::TypeError,
+        "Result of the Symbol.iterator method is not an object",
+      ))
+    }
+  };
+  // 3. Let nextMethod be ? GetV(iterator, "next").
+  let next_method = get_v(&iterator.clone().into(), &"next".into())?;
+  // 4. Let iteratorRecord be the Iterator Record { [[Iterator]]: iterator,
+  //    [[NextMethod]]: nextMethod, [[Done]]: false }.
+  // 5. Return iteratorRecord.
+  Ok(IteratorRecord {
+    iterator,
+    next_method,
+    done: false,
+  })
+}
+
+/// https://tc39.es/ecma262/#sec-getiterator
+pub fn get_iterator(
+  obj: &Value,
+  kind: IteratorKind,
+) -> Result<IteratorRecord, Value> {
+  // 1. If kind is async, then
+  //   a. Let method be ? GetMethod(obj, @@asyncIterator).
+  //   b. If method is undefined, then
+  //     i. Let syncMethod be ? GetMethod(obj, @@iterator).
+  //     ii. If syncMethod is undefined, throw a TypeError exception.
+  //     iii. Let syncIteratorRecord be ? GetIteratorFromMethod(obj, syncMethod).
+  //     iv. Return CreateAsyncFromSyncIterator(syncIteratorRecord).
+  // 2. Else,
+  //   a. Let method be ? GetMethod(obj, @@iterator).
+  let method = match kind {
+    // TODO: CreateAsyncFromSyncIterator
+    IteratorKind::Async => get_method(obj, &JsSymbol::async_iterator().into())?,
+    IteratorKind::Sync => get_method(obj, &JsSymbol::iterator().into())?,
+  };
+  // 3. If method is undefined, throw a TypeError exception.
+  let method = method.ok_or_else(|| {
+    throw_error(
+      ErrorKind::TypeError,
+      &format!("{} is not iterable", obj.type_name()),
+    )
+  })?;
+  // 4. Return ? GetIteratorFromMethod(obj, method).
+  get_iterator_from_method(obj, &method)
+}
+
+/// https://tc39.es/ecma262/#sec-iteratornext
+pub fn iterator_next(
+  iterator_record: &IteratorRecord,
+  value: Option<Value>,
+) -> Result<JsObject, Value> {
+  // 1. If value is not present, then
+  //   a. Let result be ? Call(iteratorRecord.[[NextMethod]], iteratorRecord.[[Iterator]]).
+  // 2. Else,
+  //   a. Let result be ? Call(iteratorRecord.[[NextMethod]], iteratorRecord.[[Iterator]],
+  //      « value »).
+  let arguments = value.into_iter().collect::<Vec<_>>();
+  let result = call(
+    &iterator_record.next_method,
+    &iterator_record.iterator.clone().into(),
+    &arguments,
+  )?;
+  // 3. If Type(result) is not Object, throw a TypeError exception.
+  // 4. Return result.
+  match result {
+    Value::Object(result) => Ok(result),
+    result => Err(throw_error(
+      ErrorKind::TypeError,
+      &format!("Iterator result {} is not an object", result.type_name()),
+    )),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-iteratorcomplete
+pub fn iterator_complete(iter_result: &JsObject) -> Result<bool, Value> {
+  // 1. Return ToBoolean(? Get(iterResult, "done")).
+  Ok(get(iter_result, &"done".into())?.to_boolean() == JsBoolean::True)
+}
+
+/// https://tc39.es/ecma262/#sec-iteratorvalue
+pub fn iterator_value(iter_result: &JsObject) -> Result<Value, Value> {
+  // 1. Return ? Get(iterResult, "value").
+  get(iter_result, &"value".into())
+}
+
+/// https://tc39.es/ecma262/#sec-iteratorstep, `None` stands for false.
+pub fn iterator_step(
+  iterator_record: &IteratorRecord,
+) -> Result<Option<JsObject>, Value> {
+  // 1. Let result be ? IteratorNext(iteratorRecord).
+  let result = iterator_next(iterator_record, None)?;
+  // 2. Let done be ? IteratorComplete(result).
+  // 3. If done is true, return false.
+  if iterator_complete(&result)? {
+    return Ok(None);
+  }
+  // 4. Return result.
+  Ok(Some(result))
+}
+
+/// https://tc39.es/ecma262/#sec-iteratorclose, the completion is either a
+/// normal or a throw completion.
+pub fn iterator_close(
+  iterator_record: &IteratorRecord,
+  completion: Result<Value, Value>,
+) -> Result<Value, Value> {
+  // 1. Assert: Type(iteratorRecord.[[Iterator]]) is Object.
+  // 2. Let iterator be iteratorRecord.[[Iterator]].
+  let iterator = Value::from(iterator_record.iterator.clone());
+  // 3. Let innerResult be Completion(GetMethod(iterator, "return")).
+  // 4. If innerResult.[[Type]] is normal, then
+  //   a. Let return be innerResult.[[Value]].
+  //   b. If return is undefined, return ? completion.
+  //   c. Set innerResult to Completion(Call(return, iterator)).
+  let inner_result = match get_method(&iterator, &"return".into()) {
+    Ok(None) => return completion,
+    Ok(Some(r#return)) => call(&r#return, &iterator, &[]),
+    Err(error) => Err(error),
+  };
+  // 5. If completion.[[Type]] is throw, return ? completion.
+  let value = completion?;
+  // 6. If innerResult.[[Type]] is throw, return ? innerResult.
+  // 7. If Type(innerResult.[[Value]]) is not Object, throw a TypeError exception.
+  match inner_result? {
+    Value::Object(_) => {}
+    result => {
+      return Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("Iterator result {} is not an object", result.type_name()),
+      ))
+    }
+  }
+  // 8. Return ? completion.
+  Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::{
+      operations_on_bjects::{create_data_property, get},
+      ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    helpers::Either,
+    language_types::null::JsNull,
+    ordinary_and_exotic_objects_behaviours::builtin_function::create_builtin_function,
+  };
+
+  fn object(properties: &[(&str, Value)]) -> JsObject {
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    for (key, value) in properties {
+      create_data_property(&o, &(*key).into(), value.clone()).unwrap();
+    }
+    o
+  }
+
+  /// `next` counts `this.count` up to 2.
+  fn next(
+    this: &Value,
+    _: &[Value],
+    _: Option<&JsObject>,
+  ) -> Result<Value, Value> {
+    let this = this.to_object()?;
+    let count = *get(&this, &"count".into())?.to_number()? + 1.0;
+    create_data_property(&this, &"count".into(), count.into())?;
+    Ok(
+      object(&[("value", count.into()), ("done", (count > 2.0).into())]).into(),
+    )
+  }
+
+  /// `return` records it was called.
+  fn r#return(
+    this: &Value,
+    _: &[Value],
+    _: Option<&JsObject>,
+  ) -> Result<Value, Value> {
+    create_data_property(&this.to_object()?, &"closed".into(), true.into())?;
+    Ok(object(&[]).into())
+  }
+
+  fn iterator() -> IteratorRecord {
+    let next = create_builtin_function(next, 0.0, "next", None, None, false);
+    let r#return =
+      create_builtin_function(r#return, 0.0, "return", None, None, false);
+    let iterator = object(&[
+      ("count", 0.0.into()),
+      ("next", next.clone().into()),
+      ("return", r#return.into()),
+    ]);
+    IteratorRecord {
+      iterator,
+      next_method: next.into(),
+      done: false,
+    }
+  }
+
+  #[test]
+  fn step() {
+    initialize_host_defined_realm().unwrap();
+    let record = iterator();
+    let mut values = Vec::new();
+    while let Some(result) = iterator_step(&record).unwrap() {
+      values.push(*iterator_value(&result).unwrap().to_number().unwrap());
+    }
+    assert_eq!(values, [1.0, 2.0]);
+    pop_execution_context();
+  }
+
+  #[test]
+  fn close() {
+    initialize_host_defined_realm().unwrap();
+    let record = iterator();
+    let closed = || get(&record.iterator, &"closed".into()).unwrap();
+    assert!(matches!(closed(), Value::Undefined(_)));
+    let value = iterator_close(&record, Ok(Value::from(1.0))).unwrap();
+    assert_eq!(*value.to_number().unwrap(), 1.0);
+    assert!(matches!(closed(), Value::Boolean(JsBoolean::True)));
+    // the original throw completion wins
+    let error = iterator_close(&record, Err(Value::from("error")));
+    assert!(matches!(error, Err(Value::String(s)) if s == "error"));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn get_iterator_errors() {
+    initialize_host_defined_realm().unwrap();
+    let not_iterable = Value::from(object(&[]));
+    assert!(get_iterator(&not_iterable, IteratorKind::Sync).is_err());
+    let record = IteratorRecord {
+      next_method: create_builtin_function(
+        |_, _, _| Ok(Value::from(1.0)),
+        0.0,
+        "next",
+        None,
+        None,
+        false,
+      )
+      .into(),
+      ..iterator()
+    };
+    assert!(iterator_next(&record, None).is_err());
+    pop_execution_context();
+  }
+}
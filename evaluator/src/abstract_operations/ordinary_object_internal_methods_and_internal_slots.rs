@@ -1,7 +1,716 @@
-use crate::language_types::object::{JsObject, Prototype};
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{
+      call, create_data_property, get, get_function_realm,
+    },
+    testing_and_comparison_operations::same_value,
+  },
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    object::{InternalMethods, InternalSlot, JsObject, PropertyKey, Prototype},
+    undefined::JsUndefined,
+    Value,
+  },
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+/// https://tc39.es/ecma262/#sec-ordinary-object-internal-methods-and-internal-slots
+pub static ORDINARY_INTERNAL_METHODS: InternalMethods = InternalMethods {
+  get_prototype_of: ordinary_get_prototype_of,
+  set_prototype_of: ordinary_set_prototype_of,
+  is_extensible: ordinary_is_extensible,
+  prevent_extensions: ordinary_prevent_extensions,
+  get_own_property: ordinary_get_own_property,
+  define_own_property: ordinary_define_own_property,
+  has_property: ordinary_has_property,
+  get: ordinary_get,
+  set: ordinary_set,
+  delete: ordinary_delete,
+  own_property_keys: ordinary_own_property_keys,
+  call: None,
+  construct: None,
+};
 
 /// https://tc39.es/ecma262/#sec-ordinarygetprototypeof
-pub fn ordinary_get_prototype_of(o: JsObject) -> Prototype {
+pub fn ordinary_get_prototype_of(o: &JsObject) -> Prototype {
   // 1. Return O.[[Prototype]].
   o.get_prototype()
 }
+
+/// https://tc39.es/ecma262/#sec-ordinarysetprototypeof
+pub fn ordinary_set_prototype_of(
+  o: &JsObject,
+  v: Prototype,
+) -> Result<bool, Value> {
+  let same = |x: &Prototype, y: &Prototype| match (x, y) {
+    (Either::A(x), Either::A(y)) => JsObject::equals(x, y),
+    (Either::B(_), Either::B(_)) => true,
+    _ => false,
+  };
+  // 1. Let current be O.[[Prototype]].
+  let current = o.get_prototype();
+  // 2. If SameValue(V, current) is true, return true.
+  if same(&v, &current) {
+    return Ok(true);
+  }
+  // 3. Let extensible be O.[[Extensible]].
+  // 4. If extensible is false, return false.
+  if !o.extensible() {
+    return Ok(false);
+  }
+  // 5. Let p be V.
+  let mut p = v.clone();
+  // 6. Let done be false.
+  // 7. Repeat, while done is false,
+  //   a. If p is null, set done to true.
+  //   b. Else if SameValue(p, O) is true, return false.
+  //   c. Else,
+  //     i. If p.[[GetPrototypeOf]] is not the ordinary object internal method
+  //        defined in 10.1.1, set done to true.
+  //     ii. Else, set p to p.[[Prototype]].
+  // TODO: there are no Proxy exotic objects, every [[GetPrototypeOf]] is the
+  // ordinary one
+  while let Either::A(object) = p {
+    if JsObject::equals(&object, o) {
+      return Ok(false);
+    }
+    p = object.get_prototype();
+  }
+  // 8. Set O.[[Prototype]] to V.
+  o.set_prototype(v);
+  // 9. Return true.
+  Ok(true)
+}
+
+/// https://tc39.es/ecma262/#sec-ordinaryisextensible
+pub fn ordinary_is_extensible(o: &JsObject) -> Result<bool, Value> {
+  // 1. Return O.[[Extensible]].
+  Ok(o.extensible())
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarypreventextensions
+pub fn ordinary_prevent_extensions(o: &JsObject) -> Result<bool, Value> {
+  // 1. Set O.[[Extensible]] to false.
+  o.set_extensible(false);
+  // 2. Return true.
+  Ok(true)
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarygetownproperty
+pub fn ordinary_get_own_property(
+  o: &JsObject,
+  p: &PropertyKey,
+) -> Result<Option<PropertyDescriptor>, Value> {
+  // 1. If O does not have an own property with key P, return undefined.
+  // 2. Let D be a newly created Property Descriptor with no fields.
+  // 3. Let X be O's own property whose key is P.
+  // 4. If X is a data property, then
+  //   a. Set D.[[Value]] to the value of X's [[Value]] attribute.
+  //   b. Set D.[[Writable]] to the value of X's [[Writable]] attribute.
+  // 5. Else,
+  //   a. Assert: X is an accessor property.
+  //   b. Set D.[[Get]] to the value of X's [[Get]] attribute.
+  //   c. Set D.[[Set]] to the value of X's [[Set]] attribute.
+  // 6. Set D.[[Enumerable]] to the value of X's [[Enumerable]] attribute.
+  // 7. Set D.[[Configurable]] to the value of X's [[Configurable]] attribute.
+  // 8. Return D.
+  Ok(o.get_properties().get(p).cloned())
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarydefineownproperty
+pub fn ordinary_define_own_property(
+  o: &JsObject,
+  p: &PropertyKey,
+  desc: PropertyDescriptor,
+) -> Result<bool, Value> {
+  // 1. Let current be ? O.[[GetOwnProperty]](P).
+  let current = o.get_own_property(p)?;
+  // 2. Let extensible be ? IsExtensible(O).
+  let extensible = o.is_extensible()?;
+  // 3. Return ValidateAndApplyPropertyDescriptor(O, P, extensible, Desc, current).
+  Ok(validate_and_apply_property_descriptor(
+    Some(o),
+    p,
+    extensible,
+    desc,
+    current,
+  ))
+}
+
+/// https://tc39.es/ecma262/#sec-validateandapplypropertydescriptor
+pub fn validate_and_apply_property_descriptor(
+  o: Option<&JsObject>,
+  p: &PropertyKey,
+  extensible: bool,
+  desc: PropertyDescriptor,
+  current: Option<PropertyDescriptor>,
+) -> bool {
+  // 1. Assert: IsPropertyKey(P) is true.
+  // 2. If current is undefined, then
+  let current = match current {
+    Some(current) => current,
+    None => {
+      // a. If extensible is false, return false.
+      if !extensible {
+        return false;
+      }
+      // b. If O is undefined, return true.
+      let o = match o {
+        Some(o) => o,
+        None => return true,
+      };
+      let enumerable = desc.enumerable.or(Some(JsBoolean::False));
+      let configurable = desc.configurable.or(Some(JsBoolean::False));
+      // c. If IsAccessorDescriptor(Desc) is true, then
+      let property = if desc.is_accessor_descriptor() {
+        // i. Create an own accessor property named P of object O whose [[Get]], [[Set]],
+        //    [[Enumerable]], and [[Configurable]] attributes are set to the value of the
+        //    corresponding field in Desc if Desc has that field, or to the attribute's
+        //    default value otherwise.
+        PropertyDescriptor {
+          value: None,
+          writable: None,
+          get: desc.get.or(Some(Either::B(JsUndefined))),
+          set: desc.set.or(Some(Either::B(JsUndefined))),
+          enumerable,
+          configurable,
+        }
+      } else {
+        // d. Else,
+        //   i. Create an own data property named P of object O whose [[Value]], [[Writable]],
+        //      [[Enumerable]], and [[Configurable]] attributes are set to the value of the
+        //      corresponding field in Desc if Desc has that field, or to the attribute's
+        //      default value otherwise.
+        PropertyDescriptor {
+          value: desc.value.or_else(|| Some(Value::undefined())),
+          writable: desc.writable.or(Some(JsBoolean::False)),
+          get: None,
+          set: None,
+          enumerable,
+          configurable,
+        }
+      };
+      o.get_properties_mut().insert(p.clone(), property);
+      // e. Return true.
+      return true;
+    }
+  };
+  // 3. Assert: current is a fully populated Property Descriptor.
+  // 4. If Desc does not have any fields, return true.
+  if desc.value.is_none()
+    && desc.writable.is_none()
+    && desc.get.is_none()
+    && desc.set.is_none()
+    && desc.enumerable.is_none()
+    && desc.configurable.is_none()
+  {
+    return true;
+  }
+  // 5. If current.[[Configurable]] is false, then
+  if current.configurable == Some(JsBoolean::False) {
+    // a. If Desc has a [[Configurable]] field and Desc.[[Configurable]] is true, return false.
+    if desc.configurable == Some(JsBoolean::True) {
+      return false;
+    }
+    // b. If Desc has an [[Enumerable]] field and SameValue(Desc.[[Enumerable]], current.[[Enumerable]])
+    //    is false, return false.
+    if desc.enumerable.is_some() && desc.enumerable != current.enumerable {
+      return false;
+    }
+    // c. If IsGenericDescriptor(Desc) is false and SameValue(IsAccessorDescriptor(Desc),
+    //    IsAccessorDescriptor(current)) is false, return false.
+    if !desc.is_generic_descriptor()
+      && desc.is_accessor_descriptor() != current.is_accessor_descriptor()
+    {
+      return false;
+    }
+    // d. If IsAccessorDescriptor(current) is true, then
+    if current.is_accessor_descriptor() {
+      // i. If Desc has a [[Get]] field and SameValue(Desc.[[Get]], current.[[Get]]) is false,
+      //    return false.
+      if matches!(&desc.get, Some(get) if !same_function(get, &current.get)) {
+        return false;
+      }
+      // ii. If Desc has a [[Set]] field and SameValue(Desc.[[Set]], current.[[Set]]) is false,
+      //     return false.
+      if matches!(&desc.set, Some(set) if !same_function(set, &current.set)) {
+        return false;
+      }
+    } else if current.writable == Some(JsBoolean::False) {
+      // e. Else if current.[[Writable]] is false, then
+      //   i. If Desc has a [[Writable]] field and Desc.[[Writable]] is true, return false.
+      if desc.writable == Some(JsBoolean::True) {
+        return false;
+      }
+      //   ii. If Desc has a [[Value]] field and SameValue(Desc.[[Value]], current.[[Value]])
+      //       is false, return false.
+      if let (Some(value), Some(current)) = (&desc.value, &current.value) {
+        if same_value(value, current) == JsBoolean::False {
+          return false;
+        }
+      }
+    }
+  }
+  // 6. If O is not undefined, then
+  if let Some(o) = o {
+    let configurable = desc.configurable.or(current.configurable);
+    let enumerable = desc.enumerable.or(current.enumerable);
+    let property =
+      if current.is_data_descriptor() && desc.is_accessor_descriptor() {
+        // a. If IsDataDescriptor(current) is true and IsAccessorDescriptor(Desc) is true, then
+        //   i. If Desc has a [[Configurable]] field, let configurable be Desc.[[Configurable]];
+        //      else let configurable be current.[[Configurable]].
+        //   ii. If Desc has a [[Enumerable]] field, let enumerable be Desc.[[Enumerable]]; else
+        //       let enumerable be current.[[Enumerable]].
+        //   iii. Replace the property named P of object O with an accessor property whose
+        //        [[Configurable]] and [[Enumerable]] attributes are set to configurable and
+        //        enumerable, respectively, and whose [[Get]] and [[Set]] attributes are set to
+        //        the value of the corresponding field in Desc if Desc has that field, or to the
+        //        attribute's default value otherwise.
+        PropertyDescriptor {
+          value: None,
+          writable: None,
+          get: desc.get.or(Some(Either::B(JsUndefined))),
+          set: desc.set.or(Some(Either::B(JsUndefined))),
+          enumerable,
+          configurable,
+        }
+      } else if current.is_accessor_descriptor() && desc.is_data_descriptor() {
+        // b. Else if IsAccessorDescriptor(current) is true and IsDataDescriptor(Desc) is true, then
+        //   i-ii. as above.
+        //   iii. Replace the property named P of object O with a data property whose
+        //        [[Configurable]] and [[Enumerable]] attributes are set to configurable and
+        //        enumerable, respectively, and whose [[Value]] and [[Writable]] attributes are
+        //        set to the value of the corresponding field in Desc if Desc has that field, or
+        //        to the attribute's default value otherwise.
+        PropertyDescriptor {
+          value: desc.value.or_else(|| Some(Value::undefined())),
+          writable: desc.writable.or(Some(JsBoolean::False)),
+          get: None,
+          set: None,
+          enumerable,
+          configurable,
+        }
+      } else {
+        // c. Else,
+        //   i. For each field of Desc, set the corresponding attribute of the property named P
+        //      of object O to the value of the field.
+        PropertyDescriptor {
+          value: desc.value.or(current.value),
+          writable: desc.writable.or(current.writable),
+          get: desc.get.or(current.get),
+          set: desc.set.or(current.set),
+          enumerable,
+          configurable,
+        }
+      };
+    o.get_properties_mut().insert(p.clone(), property);
+  }
+  // 7. Return true.
+  true
+}
+
+/// SameValue over the [[Get]]/[[Set]] fields.
+fn same_function(
+  x: &Either<JsObject, JsUndefined>,
+  y: &Option<Either<JsObject, JsUndefined>>,
+) -> bool {
+  match (x, y) {
+    (Either::A(x), Some(Either::A(y))) => JsObject::equals(x, y),
+    (Either::B(_), Some(Either::B(_))) => true,
+    _ => false,
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ordinaryhasproperty
+pub fn ordinary_has_property(
+  o: &JsObject,
+  p: &PropertyKey,
+) -> Result<bool, Value> {
+  // 1. Let hasOwn be ? O.[[GetOwnProperty]](P).
+  // 2. If hasOwn is not undefined, return true.
+  if o.get_own_property(p)?.is_some() {
+    return Ok(true);
+  }
+  // 3. Let parent be ? O.[[GetPrototypeOf]]().
+  // 4. If parent is not null, then
+  //   a. Return ? parent.[[HasProperty]](P).
+  // 5. Return false.
+  match o.get_prototype_of() {
+    Either::A(parent) => parent.has_property(p),
+    Either::B(_) => Ok(false),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ordinaryget
+pub fn ordinary_get(
+  o: &JsObject,
+  p: &PropertyKey,
+  receiver: &Value,
+) -> Result<Value, Value> {
+  // 1. Let desc be ? O.[[GetOwnProperty]](P).
+  let desc = match o.get_own_property(p)? {
+    Some(desc) => desc,
+    // 2. If desc is undefined, then
+    None => {
+      // a. Let parent be ? O.[[GetPrototypeOf]]().
+      // b. If parent is null, return undefined.
+      // c. Return ? parent.[[Get]](P, Receiver).
+      return match o.get_prototype_of() {
+        Either::A(parent) => parent.get(p, receiver),
+        Either::B(_) => Ok(Value::undefined()),
+      };
+    }
+  };
+  // 3. If IsDataDescriptor(desc) is true, return desc.[[Value]].
+  if desc.is_data_descriptor() {
+    return Ok(desc.value.unwrap_or_else(Value::undefined));
+  }
+  // 4. Assert: IsAccessorDescriptor(desc) is true.
+  // 5. Let getter be desc.[[Get]].
+  // 6. If getter is undefined, return undefined.
+  // 7. Return ? Call(getter, Receiver).
+  match desc.get {
+    Some(Either::A(getter)) => call(&Value::from(getter), receiver, &[]),
+    _ => Ok(Value::undefined()),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ordinaryset
+pub fn ordinary_set(
+  o: &JsObject,
+  p: &PropertyKey,
+  v: Value,
+  receiver: &Value,
+) -> Result<bool, Value> {
+  // 1. Let ownDesc be ? O.[[GetOwnProperty]](P).
+  let own_desc = o.get_own_property(p)?;
+  // 2. Return ? OrdinarySetWithOwnDescriptor(O, P, V, Receiver, ownDesc).
+  ordinary_set_with_own_descriptor(o, p, v, receiver, own_desc)
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarysetwithowndescriptor
+pub fn ordinary_set_with_own_descriptor(
+  o: &JsObject,
+  p: &PropertyKey,
+  v: Value,
+  receiver: &Value,
+  own_desc: Option<PropertyDescriptor>,
+) -> Result<bool, Value> {
+  // 1. If ownDesc is undefined, then
+  let own_desc = match own_desc {
+    Some(own_desc) => own_desc,
+    None => match o.get_prototype_of() {
+      // a. Let parent be ? O.[[GetPrototypeOf]]().
+      // b. If parent is not null, then
+      //   i. Return ? parent.[[Set]](P, V, Receiver).
+      Either::A(parent) => return parent.set(p, v, receiver),
+      // c. Else,
+      //   i. Set ownDesc to the PropertyDescriptor { [[Value]]: undefined,
+      //      [[Writable]]: true, [[Enumerable]]: true, [[Configurable]]: true }.
+      Either::B(_) => PropertyDescriptor {
+        value: Some(Value::undefined()),
+        writable: Some(JsBoolean::True),
+        get: None,
+        set: None,
+        enumerable: Some(JsBoolean::True),
+        configurable: Some(JsBoolean::True),
+      },
+    },
+  };
+  // 2. If IsDataDescriptor(ownDesc) is true, then
+  if own_desc.is_data_descriptor() {
+    // a. If ownDesc.[[Writable]] is false, return false.
+    if own_desc.writable == Some(JsBoolean::False) {
+      return Ok(false);
+    }
+    // b. If Type(Receiver) is not Object, return false.
+    let receiver = match receiver {
+      Value::Object(receiver) => receiver,
+      _ => return Ok(false),
+    };
+    // c. Let existingDescriptor be ? Receiver.[[GetOwnProperty]](P).
+    // d. If existingDescriptor is not undefined, then
+    if let Some(existing_descriptor) = receiver.get_own_property(p)? {
+      // i. If IsAccessorDescriptor(existingDescriptor) is true, return false.
+      if existing_descriptor.is_accessor_descriptor() {
+        return Ok(false);
+      }
+      // ii. If existingDescriptor.[[Writable]] is false, return false.
+      if existing_descriptor.writable == Some(JsBoolean::False) {
+        return Ok(false);
+      }
+      // iii. Let valueDesc be the PropertyDescriptor { [[Value]]: V }.
+      let value_desc = PropertyDescriptor {
+        value: Some(v),
+        writable: None,
+        get: None,
+        set: None,
+        enumerable: None,
+        configurable: None,
+      };
+      // iv. Return ? Receiver.[[DefineOwnProperty]](P, valueDesc).
+      return receiver.define_own_property(p, value_desc);
+    }
+    // e. Else,
+    //   i. Assert: Receiver does not currently have a property P.
+    //   ii. Return ? CreateDataProperty(Receiver, P, V).
+    return create_data_property(receiver, p, v);
+  }
+  // 3. Assert: IsAccessorDescriptor(ownDesc) is true.
+  // 4. Let setter be ownDesc.[[Set]].
+  // 5. If setter is undefined, return false.
+  // 6. Perform ? Call(setter, Receiver, « V »).
+  // 7. Return true.
+  match own_desc.set {
+    Some(Either::A(setter)) => {
+      call(&Value::from(setter), receiver, &[v])?;
+      Ok(true)
+    }
+    _ => Ok(false),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarydelete
+pub fn ordinary_delete(o: &JsObject, p: &PropertyKey) -> Result<bool, Value> {
+  // 1. Let desc be ? O.[[GetOwnProperty]](P).
+  match o.get_own_property(p)? {
+    // 2. If desc is undefined, return true.
+    None => Ok(true),
+    // 3. If desc.[[Configurable]] is true, then
+    Some(desc) if desc.configurable == Some(JsBoolean::True) => {
+      // a. Remove the own property with name P from O.
+      o.get_properties_mut().shift_remove(p);
+      // b. Return true.
+      Ok(true)
+    }
+    // 4. Return false.
+    Some(_) => Ok(false),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ordinaryownpropertykeys
+pub fn ordinary_own_property_keys(
+  o: &JsObject,
+) -> Result<Vec<PropertyKey>, Value> {
+  let properties = o.get_properties();
+  // 1. Let keys be a new empty List.
+  // 2. For each own property key P of O such that P is an array index, in ascending numeric
+  //    index order, do
+  //   a. Add P as the last element of keys.
+  let mut indices = properties
+    .keys()
+    .filter_map(|p| p.as_array_index().map(|index| (index, p)))
+    .collect::<Vec<_>>();
+  indices.sort_by_key(|(index, _)| *index);
+  let mut keys = indices.into_iter().map(|(_, p)| p).collect::<Vec<_>>();
+  // 3. For each own property key P of O such that Type(P) is String and P is not an array
+  //    index, in ascending chronological order of property creation, do
+  //   a. Add P as the last element of keys.
+  keys.extend(properties.keys().filter(|p| {
+    matches!(p, PropertyKey::String(_)) && p.as_array_index().is_none()
+  }));
+  // 4. For each own property key P of O such that Type(P) is Symbol, in ascending
+  //    chronological order of property creation, do
+  //   a. Add P as the last element of keys.
+  keys.extend(
+    properties
+      .keys()
+      .filter(|p| matches!(p, PropertyKey::Symbol(_))),
+  );
+  // 5. Return keys.
+  Ok(keys.into_iter().cloned().collect())
+}
+
+/// https://tc39.es/ecma262/#sec-ordinaryobjectcreate
+pub fn ordinary_object_create(
+  proto: Prototype,
+  additional_internal_slots_list: &[&'static str],
+) -> JsObject {
+  // 1. Let internalSlotsList be « [[Prototype]], [[Extensible]] ».
+  // 2. If additionalInternalSlotsList is present, append each of its elements to
+  //    internalSlotsList.
+  // 3. Let O be ! MakeBasicObject(internalSlotsList).
+  let o = JsObject::new(proto, true, &ORDINARY_INTERNAL_METHODS);
+  for name in additional_internal_slots_list {
+    o.set_internal_slot(name, InternalSlot::Value(Value::undefined()));
+  }
+  // 4. Set O.[[Prototype]] to proto.
+  // 5. Return O.
+  o
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarycreatefromconstructor
+pub fn ordinary_create_from_constructor(
+  constructor: &JsObject,
+  intrinsic_default_proto: &str,
+  internal_slots_list: &[&'static str],
+) -> Result<JsObject, Value> {
+  // 1. Assert: intrinsicDefaultProto is this specification's name of an intrinsic
+  //    object. The corresponding object must be an intrinsic that is intended to be
+  //    used as the [[Prototype]] value of an object.
+  // 2. Let proto be ? GetPrototypeFromConstructor(constructor, intrinsicDefaultProto).
+  let proto =
+    get_prototype_from_constructor(constructor, intrinsic_default_proto)?;
+  // 3. If internalSlotsList is present, let slotsList be internalSlotsList.
+  // 4. Else, let slotsList be a new empty List.
+  // 5. Return OrdinaryObjectCreate(proto, slotsList).
+  Ok(ordinary_object_create(
+    Either::A(proto),
+    internal_slots_list,
+  ))
+}
+
+/// https://tc39.es/ecma262/#sec-getprototypefromconstructor
+pub fn get_prototype_from_constructor(
+  constructor: &JsObject,
+  intrinsic_default_proto: &str,
+) -> Result<JsObject, Value> {
+  // 1. Assert: intrinsicDefaultProto is this specification's name of an intrinsic
+  //    object. The corresponding object must be an intrinsic that is intended to be
+  //    used as the [[Prototype]] value of an object.
+  // 2. Let proto be ? Get(constructor, "prototype").
+  match get(constructor, &"prototype".into())? {
+    Value::Object(proto) => Ok(proto),
+    // 3. If Type(proto) is not Object, then
+    _ => {
+      // a. Let realm be ? GetFunctionRealm(constructor).
+      let realm = get_function_realm(constructor)?;
+      // b. Set proto to realm's intrinsic object named intrinsicDefaultProto.
+      Ok(realm.intrinsic(intrinsic_default_proto))
+    }
+  }
+  // 4. Return proto.
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::create_data_property,
+    language_types::{null::JsNull, symbol::JsSymbol},
+  };
+
+  #[test]
+  fn object_create() {
+    let o = ordinary_object_create(Either::B(JsNull), &["ErrorData"]);
+    assert!(std::ptr::eq(
+      o.internal_methods(),
+      &ORDINARY_INTERNAL_METHODS
+    ));
+    assert!(matches!(o.get_prototype_of(), Either::B(JsNull)));
+    assert!(o.is_extensible().unwrap());
+    assert!(o.get_call().is_none() && o.get_construct().is_none());
+    assert!(o.has_internal_slot("ErrorData"));
+    assert!(!o.has_internal_slot("BooleanData"));
+    assert!(o.own_property_keys().unwrap().is_empty());
+
+    let child = ordinary_object_create(Either::A(o.clone()), &[]);
+    assert!(
+      matches!(child.get_prototype_of(), Either::A(proto) if JsObject::equals(&proto, &o))
+    );
+  }
+
+  #[test]
+  fn set_prototype_of() {
+    let proto = ordinary_object_create(Either::B(JsNull), &[]);
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    assert!(ordinary_set_prototype_of(&o, Either::A(proto.clone())).unwrap());
+    assert!(
+      matches!(o.get_prototype_of(), Either::A(p) if JsObject::equals(&p, &proto))
+    );
+    // a cycle isn't allowed
+    assert!(!ordinary_set_prototype_of(&proto, Either::A(o.clone())).unwrap());
+
+    assert!(ordinary_prevent_extensions(&o).unwrap());
+    assert!(!ordinary_set_prototype_of(&o, Either::B(JsNull)).unwrap());
+    assert!(
+      matches!(o.get_prototype_of(), Either::A(p) if JsObject::equals(&p, &proto))
+    );
+    // setting the same prototype still succeeds
+    assert!(ordinary_set_prototype_of(&o, Either::A(proto)).unwrap());
+  }
+
+  #[test]
+  fn redefine_non_configurable() {
+    let attributes = |enumerable, configurable| PropertyDescriptor {
+      value: None,
+      writable: None,
+      get: None,
+      set: None,
+      enumerable,
+      configurable,
+    };
+    let define = |o: &JsObject, enumerable, configurable| {
+      ordinary_define_own_property(
+        o,
+        &"a".into(),
+        attributes(enumerable, configurable),
+      )
+      .unwrap()
+    };
+    for (enumerable, flipped) in [
+      (JsBoolean::True, JsBoolean::False),
+      (JsBoolean::False, JsBoolean::True),
+    ] {
+      let o = ordinary_object_create(Either::B(JsNull), &[]);
+      let desc = PropertyDescriptor {
+        value: Some(Value::null()),
+        writable: Some(JsBoolean::False),
+        ..attributes(Some(enumerable), Some(JsBoolean::False))
+      };
+      assert!(ordinary_define_own_property(&o, &"a".into(), desc).unwrap());
+      // flipping [[Enumerable]] fails in both directions
+      assert!(!define(&o, Some(flipped), None));
+      assert!(define(&o, Some(enumerable), None));
+      assert!(!define(&o, None, Some(JsBoolean::True)));
+      assert!(define(&o, None, Some(JsBoolean::False)));
+      let current =
+        ordinary_get_own_property(&o, &"a".into()).unwrap().unwrap();
+      assert_eq!(current.enumerable, Some(enumerable));
+      assert_eq!(current.configurable, Some(JsBoolean::False));
+    }
+  }
+
+  #[test]
+  fn own_property_keys_order() {
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    for key in [
+      PropertyKey::from("b"),
+      PropertyKey::from(JsSymbol::iterator()),
+      PropertyKey::from("2"),
+      PropertyKey::from("a"),
+      PropertyKey::from("1"),
+      PropertyKey::from(JsSymbol::species()),
+    ] {
+      create_data_property(&o, &key, Value::undefined()).unwrap();
+    }
+    let expected = [
+      PropertyKey::from("1"),
+      PropertyKey::from("2"),
+      PropertyKey::from("b"),
+      PropertyKey::from("a"),
+      PropertyKey::from(JsSymbol::iterator()),
+      PropertyKey::from(JsSymbol::species()),
+    ];
+    assert_eq!(ordinary_own_property_keys(&o).unwrap(), expected);
+    // redefining a property keeps its position
+    create_data_property(&o, &"b".into(), Value::null()).unwrap();
+    assert_eq!(ordinary_own_property_keys(&o).unwrap(), expected);
+    // deleting keeps the order of the others, a recreated property is newest
+    assert!(ordinary_delete(&o, &"b".into()).unwrap());
+    assert!(ordinary_delete(&o, &JsSymbol::iterator().into()).unwrap());
+    create_data_property(&o, &"b".into(), Value::null()).unwrap();
+    assert_eq!(
+      ordinary_own_property_keys(&o).unwrap(),
+      [
+        PropertyKey::from("1"),
+        PropertyKey::from("2"),
+        PropertyKey::from("a"),
+        PropertyKey::from("b"),
+        PropertyKey::from(JsSymbol::species()),
+      ]
+    );
+  }
+}
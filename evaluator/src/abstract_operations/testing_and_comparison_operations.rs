@@ -1,11 +1,73 @@
 //! https://tc39.es/ecma262/#sec-testing-and-comparison-operations
 
-use crate::language_types::{
-  big_int::JsBigInt, boolean::JsBoolean, number::JsNumber, object::JsObject,
-  Value,
+use std::mem;
+
+use num_bigint::BigInt;
+use num_traits::FromPrimitive;
+
+use crate::{
+  abstract_operations::type_conversion::{
+    string_to_bigint, Numeric, PreferredType,
+  },
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  language_types::{
+    big_int::JsBigInt,
+    boolean::JsBoolean,
+    number::JsNumber,
+    object::{InternalSlot, JsObject},
+    Value,
+  },
+  ordinary_and_exotic_objects_behaviours::array_exotic_objects::is_array_exotic_object,
 };
 
+/// https://tc39.es/ecma262/#sec-requireobjectcoercible
+pub fn require_object_coercible(argument: &Value) -> Result<Value, Value> {
+  match argument {
+    // Undefined / Null: Throw a TypeError exception.
+    Value::Undefined(_) | Value::Null(_) => Err(throw_error(
+      ErrorKind::TypeError,
+      &format!("Cannot convert {} to object", argument.type_name()),
+    )),
+    // Otherwise: Return argument.
+    _ => Ok(argument.clone()),
+  }
+}
+
 impl Value {
+  /// https://tc39.es/ecma262/#sec-isarray
+  pub fn is_array(&self) -> Result<bool, Value> {
+    // 1. If Type(argument) is not Object, return false.
+    let argument = match self {
+      Self::Object(argument) => argument,
+      _ => return Ok(false),
+    };
+    // 2. If argument is an Array exotic object, return true.
+    if is_array_exotic_object(argument) {
+      return Ok(true);
+    }
+    // 3. If argument is a Proxy exotic object, then
+    if let Some(InternalSlot::Value(handler)) =
+      argument.get_internal_slot("ProxyHandler")
+    {
+      // a. If argument.[[ProxyHandler]] is null, throw a TypeError exception.
+      if let Self::Null(_) = handler {
+        return Err(throw_error(
+          ErrorKind::TypeError,
+          "Cannot perform 'IsArray' on a revoked proxy",
+        ));
+      }
+      // b. Let target be argument.[[ProxyTarget]].
+      // c. Return ? IsArray(target).
+      if let Some(InternalSlot::Value(target)) =
+        argument.get_internal_slot("ProxyTarget")
+      {
+        return target.is_array();
+      }
+    }
+    // 4. Return false.
+    Ok(false)
+  }
+
   /// https://tc39.es/ecma262/#sec-iscallable
   pub fn is_callable(&self) -> bool {
     // 1. If Type(argument) is not Object, return false.
@@ -22,6 +84,22 @@ impl Value {
     }
   }
 
+  /// https://tc39.es/ecma262/#sec-isconstructor
+  pub fn is_constructor(&self) -> bool {
+    // 1. If Type(argument) is not Object, return false.
+    match self {
+      Self::Object(v) => {
+        // 2. If argument has a [[Construct]] internal method, return true.
+        if v.get_construct().is_some() {
+          return true;
+        }
+        // 3. Return false.
+        false
+      }
+      _ => false,
+    }
+  }
+
   /// https://tc39.es/ecma262/#sec-ispropertykey
   pub fn is_property_key(&self) -> bool {
     // 1. If Type(argument) is String, return true.
@@ -31,10 +109,16 @@ impl Value {
   }
 }
 
+/// Whether Type(x) is the same as Type(y).
+fn same_type(x: &Value, y: &Value) -> bool {
+  mem::discriminant(x) == mem::discriminant(y)
+}
+
 /// https://tc39.es/ecma262/#sec-samevalue
 pub fn same_value(x: &Value, y: &Value) -> JsBoolean {
-  // 1. If Type(x) is different from Type(y), return false.
   match (x, y) {
+    // 1. If Type(x) is different from Type(y), return false.
+    _ if !same_type(x, y) => JsBoolean::False,
     // 2. If Type(x) is Number, then
     //   a. Return ! Number::sameValue(x, y).
     (Value::Number(x), Value::Number(y)) => JsNumber::same_value(x, y),
@@ -42,19 +126,21 @@ pub fn same_value(x: &Value, y: &Value) -> JsBoolean {
     //   a. Return ! BigInt::sameValue(x, y).
     (Value::BigInt(x), Value::BigInt(y)) => JsBigInt::same_value(x, y),
     // 4. Return ! SameValueNonNumeric(x, y).
-    _ if matches!(
-      (x, y),
-      (Value::Boolean(_), Value::Boolean(_))
-        | (Value::Null(_), Value::Null(_))
-        | (Value::Undefined(_), Value::Undefined(_))
-        | (Value::String(_), Value::String(_))
-        | (Value::Object(_), Value::Object(_))
-        | (Value::Symbol(_), Value::Symbol(_))
-    ) =>
-    {
-      same_value_non_numeric(x, y)
-    }
-    _ => JsBoolean::False,
+    _ => same_value_non_numeric(x, y),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-samevaluezero
+pub fn same_value_zero(x: &Value, y: &Value) -> JsBoolean {
+  match (x, y) {
+    // 1. If Type(x) is different from Type(y), return false.
+    _ if !same_type(x, y) => JsBoolean::False,
+    // 2. If Type(x) is Number or BigInt, then
+    //   a. Return ! Type(x)::sameValueZero(x, y).
+    (Value::Number(x), Value::Number(y)) => JsNumber::same_value_zero(x, y),
+    (Value::BigInt(x), Value::BigInt(y)) => JsBigInt::same_value_zero(x, y),
+    // 3. Return ! SameValueNonNumeric(x, y).
+    _ => same_value_non_numeric(x, y),
   }
 }
 
@@ -83,3 +169,275 @@ pub fn same_value_non_numeric(x: &Value, y: &Value) -> JsBoolean {
     _ => panic!("expect same type"),
   }
 }
+
+/// https://tc39.es/ecma262/#sec-islooselyequal
+pub fn is_loosely_equal(x: &Value, y: &Value) -> Result<JsBoolean, Value> {
+  match (x, y) {
+    // 1. If Type(x) is the same as Type(y), then
+    //   a. Return IsStrictlyEqual(x, y).
+    _ if same_type(x, y) => Ok(is_strictly_equal(x, y)),
+    // 2. If x is null and y is undefined, return true.
+    // 3. If x is undefined and y is null, return true.
+    (Value::Null(_), Value::Undefined(_))
+    | (Value::Undefined(_), Value::Null(_)) => Ok(JsBoolean::True),
+    // 5. If x is a Number and y is a String, return ! IsLooselyEqual(x,
+    //    ! ToNumber(y)).
+    (Value::Number(_), Value::String(_)) => {
+      is_loosely_equal(x, &y.to_number()?.into())
+    }
+    // 6. If x is a String and y is a Number, return ! IsLooselyEqual(!
+    //    ToNumber(x), y).
+    (Value::String(_), Value::Number(_)) => {
+      is_loosely_equal(&x.to_number()?.into(), y)
+    }
+    // 7. If x is a BigInt and y is a String, then
+    //   a. Let n be StringToBigInt(y).
+    //   b. If n is undefined, return false.
+    //   c. Return ! IsLooselyEqual(x, n).
+    (Value::BigInt(_), Value::String(y)) => {
+      match string_to_bigint(&y.to_string_lossy()) {
+        Some(n) => is_loosely_equal(x, &n.into()),
+        None => Ok(JsBoolean::False),
+      }
+    }
+    // 8. If x is a String and y is a BigInt, return ! IsLooselyEqual(y, x).
+    (Value::String(_), Value::BigInt(_)) => is_loosely_equal(y, x),
+    // 9. If x is a Boolean, return ! IsLooselyEqual(! ToNumber(x), y).
+    (Value::Boolean(_), _) => is_loosely_equal(&x.to_number()?.into(), y),
+    // 10. If y is a Boolean, return ! IsLooselyEqual(x, ! ToNumber(y)).
+    (_, Value::Boolean(_)) => is_loosely_equal(x, &y.to_number()?.into()),
+    // 11. If x is either a String, a Number, a BigInt, or a Symbol and y is
+    //     an Object, return ! IsLooselyEqual(x, ? ToPrimitive(y)).
+    (
+      Value::String(_) | Value::Number(_) | Value::BigInt(_) | Value::Symbol(_),
+      Value::Object(_),
+    ) => is_loosely_equal(x, &y.to_primitive(PreferredType::Default)?),
+    // 12. If x is an Object and y is either a String, a Number, a BigInt,
+    //     or a Symbol, return ! IsLooselyEqual(? ToPrimitive(x), y).
+    (
+      Value::Object(_),
+      Value::String(_) | Value::Number(_) | Value::BigInt(_) | Value::Symbol(_),
+    ) => is_loosely_equal(&x.to_primitive(PreferredType::Default)?, y),
+    // 13. If x is a BigInt and y is a Number, or if x is a Number and y is a
+    //     BigInt, then
+    //   a. If x is not finite or y is not finite, return false.
+    //   b. If ℝ(x) = ℝ(y), return true; otherwise return false.
+    (Value::BigInt(x), Value::Number(y))
+    | (Value::Number(y), Value::BigInt(x)) => Ok(
+      (y.fract() == 0.0 && BigInt::from_f64(**y).as_ref() == Some(&**x)).into(),
+    ),
+    // 14. Return false.
+    _ => Ok(JsBoolean::False),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-isstrictlyequal
+pub fn is_strictly_equal(x: &Value, y: &Value) -> JsBoolean {
+  match (x, y) {
+    // 1. If Type(x) is different from Type(y), return false.
+    _ if !same_type(x, y) => JsBoolean::False,
+    // 2. If Type(x) is Number or BigInt, then
+    //   a. Return ! Type(x)::equal(x, y).
+    (Value::Number(x), Value::Number(y)) => JsNumber::equal(x, y),
+    (Value::BigInt(x), Value::BigInt(y)) => JsBigInt::equal(x, y),
+    // 3. Return ! SameValueNonNumeric(x, y).
+    _ => same_value_non_numeric(x, y),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-islessthan, `None` stands for undefined,
+/// i.e. at least one operand is NaN.
+pub fn is_less_than(
+  x: &Value,
+  y: &Value,
+  left_first: bool,
+) -> Result<Option<bool>, Value> {
+  // 1. If the LeftFirst flag is true, then
+  //   a. Let px be ? ToPrimitive(x, number).
+  //   b. Let py be ? ToPrimitive(y, number).
+  // 2. Else,
+  //   a. NOTE: The order of evaluation needs to be reversed to preserve
+  //      left to right evaluation.
+  //   b. Let py be ? ToPrimitive(y, number).
+  //   c. Let px be ? ToPrimitive(x, number).
+  let (px, py) = if left_first {
+    let px = x.to_primitive(PreferredType::Number)?;
+    (px, y.to_primitive(PreferredType::Number)?)
+  } else {
+    let py = y.to_primitive(PreferredType::Number)?;
+    (x.to_primitive(PreferredType::Number)?, py)
+  };
+  match (&px, &py) {
+    // 3. If px is a String and py is a String, then
+    //   a. Let lx be the length of px.
+    //   b. Let ly be the length of py.
+    //   c. For each integer i such that 0 ≤ i < min(lx, ly), in ascending
+    //      order, do
+    //     i. Let cx be the numeric value of the code unit at index i within px.
+    //     ii. Let cy be the numeric value of the code unit at index i within py.
+    //     iii. If cx < cy, return true.
+    //     iv. If cx > cy, return false.
+    //   d. If lx < ly, return true. Otherwise, return false.
+    (Value::String(px), Value::String(py)) => Ok(Some(px < py)),
+    // 4. Else,
+    //   a. If px is a BigInt and py is a String, then
+    //     i. Let ny be StringToBigInt(py).
+    //     ii. If ny is undefined, return undefined.
+    //     iii. Return BigInt::lessThan(px, ny).
+    (Value::BigInt(px), Value::String(py)) => {
+      Ok(string_to_bigint(&py.to_string_lossy()).map(|ny| **px < *ny))
+    }
+    //   b. If px is a String and py is a BigInt, then
+    //     i. Let nx be StringToBigInt(px).
+    //     ii. If nx is undefined, return undefined.
+    //     iii. Return BigInt::lessThan(nx, py).
+    (Value::String(px), Value::BigInt(py)) => {
+      Ok(string_to_bigint(&px.to_string_lossy()).map(|nx| *nx < **py))
+    }
+    _ => {
+      // c. NOTE: Because px and py are primitive values, evaluation order
+      //    is not important.
+      // d. Let nx be ? ToNumeric(px).
+      // e. Let ny be ? ToNumeric(py).
+      let nx = px.to_numeric()?;
+      let ny = py.to_numeric()?;
+      Ok(match (nx, ny) {
+        // f. If Type(nx) is the same as Type(ny), then
+        //   i. If nx is a Number, then
+        //     1. Return Number::lessThan(nx, ny).
+        (Numeric::Number(nx), Numeric::Number(ny)) => {
+          (!nx.is_nan() && !ny.is_nan()).then_some(nx < ny)
+        }
+        //   ii. Else,
+        //     1. Assert: nx is a BigInt.
+        //     2. Return BigInt::lessThan(nx, ny).
+        (Numeric::BigInt(nx), Numeric::BigInt(ny)) => Some(*nx < *ny),
+        // g. Assert: nx is a BigInt and ny is a Number, or nx is a Number
+        //    and ny is a BigInt.
+        (Numeric::BigInt(nx), Numeric::Number(ny)) => {
+          big_int_less_than_number(&nx, ny)
+        }
+        (Numeric::Number(nx), Numeric::BigInt(ny)) => {
+          number_less_than_big_int(nx, &ny)
+        }
+      })
+    }
+  }
+}
+
+/// The mixed steps of IsLessThan, the BigInt is compared with the exact
+/// value of the Number.
+fn big_int_less_than_number(nx: &BigInt, ny: f64) -> Option<bool> {
+  // h. If nx or ny is NaN, return undefined.
+  // i. If nx is -∞𝔽 or ny is +∞𝔽, return true.
+  // j. If nx is +∞𝔽 or ny is -∞𝔽, return false.
+  // k. If ℝ(nx) < ℝ(ny), return true; otherwise return false.
+  if ny.is_nan() {
+    return None;
+  }
+  if ny.is_infinite() {
+    return Some(ny > 0.0);
+  }
+  // an integer is less than ny iff it's less than ceil(ny)
+  let ny = BigInt::from_f64(ny.ceil()).expect("a finite Number");
+  Some(*nx < ny)
+}
+
+fn number_less_than_big_int(nx: f64, ny: &BigInt) -> Option<bool> {
+  if nx.is_nan() {
+    return None;
+  }
+  if nx.is_infinite() {
+    return Some(nx < 0.0);
+  }
+  // nx is less than an integer iff floor(nx) is
+  let nx = BigInt::from_f64(nx.floor()).expect("a finite Number");
+  Some(nx < *ny)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn strictly_equal() {
+    let nan = Value::from(f64::NAN);
+    assert_eq!(is_strictly_equal(&nan, &nan), JsBoolean::False);
+    assert_eq!(same_value(&nan, &nan), JsBoolean::True);
+    let (zero, negative_zero) = (Value::from(0.0), Value::from(-0.0));
+    assert_eq!(is_strictly_equal(&zero, &negative_zero), JsBoolean::True);
+    assert_eq!(same_value(&zero, &negative_zero), JsBoolean::False);
+    assert_eq!(
+      is_strictly_equal(&Value::from("1"), &Value::from(1.0)),
+      JsBoolean::False
+    );
+    assert_eq!(
+      is_strictly_equal(&Value::from("a"), &Value::from("a")),
+      JsBoolean::True
+    );
+    let big_int = |n: i32| Value::from(JsBigInt::from(BigInt::from(n)));
+    assert_eq!(is_strictly_equal(&big_int(1), &big_int(1)), JsBoolean::True);
+    assert_eq!(
+      is_strictly_equal(&big_int(1), &Value::from(1.0)),
+      JsBoolean::False
+    );
+  }
+
+  #[test]
+  fn same_value_zero_of_values() {
+    let nan = Value::from(f64::NAN);
+    assert_eq!(same_value_zero(&nan, &nan), JsBoolean::True);
+    let (zero, negative_zero) = (Value::from(0.0), Value::from(-0.0));
+    assert_eq!(same_value_zero(&zero, &negative_zero), JsBoolean::True);
+    assert_eq!(
+      same_value_zero(&Value::from("a"), &Value::from("b")),
+      JsBoolean::False
+    );
+    assert_eq!(
+      same_value_zero(&Value::from("a"), &Value::from("a")),
+      JsBoolean::True
+    );
+    assert_eq!(same_value_zero(&Value::from("0"), &zero), JsBoolean::False);
+    assert_eq!(
+      same_value_zero(&Value::undefined(), &Value::null()),
+      JsBoolean::False
+    );
+  }
+
+  #[test]
+  fn loosely_equal() {
+    let equal = |x: Value, y: Value| is_loosely_equal(&x, &y).unwrap();
+    let big_int = |n: i32| Value::from(JsBigInt::from(BigInt::from(n)));
+    assert_eq!(equal(Value::null(), Value::undefined()), JsBoolean::True);
+    assert_eq!(equal(Value::null(), Value::from(0.0)), JsBoolean::False);
+    assert_eq!(equal(Value::from(1.0), Value::from("1")), JsBoolean::True);
+    assert_eq!(equal(Value::from(" 1 "), Value::from(1.0)), JsBoolean::True);
+    assert_eq!(equal(Value::from(0.0), Value::from(false)), JsBoolean::True);
+    assert_eq!(equal(Value::from(true), Value::from("1")), JsBoolean::True);
+    assert_eq!(equal(big_int(1), Value::from(1.0)), JsBoolean::True);
+    assert_eq!(equal(Value::from(1.5), big_int(1)), JsBoolean::False);
+    assert_eq!(equal(big_int(1), Value::from(f64::NAN)), JsBoolean::False);
+    assert_eq!(equal(Value::from("1"), big_int(1)), JsBoolean::True);
+    assert_eq!(equal(big_int(1), Value::from("x")), JsBoolean::False);
+    let nan = Value::from(f64::NAN);
+    assert_eq!(equal(nan.clone(), nan), JsBoolean::False);
+  }
+
+  #[test]
+  fn less_than() {
+    let less_than = |x: Value, y: Value| is_less_than(&x, &y, true).unwrap();
+    let big_int = |n: i32| Value::from(JsBigInt::from(BigInt::from(n)));
+    assert_eq!(less_than(Value::from("a"), Value::from("b")), Some(true));
+    // code units compare, not the numeric values
+    assert_eq!(less_than(Value::from("10"), Value::from("9")), Some(true));
+    assert_eq!(less_than(Value::from(1.0), Value::from(2.0)), Some(true));
+    assert_eq!(less_than(Value::from(f64::NAN), Value::from(1.0)), None);
+    assert_eq!(less_than(big_int(1), Value::from(2.0)), Some(true));
+    assert_eq!(less_than(Value::from(1.5), big_int(1)), Some(false));
+    assert_eq!(less_than(big_int(1), Value::from("x")), None);
+    assert_eq!(
+      is_less_than(&Value::from(2.0), &Value::from(1.0), false).unwrap(),
+      Some(false)
+    );
+  }
+}
@@ -1,10 +1,101 @@
 //! https://tc39.es/ecma262/#sec-type-conversion
 
-use num_traits::Zero;
+use num_bigint::BigInt;
+use num_traits::{ToPrimitive, Zero};
 
-use crate::language_types::{boolean::JsBoolean, Value};
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{call, get, get_method},
+    ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+  },
+  agent::current_realm,
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  helpers::Either,
+  language_types::{
+    big_int::JsBigInt,
+    boolean::JsBoolean,
+    number::JsNumber,
+    object::{InternalSlot, JsObject, PropertyKey},
+    string::JsString,
+    symbol::JsSymbol,
+    Value,
+  },
+};
+
+/// The optional hint of `ToPrimitive`, `Default` stands for an absent
+/// preferredType.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferredType {
+  Default,
+  String,
+  Number,
+}
+
+/// The result of `ToNumeric`, the operands of the numeric operators are
+/// either both Numbers or both BigInts.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Numeric {
+  Number(f64),
+  BigInt(JsBigInt),
+}
+
+impl From<Numeric> for Value {
+  fn from(numeric: Numeric) -> Self {
+    match numeric {
+      Numeric::Number(n) => Value::from(n),
+      Numeric::BigInt(n) => Value::from(n),
+    }
+  }
+}
 
 impl Value {
+  /// https://tc39.es/ecma262/#sec-toprimitive
+  pub fn to_primitive(
+    &self,
+    preferred_type: PreferredType,
+  ) -> Result<Value, Value> {
+    // 1. Assert: input is an ECMAScript language value.
+    // 2. If Type(input) is Object, then
+    let input = match self {
+      Value::Object(input) => input,
+      // 3. Return input.
+      _ => return Ok(self.clone()),
+    };
+    // a. Let exoticToPrim be ? GetMethod(input, @@toPrimitive).
+    let exotic_to_prim = get_method(self, &JsSymbol::to_primitive().into())?;
+    // b. If exoticToPrim is not undefined, then
+    if let Some(exotic_to_prim) = exotic_to_prim {
+      // i. If preferredType is not present, let hint be "default".
+      // ii. Else if preferredType is string, let hint be "string".
+      // iii. Else,
+      //   1. Assert: preferredType is number.
+      //   2. Let hint be "number".
+      let hint = match preferred_type {
+        PreferredType::Default => "default",
+        PreferredType::String => "string",
+        PreferredType::Number => "number",
+      };
+      // iv. Let result be ? Call(exoticToPrim, input, « hint »).
+      let result = call(&exotic_to_prim, self, &[Value::from(hint)])?;
+      // v. If Type(result) is not Object, return result.
+      // vi. Throw a TypeError exception.
+      return match result {
+        Value::Object(_) => Err(throw_error(
+          ErrorKind::TypeError,
+          "Cannot convert object to primitive value",
+        )),
+        result => Ok(result),
+      };
+    }
+    // c. If preferredType is not present, let preferredType be number.
+    // d. Return ? OrdinaryToPrimitive(input, preferredType).
+    let preferred_type = match preferred_type {
+      PreferredType::String => PreferredType::String,
+      PreferredType::Default | PreferredType::Number => PreferredType::Number,
+    };
+    ordinary_to_primitive(input, preferred_type)
+  }
+
   /// https://tc39.es/ecma262/#sec-toboolean
   pub fn to_boolean(&self) -> JsBoolean {
     match self {
@@ -36,4 +127,667 @@ impl Value {
       }
     }
   }
+
+  /// https://tc39.es/ecma262/#sec-tonumber
+  pub fn to_number(&self) -> Result<JsNumber, Value> {
+    match self {
+      // 1. If argument is a Number, return argument.
+      Value::Number(n) => Ok(*n),
+      // 2. If argument is either a Symbol or a BigInt, throw a TypeError exception.
+      Value::Symbol(_) | Value::BigInt(_) => Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("Cannot convert a {} value to a number", self.type_name()),
+      )),
+      // 3. If argument is undefined, return NaN.
+      Value::Undefined(_) => Ok(f64::NAN.into()),
+      // 4. If argument is either null or false, return +0𝔽.
+      // 5. If argument is true, return 1𝔽.
+      Value::Null(_) | Value::Boolean(JsBoolean::False) => Ok(0.0.into()),
+      Value::Boolean(JsBoolean::True) => Ok(1.0.into()),
+      // 6. If argument is a String, return StringToNumber(argument).
+      Value::String(s) => Ok(string_to_number(&s.to_string_lossy())),
+      // 7. Assert: argument is an Object.
+      // 8. Let primValue be ? ToPrimitive(argument, number).
+      // 9. Assert: primValue is not an Object.
+      // 10. Return ? ToNumber(primValue).
+      Value::Object(_) => self.to_primitive(PreferredType::Number)?.to_number(),
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-tonumeric
+  pub fn to_numeric(&self) -> Result<Numeric, Value> {
+    // 1. Let primValue be ? ToPrimitive(value, number).
+    let prim_value = self.to_primitive(PreferredType::Number)?;
+    // 2. If primValue is a BigInt, return primValue.
+    if let Value::BigInt(n) = prim_value {
+      return Ok(Numeric::BigInt(n));
+    }
+    // 3. Return ? ToNumber(primValue).
+    Ok(Numeric::Number(*prim_value.to_number()?))
+  }
+
+  /// https://tc39.es/ecma262/#sec-tointegerorinfinity
+  pub fn to_integer_or_infinity(&self) -> Result<f64, Value> {
+    // 1. Let number be ? ToNumber(argument).
+    let number = *self.to_number()?;
+    // 2. If number is one of NaN, +0𝔽, or -0𝔽, return 0.
+    if number.is_nan() || number == 0.0 {
+      return Ok(0.0);
+    }
+    // 3. If number is +∞𝔽, return +∞.
+    // 4. If number is -∞𝔽, return -∞.
+    // 5. Let integer be floor(abs(ℝ(number))).
+    // 6. If number < -0𝔽, set integer to -integer.
+    // 7. Return integer.
+    Ok(number.trunc())
+  }
+
+  /// https://tc39.es/ecma262/#sec-toint32
+  pub fn to_int32(&self) -> Result<i32, Value> {
+    // 1. Let number be ? ToNumber(argument).
+    // 2. ..., the rest is JsNumber::to_int32.
+    Ok(self.to_number()?.to_int32())
+  }
+
+  /// https://tc39.es/ecma262/#sec-touint32
+  pub fn to_uint32(&self) -> Result<u32, Value> {
+    // 1. Let number be ? ToNumber(argument).
+    // 2. ..., the rest is JsNumber::to_uint32.
+    Ok(self.to_number()?.to_uint32())
+  }
+
+  /// https://tc39.es/ecma262/#sec-tobigint
+  pub fn to_bigint(&self) -> Result<JsBigInt, Value> {
+    // 1. Let prim be ? ToPrimitive(argument, number).
+    let prim = self.to_primitive(PreferredType::Number)?;
+    // 2. Return the value that prim corresponds to in Table 12.
+    match prim {
+      Value::Undefined(_)
+      | Value::Null(_)
+      | Value::Number(_)
+      | Value::Symbol(_) => Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("Cannot convert {} to a BigInt", prim.type_name()),
+      )),
+      Value::Boolean(b) => {
+        Ok(BigInt::from((b == JsBoolean::True) as u8).into())
+      }
+      Value::BigInt(n) => Ok(n),
+      Value::String(s) => {
+        string_to_bigint(&s.to_string_lossy()).ok_or_else(|| {
+          throw_error(
+            ErrorKind::SyntaxError,
+            &format!("Cannot convert {} to a BigInt", s),
+          )
+        })
+      }
+      Value::Object(_) => unreachable!("ToPrimitive returns a primitive"),
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-tostring
+  pub fn to_string(&self) -> Result<JsString, Value> {
+    match self {
+      // 1. If argument is a String, return argument.
+      Value::String(s) => Ok(s.clone()),
+      // 2. If argument is a Symbol, throw a TypeError exception.
+      Value::Symbol(_) => Err(throw_error(
+        ErrorKind::TypeError,
+        "Cannot convert a Symbol value to a string",
+      )),
+      // 3. If argument is undefined, return "undefined".
+      Value::Undefined(_) => Ok("undefined".into()),
+      // 4. If argument is null, return "null".
+      Value::Null(_) => Ok("null".into()),
+      // 5. If argument is true, return "true".
+      Value::Boolean(JsBoolean::True) => Ok("true".into()),
+      // 6. If argument is false, return "false".
+      Value::Boolean(JsBoolean::False) => Ok("false".into()),
+      // 7. If argument is a Number, return Number::toString(argument, 10).
+      Value::Number(n) => Ok(JsNumber::to_string(n)),
+      // 8. If argument is a BigInt, return BigInt::toString(argument, 10).
+      Value::BigInt(n) => Ok(n.to_str_radix(10).into()),
+      // 9. Assert: argument is an Object.
+      // 10. Let primValue be ? ToPrimitive(argument, string).
+      // 11. Assert: primValue is not an Object.
+      // 12. Return ? ToString(primValue).
+      Value::Object(_) => self.to_primitive(PreferredType::String)?.to_string(),
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-toobject
+  pub fn to_object(&self) -> Result<JsObject, Value> {
+    // TODO: %Boolean.prototype%, %Number.prototype%, %Symbol.prototype% and
+    // %BigInt.prototype%, the wrappers inherit from %Object.prototype% until
+    // they exist
+    let wrap = |slot, prototype| {
+      let prototype = current_realm().intrinsic(prototype);
+      let o = ordinary_object_create(Either::A(prototype), &[]);
+      o.set_internal_slot(slot, InternalSlot::Value(self.clone()));
+      Ok(o)
+    };
+    match self {
+      // Undefined, Null: Throw a TypeError exception.
+      Value::Undefined(_) | Value::Null(_) => Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("Cannot convert {} to object", self.type_name()),
+      )),
+      // Boolean: Return a new Boolean object whose [[BooleanData]] internal
+      // slot is set to argument.
+      Value::Boolean(_) => wrap("BooleanData", "Object.prototype"),
+      // Number: Return a new Number object whose [[NumberData]] internal
+      // slot is set to argument.
+      Value::Number(_) => wrap("NumberData", "Object.prototype"),
+      // String: Return a new String object whose [[StringData]] internal
+      // slot is set to argument.
+      Value::String(_) => wrap("StringData", "String.prototype"),
+      // Symbol: Return a new Symbol object whose [[SymbolData]] internal
+      // slot is set to argument.
+      Value::Symbol(_) => wrap("SymbolData", "Object.prototype"),
+      // BigInt: Return a new BigInt object whose [[BigIntData]] internal
+      // slot is set to argument.
+      Value::BigInt(_) => wrap("BigIntData", "Object.prototype"),
+      // Object: Return argument.
+      Value::Object(o) => Ok(o.clone()),
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-topropertykey
+  pub fn to_property_key(&self) -> Result<PropertyKey, Value> {
+    // 1. Let key be ? ToPrimitive(argument, string).
+    let key = self.to_primitive(PreferredType::String)?;
+    // 2. If Type(key) is Symbol, then
+    //   a. Return key.
+    // 3. Return ! ToString(key).
+    match key {
+      Value::Symbol(key) => Ok(key.into()),
+      key => key.to_string().map(PropertyKey::from),
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-tolength
+  pub fn to_length(&self) -> Result<f64, Value> {
+    // 1. Let len be ? ToIntegerOrInfinity(argument).
+    let len = self.to_integer_or_infinity()?;
+    // 2. If len ≤ 0, return +0𝔽.
+    // 3. Return 𝔽(min(len, 2^53 - 1)).
+    Ok(len.clamp(0.0, 9007199254740991.0))
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarytoprimitive
+pub fn ordinary_to_primitive(
+  o: &JsObject,
+  hint: PreferredType,
+) -> Result<Value, Value> {
+  // 1. If hint is string, then
+  //   a. Let methodNames be « "toString", "valueOf" ».
+  // 2. Else,
+  //   a. Let methodNames be « "valueOf", "toString" ».
+  let method_names = if hint == PreferredType::String {
+    ["toString", "valueOf"]
+  } else {
+    ["valueOf", "toString"]
+  };
+  // 3. For each element name of methodNames, do
+  for name in method_names {
+    // a. Let method be ? Get(O, name).
+    let method = get(o, &name.into())?;
+    // b. If IsCallable(method) is true, then
+    if method.is_callable() {
+      // i. Let result be ? Call(method, O).
+      let result = call(&method, &o.clone().into(), &[])?;
+      // ii. If Type(result) is not Object, return result.
+      if !matches!(result, Value::Object(_)) {
+        return Ok(result);
+      }
+    }
+  }
+  // 4. Throw a TypeError exception.
+  Err(throw_error(
+    ErrorKind::TypeError,
+    "Cannot convert object to primitive value",
+  ))
+}
+
+/// https://tc39.es/ecma262/#sec-stringtonumber
+pub fn string_to_number(s: &str) -> JsNumber {
+  // 1. Let text be ! StringToCodePoints(str).
+  // 2. Let literal be ParseText(text, StringNumericLiteral).
+  // 3. If literal is a List of errors, return NaN.
+  // 4. Return StringNumericValue of literal.
+  let text = s.trim_matches(is_str_white_space_char);
+  if text.is_empty() {
+    return 0.0.into();
+  }
+  let non_decimal = [("0x", 16), ("0X", 16), ("0o", 8), ("0O", 8)]
+    .into_iter()
+    .chain([("0b", 2), ("0B", 2)])
+    .find_map(|(prefix, radix)| text.strip_prefix(prefix).map(|d| (d, radix)));
+  if let Some((digits, radix)) = non_decimal {
+    return BigInt::parse_bytes(digits.as_bytes(), radix)
+      .filter(|_| digits.chars().all(|c| c.is_digit(radix)))
+      .and_then(|n| n.to_f64())
+      .unwrap_or(f64::NAN)
+      .into();
+  }
+  let (sign, unsigned) = match text.as_bytes()[0] {
+    b'+' => (1.0, &text[1..]),
+    b'-' => (-1.0, &text[1..]),
+    _ => (1.0, text),
+  };
+  if unsigned == "Infinity" {
+    return (sign * f64::INFINITY).into();
+  }
+  if !is_str_unsigned_decimal_literal(unsigned) {
+    return f64::NAN.into();
+  }
+  (sign * unsigned.parse::<f64>().unwrap_or(f64::NAN)).into()
+}
+
+/// https://tc39.es/ecma262/#sec-stringtobigint, `None` stands for undefined.
+pub fn string_to_bigint(s: &str) -> Option<JsBigInt> {
+  // 1. Let text be StringToCodePoints(str).
+  // 2. Let literal be ParseText(text, StringIntegerLiteral).
+  // 3. If literal is a List of errors, return undefined.
+  // 4. Let mv be the MV of literal.
+  // 5. Assert: mv is an integer.
+  // 6. Return ℤ(mv).
+  let text = s.trim_matches(is_str_white_space_char);
+  if text.is_empty() {
+    return Some(BigInt::zero().into());
+  }
+  // only decimal digits may be signed
+  let (digits, radix) = [("0x", 16), ("0X", 16), ("0o", 8), ("0O", 8)]
+    .into_iter()
+    .chain([("0b", 2), ("0B", 2)])
+    .find_map(|(prefix, radix)| text.strip_prefix(prefix).map(|d| (d, radix)))
+    .unwrap_or((text, 10));
+  let unsigned = match radix {
+    10 => digits.strip_prefix(['+', '-']).unwrap_or(digits),
+    _ => digits,
+  };
+  if unsigned.is_empty() || !unsigned.chars().all(|c| c.is_digit(radix)) {
+    return None;
+  }
+  BigInt::parse_bytes(digits.as_bytes(), radix).map(JsBigInt::from)
+}
+
+/// https://tc39.es/ecma262/#sec-canonicalnumericindexstring, `None` stands
+/// for undefined.
+pub fn canonical_numeric_index_string(argument: &str) -> Option<JsNumber> {
+  // 1. If argument is "-0", return -0𝔽.
+  if argument == "-0" {
+    return Some((-0.0).into());
+  }
+  // 2. Let n be ! ToNumber(argument).
+  let n = string_to_number(argument);
+  // 3. If ! ToString(n) is argument, return n.
+  // "NaN", "Infinity" and "-Infinity" round trip too.
+  if JsNumber::to_string(&n) == argument {
+    return Some(n);
+  }
+  // 4. Return undefined.
+  None
+}
+
+/// https://tc39.es/ecma262/#sec-typeof-operator-runtime-semantics-evaluation,
+/// the type of `val` after GetValue.
+pub fn type_of(val: &Value) -> JsString {
+  match val {
+    // 4. If val is undefined, return "undefined".
+    Value::Undefined(_) => "undefined",
+    // 5. If val is null, return "object".
+    Value::Null(_) => "object",
+    // 6. If val is a String, return "string".
+    Value::String(_) => "string",
+    // 7. If val is a Symbol, return "symbol".
+    Value::Symbol(_) => "symbol",
+    // 8. If val is a Boolean, return "boolean".
+    Value::Boolean(_) => "boolean",
+    // 9. If val is a Number, return "number".
+    Value::Number(_) => "number",
+    // 10. If val is a BigInt, return "bigint".
+    Value::BigInt(_) => "bigint",
+    // 11. Assert: val is an Object.
+    // 12. NOTE: This step is replaced in section B.3.6.3.
+    // 13. If val has a [[Call]] internal slot, return "function".
+    Value::Object(_) if val.is_callable() => "function",
+    // 14. Return "object".
+    Value::Object(_) => "object",
+  }
+  .into()
+}
+
+/// https://tc39.es/ecma262/#prod-StrWhiteSpaceChar
+fn is_str_white_space_char(c: char) -> bool {
+  matches!(
+    c,
+    '\u{0009}'
+      | '\u{000B}'
+      | '\u{000C}'
+      | '\u{FEFF}'
+      | '\u{000A}'
+      | '\u{000D}'
+      | '\u{2028}'
+      | '\u{2029}'
+  ) || (c != '\u{0085}' && c.is_whitespace())
+}
+
+/// https://tc39.es/ecma262/#prod-StrUnsignedDecimalLiteral, without
+/// `Infinity`, Rust's `f64` parsing accepts `inf` and `nan` as well.
+fn is_str_unsigned_decimal_literal(s: &str) -> bool {
+  let (mantissa, exponent) = match s.find(['e', 'E']) {
+    Some(i) => (&s[..i], Some(&s[i + 1..])),
+    None => (s, None),
+  };
+  let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+  let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+  if !is_digits(int) || !is_digits(frac) || int.is_empty() && frac.is_empty() {
+    return false;
+  }
+  match exponent {
+    Some(exponent) => {
+      let digits = exponent.strip_prefix(['+', '-']).unwrap_or(exponent);
+      !digits.is_empty() && is_digits(digits)
+    }
+    None => true,
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::ordinary_object_internal_methods_and_internal_slots::ORDINARY_INTERNAL_METHODS,
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    language_types::{null::JsNull, object::InternalMethods},
+    specification_types::property_descriptor::PropertyDescriptor,
+    test_helpers::error_name,
+  };
+
+  static VALUE_OF: InternalMethods = InternalMethods {
+    call: Some(|_, _, _| Ok(Value::from(42.0))),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+  static RETURN_HINT: InternalMethods = InternalMethods {
+    call: Some(|_, _, arguments| Ok(arguments[0].clone())),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+  static RETURN_THIS: InternalMethods = InternalMethods {
+    call: Some(|_, this, _| Ok(this.clone())),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+  fn define(o: &JsObject, key: impl Into<PropertyKey>, value: Value) {
+    o.get_properties_mut().insert(
+      key.into(),
+      PropertyDescriptor {
+        value: Some(value),
+        writable: Some(JsBoolean::True),
+        get: None,
+        set: None,
+        enumerable: Some(JsBoolean::True),
+        configurable: Some(JsBoolean::True),
+      },
+    );
+  }
+
+  #[test]
+  fn type_of() {
+    let cases = [
+      (Value::undefined(), "undefined"),
+      (Value::null(), "object"),
+      (Value::from(""), "string"),
+      (Value::from(JsSymbol::iterator()), "symbol"),
+      (Value::from(false), "boolean"),
+      (Value::from(f64::NAN), "number"),
+      (Value::from(JsBigInt::from(BigInt::from(0))), "bigint"),
+      (
+        JsObject::new(Either::B(JsNull), true, &ORDINARY_INTERNAL_METHODS)
+          .into(),
+        "object",
+      ),
+      (
+        JsObject::new(Either::B(JsNull), true, &VALUE_OF).into(),
+        "function",
+      ),
+    ];
+    for (val, expected) in cases {
+      assert_eq!(super::type_of(&val), expected);
+    }
+  }
+
+  #[test]
+  fn string_to_number() {
+    let cases = [
+      ("", 0.0),
+      ("  \n42\t", 42.0),
+      ("-1.5e3", -1500.0),
+      (".5", 0.5),
+      ("5.", 5.0),
+      ("0x1F", 31.0),
+      ("0b101", 5.0),
+      ("-Infinity", f64::NEG_INFINITY),
+    ];
+    for (s, expected) in cases {
+      assert_eq!(*super::string_to_number(s), expected, "{:?}", s);
+    }
+    for s in ["inf", "NaN", "1_000", "0x", "-0x1", "1e", ".", "12px"] {
+      assert!(super::string_to_number(s).is_nan(), "{:?}", s);
+    }
+  }
+
+  #[test]
+  fn canonical_numeric_index_string() {
+    let cases = [
+      ("0", 0.0),
+      ("1.5", 1.5),
+      ("-1", -1.0),
+      ("1e+21", 1e21),
+      ("Infinity", f64::INFINITY),
+      ("-Infinity", f64::NEG_INFINITY),
+    ];
+    for (s, expected) in cases {
+      let n = super::canonical_numeric_index_string(s);
+      assert_eq!(n.map(|n| *n), Some(expected), "{:?}", s);
+    }
+    let negative_zero = super::canonical_numeric_index_string("-0").unwrap();
+    assert!(*negative_zero == 0.0 && negative_zero.is_sign_negative());
+    assert!(super::canonical_numeric_index_string("NaN")
+      .unwrap()
+      .is_nan());
+    for s in [
+      "", "01", "+1", "1.0", "1e3", " 1", "0x1", "-NaN", "infinity",
+    ] {
+      assert!(
+        super::canonical_numeric_index_string(s).is_none(),
+        "{:?}",
+        s
+      );
+    }
+  }
+
+  #[test]
+  fn to_primitive() {
+    initialize_host_defined_realm().unwrap();
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    let value = Value::from(o.clone());
+    let error = value.to_primitive(PreferredType::Default).unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    define(
+      &o,
+      "valueOf",
+      JsObject::new(Either::B(JsNull), true, &VALUE_OF).into(),
+    );
+    assert_eq!(*value.to_number().unwrap(), 42.0);
+    assert_eq!(value.to_string().unwrap(), "42");
+    assert_eq!(value.to_property_key().unwrap(), PropertyKey::from("42"));
+    // @@toPrimitive is called with the hint and must return a primitive
+    let return_hint = JsObject::new(Either::B(JsNull), true, &RETURN_HINT);
+    define(&o, JsSymbol::to_primitive(), return_hint.into());
+    for (preferred_type, hint) in [
+      (PreferredType::Default, "default"),
+      (PreferredType::String, "string"),
+      (PreferredType::Number, "number"),
+    ] {
+      let prim = value.to_primitive(preferred_type).unwrap();
+      assert_eq!(prim.to_string().unwrap(), hint);
+    }
+    let return_this = JsObject::new(Either::B(JsNull), true, &RETURN_THIS);
+    define(&o, JsSymbol::to_primitive(), return_this.into());
+    let error = value.to_primitive(PreferredType::Number).unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    // GetMethod throws for a @@toPrimitive that isn't callable
+    define(&o, JsSymbol::to_primitive(), Value::from(1.0));
+    let error = value.to_primitive(PreferredType::Default).unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn to_numeric() {
+    let one = JsBigInt::from(BigInt::from(1));
+    assert_eq!(
+      Value::from(one.clone()).to_numeric().unwrap(),
+      Numeric::BigInt(one)
+    );
+    assert_eq!(Value::from("5").to_numeric().unwrap(), Numeric::Number(5.0));
+    assert_eq!(
+      Value::from(true).to_numeric().unwrap(),
+      Numeric::Number(1.0)
+    );
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    define(
+      &o,
+      "valueOf",
+      JsObject::new(Either::B(JsNull), true, &VALUE_OF).into(),
+    );
+    assert_eq!(Value::from(o).to_numeric().unwrap(), Numeric::Number(42.0));
+  }
+
+  #[test]
+  fn symbol_conversions() {
+    initialize_host_defined_realm().unwrap();
+    let symbol = Value::from(JsSymbol::iterator());
+    assert_eq!(error_name(symbol.to_numeric().unwrap_err()), "TypeError");
+    assert_eq!(error_name(symbol.to_string().unwrap_err()), "TypeError");
+    let one = Value::from(JsBigInt::from(BigInt::from(1)));
+    assert_eq!(error_name(one.to_number().unwrap_err()), "TypeError");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn string_to_bigint() {
+    let cases = [
+      ("", 0),
+      (" -12\n", -12),
+      ("+7", 7),
+      ("0x10", 16),
+      ("0o17", 15),
+      ("0B11", 3),
+    ];
+    for (s, expected) in cases {
+      assert_eq!(
+        super::string_to_bigint(s),
+        Some(BigInt::from(expected).into()),
+        "{:?}",
+        s
+      );
+    }
+    for s in ["1.5", "1e3", "1n", "-0x1", "0x", "Infinity", "-"] {
+      assert_eq!(super::string_to_bigint(s), None, "{:?}", s);
+    }
+  }
+
+  #[test]
+  fn to_bigint() {
+    initialize_host_defined_realm().unwrap();
+    assert_eq!(
+      Value::from("0x10").to_bigint().unwrap(),
+      BigInt::from(16).into()
+    );
+    assert_eq!(
+      Value::from(true).to_bigint().unwrap(),
+      BigInt::from(1).into()
+    );
+    assert!(Value::from("1.5").to_bigint().is_err());
+    assert!(Value::from(1.0).to_bigint().is_err());
+    assert!(Value::undefined().to_bigint().is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn to_object() {
+    let realm = initialize_host_defined_realm().unwrap();
+    assert_eq!(
+      error_name(Value::undefined().to_object().unwrap_err()),
+      "TypeError"
+    );
+    assert_eq!(
+      error_name(Value::null().to_object().unwrap_err()),
+      "TypeError"
+    );
+    let o = Value::from(1.0).to_object().unwrap();
+    assert!(o.has_internal_slot("NumberData"));
+    let o = Value::from("ab").to_object().unwrap();
+    assert!(o.has_internal_slot("StringData"));
+    let string_prototype = realm.intrinsic("String.prototype");
+    assert!(
+      matches!(o.get_prototype_of(), Either::A(p) if JsObject::equals(&p, &string_prototype))
+    );
+    // an object is returned as is
+    let same = Value::from(o.clone()).to_object().unwrap();
+    assert!(JsObject::equals(&same, &o));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn to_property_key() {
+    let key = |value: Value| value.to_property_key().unwrap();
+    assert_eq!(key(Value::from(1.0)), PropertyKey::from("1"));
+    assert_eq!(key(Value::from(-0.0)), PropertyKey::from("0"));
+    assert_eq!(key(Value::null()), PropertyKey::from("null"));
+    assert_eq!(
+      key(Value::from(JsSymbol::iterator())),
+      PropertyKey::from(JsSymbol::iterator())
+    );
+    assert_ne!(
+      key(Value::from(JsSymbol::iterator())),
+      PropertyKey::from("Symbol(Symbol.iterator)")
+    );
+  }
+
+  #[test]
+  fn to_length() {
+    assert_eq!(Value::from(-1.0).to_length().unwrap(), 0.0);
+    assert_eq!(Value::from(2.5).to_length().unwrap(), 2.0);
+    assert_eq!(
+      Value::from(f64::INFINITY).to_length().unwrap(),
+      9007199254740991.0
+    );
+  }
+
+  #[test]
+  fn to_integer() {
+    assert_eq!(Value::from(-2.5).to_integer_or_infinity().unwrap(), -2.0);
+    assert_eq!(Value::from(f64::NAN).to_integer_or_infinity().unwrap(), 0.0);
+    assert_eq!(
+      Value::from(f64::NEG_INFINITY)
+        .to_integer_or_infinity()
+        .unwrap(),
+      f64::NEG_INFINITY
+    );
+    assert_eq!(Value::from("7.9").to_integer_or_infinity().unwrap(), 7.0);
+    assert_eq!(Value::from(4294967297.0).to_int32().unwrap(), 1);
+    assert_eq!(Value::from(2147483648.0).to_int32().unwrap(), i32::MIN);
+    assert_eq!(Value::from(-1.5).to_int32().unwrap(), -1);
+    assert_eq!(Value::from(f64::INFINITY).to_int32().unwrap(), 0);
+    assert_eq!(Value::from(-1.0).to_uint32().unwrap(), u32::MAX);
+    assert_eq!(Value::from(4294967296.0).to_uint32().unwrap(), 0);
+    assert_eq!(Value::from(-5.0).to_length().unwrap(), 0.0);
+  }
 }
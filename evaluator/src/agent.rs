@@ -1,22 +1,123 @@
-use crate::language_types::{boolean::JsBoolean, object::JsObject};
+use std::{cell::RefCell, collections::VecDeque};
+
+use crate::{
+  executable_code_and_execution_contexts::{
+    environment_records::Environment, execution_context::ExecutionContext,
+    jobs::Job, realm::Realm,
+  },
+  language_types::{
+    string::JsString,
+    symbol::{JsSymbol, SymbolRegistry},
+    Value,
+  },
+};
 
 /// https://tc39.es/ecma262/#sec-agents
 pub struct Agent {
-  agent_record: AgentRecord,
+  execution_context_stack: Vec<ExecutionContext>,
+  /// The pending Jobs, run in FIFO order once the execution context stack is
+  /// empty.
+  job_queue: VecDeque<Job>,
+  /// https://tc39.es/ecma262/#sec-symbol.for, shared by all realms of the
+  /// agent.
+  global_symbol_registry: SymbolRegistry,
+}
+
+impl Agent {
+  fn new() -> Self {
+    Self {
+      execution_context_stack: Vec::new(),
+      job_queue: VecDeque::new(),
+      global_symbol_registry: SymbolRegistry::default(),
+    }
+  }
+}
+
+thread_local! {
+  /// The agent evaluating on this thread, the spec calls it the surrounding
+  /// agent.
+  static SURROUNDING_AGENT: RefCell<Agent> = RefCell::new(Agent::new());
+}
+
+pub fn push_execution_context(context: ExecutionContext) {
+  SURROUNDING_AGENT
+    .with(|agent| agent.borrow_mut().execution_context_stack.push(context));
+}
+
+pub fn pop_execution_context() -> Option<ExecutionContext> {
+  SURROUNDING_AGENT
+    .with(|agent| agent.borrow_mut().execution_context_stack.pop())
+}
+
+/// https://tc39.es/ecma262/#running-execution-context
+pub fn running_execution_context() -> Option<ExecutionContext> {
+  SURROUNDING_AGENT
+    .with(|agent| agent.borrow().execution_context_stack.last().cloned())
+}
+
+fn with_running_execution_context(f: impl FnOnce(&mut ExecutionContext)) {
+  SURROUNDING_AGENT.with(|agent| {
+    f(agent
+      .borrow_mut()
+      .execution_context_stack
+      .last_mut()
+      .expect("no running execution context"))
+  });
+}
+
+/// Set the LexicalEnvironment of the running execution context.
+pub fn set_lexical_environment(env: Environment) {
+  with_running_execution_context(|context| {
+    context.lexical_environment = Some(env)
+  });
+}
+
+/// Set the VariableEnvironment of the running execution context.
+pub fn set_variable_environment(env: Environment) {
+  with_running_execution_context(|context| {
+    context.variable_environment = Some(env)
+  });
+}
+
+/// https://tc39.es/ecma262/#current-realm
+pub fn current_realm() -> Realm {
+  running_execution_context()
+    .expect("no running execution context")
+    .realm
+}
+
+/// The symbol of `key` in the GlobalSymbolRegistry, like `Symbol.for(key)`.
+pub fn symbol_for(key: JsString) -> JsSymbol {
+  SURROUNDING_AGENT
+    .with(|agent| agent.borrow_mut().global_symbol_registry.symbol_for(key))
 }
 
-/// https://tc39.es/ecma262/#agent-record
-struct AgentRecord {
-  little_endian: JsBoolean,
-  can_block: JsBoolean,
-  signifier: usize,
-  is_lock_free1: JsBoolean,
-  is_lock_free2: JsBoolean,
-  is_lock_free8: JsBoolean,
-  candidate_execution: CandidateExecution,
-  kept_alive: Vec<JsObject>,
+/// https://tc39.es/ecma262/#sec-keyforsymbol, like `Symbol.keyFor(sym)`.
+pub fn key_for_symbol(sym: &JsSymbol) -> Option<JsString> {
+  SURROUNDING_AGENT
+    .with(|agent| agent.borrow().global_symbol_registry.key_for(sym))
 }
 
-/// TODO
-/// https://tc39.es/ecma262/#sec-candidate-executions
-struct CandidateExecution {}
+/// Add `job` to the end of the surrounding agent's job queue.
+pub fn enqueue_job(job: Job) {
+  SURROUNDING_AGENT.with(|agent| agent.borrow_mut().job_queue.push_back(job));
+}
+
+/// Run the queued Jobs in FIFO order until the queue is empty, including the
+/// ones enqueued meanwhile. A Job is only initiated when the execution
+/// context stack is empty, so this runs after the synchronous work.
+///
+/// The host would report a throw completion of a Job and go on, here it's
+/// returned and the remaining Jobs stay queued.
+pub fn run_jobs() -> Result<(), Value> {
+  assert!(
+    running_execution_context().is_none(),
+    "Jobs run when the execution context stack is empty"
+  );
+  while let Some(job) =
+    SURROUNDING_AGENT.with(|agent| agent.borrow_mut().job_queue.pop_front())
+  {
+    job()?;
+  }
+  Ok(())
+}
@@ -0,0 +1,657 @@
+//! https://tc39.es/ecma262/#sec-environment-records
+
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+  abstract_operations::operations_on_bjects::{
+    define_property_or_throw, get, has_property, set,
+  },
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  language_types::{
+    boolean::JsBoolean, object::JsObject, symbol::JsSymbol, Value,
+  },
+  ordinary_and_exotic_objects_behaviours::ecmascript_function_objects::{
+    function_environment, function_this_mode, ThisMode,
+  },
+  specification_types::{
+    property_descriptor::PropertyDescriptor,
+    reference_record::{Base, Reference},
+  },
+};
+
+struct Binding {
+  /// `None` until the binding is initialized.
+  value: Option<Value>,
+  mutable: bool,
+  /// Immutable bindings only, assigning to a strict one always throws.
+  strict: bool,
+  deletable: bool,
+}
+
+/// https://tc39.es/ecma262/#table-additional-fields-of-function-environment-records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThisBindingStatus {
+  Lexical,
+  Initialized,
+  Uninitialized,
+}
+
+enum Kind {
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records
+  Declarative,
+  /// https://tc39.es/ecma262/#sec-function-environment-records
+  Function {
+    this_value: Value,
+    this_binding_status: ThisBindingStatus,
+    function_object: JsObject,
+    new_target: Option<JsObject>,
+  },
+  /// https://tc39.es/ecma262/#sec-object-environment-records, the bindings
+  /// are the properties of the binding object.
+  Object {
+    binding_object: JsObject,
+    is_with_environment: bool,
+  },
+}
+
+struct EnvironmentRecord {
+  bindings: HashMap<String, Binding>,
+  outer: Option<Environment>,
+  kind: Kind,
+}
+
+/// https://tc39.es/ecma262/#sec-the-environment-record-type-hierarchy, a
+/// function Environment Record is a declarative one with a this binding.
+#[derive(Clone)]
+pub struct Environment(Rc<RefCell<EnvironmentRecord>>);
+
+impl Environment {
+  fn new(outer: Option<Environment>, kind: Kind) -> Self {
+    Self(Rc::new(RefCell::new(EnvironmentRecord {
+      bindings: HashMap::new(),
+      outer,
+      kind,
+    })))
+  }
+
+  pub fn equals(lhs: &Self, rhs: &Self) -> bool {
+    Rc::ptr_eq(&lhs.0, &rhs.0)
+  }
+
+  /// [[OuterEnv]]
+  pub fn outer(&self) -> Option<Environment> {
+    self.0.borrow().outer.clone()
+  }
+
+  /// The [[BindingObject]] of an object Environment Record.
+  fn binding_object(&self) -> Option<JsObject> {
+    match &self.0.borrow().kind {
+      Kind::Object { binding_object, .. } => Some(binding_object.clone()),
+      _ => None,
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records-hasbinding-n
+  pub fn has_binding(&self, n: &str) -> Result<bool, Value> {
+    let (binding_object, is_with_environment) = match &self.0.borrow().kind {
+      Kind::Object {
+        binding_object,
+        is_with_environment,
+      } => (binding_object.clone(), *is_with_environment),
+      // 1. If envRec has a binding for N, return true.
+      // 2. Return false.
+      _ => return Ok(self.0.borrow().bindings.contains_key(n)),
+    };
+    // https://tc39.es/ecma262/#sec-object-environment-records-hasbinding-n
+    // 1. Let bindingObject be envRec.[[BindingObject]].
+    // 2. Let foundBinding be ? HasProperty(bindingObject, N).
+    // 3. If foundBinding is false, return false.
+    if !has_property(&binding_object, &n.into())? {
+      return Ok(false);
+    }
+    // 4. If envRec.[[IsWithEnvironment]] is false, return true.
+    if !is_with_environment {
+      return Ok(true);
+    }
+    // 5. Let unscopables be ? Get(bindingObject, @@unscopables).
+    let unscopables = get(&binding_object, &JsSymbol::unscopables().into())?;
+    // 6. If unscopables is an Object, then
+    if let Value::Object(unscopables) = unscopables {
+      // a. Let blocked be ToBoolean(? Get(unscopables, N)).
+      let blocked = get(&unscopables, &n.into())?.to_boolean();
+      // b. If blocked is true, return false.
+      if blocked == JsBoolean::True {
+        return Ok(false);
+      }
+    }
+    // 7. Return true.
+    Ok(true)
+  }
+
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records-createmutablebinding-n-d
+  pub fn create_mutable_binding(&self, n: &str, d: bool) -> Result<(), Value> {
+    if let Some(binding_object) = self.binding_object() {
+      // https://tc39.es/ecma262/#sec-object-environment-records-createmutablebinding-n-d
+      // 1. Let bindingObject be envRec.[[BindingObject]].
+      // 2. Perform ? DefinePropertyOrThrow(bindingObject, N, PropertyDescriptor {
+      //    [[Value]]: undefined, [[Writable]]: true, [[Enumerable]]: true,
+      //    [[Configurable]]: D }).
+      // 3. Return unused.
+      let desc = PropertyDescriptor {
+        value: Some(Value::undefined()),
+        writable: Some(JsBoolean::True),
+        get: None,
+        set: None,
+        enumerable: Some(JsBoolean::True),
+        configurable: Some(d.into()),
+      };
+      return define_property_or_throw(&binding_object, &n.into(), desc);
+    }
+    // 1. Assert: envRec does not already have a binding for N.
+    // 2. Create a mutable binding in envRec for N and record that it is uninitialized.
+    //    If D is true, record that the newly created binding may be deleted by a
+    //    subsequent DeleteBinding call.
+    let previous = self.0.borrow_mut().bindings.insert(
+      n.to_owned(),
+      Binding {
+        value: None,
+        mutable: true,
+        strict: false,
+        deletable: d,
+      },
+    );
+    debug_assert!(previous.is_none(), "{} is already bound", n);
+    // 3. Return unused.
+    Ok(())
+  }
+
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records-createimmutablebinding-n-s,
+  /// object Environment Records never create immutable bindings.
+  pub fn create_immutable_binding(&self, n: &str, s: bool) {
+    // 1. Assert: envRec does not already have a binding for N.
+    // 2. Create an immutable binding in envRec for N and record that it is
+    //    uninitialized. If S is true, record that the newly created binding is a strict
+    //    binding.
+    let previous = self.0.borrow_mut().bindings.insert(
+      n.to_owned(),
+      Binding {
+        value: None,
+        mutable: false,
+        strict: s,
+        deletable: false,
+      },
+    );
+    debug_assert!(previous.is_none(), "{} is already bound", n);
+    // 3. Return unused.
+  }
+
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records-initializebinding-n-v
+  pub fn initialize_binding(&self, n: &str, v: Value) -> Result<(), Value> {
+    if self.binding_object().is_some() {
+      // https://tc39.es/ecma262/#sec-object-environment-records-initializebinding-n-v
+      // 1. Perform ? envRec.SetMutableBinding(N, V, false).
+      // 2. Return unused.
+      return self.set_mutable_binding(n, v, false);
+    }
+    // 1. Assert: envRec must have an uninitialized binding for N.
+    // 2. Set the bound value for N in envRec to V.
+    // 3. Record that the binding for N in envRec has been initialized.
+    let mut record = self.0.borrow_mut();
+    let binding = record
+      .bindings
+      .get_mut(n)
+      .unwrap_or_else(|| panic!("{} isn't bound", n));
+    debug_assert!(binding.value.is_none(), "{} is already initialized", n);
+    binding.value = Some(v);
+    // 4. Return unused.
+    Ok(())
+  }
+
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records-setmutablebinding-n-v-s
+  pub fn set_mutable_binding(
+    &self,
+    n: &str,
+    v: Value,
+    s: bool,
+  ) -> Result<(), Value> {
+    if let Some(binding_object) = self.binding_object() {
+      // https://tc39.es/ecma262/#sec-object-environment-records-setmutablebinding-n-v-s
+      // 1. Let bindingObject be envRec.[[BindingObject]].
+      // 2. Let stillExists be ? HasProperty(bindingObject, N).
+      // 3. If stillExists is false and S is true, throw a ReferenceError exception.
+      if !has_property(&binding_object, &n.into())? && s {
+        return Err(throw_error(
+          ErrorKind::ReferenceError,
+          &format!("{} is not defined", n),
+        ));
+      }
+      // 4. Perform ? Set(bindingObject, N, V, S).
+      // 5. Return unused.
+      return set(&binding_object, &n.into(), v, s);
+    }
+    // 1. If envRec does not have a binding for N, then
+    if !self.0.borrow().bindings.contains_key(n) {
+      // a. If S is true, throw a ReferenceError exception.
+      if s {
+        return Err(throw_error(
+          ErrorKind::ReferenceError,
+          &format!("{} is not defined", n),
+        ));
+      }
+      // b. Perform ! envRec.CreateMutableBinding(N, true).
+      self.create_mutable_binding(n, true)?;
+      // c. Perform ! envRec.InitializeBinding(N, V).
+      // d. Return unused.
+      return self.initialize_binding(n, v);
+    }
+    let mut record = self.0.borrow_mut();
+    let binding = record.bindings.get_mut(n).expect("the binding for N");
+    // 2. If the binding for N in envRec is a strict binding, set S to true.
+    let s = s || binding.strict;
+    // 3. If the binding for N in envRec has not yet been initialized, then
+    if binding.value.is_none() {
+      // a. Throw a ReferenceError exception.
+      drop(record);
+      return Err(throw_error(
+        ErrorKind::ReferenceError,
+        &format!("Cannot access '{}' before initialization", n),
+      ));
+    }
+    // 4. Else if the binding for N in envRec is a mutable binding, then
+    if binding.mutable {
+      // a. Change its bound value to V.
+      binding.value = Some(v);
+    // 5. Else,
+    } else {
+      // a. Assert: This is an attempt to change the value of an immutable binding.
+      // b. If S is true, throw a TypeError exception.
+      if s {
+        drop(record);
+        return Err(throw_error(
+          ErrorKind::TypeError,
+          "Assignment to constant variable.",
+        ));
+      }
+    }
+    // 6. Return unused.
+    Ok(())
+  }
+
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records-getbindingvalue-n-s
+  pub fn get_binding_value(&self, n: &str, s: bool) -> Result<Value, Value> {
+    if let Some(binding_object) = self.binding_object() {
+      // https://tc39.es/ecma262/#sec-object-environment-records-getbindingvalue-n-s
+      // 1. Let bindingObject be envRec.[[BindingObject]].
+      // 2. Let value be ? HasProperty(bindingObject, N).
+      // 3. If value is false, then
+      if !has_property(&binding_object, &n.into())? {
+        // a. If S is false, return undefined; otherwise throw a ReferenceError
+        //    exception.
+        if !s {
+          return Ok(Value::undefined());
+        }
+        return Err(throw_error(
+          ErrorKind::ReferenceError,
+          &format!("{} is not defined", n),
+        ));
+      }
+      // 4. Return ? Get(bindingObject, N).
+      return get(&binding_object, &n.into());
+    }
+    // 1. Assert: envRec has a binding for N.
+    // 2. If the binding for N in envRec is an uninitialized binding, throw a
+    //    ReferenceError exception.
+    // 3. Return the value currently bound to N in envRec.
+    let value = self
+      .0
+      .borrow()
+      .bindings
+      .get(n)
+      .unwrap_or_else(|| panic!("{} isn't bound", n))
+      .value
+      .clone();
+    value.ok_or_else(|| {
+      throw_error(
+        ErrorKind::ReferenceError,
+        &format!("Cannot access '{}' before initialization", n),
+      )
+    })
+  }
+
+  /// https://tc39.es/ecma262/#sec-declarative-environment-records-deletebinding-n
+  pub fn delete_binding(&self, n: &str) -> Result<bool, Value> {
+    if let Some(binding_object) = self.binding_object() {
+      // https://tc39.es/ecma262/#sec-object-environment-records-deletebinding-n
+      // 1. Let bindingObject be envRec.[[BindingObject]].
+      // 2. Return ? bindingObject.[[Delete]](N).
+      return binding_object.delete(&n.into());
+    }
+    // 1. Assert: envRec has a binding for N.
+    let mut record = self.0.borrow_mut();
+    // 2. If the binding for N in envRec cannot be deleted, return false.
+    if !record.bindings.get(n).expect("the binding for N").deletable {
+      return Ok(false);
+    }
+    // 3. Remove the binding for N from envRec.
+    record.bindings.remove(n);
+    // 4. Return true.
+    Ok(true)
+  }
+
+  /// https://tc39.es/ecma262/#sec-function-environment-records-hasthisbinding
+  pub fn has_this_binding(&self) -> bool {
+    match &self.0.borrow().kind {
+      // 1. Return false.
+      Kind::Declarative | Kind::Object { .. } => false,
+      // 1. If envRec.[[ThisBindingStatus]] is lexical, return false; otherwise,
+      //    return true.
+      Kind::Function {
+        this_binding_status,
+        ..
+      } => *this_binding_status != ThisBindingStatus::Lexical,
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-bindthisvalue
+  pub fn bind_this_value(&self, v: Value) -> Result<Value, Value> {
+    let mut record = self.0.borrow_mut();
+    let (this_value, this_binding_status) = match &mut record.kind {
+      Kind::Function {
+        this_value,
+        this_binding_status,
+        ..
+      } => (this_value, this_binding_status),
+      _ => panic!("only function environments bind this"),
+    };
+    // 1. Assert: envRec.[[ThisBindingStatus]] is not lexical.
+    debug_assert_ne!(*this_binding_status, ThisBindingStatus::Lexical);
+    // 2. If envRec.[[ThisBindingStatus]] is initialized, throw a ReferenceError
+    //    exception.
+    if *this_binding_status == ThisBindingStatus::Initialized {
+      drop(record);
+      return Err(throw_error(
+        ErrorKind::ReferenceError,
+        "Super constructor may only be called once",
+      ));
+    }
+    // 3. Set envRec.[[ThisValue]] to V.
+    *this_value = v.clone();
+    // 4. Set envRec.[[ThisBindingStatus]] to initialized.
+    *this_binding_status = ThisBindingStatus::Initialized;
+    // 5. Return V.
+    Ok(v)
+  }
+
+  /// https://tc39.es/ecma262/#sec-function-environment-records-getthisbinding
+  pub fn get_this_binding(&self) -> Result<Value, Value> {
+    let record = self.0.borrow();
+    match &record.kind {
+      Kind::Function {
+        this_value,
+        this_binding_status,
+        ..
+      } => {
+        // 1. Assert: envRec.[[ThisBindingStatus]] is not lexical.
+        debug_assert_ne!(*this_binding_status, ThisBindingStatus::Lexical);
+        // 2. If envRec.[[ThisBindingStatus]] is uninitialized, throw a ReferenceError
+        //    exception.
+        if *this_binding_status == ThisBindingStatus::Uninitialized {
+          drop(record);
+          return Err(throw_error(
+            ErrorKind::ReferenceError,
+            "Must call super constructor before accessing 'this'",
+          ));
+        }
+        // 3. Return envRec.[[ThisValue]].
+        Ok(this_value.clone())
+      }
+      _ => panic!("only function environments bind this"),
+    }
+  }
+
+  /// [[FunctionObject]] of a function Environment Record.
+  pub fn function_object(&self) -> Option<JsObject> {
+    match &self.0.borrow().kind {
+      Kind::Function {
+        function_object, ..
+      } => Some(function_object.clone()),
+      _ => None,
+    }
+  }
+
+  /// [[NewTarget]] of a function Environment Record.
+  pub fn new_target(&self) -> Option<JsObject> {
+    match &self.0.borrow().kind {
+      Kind::Function { new_target, .. } => new_target.clone(),
+      _ => None,
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-object-environment-records-withbaseobject
+  pub fn with_base_object(&self) -> Option<JsObject> {
+    match &self.0.borrow().kind {
+      // 1. If envRec.[[IsWithEnvironment]] is true, return envRec.[[BindingObject]].
+      Kind::Object {
+        binding_object,
+        is_with_environment: true,
+      } => Some(binding_object.clone()),
+      // 2. Otherwise, return undefined.
+      _ => None,
+    }
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-getidentifierreference
+pub fn get_identifier_reference(
+  env: Option<&Environment>,
+  name: &str,
+  strict: bool,
+) -> Result<Reference, Value> {
+  match env {
+    // 1. If env is null, then
+    //   a. Return the Reference Record { [[Base]]: unresolvable, [[ReferencedName]]:
+    //      name, [[Strict]]: strict, [[ThisValue]]: empty }.
+    None => Ok(Reference {
+      base: Base::Unresolvable,
+      referenced_name: name.into(),
+      strict,
+    }),
+    Some(env) => {
+      // 2. Let exists be ? env.HasBinding(name).
+      // 3. If exists is true, then
+      if env.has_binding(name)? {
+        // a. Return the Reference Record { [[Base]]: env, [[ReferencedName]]: name,
+        //    [[Strict]]: strict, [[ThisValue]]: empty }.
+        Ok(Reference {
+          base: Base::Environment(env.clone()),
+          referenced_name: name.into(),
+          strict,
+        })
+      // 4. Else,
+      } else {
+        // a. Let outer be env.[[OuterEnv]].
+        // b. Return ? GetIdentifierReference(outer, name, strict).
+        get_identifier_reference(env.outer().as_ref(), name, strict)
+      }
+    }
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-newdeclarativeenvironment
+pub fn new_declarative_environment(e: Option<Environment>) -> Environment {
+  // 1. Let env be a new Declarative Environment Record containing no bindings.
+  // 2. Set env.[[OuterEnv]] to E.
+  // 3. Return env.
+  Environment::new(e, Kind::Declarative)
+}
+
+/// https://tc39.es/ecma262/#sec-newobjectenvironment
+pub fn new_object_environment(
+  o: JsObject,
+  w: bool,
+  e: Option<Environment>,
+) -> Environment {
+  // 1. Let env be a new Object Environment Record.
+  // 2. Set env.[[BindingObject]] to O.
+  // 3. Set env.[[IsWithEnvironment]] to W.
+  // 4. Set env.[[OuterEnv]] to E.
+  // 5. Return env.
+  Environment::new(
+    e,
+    Kind::Object {
+      binding_object: o,
+      is_with_environment: w,
+    },
+  )
+}
+
+/// https://tc39.es/ecma262/#sec-newfunctionenvironment
+pub fn new_function_environment(
+  f: &JsObject,
+  new_target: Option<JsObject>,
+) -> Environment {
+  // 1. Let env be a new Function Environment Record containing no bindings.
+  // 2. Set env.[[FunctionObject]] to F.
+  // 3. If F.[[ThisMode]] is lexical, set env.[[ThisBindingStatus]] to lexical.
+  // 4. Else, set env.[[ThisBindingStatus]] to uninitialized.
+  let this_binding_status = if function_this_mode(f) == ThisMode::Lexical {
+    ThisBindingStatus::Lexical
+  } else {
+    ThisBindingStatus::Uninitialized
+  };
+  // 5. Set env.[[NewTarget]] to newTarget.
+  // 6. Set env.[[OuterEnv]] to F.[[Environment]].
+  // 7. Return env.
+  Environment::new(
+    Some(function_environment(f)),
+    Kind::Function {
+      this_value: Value::undefined(),
+      this_binding_status,
+      function_object: f.clone(),
+      new_target,
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::{
+      operations_on_bjects::create_data_property,
+      ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+    },
+    agent::{pop_execution_context, push_execution_context},
+    executable_code_and_execution_contexts::{
+      execution_context::ExecutionContext,
+      realm::{create_realm, initialize_host_defined_realm},
+    },
+    helpers::Either,
+  };
+
+  #[test]
+  fn declarative_bindings() {
+    push_execution_context(ExecutionContext {
+      function: None,
+      realm: create_realm(),
+      variable_environment: None,
+      lexical_environment: None,
+    });
+    let outer = new_declarative_environment(None);
+    let env = new_declarative_environment(Some(outer.clone()));
+    assert!(Environment::equals(&env.outer().unwrap(), &outer));
+
+    env.create_mutable_binding("a", false).unwrap();
+    assert!(env.has_binding("a").unwrap());
+    assert!(!outer.has_binding("a").unwrap());
+    // the temporal dead zone
+    assert!(env.get_binding_value("a", false).is_err());
+    assert!(env.set_mutable_binding("a", 1.0.into(), false).is_err());
+    env.initialize_binding("a", 1.0.into()).unwrap();
+    env.set_mutable_binding("a", 2.0.into(), false).unwrap();
+    let a = env.get_binding_value("a", false).unwrap();
+    assert!(matches!(a, Value::Number(n) if *n == 2.0));
+    assert!(!env.delete_binding("a").unwrap());
+
+    env.create_immutable_binding("b", true);
+    env.initialize_binding("b", 1.0.into()).unwrap();
+    assert!(env.set_mutable_binding("b", 2.0.into(), false).is_err());
+
+    let reference = get_identifier_reference(Some(&env), "b", true).unwrap();
+    assert!(
+      matches!(reference.base, Base::Environment(e) if Environment::equals(&e, &env))
+    );
+    outer.create_mutable_binding("d", false).unwrap();
+    let reference = get_identifier_reference(Some(&env), "d", true).unwrap();
+    assert!(
+      matches!(reference.base, Base::Environment(e) if Environment::equals(&e, &outer))
+    );
+    let reference = get_identifier_reference(Some(&env), "e", true).unwrap();
+    assert!(reference.is_unresolvable_reference());
+    assert!(reference.get_value().is_err());
+
+    // sloppy assignments to a missing binding create a deletable one
+    assert!(env.set_mutable_binding("c", 1.0.into(), true).is_err());
+    env.set_mutable_binding("c", 1.0.into(), false).unwrap();
+    assert!(env.delete_binding("c").unwrap());
+    assert!(!env.has_binding("c").unwrap());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn object_bindings() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let object = || {
+      ordinary_object_create(
+        Either::A(realm.intrinsic("Object.prototype")),
+        &[],
+      )
+    };
+    let proto = object();
+    create_data_property(&proto, &"inherited".into(), 1.0.into()).unwrap();
+    let o = ordinary_object_create(Either::A(proto), &[]);
+    create_data_property(&o, &"a".into(), 1.0.into()).unwrap();
+    create_data_property(&o, &"hidden".into(), 2.0.into()).unwrap();
+    let unscopables = object();
+    create_data_property(&unscopables, &"hidden".into(), true.into()).unwrap();
+    create_data_property(&unscopables, &"a".into(), false.into()).unwrap();
+    create_data_property(
+      &o,
+      &JsSymbol::unscopables().into(),
+      unscopables.into(),
+    )
+    .unwrap();
+
+    let object_env = new_object_environment(o.clone(), false, None);
+    let with_env = new_object_environment(o.clone(), true, None);
+    assert!(object_env.with_base_object().is_none());
+    assert!(JsObject::equals(&with_env.with_base_object().unwrap(), &o));
+    assert!(!object_env.has_this_binding());
+    // the bindings are the properties, inherited ones included
+    for env in [&object_env, &with_env] {
+      assert!(env.has_binding("a").unwrap());
+      assert!(env.has_binding("inherited").unwrap());
+      assert!(!env.has_binding("missing").unwrap());
+    }
+    // a key listed in @@unscopables is hidden from with statements only
+    assert!(object_env.has_binding("hidden").unwrap());
+    assert!(!with_env.has_binding("hidden").unwrap());
+    let env = new_declarative_environment(Some(with_env.clone()));
+    let reference =
+      get_identifier_reference(Some(&env), "hidden", false).unwrap();
+    assert!(reference.is_unresolvable_reference());
+
+    with_env.create_mutable_binding("b", true).unwrap();
+    with_env.initialize_binding("b", 3.0.into()).unwrap();
+    let b = with_env.get_binding_value("b", true).unwrap();
+    assert!(matches!(b, Value::Number(n) if *n == 3.0));
+    assert!(with_env.delete_binding("b").unwrap());
+    assert!(!with_env.has_binding("b").unwrap());
+    // a binding deleted in the meantime
+    let b = with_env.get_binding_value("b", false).unwrap();
+    assert!(matches!(b, Value::Undefined(_)));
+    assert!(with_env.get_binding_value("b", true).is_err());
+    assert!(with_env.set_mutable_binding("b", 1.0.into(), true).is_err());
+    pop_execution_context();
+  }
+}
@@ -0,0 +1,77 @@
+use crate::{
+  agent::{current_realm, running_execution_context},
+  language_types::{object::JsObject, Value},
+  specification_types::reference_record::Reference,
+};
+
+use super::{
+  environment_records::{get_identifier_reference, Environment},
+  realm::Realm,
+};
+
+/// https://tc39.es/ecma262/#sec-execution-contexts
+#[derive(Clone)]
+pub struct ExecutionContext {
+  /// `None` when evaluating script or module code.
+  pub function: Option<JsObject>,
+  pub realm: Realm,
+  /// `None` for contexts that don't evaluate ECMAScript code, e.g. the ones
+  /// of built-in functions.
+  pub variable_environment: Option<Environment>,
+  pub lexical_environment: Option<Environment>,
+}
+
+/// https://tc39.es/ecma262/#sec-resolvebinding
+pub fn resolve_binding(
+  name: &str,
+  env: Option<&Environment>,
+  strict: bool,
+) -> Result<Reference, Value> {
+  // 1. If env is not present or env is undefined, then
+  //   a. Set env to the running execution context's LexicalEnvironment.
+  let running = running_execution_context();
+  let env = env.or_else(|| {
+    running
+      .as_ref()
+      .and_then(|context| context.lexical_environment.as_ref())
+  });
+  // 2. Assert: env is an Environment Record.
+  // 3. If the source text matched by the syntactic production that is being
+  //    evaluated is contained in strict mode code, let strict be true; else let
+  //    strict be false.
+  // 4. Return ? GetIdentifierReference(env, name, strict).
+  get_identifier_reference(env, name, strict)
+}
+
+/// https://tc39.es/ecma262/#sec-getthisenvironment, `None` for the global
+/// environment.
+pub fn get_this_environment() -> Option<Environment> {
+  // 1. Let env be the running execution context's LexicalEnvironment.
+  let mut env =
+    running_execution_context().and_then(|context| context.lexical_environment);
+  // 2. Repeat,
+  while let Some(e) = env {
+    // a. Let exists be env.HasThisBinding().
+    // b. If exists is true, return env.
+    if e.has_this_binding() {
+      return Some(e);
+    }
+    // c. Let outer be env.[[OuterEnv]].
+    // d. Assert: outer is not null.
+    // e. Set env to outer.
+    env = e.outer();
+  }
+  // TODO: the global environment
+  None
+}
+
+/// https://tc39.es/ecma262/#sec-resolvethisbinding
+pub fn resolve_this_binding() -> Result<Value, Value> {
+  // 1. Let envRec be GetThisEnvironment().
+  match get_this_environment() {
+    // 2. Return ? envRec.GetThisBinding().
+    Some(env) => env.get_this_binding(),
+    // TODO: the global environment
+    None => Ok(Value::from(current_realm().global_object())),
+  }
+}
@@ -0,0 +1,119 @@
+//! https://tc39.es/ecma262/#sec-jobs
+
+use crate::{
+  abstract_operations::operations_on_bjects::call,
+  agent::{enqueue_job, pop_execution_context, push_execution_context},
+  language_types::{object::JsObject, Value},
+};
+
+use super::{execution_context::ExecutionContext, realm::Realm};
+
+/// https://tc39.es/ecma262/#job, an Abstract Closure with no parameters that
+/// returns unused or a throw completion.
+pub type Job = Box<dyn FnOnce() -> Result<(), Value>>;
+
+/// https://tc39.es/ecma262/#sec-jobcallback-records
+///
+/// The host doesn't need [[HostDefined]], so it's left out.
+#[derive(Clone)]
+pub struct JobCallback {
+  pub callback: JsObject,
+}
+
+/// https://tc39.es/ecma262/#sec-hostmakejobcallback
+pub fn host_make_job_callback(callback: JsObject) -> JobCallback {
+  // 1. Return the JobCallback Record { [[Callback]]: callback,
+  //    [[HostDefined]]: empty }.
+  JobCallback { callback }
+}
+
+/// https://tc39.es/ecma262/#sec-hostcalljobcallback
+pub fn host_call_job_callback(
+  job_callback: &JobCallback,
+  v: &Value,
+  arguments_list: &[Value],
+) -> Result<Value, Value> {
+  // 1. Assert: IsCallable(jobCallback.[[Callback]]) is true.
+  // 2. Return ? Call(jobCallback.[[Callback]], V, argumentsList).
+  call(&job_callback.callback.clone().into(), v, arguments_list)
+}
+
+/// https://tc39.es/ecma262/#sec-hostenqueuepromisejob
+///
+/// A `realm` prepares an execution context in it for the duration of `job`,
+/// the Job runs without one otherwise.
+pub fn host_enqueue_promise_job(job: Job, realm: Option<Realm>) {
+  enqueue_job(match realm {
+    None => job,
+    Some(realm) => Box::new(move || {
+      push_execution_context(ExecutionContext {
+        function: None,
+        realm,
+        variable_environment: None,
+        lexical_environment: None,
+      });
+      let result = job();
+      pop_execution_context();
+      result
+    }),
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
+  use super::*;
+  use crate::{
+    agent::{current_realm, run_jobs, running_execution_context},
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+  };
+
+  #[test]
+  fn promise_jobs_run_in_order() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let log = Rc::new(RefCell::new(Vec::new()));
+    for i in 1..=2 {
+      let log = log.clone();
+      let expected = realm.clone();
+      host_enqueue_promise_job(
+        Box::new(move || {
+          assert!(Realm::equals(&current_realm(), &expected));
+          log.borrow_mut().push(i);
+          Ok(())
+        }),
+        Some(realm.clone()),
+      );
+    }
+    let nested = log.clone();
+    host_enqueue_promise_job(
+      Box::new(move || {
+        assert!(running_execution_context().is_none());
+        let log = nested.clone();
+        host_enqueue_promise_job(
+          Box::new(move || {
+            log.borrow_mut().push(4);
+            Ok(())
+          }),
+          None,
+        );
+        nested.borrow_mut().push(3);
+        Ok(())
+      }),
+      None,
+    );
+    // nothing runs before the synchronous work completes
+    assert!(log.borrow().is_empty());
+    pop_execution_context();
+    run_jobs().unwrap();
+    assert_eq!(*log.borrow(), [1, 2, 3, 4]);
+  }
+
+  #[test]
+  fn a_throwing_job_keeps_the_rest_queued() {
+    host_enqueue_promise_job(Box::new(|| Err(Value::from(1.0))), None);
+    host_enqueue_promise_job(Box::new(|| Ok(())), None);
+    assert!(run_jobs().is_err());
+    assert!(run_jobs().is_ok());
+  }
+}
@@ -0,0 +1,6 @@
+//! https://tc39.es/ecma262/#sec-executable-code-and-execution-contexts
+
+pub mod environment_records;
+pub mod execution_context;
+pub mod jobs;
+pub mod realm;
@@ -0,0 +1,238 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::define_property_or_throw,
+    ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+  },
+  agent::push_execution_context,
+  fundamental_objects::{
+    error_objects::{create_error_objects, ErrorKind},
+    function_objects::{
+      create_function_constructor, create_function_prototype,
+    },
+    object_objects::create_object_objects,
+  },
+  helpers::Either,
+  host,
+  indexed_collections::array_objects::create_array_prototype,
+  language_types::{boolean::JsBoolean, null::JsNull, object::JsObject, Value},
+  specification_types::property_descriptor::PropertyDescriptor,
+  text_processing::string_objects::create_string_prototype,
+};
+
+use super::execution_context::ExecutionContext;
+
+/// Receives each line `console.log` prints.
+pub type ConsoleSink = Box<dyn FnMut(&str)>;
+
+/// https://tc39.es/ecma262/#realm-record
+struct RealmRecord {
+  /// Keyed by the intrinsic name without the `%`, e.g. `Object.prototype`.
+  intrinsics: HashMap<&'static str, JsObject>,
+  global_object: Option<JsObject>,
+  /// Part of [[HostDefined]], stdout unless the host replaces it.
+  console: ConsoleSink,
+}
+
+/// https://tc39.es/ecma262/#sec-code-realms
+#[derive(Clone)]
+pub struct Realm(Rc<RefCell<RealmRecord>>);
+
+impl Realm {
+  pub fn equals(lhs: &Self, rhs: &Self) -> bool {
+    Rc::ptr_eq(&lhs.0, &rhs.0)
+  }
+
+  /// The realm's `%name%` intrinsic, panics if it hasn't been created.
+  pub fn intrinsic(&self, name: &str) -> JsObject {
+    self
+      .get_intrinsic(name)
+      .unwrap_or_else(|| panic!("%{}% isn't an intrinsic", name))
+  }
+
+  /// The realm's `%name%` intrinsic, `None` if it hasn't been created.
+  pub fn get_intrinsic(&self, name: &str) -> Option<JsObject> {
+    self.0.borrow().intrinsics.get(name).cloned()
+  }
+
+  pub(crate) fn set_intrinsic(&self, name: &'static str, intrinsic: JsObject) {
+    self.0.borrow_mut().intrinsics.insert(name, intrinsic);
+  }
+
+  /// [[GlobalObject]], panics before `SetRealmGlobalObject`.
+  pub fn global_object(&self) -> JsObject {
+    self
+      .0
+      .borrow()
+      .global_object
+      .clone()
+      .expect("the realm's global object isn't set")
+  }
+
+  pub fn set_console_sink(&self, sink: ConsoleSink) {
+    self.0.borrow_mut().console = sink;
+  }
+
+  pub fn write_console(&self, line: &str) {
+    (self.0.borrow_mut().console)(line);
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-createrealm
+pub fn create_realm() -> Realm {
+  // 1. Let realmRec be a new Realm Record.
+  let realm_rec = Realm(Rc::new(RefCell::new(RealmRecord {
+    intrinsics: HashMap::new(),
+    global_object: None,
+    console: Box::new(|line| println!("{}", line)),
+  })));
+  // 2. Perform CreateIntrinsics(realmRec).
+  create_intrinsics(&realm_rec);
+  // 3. Set realmRec.[[GlobalObject]] to undefined.
+  // 4. Set realmRec.[[GlobalEnv]] to undefined.
+  // 5. Set realmRec.[[TemplateMap]] to a new empty List.
+  // 6. Return realmRec.
+  realm_rec
+}
+
+/// https://tc39.es/ecma262/#sec-createintrinsics
+pub fn create_intrinsics(realm_rec: &Realm) {
+  // 1. Let intrinsics be a new Record.
+  // 2. Set realmRec.[[Intrinsics]] to intrinsics.
+  // 3. Set fields of realmRec.[[Intrinsics]] with the values listed in Table 6. The
+  //    field names are the names listed in column one of the table. The value of each
+  //    field is a new object value fully and recursively populated with property values
+  //    as defined by the specification of each object in clauses 19 through 28. All
+  //    object property values are newly created object values. All values that are
+  //    built-in function objects are created by performing CreateBuiltinFunction(steps,
+  //    length, name, slots, realmRec, prototype) where steps is the definition of that
+  //    function provided by this specification, name is the initial value of the
+  //    function's "name" property, length is the initial value of the function's
+  //    "length" property, slots is a list of the names, if any, of the function's
+  //    specified internal slots, and prototype is the specified value of the
+  //    function's [[Prototype]] internal slot. The creation of the intrinsics and their
+  //    properties must be ordered to avoid any dependencies upon objects that have not
+  //    yet been created.
+  // TODO: %Object.prototype% is an immutable prototype exotic object
+  let object_prototype = ordinary_object_create(Either::B(JsNull), &[]);
+  realm_rec.set_intrinsic("Object.prototype", object_prototype.clone());
+  realm_rec.set_intrinsic(
+    "Function.prototype",
+    create_function_prototype(realm_rec, object_prototype),
+  );
+  create_object_objects(realm_rec);
+  create_function_constructor(realm_rec);
+  create_error_objects(realm_rec);
+  create_array_prototype(realm_rec);
+  create_string_prototype(realm_rec);
+  // 4. Perform AddRestrictedFunctionProperties(intrinsics.[[%Function.prototype%]],
+  //    realmRec).
+  // TODO: AddRestrictedFunctionProperties
+  // 5. Return unused.
+}
+
+/// https://tc39.es/ecma262/#sec-setrealmglobalobject, without the this value
+/// and the global environment.
+pub fn set_realm_global_object(
+  realm_rec: &Realm,
+  global_obj: Option<JsObject>,
+) {
+  // 1. If globalObj is undefined, then
+  //   a. Let intrinsics be realmRec.[[Intrinsics]].
+  //   b. Set globalObj to OrdinaryObjectCreate(intrinsics.[[%Object.prototype%]]).
+  let global_obj = global_obj.unwrap_or_else(|| {
+    ordinary_object_create(
+      Either::A(realm_rec.intrinsic("Object.prototype")),
+      &[],
+    )
+  });
+  // 2. Assert: Type(globalObj) is Object.
+  // 3. If thisValue is undefined, set thisValue to globalObj.
+  // 4. Set realmRec.[[GlobalObject]] to globalObj.
+  realm_rec.0.borrow_mut().global_object = Some(global_obj);
+  // 5. Let newGlobalEnv be NewGlobalEnvironment(globalObj, thisValue).
+  // 6. Set realmRec.[[GlobalEnv]] to newGlobalEnv.
+  // TODO: global environment
+  // 7. Return unused.
+}
+
+/// https://tc39.es/ecma262/#sec-setdefaultglobalbindings
+pub fn set_default_global_bindings(
+  realm_rec: &Realm,
+) -> Result<JsObject, Value> {
+  // 1. Let global be realmRec.[[GlobalObject]].
+  let global = realm_rec.global_object();
+  // 2. For each property of the Global Object specified in clause 19, do
+  //   a. Let name be the String value of the property name.
+  //   b. Let desc be the fully populated data Property Descriptor for the property,
+  //      containing the specified attributes for the property. For properties listed
+  //      in 19.2, 19.3, or 19.4 the value of the [[Value]] attribute is the
+  //      corresponding intrinsic object from realmRec.
+  //   c. Perform ? DefinePropertyOrThrow(global, name, desc).
+  // TODO: globalThis is realmRec.[[GlobalEnv]].[[GlobalThisValue]]
+  define_property_or_throw(
+    &global,
+    &"globalThis".into(),
+    PropertyDescriptor {
+      value: Some(Value::from(global.clone())),
+      writable: Some(JsBoolean::True),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::True),
+    },
+  )?;
+  let constructors = ErrorKind::ALL.map(ErrorKind::name);
+  for name in ["Object", "Function"].into_iter().chain(constructors) {
+    define_property_or_throw(
+      &global,
+      &name.into(),
+      PropertyDescriptor {
+        value: Some(Value::from(realm_rec.intrinsic(name))),
+        writable: Some(JsBoolean::True),
+        get: None,
+        set: None,
+        enumerable: Some(JsBoolean::False),
+        configurable: Some(JsBoolean::True),
+      },
+    )?;
+  }
+  // 3. Return global.
+  Ok(global)
+}
+
+/// https://tc39.es/ecma262/#sec-initializehostdefinedrealm, returns the realm
+/// whose execution context is left running.
+pub fn initialize_host_defined_realm() -> Result<Realm, Value> {
+  // 1. Let realm be CreateRealm().
+  let realm = create_realm();
+  // 2. Let newContext be a new execution context.
+  // 3. Set the Function of newContext to null.
+  // 4. Set the Realm of newContext to realm.
+  // 5. Set the ScriptOrModule of newContext to null.
+  // 6. Push newContext onto the execution context stack; newContext is now the
+  //    running execution context.
+  push_execution_context(ExecutionContext {
+    function: None,
+    realm: realm.clone(),
+    variable_environment: None,
+    lexical_environment: None,
+  });
+  // 7. If the host requires use of an exotic object to serve as realm's global object,
+  //    let global be such an object created in a host-defined manner. Otherwise, let
+  //    global be undefined, indicating that an ordinary object should be created as
+  //    the global object.
+  // 8. If the host requires that the this binding in realm's global scope return an
+  //    object other than the global object, let thisValue be such an object created in
+  //    a host-defined manner. Otherwise, let thisValue be undefined, indicating that
+  //    realm's global this binding should be the global object.
+  // 9. Perform SetRealmGlobalObject(realm, global, thisValue).
+  set_realm_global_object(&realm, None);
+  // 10. Let globalObj be ? SetDefaultGlobalBindings(realm).
+  let global_obj = set_default_global_bindings(&realm)?;
+  // 11. Create any host-defined global object properties on globalObj.
+  host::console::add_console(&realm, &global_obj)?;
+  // 12. Return unused.
+  Ok(realm)
+}
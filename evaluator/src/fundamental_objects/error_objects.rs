@@ -0,0 +1,318 @@
+//! https://tc39.es/ecma262/#sec-error-objects
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{
+      create_non_enumerable_data_property_or_throw, define_property_or_throw,
+      get,
+    },
+    ordinary_object_internal_methods_and_internal_slots::{
+      ordinary_create_from_constructor, ordinary_object_create,
+    },
+  },
+  agent::{current_realm, running_execution_context},
+  executable_code_and_execution_contexts::realm::Realm,
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean, object::JsObject, string::JsString, Value,
+  },
+  ordinary_and_exotic_objects_behaviours::builtin_function::{
+    create_builtin_function, BuiltinBehaviour,
+  },
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+/// `Error` and the NativeError types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+  Error,
+  RangeError,
+  ReferenceError,
+  SyntaxError,
+  TypeError,
+}
+
+impl ErrorKind {
+  pub const ALL: [ErrorKind; 5] = [
+    Self::Error,
+    Self::RangeError,
+    Self::ReferenceError,
+    Self::SyntaxError,
+    Self::TypeError,
+  ];
+
+  /// The constructor's name, which is also its intrinsic name.
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Error => "Error",
+      Self::RangeError => "RangeError",
+      Self::ReferenceError => "ReferenceError",
+      Self::SyntaxError => "SyntaxError",
+      Self::TypeError => "TypeError",
+    }
+  }
+
+  /// The intrinsic name of the constructor's prototype.
+  pub fn prototype(self) -> &'static str {
+    match self {
+      Self::Error => "Error.prototype",
+      Self::RangeError => "RangeError.prototype",
+      Self::ReferenceError => "ReferenceError.prototype",
+      Self::SyntaxError => "SyntaxError.prototype",
+      Self::TypeError => "TypeError.prototype",
+    }
+  }
+
+  fn behaviour(self) -> BuiltinBehaviour {
+    match self {
+      Self::Error => |_, arguments, new_target| {
+        native_error(Self::Error, arguments, new_target)
+      },
+      Self::RangeError => |_, arguments, new_target| {
+        native_error(Self::RangeError, arguments, new_target)
+      },
+      Self::ReferenceError => |_, arguments, new_target| {
+        native_error(Self::ReferenceError, arguments, new_target)
+      },
+      Self::SyntaxError => |_, arguments, new_target| {
+        native_error(Self::SyntaxError, arguments, new_target)
+      },
+      Self::TypeError => |_, arguments, new_target| {
+        native_error(Self::TypeError, arguments, new_target)
+      },
+    }
+  }
+}
+
+/// A new error object of the current realm, for the `Err` of an abstract
+/// operation that throws, e.g. `Err(throw_error(ErrorKind::TypeError, ..))`.
+pub fn throw_error(kind: ErrorKind, message: &str) -> Value {
+  let o = ordinary_object_create(
+    Either::A(current_realm().intrinsic(kind.prototype())),
+    &["ErrorData"],
+  );
+  create_non_enumerable_data_property_or_throw(
+    &o,
+    &"message".into(),
+    Value::from(message),
+  );
+  o.into()
+}
+
+/// https://tc39.es/ecma262/#sec-error-message and
+/// https://tc39.es/ecma262/#sec-nativeerror
+fn native_error(
+  kind: ErrorKind,
+  arguments: &[Value],
+  new_target: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. If NewTarget is undefined, let newTarget be the active function object; else
+  //    let newTarget be NewTarget.
+  let new_target = match new_target {
+    Some(new_target) => new_target.clone(),
+    None => running_execution_context()
+      .and_then(|context| context.function)
+      .expect("the active function object"),
+  };
+  // 2. Let O be ? OrdinaryCreateFromConstructor(newTarget, "%NativeError.prototype%",
+  //    « [[ErrorData]] »).
+  let o = ordinary_create_from_constructor(
+    &new_target,
+    kind.prototype(),
+    &["ErrorData"],
+  )?;
+  // 3. If message is not undefined, then
+  match arguments.first() {
+    None | Some(Value::Undefined(_)) => {}
+    Some(message) => {
+      // a. Let msg be ? ToString(message).
+      let msg = message.to_string()?;
+      // b. Perform CreateNonEnumerableDataPropertyOrThrow(O, "message", msg).
+      create_non_enumerable_data_property_or_throw(
+        &o,
+        &"message".into(),
+        Value::from(msg),
+      );
+    }
+  }
+  // 4. Perform ? InstallErrorCause(O, options).
+  // TODO: InstallErrorCause needs HasProperty
+  // 5. Return O.
+  Ok(o.into())
+}
+
+/// https://tc39.es/ecma262/#sec-error.prototype.tostring
+fn error_prototype_to_string(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be the this value.
+  // 2. If Type(O) is not Object, throw a TypeError exception.
+  let o = match this {
+    Value::Object(o) => o,
+    _ => {
+      return Err(throw_error(
+        ErrorKind::TypeError,
+        "Error.prototype.toString called on non-object",
+      ))
+    }
+  };
+  // 3. Let name be ? Get(O, "name").
+  // 4. If name is undefined, set name to "Error"; otherwise set name to ? ToString(name).
+  let name = match get(o, &"name".into())? {
+    Value::Undefined(_) => "Error".into(),
+    name => name.to_string()?,
+  };
+  // 5. Let msg be ? Get(O, "message").
+  // 6. If msg is undefined, set msg to the empty String; otherwise set msg to
+  //    ? ToString(msg).
+  let msg = match get(o, &"message".into())? {
+    Value::Undefined(_) => JsString::default(),
+    msg => msg.to_string()?,
+  };
+  // 7. If name is the empty String, return msg.
+  if name.is_empty() {
+    return Ok(msg.into());
+  }
+  // 8. If msg is the empty String, return name.
+  if msg.is_empty() {
+    return Ok(name.into());
+  }
+  // 9. Return the string-concatenation of name, the code unit 0x003A (COLON), the code
+  //    unit 0x0020 (SPACE), and msg.
+  Ok((name + ": " + &msg).into())
+}
+
+/// Creates %Error%, the NativeError constructors and their prototypes.
+pub fn create_error_objects(realm: &Realm) {
+  for kind in ErrorKind::ALL {
+    // The Error constructor has a [[Prototype]] internal slot whose value is
+    // %Function.prototype%, each NativeError constructor's is %Error%.
+    let constructor_prototype = match kind {
+      ErrorKind::Error => None,
+      _ => Some(Either::A(realm.intrinsic("Error"))),
+    };
+    let constructor = create_builtin_function(
+      kind.behaviour(),
+      1.0,
+      kind.name(),
+      Some(realm),
+      constructor_prototype,
+      true,
+    );
+    // The Error prototype object has a [[Prototype]] internal slot whose value is
+    // %Object.prototype%, each NativeError prototype object's is %Error.prototype%.
+    let prototype_prototype = match kind {
+      ErrorKind::Error => realm.intrinsic("Object.prototype"),
+      _ => realm.intrinsic("Error.prototype"),
+    };
+    let prototype = ordinary_object_create(Either::A(prototype_prototype), &[]);
+    // The initial value of Error.prototype is the Error prototype object. This
+    // property has the attributes { [[Writable]]: false, [[Enumerable]]: false,
+    // [[Configurable]]: false }.
+    define_property_or_throw(
+      &constructor,
+      &"prototype".into(),
+      PropertyDescriptor {
+        value: Some(prototype.clone().into()),
+        writable: Some(JsBoolean::False),
+        get: None,
+        set: None,
+        enumerable: Some(JsBoolean::False),
+        configurable: Some(JsBoolean::False),
+      },
+    )
+    .expect("the constructor is extensible");
+    // Error.prototype.constructor, Error.prototype.message and Error.prototype.name
+    create_non_enumerable_data_property_or_throw(
+      &prototype,
+      &"constructor".into(),
+      constructor.clone().into(),
+    );
+    create_non_enumerable_data_property_or_throw(
+      &prototype,
+      &"message".into(),
+      Value::from(""),
+    );
+    create_non_enumerable_data_property_or_throw(
+      &prototype,
+      &"name".into(),
+      Value::from(kind.name()),
+    );
+    if kind == ErrorKind::Error {
+      let to_string = create_builtin_function(
+        error_prototype_to_string,
+        0.0,
+        "toString",
+        Some(realm),
+        None,
+        false,
+      );
+      create_non_enumerable_data_property_or_throw(
+        &prototype,
+        &"toString".into(),
+        to_string.into(),
+      );
+    }
+    realm.set_intrinsic(kind.name(), constructor);
+    realm.set_intrinsic(kind.prototype(), prototype);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::{call, construct, invoke},
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+  };
+
+  fn get_string(o: &Value, key: &str) -> JsString {
+    match o {
+      Value::Object(o) => get(o, &key.into()).unwrap().to_string().unwrap(),
+      _ => panic!("expect an object"),
+    }
+  }
+
+  #[test]
+  fn throw_type_error() {
+    initialize_host_defined_realm().unwrap();
+    let error = throw_error(ErrorKind::TypeError, "x is not a function");
+    assert_eq!(get_string(&error, "name"), "TypeError");
+    assert_eq!(get_string(&error, "message"), "x is not a function");
+    let string = invoke(&error, &"toString".into(), &[]).unwrap();
+    assert_eq!(
+      string.to_string().unwrap(),
+      "TypeError: x is not a function"
+    );
+    pop_execution_context();
+  }
+
+  #[test]
+  fn constructors() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let range_error =
+      get(&realm.global_object(), &"RangeError".into()).unwrap();
+    assert!(range_error.is_constructor());
+    let error = construct(&range_error, &[Value::from("m")], None).unwrap();
+    assert!(matches!(
+      error.get_prototype_of(),
+      Either::A(proto) if JsObject::equals(&proto, &realm.intrinsic("RangeError.prototype"))
+    ));
+    assert!(error.has_internal_slot("ErrorData"));
+    assert_eq!(get_string(&error.into(), "message"), "m");
+    // called as a function
+    let error = call(&range_error, &Value::undefined(), &[]).unwrap();
+    assert_eq!(get_string(&error, "message"), "");
+    let string = invoke(&error, &"toString".into(), &[]).unwrap();
+    assert_eq!(string.to_string().unwrap(), "RangeError");
+    // Error.prototype.toString on a non-object
+    let to_string =
+      get(&realm.intrinsic("Error.prototype"), &"toString".into()).unwrap();
+    let error = call(&to_string, &Value::from(1.0), &[]).unwrap_err();
+    assert_eq!(get_string(&error, "name"), "TypeError");
+    pop_execution_context();
+  }
+}
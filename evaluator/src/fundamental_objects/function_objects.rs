@@ -0,0 +1,347 @@
+//! https://tc39.es/ecma262/#sec-function-objects
+
+use std::rc::Rc;
+
+use ng262_parser::parser::{
+  nodes::{Node, NodeType},
+  Parser,
+};
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{
+      create_non_enumerable_data_property_or_throw, define_property_or_throw,
+    },
+    ordinary_object_internal_methods_and_internal_slots::get_prototype_from_constructor,
+  },
+  agent::running_execution_context,
+  executable_code_and_execution_contexts::{
+    environment_records::new_declarative_environment, realm::Realm,
+  },
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean, object::JsObject, string::JsString, Value,
+  },
+  ordinary_and_exotic_objects_behaviours::{
+    builtin_function::create_builtin_function,
+    ecmascript_function_objects::{
+      is_identifier_parameter, make_constructor, ordinary_function_create,
+      set_function_name,
+    },
+  },
+  runtime_semantics::evaluation::unsupported,
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+use super::error_objects::{throw_error, ErrorKind};
+
+/// The kind argument of CreateDynamicFunction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionKind {
+  Normal,
+  Generator,
+  Async,
+  AsyncGenerator,
+}
+
+/// https://tc39.es/ecma262/#sec-function-p1-p2-pn-body
+fn function_constructor(
+  _: &Value,
+  arguments: &[Value],
+  new_target: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let C be the active function object.
+  let c = running_execution_context()
+    .and_then(|context| context.function)
+    .expect("the Function constructor is the active function object");
+  // 2. If bodyArg is not present, set bodyArg to the empty String.
+  let (body_arg, parameter_args) = match arguments.split_last() {
+    Some((body_arg, parameter_args)) => (body_arg.clone(), parameter_args),
+    None => (Value::from(""), arguments),
+  };
+  // 3. Return ? CreateDynamicFunction(C, NewTarget, normal, parameterArgs,
+  //    bodyArg).
+  create_dynamic_function(
+    &c,
+    new_target,
+    FunctionKind::Normal,
+    parameter_args,
+    &body_arg,
+  )
+  .map(Value::from)
+}
+
+/// https://tc39.es/ecma262/#sec-createdynamicfunction
+pub fn create_dynamic_function(
+  constructor: &JsObject,
+  new_target: Option<&JsObject>,
+  kind: FunctionKind,
+  parameter_args: &[Value],
+  body_arg: &Value,
+) -> Result<JsObject, Value> {
+  // 1. If newTarget is undefined, set newTarget to constructor.
+  let new_target = new_target.unwrap_or(constructor);
+  // 2. If kind is normal, then
+  //   a. Let prefix be "function".
+  //   b. Let exprSym be the grammar symbol FunctionExpression.
+  //   c. Let bodySym be the grammar symbol FunctionBody[~Yield, ~Await].
+  //   d. Let parameterSym be the grammar symbol FormalParameters[~Yield, ~Await].
+  //   e. Let fallbackProto be "%Function.prototype%".
+  // 3. Else if kind is generator, then
+  //   ...
+  // 4. Else if kind is async, then
+  //   ...
+  // 5. Else,
+  //   ...
+  // TODO: the parser doesn't support generators and async functions yet, so
+  // the other kinds always throw a SyntaxError
+  let (prefix, fallback_proto) = match kind {
+    FunctionKind::Normal => ("function", "Function.prototype"),
+    FunctionKind::Generator => ("function*", "GeneratorFunction.prototype"),
+    FunctionKind::Async => ("async function", "AsyncFunction.prototype"),
+    FunctionKind::AsyncGenerator => {
+      ("async function*", "AsyncGeneratorFunction.prototype")
+    }
+  };
+  // 6. Let argCount be the number of elements in parameterArgs.
+  // 7. Let parameterStrings be a new empty List.
+  // 8. For each element arg of parameterArgs, do
+  //   a. Append ? ToString(arg) to parameterStrings.
+  let parameter_strings = parameter_args
+    .iter()
+    .map(Value::to_string)
+    .collect::<Result<Vec<_>, _>>()?;
+  // 9. Let bodyString be ? ToString(bodyArg).
+  let body_string = body_arg.to_string()?;
+  // 10. Let currentRealm be the current Realm Record.
+  // 11. Perform ? HostEnsureCanCompileStrings(currentRealm, parameterStrings,
+  //     bodyString, false).
+  // 12. Let P be the empty String.
+  // 13. If argCount > 0, then
+  //   a. Set P to parameterStrings[0].
+  //   b. Let k be 1.
+  //   c. Repeat, while k < argCount,
+  //     i. Let nextArgString be parameterStrings[k].
+  //     ii. Set P to the string-concatenation of P, "," (a comma), and
+  //         nextArgString.
+  //     iii. Set k to k + 1.
+  let p = parameter_strings
+    .iter()
+    .map(JsString::to_string_lossy)
+    .collect::<Vec<_>>()
+    .join(",");
+  // 14. Let bodyParseString be the string-concatenation of 0x000A (LINE FEED),
+  //     bodyString, and 0x000A (LINE FEED).
+  let body_parse_string = format!("\n{}\n", body_string);
+  // 15. Let sourceString be the string-concatenation of prefix, " anonymous(",
+  //     P, 0x000A (LINE FEED), ") {", bodyParseString, and "}".
+  // 16. Let sourceText be StringToCodePoints(sourceString).
+  let source_text =
+    format!("{} anonymous({}\n) {{{}}}", prefix, p, body_parse_string);
+  // 17. Let parameters be ParseText(P, parameterSym).
+  // 18. If parameters is a List of errors, throw a SyntaxError exception.
+  // 19. Let body be ParseText(bodyParseString, bodySym).
+  // 20. If body is a List of errors, throw a SyntaxError exception.
+  // the parameters and the body are parsed in a function on their own, so
+  // neither of them can end the function early, e.g. `/*` and `*/) {`
+  parse_function(format!("{} anonymous({}\n) {{}}", prefix, p))?;
+  parse_function(format!(
+    "{} anonymous(\n) {{{}}}",
+    prefix, body_parse_string
+  ))?;
+  // 21. NOTE: The parameters and body are parsed separately to ensure that each
+  //     is valid alone. For example, new Function("/*", "*/ ) {") does not
+  //     evaluate to a function.
+  // 22. NOTE: If this step is reached, sourceText must have the syntax of
+  //     exprSym (although the reverse implication does not hold). The purpose of
+  //     the next two steps is to enforce any Early Error rules which apply to
+  //     exprSym directly.
+  // 23. Let expr be ParseText(sourceText, exprSym).
+  // 24. If expr is a List of errors, throw a SyntaxError exception.
+  let expr = parse_function(source_text)?;
+  // TODO: binding patterns
+  if let NodeType::FunctionDeclaration { params, .. } = expr.node_type() {
+    if let Some(pattern) =
+      params.iter().find(|param| !is_identifier_parameter(param))
+    {
+      return Err(unsupported(pattern));
+    }
+  }
+  // 25. Let proto be ? GetPrototypeFromConstructor(newTarget, fallbackProto).
+  let proto = get_prototype_from_constructor(new_target, fallback_proto)?;
+  // 26. Let env be currentRealm.[[GlobalEnv]].
+  // TODO: the global environment
+  let env = new_declarative_environment(None);
+  // 27. Let privateEnv be null.
+  // 28. Let F be OrdinaryFunctionCreate(proto, sourceText, parameters, body,
+  //     non-lexical-this, env, privateEnv).
+  let f = ordinary_function_create(proto, Rc::new(expr), false, env);
+  // 29. Perform SetFunctionName(F, "anonymous").
+  set_function_name(&f, "anonymous".into(), None);
+  // 30. If kind is generator, then
+  //   ...
+  // 31. Else if kind is asyncGenerator, then
+  //   ...
+  // 32. Else if kind is normal, then
+  //   a. Perform MakeConstructor(F).
+  if kind == FunctionKind::Normal {
+    make_constructor(&f);
+  }
+  // 33. NOTE: Functions whose kind is async are not constructible and do not
+  //     have a [[Construct]] internal method or a "prototype" property.
+  // 34. Return F.
+  Ok(f)
+}
+
+/// Parse `source` as the single function of a script, the parser can't start
+/// at a FunctionExpression so the function is parsed as a FunctionDeclaration
+/// named `anonymous`.
+fn parse_function(source: String) -> Result<Node, Value> {
+  let script = Parser::parse_script(&source, None).map_err(|e| {
+    throw_error(ErrorKind::SyntaxError, e.syntax_error().message())
+  })?;
+  match script.node_type() {
+    NodeType::Script { body }
+      if body.len() == 1
+        && matches!(
+          body[0].node_type(),
+          NodeType::FunctionDeclaration { .. }
+        ) =>
+    {
+      Ok(body[0].clone())
+    }
+    _ => Err(throw_error(
+      ErrorKind::SyntaxError,
+      "Unexpected token in the function source",
+    )),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-properties-of-the-function-prototype-object
+pub fn create_function_prototype(
+  realm: &Realm,
+  object_prototype: JsObject,
+) -> JsObject {
+  // The Function prototype object:
+  // - is %Function.prototype%.
+  // - is itself a built-in function object.
+  // - accepts any arguments and returns undefined when invoked.
+  // - does not have a [[Construct]] internal method; it cannot be used as a
+  //   constructor with the new operator.
+  // - has a [[Prototype]] internal slot whose value is %Object.prototype%.
+  // - does not have a "prototype" property.
+  // - has a "length" property whose value is +0𝔽.
+  // - has a "name" property whose value is the empty String.
+  create_builtin_function(
+    |_, _, _| Ok(Value::undefined()),
+    0.0,
+    "",
+    Some(realm),
+    Some(Either::A(object_prototype)),
+    false,
+  )
+}
+
+/// https://tc39.es/ecma262/#sec-properties-of-the-function-constructor
+pub fn create_function_constructor(realm: &Realm) {
+  let prototype = realm.intrinsic("Function.prototype");
+  let constructor = create_builtin_function(
+    function_constructor,
+    1.0,
+    "Function",
+    Some(realm),
+    None,
+    true,
+  );
+  // Function.prototype has the attributes { [[Writable]]: false,
+  // [[Enumerable]]: false, [[Configurable]]: false }.
+  define_property_or_throw(
+    &constructor,
+    &"prototype".into(),
+    PropertyDescriptor {
+      value: Some(prototype.clone().into()),
+      writable: Some(JsBoolean::False),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::False),
+    },
+  )
+  .expect("the constructor is extensible");
+  create_non_enumerable_data_property_or_throw(
+    &prototype,
+    &"constructor".into(),
+    constructor.clone().into(),
+  );
+  realm.set_intrinsic("Function", constructor);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::{call, construct, get},
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    test_helpers::error_name,
+  };
+
+  #[test]
+  fn dynamic_function() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let function = get(&realm.global_object(), &"Function".into()).unwrap();
+    let arguments = ["a".into(), "b".into(), "return a + b".into()];
+    let f = call(&function, &Value::undefined(), &arguments).unwrap();
+    let result = call(&f, &Value::undefined(), &[1.0.into(), 2.0.into()]);
+    assert!(matches!(result, Ok(Value::Number(n)) if *n == 3.0));
+    let f = match f {
+      Value::Object(f) => f,
+      _ => unreachable!(),
+    };
+    assert_eq!(
+      get(&f, &"name".into()).unwrap().to_string().unwrap(),
+      "anonymous"
+    );
+    assert!(matches!(
+      get(&f, &"length".into()).unwrap(),
+      Value::Number(n) if *n == 2.0
+    ));
+    // default and rest parameters
+    let arguments = ["a = 1".into(), "return a".into()];
+    let f = call(&function, &Value::undefined(), &arguments).unwrap();
+    let result = call(&f, &Value::undefined(), &[]);
+    assert!(matches!(result, Ok(Value::Number(n)) if *n == 1.0));
+    let arguments = ["...r".into(), "return r.length".into()];
+    let f = call(&function, &Value::undefined(), &arguments).unwrap();
+    let result = call(&f, &Value::undefined(), &[1.0.into(), 2.0.into()]);
+    assert!(matches!(result, Ok(Value::Number(n)) if *n == 2.0));
+    // the result is a constructor
+    let arguments = ["a".into(), "this.a = a".into()];
+    let f = call(&function, &Value::undefined(), &arguments).unwrap();
+    assert!(f.is_constructor());
+    let o = construct(&f, &[1.0.into()], None).unwrap();
+    assert!(matches!(
+      get(&o, &"a".into()).unwrap(),
+      Value::Number(n) if *n == 1.0
+    ));
+    // no arguments is an empty function
+    let f = call(&function, &Value::undefined(), &[]).unwrap();
+    let result = call(&f, &Value::undefined(), &[]);
+    assert!(matches!(result, Ok(Value::Undefined(_))));
+    for arguments in [
+      &["return +"][..],
+      &["a", "}"],
+      &["/*", "*/) {"],
+      &["}; function g() {"],
+      &["a) {}; function g(", ""],
+      // binding patterns are not supported yet
+      &["[a]", ""],
+      &["...{ length }", ""],
+    ] {
+      let arguments = arguments.iter().map(|&a| a.into()).collect::<Vec<_>>();
+      let error = call(&function, &Value::undefined(), &arguments).unwrap_err();
+      assert_eq!(error_name(error), "SyntaxError", "{:?}", arguments);
+    }
+    pop_execution_context();
+  }
+}
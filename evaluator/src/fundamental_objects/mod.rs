@@ -0,0 +1,5 @@
+//! https://tc39.es/ecma262/#sec-fundamental-objects
+
+pub mod error_objects;
+pub mod function_objects;
+pub mod object_objects;
@@ -0,0 +1,932 @@
+//! https://tc39.es/ecma262/#sec-object-objects
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{
+      create_array_from_list, create_non_enumerable_data_property_or_throw,
+      define_property_or_throw, enumerable_own_property_names, get,
+      has_own_property, invoke, set, set_integrity_level, test_integrity_level,
+      IntegrityLevel, PropertyKind,
+    },
+    ordinary_object_internal_methods_and_internal_slots::{
+      ordinary_create_from_constructor, ordinary_object_create,
+    },
+    testing_and_comparison_operations::{require_object_coercible, same_value},
+  },
+  agent::{current_realm, running_execution_context},
+  executable_code_and_execution_contexts::realm::Realm,
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    object::{JsObject, PropertyKey, Prototype},
+    string::JsString,
+    symbol::JsSymbol,
+    Value,
+  },
+  ordinary_and_exotic_objects_behaviours::builtin_function::{
+    create_builtin_function, BuiltinBehaviour,
+  },
+  specification_types::property_descriptor::{
+    from_property_descriptor, to_property_descriptor, PropertyDescriptor,
+  },
+};
+
+/// The `type` of GetOwnPropertyKeys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyType {
+  String,
+  Symbol,
+}
+
+/// https://tc39.es/ecma262/#sec-getownpropertykeys, the keys are in the
+/// order of [[OwnPropertyKeys]].
+pub fn get_own_property_keys(
+  o: &Value,
+  r#type: KeyType,
+) -> Result<Vec<PropertyKey>, Value> {
+  // 1. Let obj be ? ToObject(O).
+  let obj = o.to_object()?;
+  // 2. Let keys be ? obj.[[OwnPropertyKeys]]().
+  let keys = obj.own_property_keys()?;
+  // 3. Let nameList be a new empty List.
+  // 4. For each element nextKey of keys, do
+  //   a. If Type(nextKey) is Symbol and type is symbol or Type(nextKey) is
+  //      String and type is string, then
+  //     i. Append nextKey as the last element of nameList.
+  // 5. Return nameList.
+  Ok(
+    keys
+      .into_iter()
+      .filter(|key| match key {
+        PropertyKey::String(_) => r#type == KeyType::String,
+        PropertyKey::Symbol(_) => r#type == KeyType::Symbol,
+      })
+      .collect(),
+  )
+}
+
+pub(crate) fn argument(arguments: &[Value], index: usize) -> Value {
+  arguments
+    .get(index)
+    .cloned()
+    .unwrap_or_else(Value::undefined)
+}
+
+/// https://tc39.es/ecma262/#sec-object.prototype.hasownproperty
+fn has_own_property_method(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let P be ? ToPropertyKey(V).
+  let p = argument(arguments, 0).to_property_key()?;
+  // 2. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 3. Return ? HasOwnProperty(O, P).
+  Ok(has_own_property(&o, &p)?.into())
+}
+
+/// https://tc39.es/ecma262/#sec-object.prototype.isprototypeof
+fn is_prototype_of(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. If Type(V) is not Object, return false.
+  let mut v = match argument(arguments, 0) {
+    Value::Object(v) => v,
+    _ => return Ok(false.into()),
+  };
+  // 2. Let O be ? ToObject(this value).
+  let o = Value::from(this.to_object()?);
+  // 3. Repeat,
+  loop {
+    // a. Set V to ? V.[[GetPrototypeOf]]().
+    // b. If V is null, return false.
+    v = match v.get_prototype_of() {
+      Either::A(v) => v,
+      Either::B(_) => return Ok(false.into()),
+    };
+    // c. If SameValue(O, V) is true, return true.
+    if same_value(&o, &v.clone().into()) == JsBoolean::True {
+      return Ok(true.into());
+    }
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-object.prototype.propertyisenumerable
+fn property_is_enumerable(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let P be ? ToPropertyKey(V).
+  let p = argument(arguments, 0).to_property_key()?;
+  // 2. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 3. Let desc be ? O.[[GetOwnProperty]](P).
+  // 4. If desc is undefined, return false.
+  // 5. Return desc.[[Enumerable]].
+  Ok(
+    o.get_own_property(&p)?
+      .is_some_and(|desc| desc.enumerable == Some(JsBoolean::True))
+      .into(),
+  )
+}
+
+/// https://tc39.es/ecma262/#sec-object.prototype.tolocalestring
+fn to_locale_string(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be the this value.
+  // 2. Return ? Invoke(O, "toString").
+  invoke(this, &"toString".into(), &[])
+}
+
+/// https://tc39.es/ecma262/#sec-object.prototype.tostring
+fn to_string(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. If the this value is undefined, return "[object Undefined]".
+  // 2. If the this value is null, return "[object Null]".
+  match this {
+    Value::Undefined(_) => return Ok("[object Undefined]".into()),
+    Value::Null(_) => return Ok("[object Null]".into()),
+    _ => {}
+  }
+  // 3. Let O be ! ToObject(this value).
+  let o = this.to_object().expect("this isn't undefined or null");
+  // 4. Let isArray be ? IsArray(O).
+  // 5. If isArray is true, let builtinTag be "Array".
+  // 6. Else if O has a [[ParameterMap]] internal slot, let builtinTag be
+  //    "Arguments".
+  // 7. Else if O has a [[Call]] internal method, let builtinTag be "Function".
+  // 8. Else if O has an [[ErrorData]] internal slot, let builtinTag be "Error".
+  // 9. Else if O has a [[BooleanData]] internal slot, let builtinTag be
+  //    "Boolean".
+  // 10. Else if O has a [[NumberData]] internal slot, let builtinTag be
+  //     "Number".
+  // 11. Else if O has a [[StringData]] internal slot, let builtinTag be
+  //     "String".
+  // 12. Else if O has a [[DateValue]] internal slot, let builtinTag be "Date".
+  // 13. Else if O has a [[RegExpMatcher]] internal slot, let builtinTag be
+  //     "RegExp".
+  // 14. Else, let builtinTag be "Object".
+  let builtin_tag = if Value::from(o.clone()).is_array()? {
+    "Array"
+  } else if o.has_internal_slot("ParameterMap") {
+    "Arguments"
+  } else if o.get_call().is_some() {
+    "Function"
+  } else {
+    [
+      ("ErrorData", "Error"),
+      ("BooleanData", "Boolean"),
+      ("NumberData", "Number"),
+      ("StringData", "String"),
+      ("DateValue", "Date"),
+      ("RegExpMatcher", "RegExp"),
+    ]
+    .into_iter()
+    .find(|(slot, _)| o.has_internal_slot(slot))
+    .map_or("Object", |(_, tag)| tag)
+  };
+  // 15. Let tag be ? Get(O, @@toStringTag).
+  let tag = get(&o, &JsSymbol::to_string_tag().into())?;
+  // 16. If Type(tag) is not String, set tag to builtinTag.
+  let tag = match tag {
+    Value::String(tag) => tag,
+    _ => builtin_tag.into(),
+  };
+  // 17. Return the string-concatenation of "[object ", tag, and "]".
+  Ok((JsString::from("[object ") + &tag + "]").into())
+}
+
+/// https://tc39.es/ecma262/#sec-object.prototype.valueof
+fn value_of(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Return ? ToObject(this value).
+  Ok(this.to_object()?.into())
+}
+
+/// https://tc39.es/ecma262/#sec-object-value
+fn object_constructor(
+  _: &Value,
+  arguments: &[Value],
+  new_target: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. If NewTarget is neither undefined nor the active function object, then
+  //   a. Return ? OrdinaryCreateFromConstructor(NewTarget, "%Object.prototype%").
+  let active_function = running_execution_context().and_then(|c| c.function);
+  if let Some(new_target) = new_target {
+    if !matches!(&active_function, Some(f) if JsObject::equals(f, new_target)) {
+      return Ok(
+        ordinary_create_from_constructor(new_target, "Object.prototype", &[])?
+          .into(),
+      );
+    }
+  }
+  // 2. If value is either undefined or null, return
+  //    OrdinaryObjectCreate(%Object.prototype%).
+  // 3. Return ! ToObject(value).
+  match argument(arguments, 0) {
+    Value::Undefined(_) | Value::Null(_) => Ok(
+      ordinary_object_create(
+        Either::A(current_realm().intrinsic("Object.prototype")),
+        &[],
+      )
+      .into(),
+    ),
+    value => Ok(value.to_object().expect("value is not nullish").into()),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-object.assign
+fn assign(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let to be ? ToObject(target).
+  let to = argument(arguments, 0).to_object()?;
+  // 2. If only one argument was passed, return to.
+  // 3. For each element nextSource of sources, do
+  for next_source in arguments.iter().skip(1) {
+    // a. If nextSource is neither undefined nor null, then
+    if matches!(next_source, Value::Undefined(_) | Value::Null(_)) {
+      continue;
+    }
+    // i. Let from be ! ToObject(nextSource).
+    let from = next_source.to_object().expect("nextSource is not nullish");
+    // ii. Let keys be ? from.[[OwnPropertyKeys]]().
+    // iii. For each element nextKey of keys, do
+    for next_key in from.own_property_keys()? {
+      // 1. Let desc be ? from.[[GetOwnProperty]](nextKey).
+      // 2. If desc is not undefined and desc.[[Enumerable]] is true, then
+      //   a. Let propValue be ? Get(from, nextKey).
+      //   b. Perform ? Set(to, nextKey, propValue, true).
+      match from.get_own_property(&next_key)? {
+        Some(desc) if desc.enumerable == Some(JsBoolean::True) => {
+          let prop_value = get(&from, &next_key)?;
+          set(&to, &next_key, prop_value, true)?;
+        }
+        _ => {}
+      }
+    }
+  }
+  // 4. Return to.
+  Ok(to.into())
+}
+
+/// The `O` argument of the static methods that require an object.
+fn object_argument(o: &Value, method: &str) -> Result<JsObject, Value> {
+  match o {
+    Value::Object(o) => Ok(o.clone()),
+    _ => Err(throw_error(
+      ErrorKind::TypeError,
+      &format!("Object.{} called on non-object", method),
+    )),
+  }
+}
+
+/// The `proto` argument, which is an object or null.
+fn prototype_argument(proto: Value) -> Result<Prototype, Value> {
+  match proto {
+    Value::Object(proto) => Ok(Either::A(proto)),
+    Value::Null(null) => Ok(Either::B(null)),
+    _ => Err(throw_error(
+      ErrorKind::TypeError,
+      "Object prototype may only be an Object or null",
+    )),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-object.create
+fn create(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. If Type(O) is not Object and O is not null, throw a TypeError exception.
+  let o = prototype_argument(argument(arguments, 0))?;
+  // 2. Let obj be OrdinaryObjectCreate(O).
+  let obj = ordinary_object_create(o, &[]);
+  // 3. If Properties is not undefined, then
+  //   a. Return ? ObjectDefineProperties(obj, Properties).
+  // 4. Return obj.
+  match argument(arguments, 1) {
+    Value::Undefined(_) => Ok(obj.into()),
+    properties => Ok(object_define_properties(&obj, &properties)?.into()),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-object.defineproperties
+fn define_properties(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. If Type(O) is not Object, throw a TypeError exception.
+  let o = object_argument(&argument(arguments, 0), "defineProperties")?;
+  // 2. Return ? ObjectDefineProperties(O, Properties).
+  Ok(object_define_properties(&o, &argument(arguments, 1))?.into())
+}
+
+/// https://tc39.es/ecma262/#sec-objectdefineproperties
+fn object_define_properties(
+  o: &JsObject,
+  properties: &Value,
+) -> Result<JsObject, Value> {
+  // 1. Let props be ? ToObject(Properties).
+  let props = properties.to_object()?;
+  // 2. Let keys be ? props.[[OwnPropertyKeys]]().
+  // 3. Let descriptors be a new empty List.
+  let mut descriptors = Vec::new();
+  // 4. For each element nextKey of keys, do
+  for next_key in props.own_property_keys()? {
+    // a. Let propDesc be ? props.[[GetOwnProperty]](nextKey).
+    // b. If propDesc is not undefined and propDesc.[[Enumerable]] is true, then
+    //   i. Let descObj be ? Get(props, nextKey).
+    //   ii. Let desc be ? ToPropertyDescriptor(descObj).
+    //   iii. Append the pair (a two element List) consisting of nextKey and
+    //        desc to the end of descriptors.
+    match props.get_own_property(&next_key)? {
+      Some(prop_desc) if prop_desc.enumerable == Some(JsBoolean::True) => {
+        let desc_obj = get(&props, &next_key)?;
+        descriptors.push((next_key, to_property_descriptor(&desc_obj)?));
+      }
+      _ => {}
+    }
+  }
+  // 5. For each element pair of descriptors, do
+  //   a. Let P be the first element of pair.
+  //   b. Let desc be the second element of pair.
+  //   c. Perform ? DefinePropertyOrThrow(O, P, desc).
+  for (p, desc) in descriptors {
+    define_property_or_throw(o, &p, desc)?;
+  }
+  // 6. Return O.
+  Ok(o.clone())
+}
+
+/// https://tc39.es/ecma262/#sec-object.defineproperty
+fn define_property(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. If Type(O) is not Object, throw a TypeError exception.
+  let o = object_argument(&argument(arguments, 0), "defineProperty")?;
+  // 2. Let key be ? ToPropertyKey(P).
+  let key = argument(arguments, 1).to_property_key()?;
+  // 3. Let desc be ? ToPropertyDescriptor(Attributes).
+  let desc = to_property_descriptor(&argument(arguments, 2))?;
+  // 4. Perform ? DefinePropertyOrThrow(O, key, desc).
+  define_property_or_throw(&o, &key, desc)?;
+  // 5. Return O.
+  Ok(o.into())
+}
+
+/// https://tc39.es/ecma262/#sec-object.entries,
+/// https://tc39.es/ecma262/#sec-object.keys and
+/// https://tc39.es/ecma262/#sec-object.values
+fn enumerable_own_properties(
+  arguments: &[Value],
+  kind: PropertyKind,
+) -> Result<Value, Value> {
+  // 1. Let obj be ? ToObject(O).
+  let obj = argument(arguments, 0).to_object()?;
+  // 2. Let entryList be ? EnumerableOwnPropertyNames(obj, key+value).
+  let list = enumerable_own_property_names(&obj, kind)?;
+  // 3. Return CreateArrayFromList(entryList).
+  Ok(create_array_from_list(&list).into())
+}
+
+/// https://tc39.es/ecma262/#sec-object.freeze and
+/// https://tc39.es/ecma262/#sec-object.seal
+fn set_integrity(
+  arguments: &[Value],
+  level: IntegrityLevel,
+) -> Result<Value, Value> {
+  // 1. If Type(O) is not Object, return O.
+  let o = match argument(arguments, 0) {
+    Value::Object(o) => o,
+    o => return Ok(o),
+  };
+  // 2. Let status be ? SetIntegrityLevel(O, frozen).
+  // 3. If status is false, throw a TypeError exception.
+  if !set_integrity_level(&o, level)? {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      "Cannot change the integrity level",
+    ));
+  }
+  // 4. Return O.
+  Ok(o.into())
+}
+
+/// https://tc39.es/ecma262/#sec-object.isfrozen and
+/// https://tc39.es/ecma262/#sec-object.issealed
+fn test_integrity(
+  arguments: &[Value],
+  level: IntegrityLevel,
+) -> Result<Value, Value> {
+  // 1. If Type(O) is not Object, return true.
+  // 2. Return ? TestIntegrityLevel(O, frozen).
+  match argument(arguments, 0) {
+    Value::Object(o) => Ok(test_integrity_level(&o, level)?.into()),
+    _ => Ok(true.into()),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-object.getownpropertydescriptor
+fn get_own_property_descriptor(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let obj be ? ToObject(O).
+  let obj = argument(arguments, 0).to_object()?;
+  // 2. Let key be ? ToPropertyKey(P).
+  let key = argument(arguments, 1).to_property_key()?;
+  // 3. Let desc be ? obj.[[GetOwnProperty]](key).
+  let desc = obj.get_own_property(&key)?;
+  // 4. Return FromPropertyDescriptor(desc).
+  Ok(from_property_descriptor(desc))
+}
+
+/// https://tc39.es/ecma262/#sec-object.getownpropertynames and
+/// https://tc39.es/ecma262/#sec-object.getownpropertysymbols
+fn get_own_property_keys_array(
+  arguments: &[Value],
+  r#type: KeyType,
+) -> Result<Value, Value> {
+  // 1. Return CreateArrayFromList(? GetOwnPropertyKeys(O, string)).
+  let keys = get_own_property_keys(&argument(arguments, 0), r#type)?;
+  let keys = keys.into_iter().map(Value::from).collect::<Vec<_>>();
+  Ok(create_array_from_list(&keys).into())
+}
+
+/// https://tc39.es/ecma262/#sec-object.getprototypeof
+fn get_prototype_of(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let obj be ? ToObject(O).
+  let obj = argument(arguments, 0).to_object()?;
+  // 2. Return ? obj.[[GetPrototypeOf]]().
+  Ok(match obj.get_prototype_of() {
+    Either::A(proto) => proto.into(),
+    Either::B(_) => Value::null(),
+  })
+}
+
+/// https://tc39.es/ecma262/#sec-object.setprototypeof
+fn set_prototype_of(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Set O to ? RequireObjectCoercible(O).
+  let o = require_object_coercible(&argument(arguments, 0))?;
+  // 2. If Type(proto) is not Object and proto is not null, throw a TypeError
+  //    exception.
+  let proto = prototype_argument(argument(arguments, 1))?;
+  // 3. If Type(O) is not Object, return O.
+  let o = match o {
+    Value::Object(o) => o,
+    o => return Ok(o),
+  };
+  // 4. Let status be ? O.[[SetPrototypeOf]](proto).
+  // 5. If status is false, throw a TypeError exception.
+  if !o.set_prototype_of(proto)? {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      "Cannot set the prototype",
+    ));
+  }
+  // 6. Return O.
+  Ok(o.into())
+}
+
+pub(crate) fn add_methods(
+  realm: &Realm,
+  o: &JsObject,
+  methods: &[(&str, BuiltinBehaviour, f64)],
+) {
+  for &(name, behaviour, length) in methods {
+    let method = create_builtin_function(
+      behaviour,
+      length,
+      name,
+      Some(realm),
+      None,
+      false,
+    );
+    create_non_enumerable_data_property_or_throw(
+      o,
+      &name.into(),
+      method.into(),
+    );
+  }
+}
+
+/// Adds the methods of %Object.prototype% and creates %Object%, the methods
+/// need %Function.prototype% which is created after %Object.prototype%.
+pub fn create_object_objects(realm: &Realm) {
+  // https://tc39.es/ecma262/#sec-properties-of-the-object-prototype-object
+  let prototype = realm.intrinsic("Object.prototype");
+  add_methods(
+    realm,
+    &prototype,
+    &[
+      ("hasOwnProperty", has_own_property_method, 1.0),
+      ("isPrototypeOf", is_prototype_of, 1.0),
+      ("propertyIsEnumerable", property_is_enumerable, 1.0),
+      ("toLocaleString", to_locale_string, 0.0),
+      ("toString", to_string, 0.0),
+      ("valueOf", value_of, 0.0),
+    ],
+  );
+  // https://tc39.es/ecma262/#sec-properties-of-the-object-constructor
+  let constructor = create_builtin_function(
+    object_constructor,
+    1.0,
+    "Object",
+    Some(realm),
+    None,
+    true,
+  );
+  add_methods(
+    realm,
+    &constructor,
+    &[
+      ("assign", assign, 2.0),
+      ("create", create, 2.0),
+      ("defineProperties", define_properties, 2.0),
+      ("defineProperty", define_property, 3.0),
+      (
+        "entries",
+        |_, arguments, _| {
+          enumerable_own_properties(arguments, PropertyKind::KeyValue)
+        },
+        1.0,
+      ),
+      (
+        "freeze",
+        |_, arguments, _| set_integrity(arguments, IntegrityLevel::Frozen),
+        1.0,
+      ),
+      ("getOwnPropertyDescriptor", get_own_property_descriptor, 2.0),
+      (
+        "getOwnPropertyNames",
+        |_, arguments, _| {
+          get_own_property_keys_array(arguments, KeyType::String)
+        },
+        1.0,
+      ),
+      (
+        "getOwnPropertySymbols",
+        |_, arguments, _| {
+          get_own_property_keys_array(arguments, KeyType::Symbol)
+        },
+        1.0,
+      ),
+      ("getPrototypeOf", get_prototype_of, 1.0),
+      (
+        "isFrozen",
+        |_, arguments, _| test_integrity(arguments, IntegrityLevel::Frozen),
+        1.0,
+      ),
+      (
+        "isSealed",
+        |_, arguments, _| test_integrity(arguments, IntegrityLevel::Sealed),
+        1.0,
+      ),
+      (
+        "keys",
+        |_, arguments, _| {
+          enumerable_own_properties(arguments, PropertyKind::Key)
+        },
+        1.0,
+      ),
+      (
+        "seal",
+        |_, arguments, _| set_integrity(arguments, IntegrityLevel::Sealed),
+        1.0,
+      ),
+      ("setPrototypeOf", set_prototype_of, 2.0),
+      (
+        "values",
+        |_, arguments, _| {
+          enumerable_own_properties(arguments, PropertyKind::Value)
+        },
+        1.0,
+      ),
+    ],
+  );
+  // Object.prototype has the attributes { [[Writable]]: false,
+  // [[Enumerable]]: false, [[Configurable]]: false }.
+  define_property_or_throw(
+    &constructor,
+    &"prototype".into(),
+    PropertyDescriptor {
+      value: Some(prototype.clone().into()),
+      writable: Some(JsBoolean::False),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::False),
+    },
+  )
+  .expect("the constructor is extensible");
+  create_non_enumerable_data_property_or_throw(
+    &prototype,
+    &"constructor".into(),
+    constructor.clone().into(),
+  );
+  realm.set_intrinsic("Object", constructor);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::{
+      operations_on_bjects::{call, create_data_property, get_v},
+      ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    language_types::null::JsNull,
+  };
+
+  /// An ordinary object inheriting from %Object.prototype%.
+  fn object(realm: &Realm, properties: &[(&str, Value)]) -> Value {
+    let o = ordinary_object_create(
+      Either::A(realm.intrinsic("Object.prototype")),
+      &[],
+    );
+    for (key, value) in properties {
+      create_data_property(&o, &(*key).into(), value.clone()).unwrap();
+    }
+    o.into()
+  }
+
+  fn invoke_bool(this: &Value, name: &str, arguments: &[Value]) -> bool {
+    match invoke(this, &name.into(), arguments).unwrap() {
+      Value::Boolean(b) => b == JsBoolean::True,
+      v => panic!("expect a boolean, got {:?}", v),
+    }
+  }
+
+  fn invoke_string(this: &Value, name: &str) -> JsString {
+    match invoke(this, &name.into(), &[]).unwrap() {
+      Value::String(s) => s,
+      v => panic!("expect a string, got {:?}", v),
+    }
+  }
+
+  #[test]
+  fn object_prototype_methods() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let empty = object(&realm, &[]);
+    let o = object(&realm, &[("x", 1.0.into())]);
+    assert!(!invoke_bool(&empty, "hasOwnProperty", &["x".into()]));
+    assert!(invoke_bool(&o, "hasOwnProperty", &["x".into()]));
+    assert!(!invoke_bool(&o, "hasOwnProperty", &["toString".into()]));
+    assert!(invoke_bool(&o, "propertyIsEnumerable", &["x".into()]));
+    let prototype = Value::from(realm.intrinsic("Object.prototype"));
+    assert!(!invoke_bool(
+      &prototype,
+      "propertyIsEnumerable",
+      &["toString".into()]
+    ));
+    assert!(invoke_bool(
+      &prototype,
+      "isPrototypeOf",
+      std::slice::from_ref(&o)
+    ));
+    assert!(!invoke_bool(
+      &o,
+      "isPrototypeOf",
+      std::slice::from_ref(&prototype)
+    ));
+    assert_eq!(invoke_string(&empty, "toString"), "[object Object]");
+    assert_eq!(invoke_string(&empty, "toLocaleString"), "[object Object]");
+    let to_string = get_v(&prototype, &"toString".into()).unwrap();
+    let array = Value::from(create_array_from_list(&[]));
+    assert!(matches!(
+      call(&to_string, &array, &[]).unwrap(),
+      Value::String(s) if s == "[object Array]"
+    ));
+    assert!(matches!(
+      call(&to_string, &Value::null(), &[]).unwrap(),
+      Value::String(s) if s == "[object Null]"
+    ));
+    let value = invoke(&empty, &"valueOf".into(), &[]).unwrap();
+    assert!(matches!(
+      (value, empty),
+      (Value::Object(a), Value::Object(b)) if JsObject::equals(&a, &b)
+    ));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn to_string_tag() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let prototype = Value::from(realm.intrinsic("Object.prototype"));
+    let to_string = get_v(&prototype, &"toString".into()).unwrap();
+    let to_string = |this: &Value| match call(&to_string, this, &[]).unwrap() {
+      Value::String(s) => s,
+      v => panic!("expect a string, got {:?}", v),
+    };
+    assert_eq!(to_string(&Value::undefined()), "[object Undefined]");
+    assert_eq!(to_string(&Value::null()), "[object Null]");
+    assert_eq!(to_string(&object(&realm, &[])), "[object Object]");
+    let o = object(&realm, &[]);
+    let tag = PropertyKey::from(JsSymbol::to_string_tag());
+    create_data_property(&o.to_object().unwrap(), &tag, "Custom".into())
+      .unwrap();
+    assert_eq!(to_string(&o), "[object Custom]");
+    // the tag can be inherited
+    let o = Value::from(ordinary_object_create(
+      Either::A(o.to_object().unwrap()),
+      &[],
+    ));
+    assert_eq!(to_string(&o), "[object Custom]");
+    // a tag that isn't a String falls back to the builtin tag
+    let array = create_array_from_list(&[]);
+    create_data_property(&array, &tag, 1.0.into()).unwrap();
+    assert_eq!(to_string(&array.into()), "[object Array]");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn own_property_keys_by_type() {
+    initialize_host_defined_realm().unwrap();
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    for key in [
+      PropertyKey::from("b"),
+      PropertyKey::from(JsSymbol::iterator()),
+      PropertyKey::from("1"),
+      PropertyKey::from(JsSymbol::species()),
+    ] {
+      create_data_property(&o, &key, Value::undefined()).unwrap();
+    }
+    let o = Value::from(o);
+    assert_eq!(
+      get_own_property_keys(&o, KeyType::String).unwrap(),
+      [PropertyKey::from("1"), PropertyKey::from("b")]
+    );
+    assert_eq!(
+      get_own_property_keys(&o, KeyType::Symbol).unwrap(),
+      [
+        PropertyKey::from(JsSymbol::iterator()),
+        PropertyKey::from(JsSymbol::species())
+      ]
+    );
+    assert!(get_own_property_keys(&Value::null(), KeyType::String).is_err());
+    pop_execution_context();
+  }
+
+  /// Calls `Object[name]` with `arguments`.
+  fn call_static(
+    realm: &Realm,
+    name: &str,
+    arguments: &[Value],
+  ) -> Result<Value, Value> {
+    let object = Value::from(realm.intrinsic("Object"));
+    invoke(&object, &name.into(), arguments)
+  }
+
+  /// The elements of an array as strings, a nested array is joined by `,`.
+  fn elements(array: Value) -> Vec<String> {
+    let array = match array {
+      Value::Object(array) => array,
+      v => panic!("expect an array, got {:?}", v),
+    };
+    let len = get(&array, &"length".into()).unwrap().to_length().unwrap();
+    (0..len as usize)
+      .map(
+        |i| match get(&array, &i.to_string().as_str().into()).unwrap() {
+          Value::Object(o) => elements(o.into()).join(","),
+          v => v.to_string().unwrap().to_string_lossy(),
+        },
+      )
+      .collect()
+  }
+
+  #[test]
+  fn object_constructor() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let global = realm.global_object();
+    let object = get(&global, &"Object".into()).unwrap();
+    assert!(object.is_constructor());
+    let prototype = get_v(&object, &"prototype".into()).unwrap();
+    let constructor = get_v(&prototype, &"constructor".into()).unwrap();
+    assert_eq!(same_value(&constructor, &object), JsBoolean::True);
+    let o = call(&object, &Value::undefined(), &[]).unwrap();
+    assert_eq!(invoke_string(&o, "toString"), "[object Object]");
+    let o = call(&object, &Value::undefined(), &[1.0.into()]).unwrap();
+    assert!(matches!(o, Value::Object(o) if o.has_internal_slot("NumberData")));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn object_statics() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let o = object(&realm, &[("a", 1.0.into()), ("b", "c".into())]);
+    let result = call_static(&realm, "keys", std::slice::from_ref(&o));
+    assert_eq!(elements(result.unwrap()), ["a", "b"]);
+    let result = call_static(&realm, "values", std::slice::from_ref(&o));
+    assert_eq!(elements(result.unwrap()), ["1", "c"]);
+    let result = call_static(&realm, "entries", std::slice::from_ref(&o));
+    assert_eq!(elements(result.unwrap()), ["a,1", "b,c"]);
+    // Object.defineProperty(o, "x", { value: 2 })
+    let attributes = object(&realm, &[("value", 2.0.into())]);
+    call_static(
+      &realm,
+      "defineProperty",
+      &[o.clone(), "x".into(), attributes],
+    )
+    .unwrap();
+    let x = get_v(&o, &"x".into()).unwrap();
+    assert!(matches!(x, Value::Number(x) if *x == 2.0));
+    assert!(!invoke_bool(&o, "propertyIsEnumerable", &["x".into()]));
+    let desc =
+      call_static(&realm, "getOwnPropertyDescriptor", &[o.clone(), "x".into()])
+        .unwrap();
+    assert!(matches!(
+      get_v(&desc, &"writable".into()).unwrap(),
+      Value::Boolean(JsBoolean::False)
+    ));
+    // the descriptor can't mix a value and a getter
+    let attributes = object(
+      &realm,
+      &[("value", 1.0.into()), ("get", Value::undefined())],
+    );
+    assert!(call_static(
+      &realm,
+      "defineProperty",
+      &[o.clone(), "y".into(), attributes]
+    )
+    .is_err());
+    // Object.create(null)
+    let o = call_static(&realm, "create", &[Value::null()]).unwrap();
+    let proto =
+      call_static(&realm, "getPrototypeOf", std::slice::from_ref(&o)).unwrap();
+    assert!(matches!(proto, Value::Null(_)));
+    assert!(call_static(&realm, "create", &[1.0.into()]).is_err());
+    // Object.assign
+    let source = object(&realm, &[("a", 1.0.into())]);
+    let target = call_static(&realm, "assign", &[o.clone(), source]).unwrap();
+    assert_eq!(same_value(&target, &o), JsBoolean::True);
+    assert_eq!(
+      elements(call_static(&realm, "keys", std::slice::from_ref(&o)).unwrap()),
+      ["a"]
+    );
+    // a prototype chain can't be cyclic
+    let child =
+      call_static(&realm, "create", std::slice::from_ref(&o)).unwrap();
+    assert!(call_static(&realm, "setPrototypeOf", &[o.clone(), child]).is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn integrity_levels() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let is = |name: &str, o: &Value| {
+      matches!(
+        call_static(&realm, name, std::slice::from_ref(o)).unwrap(),
+        Value::Boolean(JsBoolean::True)
+      )
+    };
+    let o = object(&realm, &[("a", 1.0.into())]);
+    assert!(!is("isSealed", &o));
+    call_static(&realm, "seal", std::slice::from_ref(&o)).unwrap();
+    assert!(is("isSealed", &o));
+    assert!(!is("isFrozen", &o));
+    // a sealed object's properties are still writable
+    set(&o.to_object().unwrap(), &"a".into(), 2.0.into(), true).unwrap();
+    call_static(&realm, "freeze", std::slice::from_ref(&o)).unwrap();
+    assert!(is("isFrozen", &o));
+    assert!(
+      set(&o.to_object().unwrap(), &"a".into(), 3.0.into(), true).is_err()
+    );
+    assert!(
+      set(&o.to_object().unwrap(), &"b".into(), 3.0.into(), true).is_err()
+    );
+    assert!(is("isFrozen", &1.0.into()));
+    pop_execution_context();
+  }
+}
@@ -3,3 +3,86 @@ pub enum Either<A, B> {
   A(A),
   B(B),
 }
+
+impl<A, B> Either<A, B> {
+  pub fn is_a(&self) -> bool {
+    matches!(self, Self::A(_))
+  }
+
+  pub fn is_b(&self) -> bool {
+    matches!(self, Self::B(_))
+  }
+
+  pub fn a(self) -> Option<A> {
+    match self {
+      Self::A(a) => Some(a),
+      Self::B(_) => None,
+    }
+  }
+
+  pub fn b(self) -> Option<B> {
+    match self {
+      Self::A(_) => None,
+      Self::B(b) => Some(b),
+    }
+  }
+
+  pub fn as_ref(&self) -> Either<&A, &B> {
+    match self {
+      Self::A(a) => Either::A(a),
+      Self::B(b) => Either::B(b),
+    }
+  }
+
+  pub fn map_a<T>(self, f: impl FnOnce(A) -> T) -> Either<T, B> {
+    match self {
+      Self::A(a) => Either::A(f(a)),
+      Self::B(b) => Either::B(b),
+    }
+  }
+
+  pub fn map_b<T>(self, f: impl FnOnce(B) -> T) -> Either<A, T> {
+    match self {
+      Self::A(a) => Either::A(a),
+      Self::B(b) => Either::B(f(b)),
+    }
+  }
+
+  pub fn either<T>(self, f: impl FnOnce(A) -> T, g: impl FnOnce(B) -> T) -> T {
+    match self {
+      Self::A(a) => f(a),
+      Self::B(b) => g(b),
+    }
+  }
+}
+
+impl<T> Either<T, T> {
+  pub fn into_inner(self) -> T {
+    match self {
+      Self::A(v) | Self::B(v) => v,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn either() {
+    let a: Either<i32, &str> = Either::A(1);
+    let b: Either<i32, &str> = Either::B("b");
+    assert!(a.is_a() && !a.is_b());
+    assert!(b.is_b() && !b.is_a());
+    assert_eq!(a.a(), Some(1));
+    assert_eq!(a.b(), None);
+    assert_eq!(b.b(), Some("b"));
+    assert_eq!(a.as_ref().a(), Some(&1));
+    assert_eq!(a.map_a(|a| a + 1).a(), Some(2));
+    assert_eq!(b.map_b(str::len).b(), Some(1));
+    assert_eq!(b.map_a(|a| a + 1).b(), Some("b"));
+    assert_eq!(a.either(|a| a.to_string(), str::to_owned), "1");
+    assert_eq!(b.either(|a| a.to_string(), str::to_owned), "b");
+    assert_eq!(Either::<i32, i32>::B(2).into_inner(), 2);
+  }
+}
@@ -0,0 +1,83 @@
+//! The `console` object, only `console.log` for now.
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{create_data_property, define_property_or_throw},
+    ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+  },
+  agent::current_realm,
+  executable_code_and_execution_contexts::realm::Realm,
+  helpers::Either,
+  language_types::{boolean::JsBoolean, object::JsObject, Value},
+  ordinary_and_exotic_objects_behaviours::builtin_function::create_builtin_function,
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+/// Defines `console` on the realm's global object.
+pub fn add_console(realm: &Realm, global_obj: &JsObject) -> Result<(), Value> {
+  let console =
+    ordinary_object_create(Either::A(realm.intrinsic("Object.prototype")), &[]);
+  let log = create_builtin_function(log, 0.0, "log", Some(realm), None, false);
+  create_data_property(&console, &"log".into(), log.into())?;
+  define_property_or_throw(
+    global_obj,
+    &"console".into(),
+    PropertyDescriptor {
+      value: Some(console.into()),
+      writable: Some(JsBoolean::True),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::True),
+    },
+  )
+}
+
+/// Writes the arguments separated by spaces to the realm's console sink.
+fn log(
+  _: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let line = arguments
+    .iter()
+    .map(|argument| Ok(argument.to_string()?.to_string_lossy()))
+    .collect::<Result<Vec<_>, Value>>()?
+    .join(" ");
+  current_realm().write_console(&line);
+  Ok(Value::undefined())
+}
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, rc::Rc};
+
+  use crate::{
+    abstract_operations::operations_on_bjects::{get, invoke},
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    language_types::Value,
+  };
+
+  #[test]
+  fn console_log() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let sink = output.clone();
+    realm.set_console_sink(Box::new(move |line| {
+      sink.borrow_mut().push(line.to_owned())
+    }));
+    let console = get(&realm.global_object(), &"console".into()).unwrap();
+    let arguments = [
+      Value::from("a"),
+      Value::from(1.5),
+      Value::from(true),
+      Value::undefined(),
+      Value::null(),
+    ];
+    invoke(&console, &"log".into(), &arguments).unwrap();
+    invoke(&console, &"log".into(), &[]).unwrap();
+    assert_eq!(*output.borrow(), ["a 1.5 true undefined null", ""]);
+    pop_execution_context();
+  }
+}
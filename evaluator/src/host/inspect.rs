@@ -0,0 +1,315 @@
+//! A readable representation of values for the console and debugging, the
+//! properties are read directly so no getter or `toString` is called.
+
+use crate::{
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    number::JsNumber,
+    object::{JsObject, PropertyKey},
+    string::JsString,
+    symbol::JsSymbol,
+    Value,
+  },
+  ordinary_and_exotic_objects_behaviours::array_exotic_objects::is_array_exotic_object,
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+impl Value {
+  /// Like `util.inspect` of Node.js, strings are quoted, objects list their
+  /// enumerable own properties and a cyclic reference is `[Circular]`.
+  pub fn inspect(&self) -> String {
+    inspect(self, &mut Vec::new())
+  }
+}
+
+/// `seen` is the objects being inspected from the root to the current one.
+fn inspect(value: &Value, seen: &mut Vec<JsObject>) -> String {
+  match value {
+    Value::Undefined(_) => "undefined".to_owned(),
+    Value::Null(_) => "null".to_owned(),
+    Value::Boolean(b) => (*b == JsBoolean::True).to_string(),
+    Value::String(s) => quote(&s.to_string_lossy()),
+    Value::Symbol(s) => inspect_symbol(s),
+    Value::Number(n) if **n == 0.0 && n.is_sign_negative() => "-0".to_owned(),
+    Value::Number(n) => JsNumber::to_string(n).to_string_lossy(),
+    Value::BigInt(n) => format!("{}n", n.to_str_radix(10)),
+    Value::Object(o) => {
+      if seen.iter().any(|s| JsObject::equals(s, o)) {
+        return "[Circular]".to_owned();
+      }
+      seen.push(o.clone());
+      let result = inspect_object(o, seen);
+      seen.pop();
+      result
+    }
+  }
+}
+
+fn quote(s: &str) -> String {
+  let mut quoted = String::from('\'');
+  for c in s.chars() {
+    match c {
+      '\'' => quoted.push_str("\\'"),
+      '\\' => quoted.push_str("\\\\"),
+      '\n' => quoted.push_str("\\n"),
+      c => quoted.push(c),
+    }
+  }
+  quoted.push('\'');
+  quoted
+}
+
+fn inspect_symbol(s: &JsSymbol) -> String {
+  let description = s.description().map(JsString::to_string_lossy);
+  format!("Symbol({})", description.unwrap_or_default())
+}
+
+fn inspect_key(key: &PropertyKey) -> String {
+  match key {
+    PropertyKey::String(s) => {
+      let s = s.to_string_lossy();
+      if is_identifier(&s) {
+        s
+      } else {
+        quote(&s)
+      }
+    }
+    PropertyKey::Symbol(s) => format!("[{}]", inspect_symbol(s)),
+  }
+}
+
+/// Whether the key can be written without quotes, only ASCII names are
+/// considered.
+fn is_identifier(s: &str) -> bool {
+  let mut chars = s.chars();
+  matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$')
+    && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+fn inspect_property(
+  desc: &PropertyDescriptor,
+  seen: &mut Vec<JsObject>,
+) -> String {
+  match (&desc.value, desc.is_accessor_descriptor()) {
+    (Some(value), false) => inspect(value, seen),
+    _ => {
+      let getter = matches!(desc.get, Some(Either::A(_)));
+      let setter = matches!(desc.set, Some(Either::A(_)));
+      match (getter, setter) {
+        (true, true) => "[Getter/Setter]",
+        (true, false) => "[Getter]",
+        (false, true) => "[Setter]",
+        (false, false) => "undefined",
+      }
+      .to_owned()
+    }
+  }
+}
+
+fn empty_items(count: u32) -> String {
+  if count == 1 {
+    "<1 empty item>".to_owned()
+  } else {
+    format!("<{} empty items>", count)
+  }
+}
+
+fn inspect_object(o: &JsObject, seen: &mut Vec<JsObject>) -> String {
+  // the own property keys of ordinary objects have no observable effect
+  let keys = o.own_property_keys().unwrap_or_default();
+  let property = |key: &PropertyKey| o.get_properties().get(key).cloned();
+  let is_array = is_array_exotic_object(o);
+  let mut entries = Vec::new();
+  if is_array {
+    let len = match property(&"length".into()).and_then(|desc| desc.value) {
+      Some(Value::Number(n)) => *n as u32,
+      _ => 0,
+    };
+    // consecutive holes are collapsed
+    let mut next = 0;
+    for key in &keys {
+      let index = match key.as_array_index() {
+        Some(index) if index < len => index,
+        _ => continue,
+      };
+      if index > next {
+        entries.push(empty_items(index - next));
+      }
+      let desc = property(key).expect("the property of an own key");
+      entries.push(inspect_property(&desc, seen));
+      next = index + 1;
+    }
+    if len > next {
+      entries.push(empty_items(len - next));
+    }
+  }
+  for key in &keys {
+    if is_array && key.as_array_index().is_some() {
+      continue;
+    }
+    let desc = match property(key) {
+      Some(desc) if desc.enumerable == Some(JsBoolean::True) => desc,
+      _ => continue,
+    };
+    entries.push(format!(
+      "{}: {}",
+      inspect_key(key),
+      inspect_property(&desc, seen)
+    ));
+  }
+  let (open, close) = if is_array { ("[", "]") } else { ("{", "}") };
+  let body = if entries.is_empty() {
+    format!("{}{}", open, close)
+  } else {
+    format!("{} {} {}", open, entries.join(", "), close)
+  };
+  if o.get_call().is_none() {
+    return body;
+  }
+  let name = match property(&"name".into()).and_then(|desc| desc.value) {
+    Some(Value::String(name)) if !name.is_empty() => format!(": {}", name),
+    _ => " (anonymous)".to_owned(),
+  };
+  if entries.is_empty() {
+    format!("[Function{}]", name)
+  } else {
+    format!("[Function{}] {}", name, body)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use num_bigint::BigInt;
+
+  use super::*;
+  use crate::{
+    abstract_operations::{
+      operations_on_bjects::{
+        create_array_from_list, create_data_property, define_property_or_throw,
+      },
+      ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    helpers::Either,
+    language_types::{big_int::JsBigInt, null::JsNull},
+    ordinary_and_exotic_objects_behaviours::{
+      array_exotic_objects::array_create,
+      builtin_function::create_builtin_function,
+    },
+  };
+
+  fn object(properties: &[(&str, Value)]) -> JsObject {
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    for (key, value) in properties {
+      create_data_property(&o, &(*key).into(), value.clone()).unwrap();
+    }
+    o
+  }
+
+  fn behaviour(
+    _: &Value,
+    _: &[Value],
+    _: Option<&JsObject>,
+  ) -> Result<Value, Value> {
+    Ok(Value::undefined())
+  }
+
+  #[test]
+  fn inspect_primitives() {
+    assert_eq!(Value::undefined().inspect(), "undefined");
+    assert_eq!(Value::null().inspect(), "null");
+    assert_eq!(Value::from(true).inspect(), "true");
+    assert_eq!(Value::from("it's").inspect(), "'it\\'s'");
+    assert_eq!(Value::from(-0.0).inspect(), "-0");
+    assert_eq!(Value::from(1.5).inspect(), "1.5");
+    assert_eq!(Value::from(JsBigInt::from(BigInt::from(1))).inspect(), "1n");
+    assert_eq!(
+      Value::from(JsSymbol::iterator()).inspect(),
+      "Symbol(Symbol.iterator)"
+    );
+    assert_eq!(Value::from(JsSymbol::new(None)).inspect(), "Symbol()");
+  }
+
+  #[test]
+  fn inspect_objects() {
+    initialize_host_defined_realm().unwrap();
+    let empty = object(&[]);
+    assert_eq!(Value::from(empty.clone()).inspect(), "{}");
+    let o = object(&[("e", empty.into())]);
+    define_property_or_throw(
+      &o,
+      &"hidden".into(),
+      PropertyDescriptor {
+        value: Some(Value::from(1.0)),
+        ..Default::default()
+      },
+    )
+    .unwrap();
+    assert_eq!(Value::from(o).inspect(), "{ e: {} }");
+    let o = object(&[("a", 1.0.into()), ("b-c", "d".into())]);
+    assert_eq!(Value::from(o).inspect(), "{ a: 1, 'b-c': 'd' }");
+    let o = object(&[]);
+    let getter = create_builtin_function(behaviour, 0.0, "", None, None, false);
+    define_property_or_throw(
+      &o,
+      &"a".into(),
+      PropertyDescriptor {
+        value: None,
+        writable: None,
+        get: Some(Either::A(getter)),
+        enumerable: Some(JsBoolean::True),
+        ..Default::default()
+      },
+    )
+    .unwrap();
+    assert_eq!(Value::from(o).inspect(), "{ a: [Getter] }");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn inspect_arrays() {
+    initialize_host_defined_realm().unwrap();
+    let array = create_array_from_list(&[]);
+    assert_eq!(Value::from(array).inspect(), "[]");
+    let array = create_array_from_list(&[1.0.into(), "a".into()]);
+    assert_eq!(Value::from(array).inspect(), "[ 1, 'a' ]");
+    let array = array_create(4.0, None).unwrap();
+    create_data_property(&array, &"1".into(), Value::null()).unwrap();
+    assert_eq!(
+      Value::from(array).inspect(),
+      "[ <1 empty item>, null, <2 empty items> ]"
+    );
+    pop_execution_context();
+  }
+
+  #[test]
+  fn inspect_functions() {
+    initialize_host_defined_realm().unwrap();
+    let f = create_builtin_function(behaviour, 0.0, "f", None, None, false);
+    assert_eq!(Value::from(f.clone()).inspect(), "[Function: f]");
+    create_data_property(&f, &"a".into(), 1.0.into()).unwrap();
+    assert_eq!(Value::from(f).inspect(), "[Function: f] { a: 1 }");
+    let f = create_builtin_function(behaviour, 0.0, "", None, None, false);
+    assert_eq!(Value::from(f).inspect(), "[Function (anonymous)]");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn inspect_circular() {
+    initialize_host_defined_realm().unwrap();
+    let o = object(&[]);
+    create_data_property(&o, &"self".into(), o.clone().into()).unwrap();
+    assert_eq!(Value::from(o).inspect(), "{ self: [Circular] }");
+    let o = object(&[]);
+    let array = create_array_from_list(&[o.clone().into()]);
+    create_data_property(&o, &"array".into(), array.into()).unwrap();
+    assert_eq!(Value::from(o).inspect(), "{ array: [ [Circular] ] }");
+    // the same object twice isn't a cycle
+    let o = object(&[]);
+    let array = create_array_from_list(&[o.clone().into(), o.into()]);
+    assert_eq!(Value::from(array).inspect(), "[ {}, {} ]");
+    pop_execution_context();
+  }
+}
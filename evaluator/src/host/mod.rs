@@ -0,0 +1,4 @@
+//! Host-defined facilities, not part of ECMA-262.
+
+pub mod console;
+pub mod inspect;
@@ -0,0 +1,676 @@
+//! https://tc39.es/ecma262/#sec-array-objects
+
+use crate::{
+  abstract_operations::operations_on_bjects::{
+    call, construct, create_data_property_or_throw, get, get_function_realm,
+    has_property, length_of_array_like,
+  },
+  agent::current_realm,
+  executable_code_and_execution_contexts::realm::Realm,
+  fundamental_objects::{
+    error_objects::{throw_error, ErrorKind},
+    object_objects::{add_methods, argument},
+  },
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    object::{JsObject, PropertyKey},
+    symbol::JsSymbol,
+    Value,
+  },
+  ordinary_and_exotic_objects_behaviours::array_exotic_objects::array_create,
+};
+
+/// https://tc39.es/ecma262/#sec-arrayspeciescreate
+pub fn array_species_create(
+  original_array: &JsObject,
+  length: f64,
+) -> Result<JsObject, Value> {
+  // 1. Let isArray be ? IsArray(originalArray).
+  let is_array = Value::from(original_array.clone()).is_array()?;
+  // 2. If isArray is false, return ? ArrayCreate(length).
+  if !is_array {
+    return array_create(length, None);
+  }
+  // 3. Let C be ? Get(originalArray, "constructor").
+  let mut c = get(original_array, &"constructor".into())?;
+  // 4. If IsConstructor(C) is true, then
+  if let (true, Value::Object(constructor)) = (c.is_constructor(), &c) {
+    // a. Let thisRealm be the current Realm Record.
+    let this_realm = current_realm();
+    // b. Let realmC be ? GetFunctionRealm(C).
+    let realm_c = get_function_realm(constructor)?;
+    // c. If thisRealm and realmC are not the same Realm Record, then
+    //   i. If SameValue(C, realmC.[[Intrinsics]].[[%Array%]]) is true, set C
+    //      to undefined.
+    if !Realm::equals(&this_realm, &realm_c)
+      && realm_c
+        .get_intrinsic("Array")
+        .is_some_and(|array| JsObject::equals(&array, constructor))
+    {
+      c = Value::undefined();
+    }
+  }
+  // 5. If Type(C) is Object, then
+  if let Value::Object(o) = &c {
+    // a. Set C to ? Get(C, @@species).
+    c = get(o, &PropertyKey::Symbol(JsSymbol::species()))?;
+    // b. If C is null, set C to undefined.
+    if let Value::Null(_) = c {
+      c = Value::undefined();
+    }
+  }
+  // 6. If C is undefined, return ? ArrayCreate(length).
+  if let Value::Undefined(_) = c {
+    return array_create(length, None);
+  }
+  // 7. If IsConstructor(C) is false, throw a TypeError exception.
+  if !c.is_constructor() {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      "The species of the array isn't a constructor",
+    ));
+  }
+  // 8. Return ? Construct(C, « 𝔽(length) »).
+  construct(&c, &[length.into()], None)
+}
+
+/// The callback of the iteration methods, a TypeError if it isn't callable.
+fn callback(arguments: &[Value]) -> Result<Value, Value> {
+  let callback = argument(arguments, 0);
+  if !callback.is_callable() {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      &format!("{} is not a function", callback.type_name()),
+    ));
+  }
+  Ok(callback)
+}
+
+/// https://tc39.es/ecma262/#sec-array.prototype.every
+fn every(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let this_arg = argument(arguments, 1);
+  // 1. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 2. Let len be ? LengthOfArrayLike(O).
+  let len = length_of_array_like(&o)?;
+  // 3. If IsCallable(callbackfn) is false, throw a TypeError exception.
+  let callbackfn = callback(arguments)?;
+  // 4. Let k be 0.
+  let mut k = 0.0;
+  // 5. Repeat, while k < len,
+  while k < len {
+    // a. Let Pk be ! ToString(𝔽(k)).
+    let pk = PropertyKey::from(k.to_string());
+    // b. Let kPresent be ? HasProperty(O, Pk).
+    // c. If kPresent is true, then
+    if has_property(&o, &pk)? {
+      // i. Let kValue be ? Get(O, Pk).
+      let k_value = get(&o, &pk)?;
+      // ii. Let testResult be ToBoolean(? Call(callbackfn, thisArg, « kValue,
+      //     𝔽(k), O »)).
+      let test_result = call(
+        &callbackfn,
+        &this_arg,
+        &[k_value, k.into(), o.clone().into()],
+      )?
+      .to_boolean();
+      // iii. If testResult is false, return false.
+      if test_result == JsBoolean::False {
+        return Ok(false.into());
+      }
+    }
+    // d. Set k to k + 1.
+    k += 1.0;
+  }
+  // 6. Return true.
+  Ok(true.into())
+}
+
+/// https://tc39.es/ecma262/#sec-array.prototype.filter
+fn filter(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let this_arg = argument(arguments, 1);
+  // 1. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 2. Let len be ? LengthOfArrayLike(O).
+  let len = length_of_array_like(&o)?;
+  // 3. If IsCallable(callbackfn) is false, throw a TypeError exception.
+  let callbackfn = callback(arguments)?;
+  // 4. Let A be ? ArraySpeciesCreate(O, 0).
+  let a = array_species_create(&o, 0.0)?;
+  // 5. Let k be 0.
+  let mut k = 0.0;
+  // 6. Let to be 0.
+  let mut to = 0.0;
+  // 7. Repeat, while k < len,
+  while k < len {
+    // a. Let Pk be ! ToString(𝔽(k)).
+    let pk = PropertyKey::from(k.to_string());
+    // b. Let kPresent be ? HasProperty(O, Pk).
+    // c. If kPresent is true, then
+    if has_property(&o, &pk)? {
+      // i. Let kValue be ? Get(O, Pk).
+      let k_value = get(&o, &pk)?;
+      // ii. Let selected be ToBoolean(? Call(callbackfn, thisArg, « kValue,
+      //     𝔽(k), O »)).
+      let selected = call(
+        &callbackfn,
+        &this_arg,
+        &[k_value.clone(), k.into(), o.clone().into()],
+      )?
+      .to_boolean();
+      // iii. If selected is true, then
+      if selected == JsBoolean::True {
+        // 1. Perform ? CreateDataPropertyOrThrow(A, ! ToString(𝔽(to)), kValue).
+        create_data_property_or_throw(&a, &to.to_string().into(), k_value)?;
+        // 2. Set to to to + 1.
+        to += 1.0;
+      }
+    }
+    // d. Set k to k + 1.
+    k += 1.0;
+  }
+  // 8. Return A.
+  Ok(a.into())
+}
+
+/// https://tc39.es/ecma262/#sec-array.prototype.find
+fn find(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 2. Let len be ? LengthOfArrayLike(O).
+  let len = length_of_array_like(&o)?;
+  // 3. Let findRec be ? FindViaPredicate(O, len, ascending, predicate,
+  //    thisArg).
+  // 4. Return findRec.[[Value]].
+  // https://tc39.es/ecma262/#sec-findviapredicate
+  // 1. If IsCallable(predicate) is false, throw a TypeError exception.
+  let predicate = callback(arguments)?;
+  let this_arg = argument(arguments, 1);
+  // 2. If direction is ascending, then
+  //   a. Let indices be a List of the integers in the interval from 0
+  //      (inclusive) to len (exclusive), in ascending order.
+  let mut k = 0.0;
+  // 4. For each integer k of indices, do
+  while k < len {
+    // a. Let Pk be ! ToString(𝔽(k)).
+    let pk = PropertyKey::from(k.to_string());
+    // b. NOTE: If O is a TypedArray, the following invocation of Get will
+    //    return a normal completion.
+    // c. Let kValue be ? Get(O, Pk).
+    let k_value = get(&o, &pk)?;
+    // d. Let testResult be ? Call(predicate, thisArg, « kValue, 𝔽(k), O »).
+    let test_result = call(
+      &predicate,
+      &this_arg,
+      &[k_value.clone(), k.into(), o.clone().into()],
+    )?;
+    // e. If ToBoolean(testResult) is true, return the Record { [[Index]]:
+    //    𝔽(k), [[Value]]: kValue }.
+    if test_result.to_boolean() == JsBoolean::True {
+      return Ok(k_value);
+    }
+    k += 1.0;
+  }
+  // 5. Return the Record { [[Index]]: -1𝔽, [[Value]]: undefined }.
+  Ok(Value::undefined())
+}
+
+/// https://tc39.es/ecma262/#sec-array.prototype.foreach
+fn for_each(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let this_arg = argument(arguments, 1);
+  // 1. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 2. Let len be ? LengthOfArrayLike(O).
+  let len = length_of_array_like(&o)?;
+  // 3. If IsCallable(callbackfn) is false, throw a TypeError exception.
+  let callbackfn = callback(arguments)?;
+  // 4. Let k be 0.
+  let mut k = 0.0;
+  // 5. Repeat, while k < len,
+  while k < len {
+    // a. Let Pk be ! ToString(𝔽(k)).
+    let pk = PropertyKey::from(k.to_string());
+    // b. Let kPresent be ? HasProperty(O, Pk).
+    // c. If kPresent is true, then
+    if has_property(&o, &pk)? {
+      // i. Let kValue be ? Get(O, Pk).
+      let k_value = get(&o, &pk)?;
+      // ii. Perform ? Call(callbackfn, thisArg, « kValue, 𝔽(k), O »).
+      call(
+        &callbackfn,
+        &this_arg,
+        &[k_value, k.into(), o.clone().into()],
+      )?;
+    }
+    // d. Set k to k + 1.
+    k += 1.0;
+  }
+  // 6. Return undefined.
+  Ok(Value::undefined())
+}
+
+/// https://tc39.es/ecma262/#sec-array.prototype.map
+fn map(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let this_arg = argument(arguments, 1);
+  // 1. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 2. Let len be ? LengthOfArrayLike(O).
+  let len = length_of_array_like(&o)?;
+  // 3. If IsCallable(callbackfn) is false, throw a TypeError exception.
+  let callbackfn = callback(arguments)?;
+  // 4. Let A be ? ArraySpeciesCreate(O, len).
+  let a = array_species_create(&o, len)?;
+  // 5. Let k be 0.
+  let mut k = 0.0;
+  // 6. Repeat, while k < len,
+  while k < len {
+    // a. Let Pk be ! ToString(𝔽(k)).
+    let pk = PropertyKey::from(k.to_string());
+    // b. Let kPresent be ? HasProperty(O, Pk).
+    // c. If kPresent is true, then
+    if has_property(&o, &pk)? {
+      // i. Let kValue be ? Get(O, Pk).
+      let k_value = get(&o, &pk)?;
+      // ii. Let mappedValue be ? Call(callbackfn, thisArg, « kValue, 𝔽(k), O »).
+      let mapped_value = call(
+        &callbackfn,
+        &this_arg,
+        &[k_value, k.into(), o.clone().into()],
+      )?;
+      // iii. Perform ? CreateDataPropertyOrThrow(A, Pk, mappedValue).
+      create_data_property_or_throw(&a, &pk, mapped_value)?;
+    }
+    // d. Set k to k + 1.
+    k += 1.0;
+  }
+  // 7. Return A.
+  Ok(a.into())
+}
+
+/// https://tc39.es/ecma262/#sec-array.prototype.reduce
+fn reduce(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 2. Let len be ? LengthOfArrayLike(O).
+  let len = length_of_array_like(&o)?;
+  // 3. If IsCallable(callbackfn) is false, throw a TypeError exception.
+  let callbackfn = callback(arguments)?;
+  // 4. If len = 0 and initialValue is not present, throw a TypeError exception.
+  let initial_value = arguments.get(1);
+  if len == 0.0 && initial_value.is_none() {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      "Reduce of empty array with no initial value",
+    ));
+  }
+  // 5. Let k be 0.
+  let mut k = 0.0;
+  // 6. Let accumulator be undefined.
+  // 7. If initialValue is present, then
+  //   a. Set accumulator to initialValue.
+  let mut accumulator = match initial_value {
+    Some(initial_value) => initial_value.clone(),
+    // 8. Else,
+    None => {
+      // a. Let kPresent be false.
+      let mut accumulator = None;
+      // b. Repeat, while kPresent is false and k < len,
+      while accumulator.is_none() && k < len {
+        // i. Let Pk be ! ToString(𝔽(k)).
+        let pk = PropertyKey::from(k.to_string());
+        // ii. Set kPresent to ? HasProperty(O, Pk).
+        // iii. If kPresent is true, then
+        if has_property(&o, &pk)? {
+          // 1. Set accumulator to ? Get(O, Pk).
+          accumulator = Some(get(&o, &pk)?);
+        }
+        // iv. Set k to k + 1.
+        k += 1.0;
+      }
+      // c. If kPresent is false, throw a TypeError exception.
+      accumulator.ok_or_else(|| {
+        throw_error(
+          ErrorKind::TypeError,
+          "Reduce of empty array with no initial value",
+        )
+      })?
+    }
+  };
+  // 9. Repeat, while k < len,
+  while k < len {
+    // a. Let Pk be ! ToString(𝔽(k)).
+    let pk = PropertyKey::from(k.to_string());
+    // b. Let kPresent be ? HasProperty(O, Pk).
+    // c. If kPresent is true, then
+    if has_property(&o, &pk)? {
+      // i. Let kValue be ? Get(O, Pk).
+      let k_value = get(&o, &pk)?;
+      // ii. Set accumulator to ? Call(callbackfn, undefined, « accumulator,
+      //     kValue, 𝔽(k), O »).
+      accumulator = call(
+        &callbackfn,
+        &Value::undefined(),
+        &[accumulator, k_value, k.into(), o.clone().into()],
+      )?;
+    }
+    // d. Set k to k + 1.
+    k += 1.0;
+  }
+  // 10. Return accumulator.
+  Ok(accumulator)
+}
+
+/// https://tc39.es/ecma262/#sec-array.prototype.some
+fn some(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let this_arg = argument(arguments, 1);
+  // 1. Let O be ? ToObject(this value).
+  let o = this.to_object()?;
+  // 2. Let len be ? LengthOfArrayLike(O).
+  let len = length_of_array_like(&o)?;
+  // 3. If IsCallable(callbackfn) is false, throw a TypeError exception.
+  let callbackfn = callback(arguments)?;
+  // 4. Let k be 0.
+  let mut k = 0.0;
+  // 5. Repeat, while k < len,
+  while k < len {
+    // a. Let Pk be ! ToString(𝔽(k)).
+    let pk = PropertyKey::from(k.to_string());
+    // b. Let kPresent be ? HasProperty(O, Pk).
+    // c. If kPresent is true, then
+    if has_property(&o, &pk)? {
+      // i. Let kValue be ? Get(O, Pk).
+      let k_value = get(&o, &pk)?;
+      // ii. Let testResult be ToBoolean(? Call(callbackfn, thisArg, « kValue,
+      //     𝔽(k), O »)).
+      let test_result = call(
+        &callbackfn,
+        &this_arg,
+        &[k_value, k.into(), o.clone().into()],
+      )?
+      .to_boolean();
+      // iii. If testResult is true, return true.
+      if test_result == JsBoolean::True {
+        return Ok(true.into());
+      }
+    }
+    // d. Set k to k + 1.
+    k += 1.0;
+  }
+  // 6. Return false.
+  Ok(false.into())
+}
+
+/// https://tc39.es/ecma262/#sec-properties-of-the-array-prototype-object,
+/// the Array prototype object is itself an Array exotic object.
+pub fn create_array_prototype(realm: &Realm) {
+  let prototype =
+    array_create(0.0, Some(Either::A(realm.intrinsic("Object.prototype"))))
+      .expect("0 is a valid array length");
+  add_methods(
+    realm,
+    &prototype,
+    &[
+      ("every", every, 1.0),
+      ("filter", filter, 1.0),
+      ("find", find, 1.0),
+      ("forEach", for_each, 1.0),
+      ("map", map, 1.0),
+      ("reduce", reduce, 1.0),
+      ("some", some, 1.0),
+    ],
+  );
+  realm.set_intrinsic("Array.prototype", prototype);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::{
+      create_array_from_list, create_data_property,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    ordinary_and_exotic_objects_behaviours::builtin_function::{
+      create_builtin_function, BuiltinBehaviour,
+    },
+  };
+
+  fn function(behaviour: BuiltinBehaviour) -> Value {
+    create_builtin_function(behaviour, 0.0, "", None, None, false).into()
+  }
+
+  fn invoke(
+    realm: &Realm,
+    o: &JsObject,
+    name: &str,
+    arguments: &[Value],
+  ) -> Result<Value, Value> {
+    let method = get(&realm.intrinsic("Array.prototype"), &name.into())?;
+    call(&method, &o.clone().into(), arguments)
+  }
+
+  fn numbers(array: &Value) -> Vec<Option<f64>> {
+    let array = match array {
+      Value::Object(array) => array,
+      v => panic!("expect an array, got {:?}", v),
+    };
+    let len = length_of_array_like(array).unwrap() as usize;
+    (0..len)
+      .map(|k| {
+        let pk = PropertyKey::from(k.to_string());
+        has_property(array, &pk)
+          .unwrap()
+          .then(|| *get(array, &pk).unwrap().to_number().unwrap())
+      })
+      .collect()
+  }
+
+  fn list(elements: &[f64]) -> JsObject {
+    create_array_from_list(
+      &elements.iter().map(|&e| e.into()).collect::<Vec<_>>(),
+    )
+  }
+
+  /// `[1, , 3]`
+  fn sparse() -> JsObject {
+    let array = array_create(3.0, None).unwrap();
+    create_data_property(&array, &"0".into(), 1.0.into()).unwrap();
+    create_data_property(&array, &"2".into(), 3.0.into()).unwrap();
+    array
+  }
+
+  #[test]
+  fn map_and_filter() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let double = function(|_, arguments, _| {
+      Ok((*argument(arguments, 0).to_number()? * 2.0).into())
+    });
+    let result = invoke(
+      &realm,
+      &list(&[1.0, 2.0, 3.0]),
+      "map",
+      std::slice::from_ref(&double),
+    );
+    assert_eq!(numbers(&result.unwrap()), [Some(2.0), Some(4.0), Some(6.0)]);
+    let odd = function(|_, arguments, _| {
+      Ok((*argument(arguments, 0).to_number()? % 2.0 == 1.0).into())
+    });
+    let result = invoke(
+      &realm,
+      &list(&[1.0, 2.0, 3.0]),
+      "filter",
+      std::slice::from_ref(&odd),
+    );
+    assert_eq!(numbers(&result.unwrap()), [Some(1.0), Some(3.0)]);
+    // the index and the array are passed after the element
+    let index = function(|_, arguments, _| Ok(argument(arguments, 1)));
+    let result = invoke(&realm, &list(&[5.0, 5.0]), "map", &[index]);
+    assert_eq!(numbers(&result.unwrap()), [Some(0.0), Some(1.0)]);
+    let result = invoke(&realm, &list(&[1.0]), "map", &[1.0.into()]);
+    assert!(result.is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn methods_of_returned_arrays() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let array = list(&[1.0, 2.0, 3.0]);
+    assert!(matches!(
+      array.get_prototype_of(),
+      Either::A(proto) if JsObject::equals(&proto, &realm.intrinsic("Array.prototype"))
+    ));
+    let double = function(|_, arguments, _| {
+      Ok((*argument(arguments, 0).to_number()? * 2.0).into())
+    });
+    let result = invoke(&realm, &array, "map", &[double]).unwrap();
+    // the methods are looked up through the prototype of the result
+    let filter = get(&result.to_object().unwrap(), &"filter".into()).unwrap();
+    let large = function(|_, arguments, _| {
+      Ok((*argument(arguments, 0).to_number()? > 2.0).into())
+    });
+    let result = call(&filter, &result, &[large]).unwrap();
+    assert_eq!(numbers(&result), [Some(4.0), Some(6.0)]);
+    pop_execution_context();
+  }
+
+  #[test]
+  fn reduce_with_and_without_initial_value() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let sum = function(|_, arguments, _| {
+      let a = *argument(arguments, 0).to_number()?;
+      let b = *argument(arguments, 1).to_number()?;
+      Ok((a + b).into())
+    });
+    let array = list(&[1.0, 2.0, 3.0]);
+    let result =
+      invoke(&realm, &array, "reduce", std::slice::from_ref(&sum)).unwrap();
+    assert!(matches!(result, Value::Number(n) if *n == 6.0));
+    let result =
+      invoke(&realm, &array, "reduce", &[sum.clone(), 10.0.into()]).unwrap();
+    assert!(matches!(result, Value::Number(n) if *n == 16.0));
+    // the initial value of an empty array is the result
+    let result =
+      invoke(&realm, &list(&[]), "reduce", &[sum.clone(), 1.0.into()]).unwrap();
+    assert!(matches!(result, Value::Number(n) if *n == 1.0));
+    assert!(
+      invoke(&realm, &list(&[]), "reduce", std::slice::from_ref(&sum)).is_err()
+    );
+    assert!(invoke(
+      &realm,
+      &array_create(2.0, None).unwrap(),
+      "reduce",
+      &[sum]
+    )
+    .is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn holes_are_skipped() {
+    let realm = initialize_host_defined_realm().unwrap();
+    // collects the indices it's called with in `this`
+    let collect = function(|this, arguments, _| {
+      let this = this.to_object()?;
+      let len = length_of_array_like(&this)?;
+      create_data_property(
+        &this,
+        &len.to_string().into(),
+        argument(arguments, 1),
+      )?;
+      Ok(Value::undefined())
+    });
+    let indices = list(&[]);
+    let result = invoke(
+      &realm,
+      &sparse(),
+      "forEach",
+      &[collect.clone(), indices.clone().into()],
+    );
+    assert!(matches!(result, Ok(Value::Undefined(_))));
+    assert_eq!(numbers(&indices.into()), [Some(0.0), Some(2.0)]);
+    let identity = function(|_, arguments, _| Ok(argument(arguments, 0)));
+    let result =
+      invoke(&realm, &sparse(), "map", std::slice::from_ref(&identity));
+    assert_eq!(numbers(&result.unwrap()), [Some(1.0), None, Some(3.0)]);
+    let truthy = function(|_, _, _| Ok(true.into()));
+    let result =
+      invoke(&realm, &sparse(), "filter", std::slice::from_ref(&truthy));
+    assert_eq!(numbers(&result.unwrap()), [Some(1.0), Some(3.0)]);
+    // find visits the holes as undefined
+    let indices = list(&[]);
+    invoke(
+      &realm,
+      &sparse(),
+      "find",
+      &[collect, indices.clone().into()],
+    )
+    .unwrap();
+    assert_eq!(numbers(&indices.into()), [Some(0.0), Some(1.0), Some(2.0)]);
+    pop_execution_context();
+  }
+
+  #[test]
+  fn find_some_and_every() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let even = function(|_, arguments, _| {
+      Ok((*argument(arguments, 0).to_number()? % 2.0 == 0.0).into())
+    });
+    let array = list(&[1.0, 2.0, 3.0, 4.0]);
+    let result =
+      invoke(&realm, &array, "find", std::slice::from_ref(&even)).unwrap();
+    assert!(matches!(result, Value::Number(n) if *n == 2.0));
+    let result =
+      invoke(&realm, &list(&[1.0]), "find", std::slice::from_ref(&even));
+    assert!(matches!(result, Ok(Value::Undefined(_))));
+    let result =
+      invoke(&realm, &array, "some", std::slice::from_ref(&even)).unwrap();
+    assert!(matches!(result, Value::Boolean(JsBoolean::True)));
+    let result =
+      invoke(&realm, &array, "every", std::slice::from_ref(&even)).unwrap();
+    assert!(matches!(result, Value::Boolean(JsBoolean::False)));
+    let result = invoke(
+      &realm,
+      &list(&[2.0, 4.0]),
+      "every",
+      std::slice::from_ref(&even),
+    );
+    assert!(matches!(result, Ok(Value::Boolean(JsBoolean::True))));
+    // vacuous truth for an empty array
+    let result =
+      invoke(&realm, &list(&[]), "every", std::slice::from_ref(&even));
+    assert!(matches!(result, Ok(Value::Boolean(JsBoolean::True))));
+    let result = invoke(&realm, &list(&[]), "some", &[even]);
+    assert!(matches!(result, Ok(Value::Boolean(JsBoolean::False))));
+    pop_execution_context();
+  }
+}
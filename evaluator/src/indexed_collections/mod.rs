@@ -0,0 +1,3 @@
+//! https://tc39.es/ecma262/#sec-indexed-collections
+
+pub mod array_objects;
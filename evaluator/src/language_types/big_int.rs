@@ -1,12 +1,22 @@
 use std::ops::Deref;
 
 use num_bigint::BigInt;
+use num_traits::{Signed, ToPrimitive, Zero};
 
-use super::boolean::JsBoolean;
+use crate::fundamental_objects::error_objects::{throw_error, ErrorKind};
+
+use super::{boolean::JsBoolean, Value};
 
 /// https://tc39.es/ecma262/#sec-ecmascript-language-types-bigint-type
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct JsBigInt(BigInt);
 
+impl From<BigInt> for JsBigInt {
+  fn from(n: BigInt) -> Self {
+    Self(n)
+  }
+}
+
 impl Deref for JsBigInt {
   type Target = BigInt;
 
@@ -27,4 +37,214 @@ impl JsBigInt {
     // 1. Return BigInt::equal(x, y).
     Self::equal(x, y)
   }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-sameValueZero
+  pub fn same_value_zero(x: &Self, y: &Self) -> JsBoolean {
+    // 1. Return BigInt::equal(x, y).
+    Self::equal(x, y)
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-numeric-types-bigint-type, a thrown
+/// RangeError or TypeError is the `Err`.
+impl JsBigInt {
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-unaryMinus
+  pub fn unary_minus(x: &Self) -> Self {
+    // 1. If x is 0ℤ, return 0ℤ.
+    // 2. Return -x.
+    Self(-&x.0)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-bitwiseNOT
+  pub fn bitwise_not(x: &Self) -> Self {
+    // 1. Return -x - 1ℤ.
+    Self(!&x.0)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-exponentiate
+  pub fn exponentiate(base: &Self, exponent: &Self) -> Result<Self, Value> {
+    // 1. If exponent < 0ℤ, throw a RangeError exception.
+    if exponent.is_negative() {
+      return Err(throw_error(
+        ErrorKind::RangeError,
+        "Exponent must be non-negative",
+      ));
+    }
+    // 2. If base is 0ℤ and exponent is 0ℤ, return 1ℤ.
+    // 3. Return the BigInt value that represents ℝ(base) raised to the power
+    //    ℝ(exponent).
+    match exponent.to_u32() {
+      Some(exponent) => Ok(Self(base.pow(exponent))),
+      // 0, 1 and -1 are the only bases with a representable power
+      None if base.is_zero() || base.abs() == BigInt::from(1) => {
+        if base.is_negative() && exponent.bit(0) {
+          Ok(base.clone())
+        } else {
+          Ok(Self(base.abs()))
+        }
+      }
+      None => Err(throw_error(
+        ErrorKind::RangeError,
+        "Maximum BigInt size exceeded",
+      )),
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-multiply
+  pub fn multiply(x: &Self, y: &Self) -> Self {
+    // 1. Return x × y.
+    Self(&x.0 * &y.0)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-divide
+  pub fn divide(x: &Self, y: &Self) -> Result<Self, Value> {
+    // 1. If y is 0ℤ, throw a RangeError exception.
+    if y.is_zero() {
+      return Err(throw_error(ErrorKind::RangeError, "Division by zero"));
+    }
+    // 2. Let quotient be ℝ(x) / ℝ(y).
+    // 3. Return ℤ(truncate(quotient)).
+    Ok(Self(&x.0 / &y.0))
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-remainder, the sign
+  /// of the result equals the sign of the dividend.
+  pub fn remainder(n: &Self, d: &Self) -> Result<Self, Value> {
+    // 1. If d is 0ℤ, throw a RangeError exception.
+    if d.is_zero() {
+      return Err(throw_error(ErrorKind::RangeError, "Division by zero"));
+    }
+    // 2. If n is 0ℤ, return 0ℤ.
+    // 3. Let quotient be ℝ(n) / ℝ(d).
+    // 4. Let q be ℤ(truncate(quotient)).
+    // 5. Return n - (d × q).
+    Ok(Self(&n.0 % &d.0))
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-add
+  pub fn add(x: &Self, y: &Self) -> Self {
+    // 1. Return x + y.
+    Self(&x.0 + &y.0)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-subtract
+  pub fn subtract(x: &Self, y: &Self) -> Self {
+    // 1. Return x - y.
+    Self(&x.0 - &y.0)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-leftShift, the right
+  /// shift of a negative BigInt rounds toward -∞.
+  pub fn left_shift(x: &Self, y: &Self) -> Result<Self, Value> {
+    // 1. Return the BigInt value that represents ℝ(x) × 2^ℝ(y).
+    match y.abs().to_usize() {
+      Some(count) if y.is_negative() => Ok(Self(&x.0 >> count)),
+      Some(count) => Ok(Self(&x.0 << count)),
+      None if y.is_negative() => Ok(Self(if x.is_negative() {
+        BigInt::from(-1)
+      } else {
+        BigInt::zero()
+      })),
+      None => Err(throw_error(
+        ErrorKind::RangeError,
+        "Maximum BigInt size exceeded",
+      )),
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-signedRightShift
+  pub fn signed_right_shift(x: &Self, y: &Self) -> Result<Self, Value> {
+    // 1. Return BigInt::leftShift(x, -y).
+    Self::left_shift(x, &Self::unary_minus(y))
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-unsignedRightShift
+  pub fn unsigned_right_shift(_: &Self, _: &Self) -> Result<Self, Value> {
+    // 1. Throw a TypeError exception.
+    Err(throw_error(
+      ErrorKind::TypeError,
+      "BigInts have no unsigned right shift, use >> instead",
+    ))
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-bitwiseAND, the
+  /// bitwise operations of `BigInt` are on the infinite two's complement.
+  pub fn bitwise_and(x: &Self, y: &Self) -> Self {
+    // 1. Return BigIntBitwiseOp(&, x, y).
+    Self(&x.0 & &y.0)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-bitwiseXOR
+  pub fn bitwise_xor(x: &Self, y: &Self) -> Self {
+    // 1. Return BigIntBitwiseOp(^, x, y).
+    Self(&x.0 ^ &y.0)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-bigint-bitwiseOR
+  pub fn bitwise_or(x: &Self, y: &Self) -> Self {
+    // 1. Return BigIntBitwiseOp(|, x, y).
+    Self(&x.0 | &y.0)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    test_helpers::error_name,
+  };
+
+  fn big_int(n: i64) -> JsBigInt {
+    JsBigInt::from(BigInt::from(n))
+  }
+
+  #[test]
+  fn arithmetic() {
+    initialize_host_defined_realm().unwrap();
+    let divide = |x, y| JsBigInt::divide(&big_int(x), &big_int(y));
+    let remainder = |x, y| JsBigInt::remainder(&big_int(x), &big_int(y));
+    assert_eq!(divide(10, 3).unwrap(), big_int(3));
+    assert_eq!(divide(-10, 3).unwrap(), big_int(-3));
+    assert_eq!(remainder(5, -3).unwrap(), big_int(2));
+    assert_eq!(remainder(-5, 3).unwrap(), big_int(-2));
+    assert_eq!(error_name(divide(1, 0).unwrap_err()), "RangeError");
+    assert_eq!(error_name(remainder(1, 0).unwrap_err()), "RangeError");
+
+    let exponentiate = |x, y| JsBigInt::exponentiate(&big_int(x), &big_int(y));
+    assert_eq!(*exponentiate(2, 64).unwrap(), BigInt::from(1u128 << 64));
+    assert_eq!(exponentiate(0, 0).unwrap(), big_int(1));
+    assert_eq!(error_name(exponentiate(2, -1).unwrap_err()), "RangeError");
+    // the power of -1 doesn't need the exponent to fit
+    let huge = JsBigInt::from(BigInt::from(u64::MAX));
+    assert_eq!(
+      JsBigInt::exponentiate(&big_int(-1), &huge).unwrap(),
+      big_int(-1)
+    );
+    assert_eq!(
+      JsBigInt::subtract(&big_int(1), &big_int(3)),
+      JsBigInt::unary_minus(&big_int(2))
+    );
+    pop_execution_context();
+  }
+
+  #[test]
+  fn bitwise() {
+    initialize_host_defined_realm().unwrap();
+    assert_eq!(JsBigInt::bitwise_not(&big_int(0)), big_int(-1));
+    assert_eq!(JsBigInt::bitwise_and(&big_int(-1), &big_int(6)), big_int(6));
+    assert_eq!(JsBigInt::bitwise_or(&big_int(-8), &big_int(1)), big_int(-7));
+    assert_eq!(JsBigInt::bitwise_xor(&big_int(5), &big_int(3)), big_int(6));
+    let shift = |x, y| JsBigInt::left_shift(&big_int(x), &big_int(y)).unwrap();
+    assert_eq!(shift(1, 64), JsBigInt::from(BigInt::from(1u128 << 64)));
+    assert_eq!(shift(-5, -1), big_int(-3));
+    assert_eq!(
+      JsBigInt::signed_right_shift(&big_int(-5), &big_int(1)).unwrap(),
+      big_int(-3)
+    );
+    let error = JsBigInt::unsigned_right_shift(&big_int(1), &big_int(0));
+    assert_eq!(error_name(error.unwrap_err()), "TypeError");
+    pop_execution_context();
+  }
 }
@@ -14,6 +14,7 @@ use self::{
   object::JsObject, string::JsString, symbol::JsSymbol, undefined::JsUndefined,
 };
 
+#[derive(Debug, Clone)]
 pub enum Value {
   Undefined(JsUndefined),
   Null(JsNull),
@@ -24,3 +25,153 @@ pub enum Value {
   BigInt(JsBigInt),
   Object(JsObject),
 }
+
+impl Value {
+  pub fn undefined() -> Self {
+    Self::Undefined(JsUndefined)
+  }
+
+  pub fn null() -> Self {
+    Self::Null(JsNull)
+  }
+
+  /// The name of the value's type for diagnostics, unlike `typeof` `null`
+  /// is `"null"` and functions are `"object"`.
+  pub fn type_name(&self) -> &'static str {
+    match self {
+      Self::Undefined(_) => "undefined",
+      Self::Null(_) => "null",
+      Self::Boolean(_) => "boolean",
+      Self::String(_) => "string",
+      Self::Symbol(_) => "symbol",
+      Self::Number(_) => "number",
+      Self::BigInt(_) => "bigint",
+      Self::Object(_) => "object",
+    }
+  }
+}
+
+impl From<bool> for Value {
+  fn from(b: bool) -> Self {
+    Self::Boolean(b.into())
+  }
+}
+
+impl From<JsBoolean> for Value {
+  fn from(b: JsBoolean) -> Self {
+    Self::Boolean(b)
+  }
+}
+
+impl From<&str> for Value {
+  fn from(s: &str) -> Self {
+    Self::String(s.into())
+  }
+}
+
+impl From<String> for Value {
+  fn from(s: String) -> Self {
+    Self::String(s.into())
+  }
+}
+
+impl From<JsString> for Value {
+  fn from(s: JsString) -> Self {
+    Self::String(s)
+  }
+}
+
+impl From<JsSymbol> for Value {
+  fn from(s: JsSymbol) -> Self {
+    Self::Symbol(s)
+  }
+}
+
+impl From<f64> for Value {
+  fn from(n: f64) -> Self {
+    Self::Number(n.into())
+  }
+}
+
+impl From<JsNumber> for Value {
+  fn from(n: JsNumber) -> Self {
+    Self::Number(n)
+  }
+}
+
+impl From<JsBigInt> for Value {
+  fn from(n: JsBigInt) -> Self {
+    Self::BigInt(n)
+  }
+}
+
+impl From<JsObject> for Value {
+  fn from(o: JsObject) -> Self {
+    Self::Object(o)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use num_bigint::BigInt;
+
+  use super::*;
+  use crate::{
+    abstract_operations::{
+      operations_on_bjects::get,
+      ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    helpers::Either,
+  };
+
+  #[test]
+  fn value_from() {
+    assert!(matches!(Value::undefined(), Value::Undefined(_)));
+    assert!(matches!(Value::null(), Value::Null(_)));
+    assert!(matches!(Value::from(true), Value::Boolean(JsBoolean::True)));
+    assert!(matches!(Value::from("a"), Value::String(s) if s == "a"));
+    assert!(
+      matches!(Value::from("a".to_owned()), Value::String(s) if s == "a")
+    );
+    assert!(matches!(Value::from(1.0), Value::Number(n) if *n == 1.0));
+    let n = JsBigInt::from(BigInt::from(1));
+    assert!(matches!(Value::from(n.clone()), Value::BigInt(v) if v == n));
+    assert!(matches!(
+      Value::from(JsBoolean::False),
+      Value::Boolean(JsBoolean::False)
+    ));
+    assert!(
+      matches!(Value::from(JsNumber::from(1.0)), Value::Number(n) if *n == 1.0)
+    );
+    let s = JsSymbol::new(None);
+    assert!(matches!(Value::from(s.clone()), Value::Symbol(v) if v == s));
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    assert!(
+      matches!(Value::from(o.clone()), Value::Object(v) if JsObject::equals(&v, &o))
+    );
+  }
+
+  #[test]
+  fn type_name() {
+    assert_eq!(Value::undefined().type_name(), "undefined");
+    assert_eq!(Value::null().type_name(), "null");
+    assert_eq!(Value::from(false).type_name(), "boolean");
+    assert_eq!(Value::from("").type_name(), "string");
+    assert_eq!(Value::from(0.0).type_name(), "number");
+    assert_eq!(
+      Value::from(JsBigInt::from(BigInt::from(0))).type_name(),
+      "bigint"
+    );
+    assert_eq!(Value::from(JsSymbol::new(None)).type_name(), "symbol");
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    assert_eq!(Value::from(o).type_name(), "object");
+    // unlike typeof, a function is an Object too
+    let realm = initialize_host_defined_realm().unwrap();
+    let function = get(&realm.global_object(), &"Function".into()).unwrap();
+    assert!(function.is_callable());
+    assert_eq!(function.type_name(), "object");
+    pop_execution_context();
+  }
+}
@@ -1,11 +1,17 @@
 use std::ops::Deref;
 
-use super::boolean::JsBoolean;
+use super::{boolean::JsBoolean, string::JsString};
 
 /// https://tc39.es/ecma262/#sec-ecmascript-language-types-number-type
 #[derive(Debug, Clone, Copy)]
 pub struct JsNumber(f64);
 
+impl From<f64> for JsNumber {
+  fn from(n: f64) -> Self {
+    Self(n)
+  }
+}
+
 impl Deref for JsNumber {
   type Target = f64;
 
@@ -15,6 +21,169 @@ impl Deref for JsNumber {
 }
 
 impl JsNumber {
+  /// https://tc39.es/ecma262/#sec-toint32 after ToNumber.
+  pub fn to_int32(&self) -> i32 {
+    // 2. If number is not finite or number is either +0𝔽 or -0𝔽, return +0𝔽.
+    // 3. Let int be the mathematical value whose sign is the sign of number and whose
+    //    magnitude is floor(abs(ℝ(number))).
+    // 4. Let int32bit be int modulo 2^32.
+    // 5. If int32bit ≥ 2^31, return 𝔽(int32bit - 2^32); otherwise return 𝔽(int32bit).
+    self.to_uint32() as i32
+  }
+
+  /// https://tc39.es/ecma262/#sec-touint32 after ToNumber.
+  pub fn to_uint32(&self) -> u32 {
+    // 2. If number is not finite or number is either +0𝔽 or -0𝔽, return +0𝔽.
+    if !self.0.is_finite() || self.0 == 0.0 {
+      return 0;
+    }
+    // 3. Let int be the mathematical value whose sign is the sign of number and whose
+    //    magnitude is floor(abs(ℝ(number))).
+    // 4. Let int32bit be int modulo 2^32.
+    // 5. Return 𝔽(int32bit).
+    self.0.trunc().rem_euclid(4294967296.0) as u32
+  }
+}
+
+/// The shift count of the shift operators is the low 5 bits of ToUint32.
+fn shift_count(n: f64) -> u32 {
+  JsNumber(n).to_uint32() & 0x1f
+}
+
+/// https://tc39.es/ecma262/#sec-numeric-types-number-type, the operators
+/// of `f64` are IEEE 754-2019 and handle NaN, the infinities and the signs
+/// of zeros as the spec requires, except where noted.
+impl JsNumber {
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-unaryMinus
+  pub fn unary_minus(x: f64) -> f64 {
+    // 1. If x is NaN, return NaN.
+    // 2. Return the result of negating x; that is, compute a Number with the
+    //    same magnitude but opposite sign.
+    -x
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-bitwiseNOT
+  pub fn bitwise_not(x: f64) -> f64 {
+    // 1. Let oldValue be ! ToInt32(x).
+    // 2. Return the result of applying bitwise complement to oldValue.
+    !Self(x).to_int32() as f64
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-exponentiate, `powf`
+  /// is IEEE 754 `pow` except for a NaN exponent of 1 and an infinite
+  /// exponent of -1 or 1.
+  pub fn exponentiate(base: f64, exponent: f64) -> f64 {
+    // 1. If exponent is NaN, return NaN.
+    if exponent.is_nan() {
+      return f64::NAN;
+    }
+    // 2. If exponent is either +0𝔽 or -0𝔽, return 1𝔽.
+    if exponent == 0.0 {
+      return 1.0;
+    }
+    // 3. If base is NaN, return NaN.
+    if base.is_nan() {
+      return f64::NAN;
+    }
+    // 9. If exponent is +∞𝔽, then
+    //   b. If abs(ℝ(base)) = 1, return NaN.
+    // 10. If exponent is -∞𝔽, then
+    //   b. If abs(ℝ(base)) = 1, return NaN.
+    if exponent.is_infinite() && base.abs() == 1.0 {
+      return f64::NAN;
+    }
+    base.powf(exponent)
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-multiply
+  pub fn multiply(x: f64, y: f64) -> f64 {
+    x * y
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-divide
+  pub fn divide(x: f64, y: f64) -> f64 {
+    x / y
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-remainder, the sign
+  /// of the result equals the sign of the dividend like `fmod`.
+  pub fn remainder(n: f64, d: f64) -> f64 {
+    n % d
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-add
+  pub fn add(x: f64, y: f64) -> f64 {
+    x + y
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-subtract
+  pub fn subtract(x: f64, y: f64) -> f64 {
+    // 1. Return Number::add(x, Number::unaryMinus(y)).
+    Self::add(x, Self::unary_minus(y))
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-leftShift
+  pub fn left_shift(x: f64, y: f64) -> f64 {
+    // 1. Let lnum be ! ToInt32(x).
+    // 2. Let rnum be ! ToUint32(y).
+    // 3. Let shiftCount be ℝ(rnum) modulo 32.
+    // 4. Return the result of left shifting lnum by shiftCount bits. The
+    //    mathematical value of the result is exactly representable as a
+    //    32-bit two's complement bit string.
+    Self(x).to_int32().wrapping_shl(shift_count(y)) as f64
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-signedRightShift
+  pub fn signed_right_shift(x: f64, y: f64) -> f64 {
+    // 1. Let lnum be ! ToInt32(x).
+    // 2. Let rnum be ! ToUint32(y).
+    // 3. Let shiftCount be ℝ(rnum) modulo 32.
+    // 4. Return the result of performing a sign-extending right shift of
+    //    lnum by shiftCount bits.
+    (Self(x).to_int32() >> shift_count(y)) as f64
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-unsignedRightShift
+  pub fn unsigned_right_shift(x: f64, y: f64) -> f64 {
+    // 1. Let lnum be ! ToUint32(x).
+    // 2. Let rnum be ! ToUint32(y).
+    // 3. Let shiftCount be ℝ(rnum) modulo 32.
+    // 4. Return the result of performing a zero-filling right shift of lnum
+    //    by shiftCount bits.
+    (Self(x).to_uint32() >> shift_count(y)) as f64
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-bitwiseAND
+  pub fn bitwise_and(x: f64, y: f64) -> f64 {
+    // 1. Return NumberBitwiseOp(&, x, y).
+    (Self(x).to_int32() & Self(y).to_int32()) as f64
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-bitwiseXOR
+  pub fn bitwise_xor(x: f64, y: f64) -> f64 {
+    // 1. Return NumberBitwiseOp(^, x, y).
+    (Self(x).to_int32() ^ Self(y).to_int32()) as f64
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-bitwiseOR
+  pub fn bitwise_or(x: f64, y: f64) -> f64 {
+    // 1. Return NumberBitwiseOp(|, x, y).
+    (Self(x).to_int32() | Self(y).to_int32()) as f64
+  }
+}
+
+impl JsNumber {
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-equal
+  pub fn equal(x: &Self, y: &Self) -> JsBoolean {
+    // 1. If x is NaN, return false.
+    // 2. If y is NaN, return false.
+    // 3. If x is y, return true.
+    // 4. If x is +0𝔽 and y is -0𝔽, return true.
+    // 5. If x is -0𝔽 and y is +0𝔽, return true.
+    // 6. Return false.
+    (**x == **y).into()
+  }
+
   /// https://tc39.es/ecma262/#sec-numeric-types-number-sameValue
   pub fn same_value(x: &Self, y: &Self) -> JsBoolean {
     // 1. If x is NaN and y is NaN, return true.
@@ -31,4 +200,181 @@ impl JsNumber {
       JsBoolean::False
     }
   }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-sameValueZero
+  pub fn same_value_zero(x: &Self, y: &Self) -> JsBoolean {
+    // 1. If x is NaN and y is NaN, return true.
+    // 2. If x is +0𝔽 and y is -0𝔽, return true.
+    // 3. If x is -0𝔽 and y is +0𝔽, return true.
+    // 4. If x is the same Number value as y, return true.
+    // 5. Return false.
+    ((x.is_nan() && y.is_nan()) || **x == **y).into()
+  }
+
+  /// https://tc39.es/ecma262/#sec-isintegralnumber, `Number.isInteger`.
+  pub fn is_integer(x: &f64) -> bool {
+    // 1. If argument is not a Number, return false.
+    // 2. If argument is not finite, return false.
+    // 3. If truncate(ℝ(argument)) ≠ ℝ(argument), return false.
+    // 4. Return true.
+    x.is_finite() && x.trunc() == *x
+  }
+
+  /// https://tc39.es/ecma262/#sec-number.issafeinteger
+  pub fn is_safe_integer(x: &f64) -> bool {
+    // 1. If IsIntegralNumber(number) is true, then
+    //   a. If abs(ℝ(number)) ≤ 2^53 - 1, return true.
+    // 2. Return false.
+    Self::is_integer(x) && x.abs() <= 9007199254740991.0
+  }
+
+  /// https://tc39.es/ecma262/#sec-numeric-types-number-tostring
+  pub fn to_string(x: &Self) -> JsString {
+    // 1. If x is NaN, return the String "NaN".
+    if x.is_nan() {
+      return "NaN".into();
+    }
+    // 2. If x is +0𝔽 or -0𝔽, return the String "0".
+    if **x == 0.0 {
+      return "0".into();
+    }
+    // 3. If x < -0𝔽, return the string-concatenation of "-" and Number::toString(-x).
+    if **x < 0.0 {
+      return format!("-{}", Self::to_string(&Self(-**x))).into();
+    }
+    // 4. If x is +∞𝔽, return the String "Infinity".
+    if x.is_infinite() {
+      return "Infinity".into();
+    }
+    // 5. Otherwise, let n, k, and s be integers such that k ≥ 1, 10^(k-1) ≤ s < 10^k,
+    //    s × 10^(n-k) is ℝ(x), and k is as small as possible.
+    // Rust formats the shortest digits that round trip, `1.2345e-7`.
+    let exponential = format!("{:e}", **x);
+    let (mantissa, exponent) = exponential
+      .split_once('e')
+      .expect("exponential format has an exponent");
+    let digits = mantissa.replace('.', "");
+    let k = digits.len() as i32;
+    let n = exponent.parse::<i32>().expect("exponent is an integer") + 1;
+    // 6. If k ≤ n ≤ 21, return the string-concatenation of the k digits of s
+    //    followed by n - k occurrences of 0.
+    if k <= n && n <= 21 {
+      return format!("{}{}", digits, "0".repeat((n - k) as usize)).into();
+    }
+    // 7. If 0 < n ≤ 21, return the most significant n digits of s, ".", and
+    //    the remaining k - n digits of s.
+    if 0 < n && n <= 21 {
+      let (int, frac) = digits.split_at(n as usize);
+      return format!("{}.{}", int, frac).into();
+    }
+    // 8. If -6 < n ≤ 0, return "0.", -n occurrences of 0, and the k digits of s.
+    if -6 < n && n <= 0 {
+      return format!("0.{}{}", "0".repeat(-n as usize), digits).into();
+    }
+    // 9. Otherwise, if k = 1, return the single digit of s, "e", the sign of
+    //    n - 1 and abs(n - 1).
+    // 10. Return the most significant digit of s, ".", the remaining k - 1
+    //     digits of s, "e", the sign of n - 1 and abs(n - 1).
+    let sign = if n - 1 < 0 { '-' } else { '+' };
+    let (first, rest) = digits.split_at(1);
+    if k == 1 {
+      format!("{}e{}{}", first, sign, (n - 1).abs()).into()
+    } else {
+      format!("{}.{}e{}{}", first, rest, sign, (n - 1).abs()).into()
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn arithmetic() {
+    assert_eq!(JsNumber::remainder(5.0, -3.0), 2.0);
+    assert_eq!(JsNumber::remainder(-5.0, 3.0), -2.0);
+    assert!(JsNumber::remainder(-4.0, 2.0).is_sign_negative());
+    assert!(JsNumber::remainder(1.0, 0.0).is_nan());
+    assert_eq!(JsNumber::exponentiate(2.0, 10.0), 1024.0);
+    assert_eq!(JsNumber::exponentiate(f64::NAN, 0.0), 1.0);
+    assert!(JsNumber::exponentiate(1.0, f64::NAN).is_nan());
+    assert!(JsNumber::exponentiate(-1.0, f64::INFINITY).is_nan());
+    assert_eq!(JsNumber::add(0.1, 0.2), 0.30000000000000004);
+    assert!(JsNumber::add(-0.0, -0.0).is_sign_negative());
+    assert!(JsNumber::subtract(0.0, 0.0).is_sign_positive());
+    assert!(JsNumber::unary_minus(0.0).is_sign_negative());
+    assert!(JsNumber::add(f64::INFINITY, f64::NEG_INFINITY).is_nan());
+    assert!(JsNumber::multiply(-0.0, 5.0).is_sign_negative());
+    assert_eq!(JsNumber::divide(1.0, -0.0), f64::NEG_INFINITY);
+  }
+
+  #[test]
+  fn bitwise() {
+    assert_eq!(JsNumber::unsigned_right_shift(-1.0, 0.0), 4294967295.0);
+    assert_eq!(JsNumber::signed_right_shift(-8.0, 1.0), -4.0);
+    // only the low 5 bits of the shift count are used
+    assert_eq!(JsNumber::left_shift(1.0, 33.0), 2.0);
+    assert_eq!(JsNumber::left_shift(1.0, 31.0), -2147483648.0);
+    assert_eq!(JsNumber::unsigned_right_shift(f64::NAN, 0.0), 0.0);
+    assert_eq!(JsNumber::bitwise_and(4294967297.0, 3.0), 1.0);
+    assert_eq!(JsNumber::bitwise_or(-0.0, 2.5), 2.0);
+    assert_eq!(JsNumber::bitwise_xor(5.0, 3.0), 6.0);
+    assert_eq!(JsNumber::bitwise_not(0.0), -1.0);
+  }
+
+  #[test]
+  fn equal_and_same_value() {
+    let nan = JsNumber(f64::NAN);
+    assert_eq!(JsNumber::equal(&nan, &nan), JsBoolean::False);
+    assert_eq!(JsNumber::same_value(&nan, &nan), JsBoolean::True);
+    let (zero, negative_zero) = (JsNumber(0.0), JsNumber(-0.0));
+    assert_eq!(JsNumber::equal(&zero, &negative_zero), JsBoolean::True);
+    assert_eq!(
+      JsNumber::same_value(&zero, &negative_zero),
+      JsBoolean::False
+    );
+    assert_eq!(JsNumber::same_value_zero(&nan, &nan), JsBoolean::True);
+    assert_eq!(
+      JsNumber::same_value_zero(&zero, &negative_zero),
+      JsBoolean::True
+    );
+    assert_eq!(JsNumber::same_value_zero(&zero, &nan), JsBoolean::False);
+  }
+
+  #[test]
+  fn integer_predicates() {
+    for n in [0.0, -0.0, 1.0, -7.0, 1e300, 2f64.powi(53)] {
+      assert!(JsNumber::is_integer(&n));
+    }
+    for n in [1.5, -0.1, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+      assert!(!JsNumber::is_integer(&n));
+      assert!(!JsNumber::is_safe_integer(&n));
+    }
+    let max_safe = 2f64.powi(53) - 1.0;
+    assert!(JsNumber::is_safe_integer(&max_safe));
+    assert!(JsNumber::is_safe_integer(&-max_safe));
+    assert!(!JsNumber::is_safe_integer(&2f64.powi(53)));
+    assert!(!JsNumber::is_safe_integer(&-2f64.powi(53)));
+  }
+
+  #[test]
+  fn number_to_string() {
+    for (n, s) in [
+      (f64::NAN, "NaN"),
+      (-0.0, "0"),
+      (f64::NEG_INFINITY, "-Infinity"),
+      (1.0, "1"),
+      (-1.5, "-1.5"),
+      (123456789.0, "123456789"),
+      (1e21, "1e+21"),
+      (1e20, "100000000000000000000"),
+      (0.1 + 0.2, "0.30000000000000004"),
+      (0.000001, "0.000001"),
+      (1.5e-7, "1.5e-7"),
+      (2f64.powi(53), "9007199254740992"),
+      (1.7976931348623157e308, "1.7976931348623157e+308"),
+    ] {
+      assert_eq!(JsNumber::to_string(&JsNumber(n)), s);
+    }
+  }
 }
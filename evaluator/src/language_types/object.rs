@@ -1,7 +1,22 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+  cell::{Ref, RefCell, RefMut},
+  collections::HashMap,
+  fmt,
+  rc::Rc,
+};
+
+use indexmap::IndexMap;
+use ng262_parser::parser::nodes::Node;
 
 use crate::{
-  helpers::Either, specification_types::property_descriptor::PropertyDescriptor,
+  executable_code_and_execution_contexts::{
+    environment_records::Environment, realm::Realm,
+  },
+  helpers::Either,
+  ordinary_and_exotic_objects_behaviours::{
+    builtin_function::BuiltinBehaviour, ecmascript_function_objects::ThisMode,
+  },
+  specification_types::property_descriptor::PropertyDescriptor,
 };
 
 use super::{null::JsNull, string::JsString, symbol::JsSymbol, Value};
@@ -10,6 +25,7 @@ pub type Prototype = Either<JsObject, JsNull>;
 
 struct Inner {
   properties: PropertyMap,
+  internal_slots: HashMap<&'static str, InternalSlot>,
   pub internal_methods: &'static InternalMethods,
   prototype: Prototype,
   extensible: bool,
@@ -21,32 +37,278 @@ pub struct JsObject(Rc<RefCell<Inner>>);
 
 impl AsRef<RefCell<Inner>> for JsObject {
   fn as_ref(&self) -> &RefCell<Inner> {
-    &*self.0
+    &self.0
+  }
+}
+
+/// Objects can be cyclic, only the identity is printed.
+impl fmt::Debug for JsObject {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "JsObject({:p})", Rc::as_ptr(&self.0))
   }
 }
 
 impl JsObject {
-  pub fn get_call(&self) -> Option<fn(&JsObject, &[Value]) -> Value> {
-    self.0.borrow().internal_methods.call
+  pub fn new(
+    prototype: Prototype,
+    extensible: bool,
+    internal_methods: &'static InternalMethods,
+  ) -> Self {
+    Self(Rc::new(RefCell::new(Inner {
+      properties: PropertyMap::default(),
+      internal_slots: HashMap::new(),
+      internal_methods,
+      prototype,
+      extensible,
+    })))
+  }
+
+  pub(crate) fn internal_methods(&self) -> &'static InternalMethods {
+    self.0.borrow().internal_methods
+  }
+
+  pub(crate) fn set_internal_methods(
+    &self,
+    internal_methods: &'static InternalMethods,
+  ) {
+    self.0.borrow_mut().internal_methods = internal_methods;
+  }
+
+  pub fn get_call(&self) -> Option<CallMethod> {
+    self.internal_methods().call
+  }
+
+  pub fn get_construct(&self) -> Option<ConstructMethod> {
+    self.internal_methods().construct
+  }
+
+  /// [[GetPrototypeOf]]
+  pub fn get_prototype_of(&self) -> Prototype {
+    (self.internal_methods().get_prototype_of)(self)
+  }
+
+  /// [[SetPrototypeOf]]
+  pub fn set_prototype_of(&self, prototype: Prototype) -> Result<bool, Value> {
+    (self.internal_methods().set_prototype_of)(self, prototype)
+  }
+
+  /// [[IsExtensible]]
+  pub fn is_extensible(&self) -> Result<bool, Value> {
+    (self.internal_methods().is_extensible)(self)
+  }
+
+  /// [[PreventExtensions]]
+  pub fn prevent_extensions(&self) -> Result<bool, Value> {
+    (self.internal_methods().prevent_extensions)(self)
+  }
+
+  /// [[GetOwnProperty]]
+  pub fn get_own_property(
+    &self,
+    key: &PropertyKey,
+  ) -> Result<Option<PropertyDescriptor>, Value> {
+    (self.internal_methods().get_own_property)(self, key)
+  }
+
+  /// [[DefineOwnProperty]]
+  pub fn define_own_property(
+    &self,
+    key: &PropertyKey,
+    desc: PropertyDescriptor,
+  ) -> Result<bool, Value> {
+    (self.internal_methods().define_own_property)(self, key, desc)
+  }
+
+  /// [[HasProperty]]
+  pub fn has_property(&self, key: &PropertyKey) -> Result<bool, Value> {
+    (self.internal_methods().has_property)(self, key)
+  }
+
+  /// [[Get]]
+  pub fn get(
+    &self,
+    key: &PropertyKey,
+    receiver: &Value,
+  ) -> Result<Value, Value> {
+    (self.internal_methods().get)(self, key, receiver)
+  }
+
+  /// [[Set]]
+  pub fn set(
+    &self,
+    key: &PropertyKey,
+    value: Value,
+    receiver: &Value,
+  ) -> Result<bool, Value> {
+    (self.internal_methods().set)(self, key, value, receiver)
+  }
+
+  /// [[Delete]]
+  pub fn delete(&self, key: &PropertyKey) -> Result<bool, Value> {
+    (self.internal_methods().delete)(self, key)
+  }
+
+  /// [[OwnPropertyKeys]]
+  pub fn own_property_keys(&self) -> Result<Vec<PropertyKey>, Value> {
+    (self.internal_methods().own_property_keys)(self)
+  }
+
+  /// [[Call]], the caller should have checked `IsCallable`.
+  pub fn call(
+    &self,
+    this: &Value,
+    arguments: &[Value],
+  ) -> Result<Value, Value> {
+    let call = self.get_call().expect("[[Call]] of a non-callable object");
+    call(self, this, arguments)
+  }
+
+  /// [[Construct]], the caller should have checked `IsConstructor`.
+  pub fn construct(
+    &self,
+    arguments: &[Value],
+    new_target: &JsObject,
+  ) -> Result<JsObject, Value> {
+    let construct = self
+      .get_construct()
+      .expect("[[Construct]] of a non-constructor object");
+    construct(self, arguments, new_target)
   }
 
   pub fn get_prototype(&self) -> Prototype {
     self.0.borrow().prototype.clone()
   }
 
+  pub fn set_prototype(&self, prototype: Prototype) {
+    self.0.borrow_mut().prototype = prototype;
+  }
+
+  pub fn extensible(&self) -> bool {
+    self.0.borrow().extensible
+  }
+
+  pub fn set_extensible(&self, extensible: bool) {
+    self.0.borrow_mut().extensible = extensible;
+  }
+
+  pub fn has_internal_slot(&self, name: &str) -> bool {
+    self.0.borrow().internal_slots.contains_key(name)
+  }
+
+  pub fn get_internal_slot(&self, name: &str) -> Option<InternalSlot> {
+    self.0.borrow().internal_slots.get(name).cloned()
+  }
+
+  pub fn set_internal_slot(&self, name: &'static str, slot: InternalSlot) {
+    self.0.borrow_mut().internal_slots.insert(name, slot);
+  }
+
+  pub fn get_properties(&self) -> Ref<'_, PropertyMap> {
+    Ref::map(self.0.borrow(), |inner| &inner.properties)
+  }
+
+  pub fn get_properties_mut(&self) -> RefMut<'_, PropertyMap> {
+    RefMut::map(self.0.borrow_mut(), |inner| &mut inner.properties)
+  }
+
   pub fn equals(lhs: &Self, rhs: &Self) -> bool {
     std::ptr::eq(lhs.as_ref(), rhs.as_ref())
   }
 }
 
-pub struct PropertyMap {
-  /// Properties
-  string_properties: HashMap<JsString, PropertyDescriptor>,
-  /// Symbol Properties
-  symbol_properties: HashMap<JsSymbol, PropertyDescriptor>,
+/// https://tc39.es/ecma262/#sec-object-type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PropertyKey {
+  String(JsString),
+  Symbol(JsSymbol),
 }
 
+impl From<&str> for PropertyKey {
+  fn from(s: &str) -> Self {
+    Self::String(s.into())
+  }
+}
+
+impl From<String> for PropertyKey {
+  fn from(s: String) -> Self {
+    Self::String(s.into())
+  }
+}
+
+impl From<JsString> for PropertyKey {
+  fn from(s: JsString) -> Self {
+    Self::String(s)
+  }
+}
+
+impl From<JsSymbol> for PropertyKey {
+  fn from(s: JsSymbol) -> Self {
+    Self::Symbol(s)
+  }
+}
+
+impl PropertyKey {
+  /// https://tc39.es/ecma262/#array-index
+  pub fn as_array_index(&self) -> Option<u32> {
+    match self {
+      Self::String(s) => match s.to_string_lossy().parse::<u32>() {
+        // an integer index whose numeric value is less than 2^32 - 1, and
+        // the key is its canonical string, `01` isn't an array index
+        Ok(index) if index != u32::MAX && *s == index.to_string().as_str() => {
+          Some(index)
+        }
+        _ => None,
+      },
+      Self::Symbol(_) => None,
+    }
+  }
+}
+
+impl From<PropertyKey> for Value {
+  fn from(key: PropertyKey) -> Self {
+    match key {
+      PropertyKey::String(s) => Self::String(s),
+      PropertyKey::Symbol(s) => Self::Symbol(s),
+    }
+  }
+}
+
+/// The internal slots besides [[Prototype]] and [[Extensible]], keyed by
+/// the name without brackets, e.g. `Realm`.
+#[derive(Clone)]
+pub enum InternalSlot {
+  Value(Value),
+  Realm(Realm),
+  Behaviour(BuiltinBehaviour),
+  Environment(Environment),
+  /// The function node, [[FormalParameters]] and [[ECMAScriptCode]] are its
+  /// parameters and body.
+  Code(Rc<Node>),
+  ThisMode(ThisMode),
+}
+
+/// The own properties in the chronological order of their creation, which
+/// is the order of their keys.
+pub type PropertyMap = IndexMap<PropertyKey, PropertyDescriptor>;
+
+pub type CallMethod = fn(&JsObject, &Value, &[Value]) -> Result<Value, Value>;
+pub type ConstructMethod =
+  fn(&JsObject, &[Value], &JsObject) -> Result<JsObject, Value>;
+
 pub struct InternalMethods {
-  get_prototype_of: fn(&JsObject) -> Prototype, // TODO
-  call: Option<fn(&JsObject, &[Value]) -> Value>, // TODO
+  pub get_prototype_of: fn(&JsObject) -> Prototype, // TODO
+  pub set_prototype_of: fn(&JsObject, Prototype) -> Result<bool, Value>,
+  pub is_extensible: fn(&JsObject) -> Result<bool, Value>,
+  pub prevent_extensions: fn(&JsObject) -> Result<bool, Value>,
+  pub get_own_property:
+    fn(&JsObject, &PropertyKey) -> Result<Option<PropertyDescriptor>, Value>,
+  pub define_own_property:
+    fn(&JsObject, &PropertyKey, PropertyDescriptor) -> Result<bool, Value>,
+  pub has_property: fn(&JsObject, &PropertyKey) -> Result<bool, Value>,
+  pub get: fn(&JsObject, &PropertyKey, &Value) -> Result<Value, Value>,
+  pub set: fn(&JsObject, &PropertyKey, Value, &Value) -> Result<bool, Value>,
+  pub delete: fn(&JsObject, &PropertyKey) -> Result<bool, Value>,
+  pub own_property_keys: fn(&JsObject) -> Result<Vec<PropertyKey>, Value>,
+  pub call: Option<CallMethod>,
+  pub construct: Option<ConstructMethod>,
 }
@@ -1,2 +1,274 @@
+use std::{
+  fmt,
+  ops::{Add, Deref},
+};
+
 /// https://tc39.es/ecma262/#sec-ecmascript-language-types-string-type
-pub type JsString = String;
+///
+/// A sequence of UTF-16 code units, unlike a Rust `String` it can hold a
+/// lone surrogate. It derefs to the code units, so `len` and indexing count
+/// code units like `length` and `charCodeAt` do.
+#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct JsString(Vec<u16>);
+
+impl JsString {
+  /// The string with each lone surrogate replaced by U+FFFD.
+  pub fn to_string_lossy(&self) -> String {
+    String::from_utf16_lossy(&self.0)
+  }
+}
+
+/// String-concatenation.
+impl Add<&JsString> for JsString {
+  type Output = JsString;
+
+  fn add(mut self, rhs: &JsString) -> JsString {
+    self.0.extend_from_slice(&rhs.0);
+    self
+  }
+}
+
+impl Add<&str> for JsString {
+  type Output = JsString;
+
+  fn add(mut self, rhs: &str) -> JsString {
+    self.0.extend(rhs.encode_utf16());
+    self
+  }
+}
+
+impl Deref for JsString {
+  type Target = [u16];
+
+  fn deref(&self) -> &[u16] {
+    &self.0
+  }
+}
+
+impl From<&str> for JsString {
+  fn from(s: &str) -> Self {
+    Self(s.encode_utf16().collect())
+  }
+}
+
+impl From<String> for JsString {
+  fn from(s: String) -> Self {
+    Self::from(s.as_str())
+  }
+}
+
+impl From<Vec<u16>> for JsString {
+  fn from(code_units: Vec<u16>) -> Self {
+    Self(code_units)
+  }
+}
+
+impl From<&[u16]> for JsString {
+  fn from(code_units: &[u16]) -> Self {
+    Self(code_units.to_vec())
+  }
+}
+
+impl PartialEq<str> for JsString {
+  fn eq(&self, other: &str) -> bool {
+    self.0.iter().copied().eq(other.encode_utf16())
+  }
+}
+
+impl PartialEq<&str> for JsString {
+  fn eq(&self, other: &&str) -> bool {
+    *self == **other
+  }
+}
+
+impl fmt::Display for JsString {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.to_string_lossy())
+  }
+}
+
+/// Like the `Debug` of a `String`, with a lone surrogate as `\u{d800}`.
+impl fmt::Debug for JsString {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in char::decode_utf16(self.0.iter().copied()) {
+      match c {
+        Ok(c) => write!(f, "{}", c.escape_debug())?,
+        Err(e) => write!(f, "\\u{{{:x}}}", e.unpaired_surrogate())?,
+      }
+    }
+    write!(f, "\"")
+  }
+}
+
+/// The record CodePointAt returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodePoint {
+  pub code_point: u32,
+  pub code_unit_count: usize,
+  pub is_unpaired_surrogate: bool,
+}
+
+fn is_leading_surrogate(c: u16) -> bool {
+  (0xD800..=0xDBFF).contains(&c)
+}
+
+fn is_trailing_surrogate(c: u16) -> bool {
+  (0xDC00..=0xDFFF).contains(&c)
+}
+
+/// https://tc39.es/ecma262/#sec-codepointat
+pub fn code_point_at(string: &[u16], position: usize) -> CodePoint {
+  // 1. Let size be the length of string.
+  let size = string.len();
+  // 2. Assert: position ≥ 0 and position < size.
+  assert!(position < size);
+  // 3. Let first be the code unit at index position within string.
+  let first = string[position];
+  // 4. Let cp be the code point whose numeric value is the numeric value of first.
+  let cp = first as u32;
+  // 5. If first is neither a leading surrogate nor a trailing surrogate, then
+  if !is_leading_surrogate(first) && !is_trailing_surrogate(first) {
+    // a. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 1,
+    //    [[IsUnpairedSurrogate]]: false }.
+    return CodePoint {
+      code_point: cp,
+      code_unit_count: 1,
+      is_unpaired_surrogate: false,
+    };
+  }
+  // 6. If first is a trailing surrogate or position + 1 = size, then
+  //   a. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 1,
+  //      [[IsUnpairedSurrogate]]: true }.
+  // 7. Let second be the code unit at index position + 1 within string.
+  // 8. If second is not a trailing surrogate, then
+  //   a. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 1,
+  //      [[IsUnpairedSurrogate]]: true }.
+  let second = match string.get(position + 1) {
+    Some(&second)
+      if !is_trailing_surrogate(first) && is_trailing_surrogate(second) =>
+    {
+      second
+    }
+    _ => {
+      return CodePoint {
+        code_point: cp,
+        code_unit_count: 1,
+        is_unpaired_surrogate: true,
+      }
+    }
+  };
+  // 9. Set cp to UTF16SurrogatePairToCodePoint(first, second).
+  let cp = (first as u32 - 0xD800) * 0x400 + (second as u32 - 0xDC00) + 0x10000;
+  // 10. Return the Record { [[CodePoint]]: cp, [[CodeUnitCount]]: 2,
+  //     [[IsUnpairedSurrogate]]: false }.
+  CodePoint {
+    code_point: cp,
+    code_unit_count: 2,
+    is_unpaired_surrogate: false,
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-stringindexof, `None` is not-found.
+pub fn string_index_of(
+  string: &[u16],
+  search_value: &[u16],
+  from_index: usize,
+) -> Option<usize> {
+  // 1. Let len be the length of string.
+  let len = string.len();
+  // 2. If searchValue is the empty String and fromIndex ≤ len, return fromIndex.
+  if search_value.is_empty() && from_index <= len {
+    return Some(from_index);
+  }
+  // 3. Let searchLen be the length of searchValue.
+  let search_len = search_value.len();
+  // 4. For each integer i such that fromIndex ≤ i ≤ len - searchLen, in
+  //    ascending order, do
+  //   a. Let candidate be the substring of string from i to i + searchLen.
+  //   b. If candidate is searchValue, return i.
+  // 5. Return not-found.
+  (from_index..=len.checked_sub(search_len)?)
+    .find(|&i| &string[i..i + search_len] == search_value)
+}
+
+/// https://tc39.es/ecma262/#sec-isstringwellformedunicode
+pub fn is_string_well_formed_unicode(string: &[u16]) -> bool {
+  // 1. Let len be the length of string.
+  let len = string.len();
+  // 2. Let k be 0.
+  let mut k = 0;
+  // 3. Repeat, while k < len,
+  while k < len {
+    // a. Let cp be CodePointAt(string, k).
+    let cp = code_point_at(string, k);
+    // b. If cp.[[IsUnpairedSurrogate]] is true, return false.
+    if cp.is_unpaired_surrogate {
+      return false;
+    }
+    // c. Set k to k + cp.[[CodeUnitCount]].
+    k += cp.code_unit_count;
+  }
+  // 4. Return true.
+  true
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn well_formed_unicode() {
+    let pair: Vec<u16> = "a😀".encode_utf16().collect();
+    assert!(is_string_well_formed_unicode(&pair));
+    assert_eq!(
+      code_point_at(&pair, 1),
+      CodePoint {
+        code_point: 0x1F600,
+        code_unit_count: 2,
+        is_unpaired_surrogate: false,
+      }
+    );
+    assert!(is_string_well_formed_unicode(&[]));
+    // a lone leading surrogate, at the end and before another code unit
+    assert!(!is_string_well_formed_unicode(&[0x61, 0xD83D]));
+    assert!(!is_string_well_formed_unicode(&[0xD83D, 0x61]));
+    assert!(code_point_at(&[0xD83D, 0xD83D], 0).is_unpaired_surrogate);
+    // a lone trailing surrogate
+    assert!(!is_string_well_formed_unicode(&[0xDE00, 0x61]));
+    assert_eq!(code_point_at(&[0xDE00, 0xDE00], 0).code_unit_count, 1);
+  }
+
+  #[test]
+  fn lone_surrogate() {
+    let lone = JsString::from(vec![0xD83D]);
+    assert_eq!(lone.len(), 1);
+    assert_eq!(lone[0], 0xD83D);
+    assert_ne!(lone, JsString::from("\u{FFFD}"));
+    assert_eq!(lone.to_string_lossy(), "\u{FFFD}");
+    assert_eq!(format!("{:?}", lone), r#""\u{d83d}""#);
+    // concatenated with a trailing surrogate it's a pair
+    let pair = lone.clone() + &JsString::from(vec![0xDE00]);
+    assert_eq!(pair, "😀");
+    assert_eq!(pair.len(), 2);
+    assert_eq!(format!("{:?}", pair), r#""😀""#);
+    // code units compare, not code points
+    let max = JsString::from(vec![0xFFFF]);
+    assert!(pair < max);
+  }
+
+  #[test]
+  fn index_of() {
+    let string: Vec<u16> = "abcabc".encode_utf16().collect();
+    let search = |value: &str, from: usize| {
+      let value: Vec<u16> = value.encode_utf16().collect();
+      string_index_of(&string, &value, from)
+    };
+    assert_eq!(search("bc", 0), Some(1));
+    assert_eq!(search("bc", 2), Some(4));
+    assert_eq!(search("bc", 5), None);
+    assert_eq!(search("", 6), Some(6));
+    assert_eq!(search("", 7), None);
+    assert_eq!(search("abcabcabc", 0), None);
+  }
+}
@@ -1,5 +1,151 @@
+use std::{
+  collections::HashMap,
+  hash::{Hash, Hasher},
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    LazyLock,
+  },
+};
+
+use super::string::JsString;
+
 /// https://tc39.es/ecma262/#sec-ecmascript-language-types-symbol-type
-#[derive(Debug, PartialEq, Eq)]
+///
+/// A symbol is unique by its `id`, two symbols with the same description are
+/// still different.
+#[derive(Debug, Clone)]
 pub struct JsSymbol {
   id: usize,
+  description: Option<JsString>,
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+impl JsSymbol {
+  /// A new unique symbol, like `Symbol(description)`.
+  pub fn new(description: Option<JsString>) -> Self {
+    Self {
+      id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+      description,
+    }
+  }
+
+  /// The [[Description]] of the symbol, `None` is undefined.
+  pub fn description(&self) -> Option<&JsString> {
+    self.description.as_ref()
+  }
+}
+
+impl PartialEq for JsSymbol {
+  fn eq(&self, other: &Self) -> bool {
+    self.id == other.id
+  }
+}
+
+impl Eq for JsSymbol {}
+
+impl Hash for JsSymbol {
+  fn hash<H: Hasher>(&self, state: &mut H) {
+    self.id.hash(state);
+  }
+}
+
+/// Each well-known symbol is created on its first use and shared by all
+/// realms.
+macro_rules! well_known_symbols {
+  ($($name:ident: $description:literal,)*) => {
+    /// https://tc39.es/ecma262/#sec-well-known-symbols
+    impl JsSymbol {
+      $(
+        pub fn $name() -> Self {
+          static SYMBOL: LazyLock<JsSymbol> =
+            LazyLock::new(|| JsSymbol::new(Some($description.into())));
+          SYMBOL.clone()
+        }
+      )*
+    }
+  };
+}
+
+well_known_symbols! {
+  async_iterator: "Symbol.asyncIterator",
+  iterator: "Symbol.iterator",
+  species: "Symbol.species",
+  to_primitive: "Symbol.toPrimitive",
+  to_string_tag: "Symbol.toStringTag",
+  unscopables: "Symbol.unscopables",
+}
+
+/// https://tc39.es/ecma262/#sec-globalsymbolregistry-record-specification-type
+///
+/// The GlobalSymbolRegistry of `Symbol.for` and `Symbol.keyFor`, the symbols
+/// by their [[Key]].
+#[derive(Debug, Default)]
+pub struct SymbolRegistry(HashMap<JsString, JsSymbol>);
+
+impl SymbolRegistry {
+  /// https://tc39.es/ecma262/#sec-symbol.for, after ToString of the key.
+  pub fn symbol_for(&mut self, string_key: JsString) -> JsSymbol {
+    // 2. For each element e of the GlobalSymbolRegistry List, do
+    //   a. If e.[[Key]] is stringKey, return e.[[Symbol]].
+    // 3. Assert: GlobalSymbolRegistry does not currently contain an entry for
+    //    stringKey.
+    // 4. Let newSymbol be a new Symbol whose [[Description]] is stringKey.
+    // 5. Append the GlobalSymbolRegistry Record { [[Key]]: stringKey,
+    //    [[Symbol]]: newSymbol } to the GlobalSymbolRegistry List.
+    // 6. Return newSymbol.
+    self
+      .0
+      .entry(string_key)
+      .or_insert_with_key(|key| JsSymbol::new(Some(key.clone())))
+      .clone()
+  }
+
+  /// https://tc39.es/ecma262/#sec-keyforsymbol, `None` is undefined.
+  pub fn key_for(&self, sym: &JsSymbol) -> Option<JsString> {
+    // 1. For each element e of the GlobalSymbolRegistry List, do
+    //   a. If SameValue(e.[[Symbol]], sym) is true, return e.[[Key]].
+    // 2. Assert: GlobalSymbolRegistry does not currently contain an entry for
+    //    sym.
+    // 3. Return undefined.
+    let key = sym.description()?;
+    self.0.get(key).filter(|s| *s == sym).map(|_| key.clone())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::agent::{key_for_symbol, symbol_for};
+
+  #[test]
+  fn unique_symbols() {
+    assert_ne!(JsSymbol::new(None), JsSymbol::new(None));
+    let a = JsSymbol::new(Some("a".into()));
+    assert_ne!(a, JsSymbol::new(Some("a".into())));
+    assert_eq!(a, a.clone());
+    assert_eq!(a.description().unwrap(), "a");
+    assert_eq!(JsSymbol::iterator(), JsSymbol::iterator());
+    assert_ne!(JsSymbol::iterator(), JsSymbol::async_iterator());
+    assert_eq!(
+      JsSymbol::iterator().description().unwrap(),
+      "Symbol.iterator"
+    );
+  }
+
+  #[test]
+  fn registry() {
+    let mut registry = SymbolRegistry::default();
+    let x = registry.symbol_for("x".into());
+    assert_eq!(registry.symbol_for("x".into()), x);
+    assert_ne!(registry.symbol_for("y".into()), x);
+    assert_eq!(registry.key_for(&x).unwrap(), "x");
+    // a symbol with the same description isn't registered
+    assert_eq!(registry.key_for(&JsSymbol::new(Some("x".into()))), None);
+    assert_eq!(registry.key_for(&JsSymbol::iterator()), None);
+    // the registry of the surrounding agent
+    let x = symbol_for("x".into());
+    assert_eq!(symbol_for("x".into()), x);
+    assert_eq!(key_for_symbol(&x).unwrap(), "x");
+  }
 }
@@ -1,2 +1,3 @@
 /// https://tc39.es/ecma262/#sec-ecmascript-language-types-undefined-type
+#[derive(Debug, Clone, Copy)]
 pub struct JsUndefined;
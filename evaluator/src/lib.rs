@@ -1,11 +1,21 @@
 pub mod abstract_operations;
 pub mod agent;
+pub mod executable_code_and_execution_contexts;
+pub mod fundamental_objects;
 pub mod helpers;
+pub mod host;
+pub mod indexed_collections;
 pub mod language_types;
+pub mod ordinary_and_exotic_objects_behaviours;
 pub mod parser;
 pub mod runtime_semantics;
 pub mod specification_types;
 pub mod static_semantics;
+pub mod structured_data;
+pub mod text_processing;
+
+#[cfg(test)]
+mod test_helpers;
 
 #[cfg(test)]
 mod tests {
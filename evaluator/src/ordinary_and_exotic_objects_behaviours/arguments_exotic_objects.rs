@@ -0,0 +1,57 @@
+//! https://tc39.es/ecma262/#sec-arguments-exotic-objects
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{create_data_property, define_property_or_throw},
+    ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+  },
+  agent::current_realm,
+  helpers::Either,
+  language_types::{boolean::JsBoolean, object::JsObject, Value},
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+/// https://tc39.es/ecma262/#sec-createunmappedargumentsobject
+pub fn create_unmapped_arguments_object(arguments_list: &[Value]) -> JsObject {
+  // 1. Let len be the number of elements in argumentsList.
+  let len = arguments_list.len();
+  // 2. Let obj be OrdinaryObjectCreate(%Object.prototype%, « [[ParameterMap]] »).
+  // 3. Set obj.[[ParameterMap]] to undefined.
+  let obj = ordinary_object_create(
+    Either::A(current_realm().intrinsic("Object.prototype")),
+    &["ParameterMap"],
+  );
+  // 4. Perform ! DefinePropertyOrThrow(obj, "length", PropertyDescriptor { [[Value]]:
+  //    𝔽(len), [[Writable]]: true, [[Enumerable]]: false, [[Configurable]]: true }).
+  define_property_or_throw(
+    &obj,
+    &"length".into(),
+    PropertyDescriptor {
+      value: Some(Value::from(len as f64)),
+      writable: Some(JsBoolean::True),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::True),
+    },
+  )
+  .expect("define length of a new object");
+  // 5. Let index be 0.
+  // 6. Repeat, while index < len,
+  for (index, val) in arguments_list.iter().enumerate() {
+    // a. Let val be argumentsList[index].
+    // b. Perform ! CreateDataPropertyOrThrow(obj, ! ToString(𝔽(index)), val).
+    // c. Set index to index + 1.
+    create_data_property(&obj, &index.to_string().into(), val.clone())
+      .expect("define an index of a new object");
+  }
+  // 7. Perform ! DefinePropertyOrThrow(obj, @@iterator, PropertyDescriptor {
+  //    [[Value]]: %Array.prototype.values%, [[Writable]]: true, [[Enumerable]]:
+  //    false, [[Configurable]]: true }).
+  // 8. Perform ! DefinePropertyOrThrow(obj, "callee", PropertyDescriptor { [[Get]]:
+  //    %ThrowTypeError%, [[Set]]: %ThrowTypeError%, [[Enumerable]]: false,
+  //    [[Configurable]]: false }).
+  // TODO: %Array.prototype.values% and %ThrowTypeError%
+  // 9. Return obj.
+  obj
+}
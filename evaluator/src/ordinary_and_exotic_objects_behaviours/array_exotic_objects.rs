@@ -0,0 +1,312 @@
+//! https://tc39.es/ecma262/#sec-array-exotic-objects
+
+use crate::{
+  abstract_operations::ordinary_object_internal_methods_and_internal_slots::{
+    ordinary_define_own_property, ordinary_get_own_property,
+    ORDINARY_INTERNAL_METHODS,
+  },
+  agent::current_realm,
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    object::{InternalMethods, JsObject, PropertyKey, Prototype},
+    Value,
+  },
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+pub static ARRAY_INTERNAL_METHODS: InternalMethods = InternalMethods {
+  define_own_property: array_define_own_property,
+  ..ORDINARY_INTERNAL_METHODS
+};
+
+pub fn is_array_exotic_object(o: &JsObject) -> bool {
+  std::ptr::eq(o.internal_methods(), &ARRAY_INTERNAL_METHODS)
+}
+
+/// https://tc39.es/ecma262/#sec-array-exotic-objects-defineownproperty-p-desc
+pub fn array_define_own_property(
+  a: &JsObject,
+  p: &PropertyKey,
+  desc: PropertyDescriptor,
+) -> Result<bool, Value> {
+  // 1. If P is "length", then
+  if *p == PropertyKey::from("length") {
+    // a. Return ? ArraySetLength(A, Desc).
+    return array_set_length(a, desc);
+  }
+  // 2. Else if P is an array index, then
+  if let Some(index) = p.as_array_index() {
+    // a. Let oldLenDesc be OrdinaryGetOwnProperty(A, "length").
+    // b. Assert: IsDataDescriptor(oldLenDesc) is true.
+    // c. Assert: oldLenDesc.[[Configurable]] is false.
+    let mut old_len_desc = ordinary_get_own_property(a, &"length".into())?
+      .expect("array has a length property");
+    // d. Let oldLen be oldLenDesc.[[Value]].
+    // e. Assert: oldLen is a non-negative integral Number.
+    let old_len = length_value(&old_len_desc);
+    // f. Let index be ! ToUint32(P).
+    // g. If index ≥ oldLen and oldLenDesc.[[Writable]] is false, return false.
+    if index >= old_len && old_len_desc.writable == Some(JsBoolean::False) {
+      return Ok(false);
+    }
+    // h. Let succeeded be ! OrdinaryDefineOwnProperty(A, P, Desc).
+    // i. If succeeded is false, return false.
+    if !ordinary_define_own_property(a, p, desc)? {
+      return Ok(false);
+    }
+    // j. If index ≥ oldLen, then
+    if index >= old_len {
+      // i. Set oldLenDesc.[[Value]] to index + 1𝔽.
+      old_len_desc.value = Some(Value::from(index as f64 + 1.0));
+      // ii. Set succeeded to ! OrdinaryDefineOwnProperty(A, "length", oldLenDesc).
+      // iii. Assert: succeeded is true.
+      ordinary_define_own_property(a, &"length".into(), old_len_desc)?;
+    }
+    // k. Return true.
+    return Ok(true);
+  }
+  // 3. Return OrdinaryDefineOwnProperty(A, P, Desc).
+  ordinary_define_own_property(a, p, desc)
+}
+
+fn length_value(desc: &PropertyDescriptor) -> u32 {
+  match desc.value {
+    Some(Value::Number(n)) => *n as u32,
+    _ => panic!("array length is a number"),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-arraycreate
+pub fn array_create(
+  length: f64,
+  proto: Option<Prototype>,
+) -> Result<JsObject, Value> {
+  // 1. If length > 2^32 - 1, throw a RangeError exception.
+  if length > u32::MAX as f64 {
+    return Err(throw_error(ErrorKind::RangeError, "Invalid array length"));
+  }
+  // 2. If proto is not present, set proto to %Array.prototype%.
+  let proto = proto
+    .unwrap_or_else(|| Either::A(current_realm().intrinsic("Array.prototype")));
+  // 3. Let A be ! MakeBasicObject(« [[Prototype]], [[Extensible]] »).
+  // 4. Set A.[[Prototype]] to proto.
+  // 5. Set A.[[DefineOwnProperty]] as specified in 10.4.2.1.
+  let a = JsObject::new(proto, true, &ARRAY_INTERNAL_METHODS);
+  // 6. Perform ! OrdinaryDefineOwnProperty(A, "length", PropertyDescriptor { [[Value]]:
+  //    𝔽(length), [[Writable]]: true, [[Enumerable]]: false, [[Configurable]]: false }).
+  ordinary_define_own_property(
+    &a,
+    &"length".into(),
+    PropertyDescriptor {
+      value: Some(Value::from(length)),
+      writable: Some(JsBoolean::True),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::False),
+    },
+  )?;
+  // 7. Return A.
+  Ok(a)
+}
+
+/// https://tc39.es/ecma262/#sec-arraysetlength
+pub fn array_set_length(
+  a: &JsObject,
+  desc: PropertyDescriptor,
+) -> Result<bool, Value> {
+  let length = PropertyKey::from("length");
+  // 1. If Desc does not have a [[Value]] field, then
+  let value = match &desc.value {
+    Some(value) => value,
+    // a. Return OrdinaryDefineOwnProperty(A, "length", Desc).
+    None => return ordinary_define_own_property(a, &length, desc),
+  };
+  // 2. Let newLenDesc be a copy of Desc.
+  // 3. Let newLen be ℝ(? ToUint32(Desc.[[Value]])).
+  let new_len = value.to_uint32()?;
+  // 4. Let numberLen be ℝ(? ToNumber(Desc.[[Value]])).
+  let number_len = *value.to_number()?;
+  // 5. If SameValueZero(newLen, numberLen) is false, throw a RangeError exception.
+  if new_len as f64 != number_len {
+    return Err(throw_error(ErrorKind::RangeError, "Invalid array length"));
+  }
+  // 6. Set newLenDesc.[[Value]] to newLen.
+  let mut new_len_desc = PropertyDescriptor {
+    value: Some(Value::from(new_len as f64)),
+    ..desc
+  };
+  // 7. Let oldLenDesc be OrdinaryGetOwnProperty(A, "length").
+  // 8. Assert: IsDataDescriptor(oldLenDesc) is true.
+  // 9. Assert: oldLenDesc.[[Configurable]] is false.
+  let old_len_desc = ordinary_get_own_property(a, &length)?
+    .expect("array has a length property");
+  // 10. Let oldLen be oldLenDesc.[[Value]].
+  let old_len = length_value(&old_len_desc);
+  // 11. If newLen ≥ oldLen, then
+  if new_len >= old_len {
+    // a. Return OrdinaryDefineOwnProperty(A, "length", newLenDesc).
+    return ordinary_define_own_property(a, &length, new_len_desc);
+  }
+  // 12. If oldLenDesc.[[Writable]] is false, return false.
+  if old_len_desc.writable == Some(JsBoolean::False) {
+    return Ok(false);
+  }
+  // 13. If newLenDesc does not have a [[Writable]] field or newLenDesc.[[Writable]] is
+  //     true, let newWritable be true.
+  // 14. Else,
+  //   a. NOTE: Setting the [[Writable]] attribute to false is deferred in case any
+  //      elements cannot be deleted.
+  //   b. Let newWritable be false.
+  //   c. Set newLenDesc.[[Writable]] to true.
+  let new_writable = new_len_desc.writable != Some(JsBoolean::False);
+  if !new_writable {
+    new_len_desc.writable = Some(JsBoolean::True);
+  }
+  // 15. Let succeeded be ! OrdinaryDefineOwnProperty(A, "length", newLenDesc).
+  // 16. If succeeded is false, return false.
+  if !ordinary_define_own_property(a, &length, new_len_desc)? {
+    return Ok(false);
+  }
+  // 17. For each own property key P of A such that P is an array index and ! ToUint32(P)
+  //     ≥ newLen, in descending numeric index order, do
+  let mut indices = a
+    .own_property_keys()?
+    .into_iter()
+    .filter_map(|p| p.as_array_index().filter(|index| *index >= new_len))
+    .collect::<Vec<_>>();
+  indices.sort_unstable_by(|x, y| y.cmp(x));
+  for index in indices {
+    // a. Let deleteSucceeded be ! A.[[Delete]](P).
+    // TODO: [[Delete]], this is OrdinaryDelete
+    let p = PropertyKey::from(index.to_string());
+    let delete_succeeded = {
+      let mut properties = a.get_properties_mut();
+      let configurable = properties
+        .get(&p)
+        .is_some_and(|desc| desc.configurable == Some(JsBoolean::True));
+      if configurable {
+        properties.shift_remove(&p);
+      }
+      configurable
+    };
+    // b. If deleteSucceeded is false, then
+    if !delete_succeeded {
+      // i. Set newLenDesc.[[Value]] to ! ToUint32(P) + 1𝔽.
+      // ii. If newWritable is false, set newLenDesc.[[Writable]] to false.
+      // iii. Perform ! OrdinaryDefineOwnProperty(A, "length", newLenDesc).
+      ordinary_define_own_property(
+        a,
+        &length,
+        PropertyDescriptor {
+          value: Some(Value::from(index as f64 + 1.0)),
+          writable: (!new_writable).then_some(JsBoolean::False),
+          get: None,
+          set: None,
+          enumerable: None,
+          configurable: None,
+        },
+      )?;
+      // iv. Return false.
+      return Ok(false);
+    }
+  }
+  // 18. If newWritable is false, then
+  if !new_writable {
+    // a. Set succeeded to ! OrdinaryDefineOwnProperty(A, "length", PropertyDescriptor {
+    //    [[Writable]]: false }).
+    // b. Assert: succeeded is true.
+    ordinary_define_own_property(
+      a,
+      &length,
+      PropertyDescriptor {
+        value: None,
+        writable: Some(JsBoolean::False),
+        get: None,
+        set: None,
+        enumerable: None,
+        configurable: None,
+      },
+    )?;
+  }
+  // 19. Return true.
+  Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::{
+      create_data_property, get, length_of_array_like,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+  };
+
+  fn length(a: &JsObject) -> f64 {
+    length_of_array_like(a).unwrap()
+  }
+
+  #[test]
+  fn index_grows_length() {
+    initialize_host_defined_realm().unwrap();
+    let a = array_create(0.0, None).unwrap();
+    assert!(is_array_exotic_object(&a));
+    assert_eq!(length(&a), 0.0);
+    create_data_property(&a, &"2".into(), Value::from(1.0)).unwrap();
+    assert_eq!(length(&a), 3.0);
+    create_data_property(&a, &"02".into(), Value::from(1.0)).unwrap();
+    assert_eq!(length(&a), 3.0);
+    pop_execution_context();
+  }
+
+  #[test]
+  fn set_length_truncates() {
+    initialize_host_defined_realm().unwrap();
+    let a = array_create(0.0, None).unwrap();
+    for i in 0..3 {
+      create_data_property(&a, &i.to_string().into(), Value::from(1.0))
+        .unwrap();
+    }
+    // the length property isn't configurable
+    assert!(
+      !create_data_property(&a, &"length".into(), Value::from(1.0)).unwrap()
+    );
+    let desc = PropertyDescriptor {
+      value: Some(Value::from(1.0)),
+      writable: None,
+      get: None,
+      set: None,
+      enumerable: None,
+      configurable: None,
+    };
+    assert!(a.define_own_property(&"length".into(), desc).unwrap());
+    assert_eq!(length(&a), 1.0);
+    assert!(matches!(get(&a, &"1".into()).unwrap(), Value::Undefined(_)));
+    let desc = PropertyDescriptor {
+      value: Some(Value::from(1.5)),
+      writable: None,
+      get: None,
+      set: None,
+      enumerable: None,
+      configurable: None,
+    };
+    assert!(a.define_own_property(&"length".into(), desc).is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn create_too_long() {
+    initialize_host_defined_realm().unwrap();
+    let error = array_create(4294967296.0, None).unwrap_err();
+    let name = match error {
+      Value::Object(error) => get(&error, &"name".into()).unwrap(),
+      error => panic!("expect an error, got {:?}", error),
+    };
+    assert!(matches!(name, Value::String(s) if s == "RangeError"));
+    pop_execution_context();
+  }
+}
@@ -0,0 +1,228 @@
+//! https://tc39.es/ecma262/#sec-built-in-function-objects
+
+use crate::{
+  abstract_operations::ordinary_object_internal_methods_and_internal_slots::ORDINARY_INTERNAL_METHODS,
+  agent::{current_realm, pop_execution_context, push_execution_context},
+  executable_code_and_execution_contexts::{
+    execution_context::ExecutionContext, realm::Realm,
+  },
+  helpers::Either,
+  language_types::{
+    object::{InternalMethods, InternalSlot, JsObject, PropertyKey, Prototype},
+    Value,
+  },
+  ordinary_and_exotic_objects_behaviours::ecmascript_function_objects::{
+    set_function_length, set_function_name,
+  },
+};
+
+/// The steps of a built-in function, called with the this value, the
+/// arguments list and the NewTarget, which is `None` for [[Call]].
+pub type BuiltinBehaviour =
+  fn(&Value, &[Value], Option<&JsObject>) -> Result<Value, Value>;
+
+pub static BUILTIN_FUNCTION_INTERNAL_METHODS: InternalMethods =
+  InternalMethods {
+    call: Some(builtin_call),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+pub static BUILTIN_CONSTRUCTOR_INTERNAL_METHODS: InternalMethods =
+  InternalMethods {
+    call: Some(builtin_call),
+    construct: Some(builtin_construct),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+/// https://tc39.es/ecma262/#sec-built-in-function-objects-call-thisargument-argumentslist
+fn builtin_call(
+  f: &JsObject,
+  this_argument: &Value,
+  arguments_list: &[Value],
+) -> Result<Value, Value> {
+  // 1. Return ? BuiltinCallOrConstruct(F, thisArgument, argumentsList, undefined).
+  builtin_call_or_construct(f, this_argument, arguments_list, None)
+}
+
+/// https://tc39.es/ecma262/#sec-built-in-function-objects-construct-argumentslist-newtarget
+fn builtin_construct(
+  f: &JsObject,
+  arguments_list: &[Value],
+  new_target: &JsObject,
+) -> Result<JsObject, Value> {
+  // 1. Let result be ? BuiltinCallOrConstruct(F, uninitialized, argumentsList,
+  //    newTarget).
+  // 2. Assert: result is an Object.
+  // 3. Return result.
+  // TODO: uninitialized this value
+  match builtin_call_or_construct(
+    f,
+    &Value::undefined(),
+    arguments_list,
+    Some(new_target),
+  )? {
+    Value::Object(result) => Ok(result),
+    _ => panic!("built-in constructors return objects"),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-builtincallorconstruct
+fn builtin_call_or_construct(
+  f: &JsObject,
+  this_argument: &Value,
+  arguments_list: &[Value],
+  new_target: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let behaviour = match f.get_internal_slot("Behaviour") {
+    Some(InternalSlot::Behaviour(behaviour)) => behaviour,
+    _ => panic!("built-in function without steps"),
+  };
+  let callee_realm = match f.get_internal_slot("Realm") {
+    Some(InternalSlot::Realm(realm)) => realm,
+    _ => panic!("built-in function without a realm"),
+  };
+  // 1. Let callerContext be the running execution context.
+  // 2. If callerContext is not already suspended, suspend callerContext.
+  // 3. Let calleeContext be a new execution context.
+  // 4. Set the Function of calleeContext to F.
+  // 5. Let calleeRealm be F.[[Realm]].
+  // 6. Set the Realm of calleeContext to calleeRealm.
+  // 7. Set the ScriptOrModule of calleeContext to null.
+  // 8. Perform any necessary implementation-defined initialization of calleeContext.
+  let callee_context = ExecutionContext {
+    function: Some(f.clone()),
+    realm: callee_realm,
+    variable_environment: None,
+    lexical_environment: None,
+  };
+  // 9. Push calleeContext onto the execution context stack; calleeContext is now the
+  //    running execution context.
+  push_execution_context(callee_context);
+  // 10. Let result be the Completion Record that is the result of evaluating F in a
+  //     manner that conforms to the specification of F. If thisArgument is
+  //     uninitialized, the this value is uninitialized; otherwise, thisArgument
+  //     provides the this value. argumentsList provides the named parameters.
+  //     NewTarget provides the NewTarget value.
+  let result = behaviour(this_argument, arguments_list, new_target);
+  // 11. NOTE: If F is defined in this document, “the specification of F” is the
+  //     behaviour specified for it via algorithm steps or other means.
+  // 12. Remove calleeContext from the execution context stack and restore
+  //     callerContext as the running execution context.
+  pop_execution_context();
+  // 13. Return ? result.
+  result
+}
+
+/// https://tc39.es/ecma262/#sec-createbuiltinfunction, without
+/// additionalInternalSlotsList and prefix, `is_constructor` is for the
+/// functions this specification identifies as constructors.
+pub fn create_builtin_function(
+  behaviour: BuiltinBehaviour,
+  length: f64,
+  name: impl Into<PropertyKey>,
+  realm: Option<&Realm>,
+  prototype: Option<Prototype>,
+  is_constructor: bool,
+) -> JsObject {
+  // 1. If realm is not present, set realm to the current Realm Record.
+  let realm = realm.cloned().unwrap_or_else(current_realm);
+  // 2. If prototype is not present, set prototype to
+  //    realm.[[Intrinsics]].[[%Function.prototype%]].
+  let prototype = prototype
+    .unwrap_or_else(|| Either::A(realm.intrinsic("Function.prototype")));
+  // 3. Let internalSlotsList be a List containing the names of all the internal slots
+  //    that 10.3 requires for the built-in function object that is about to be
+  //    created.
+  // 4. Append to internalSlotsList the elements of additionalInternalSlotsList.
+  // 5. Let func be a new built-in function object that, when called, performs the
+  //    action described by behaviour using the provided arguments as the values of the
+  //    corresponding parameters specified by behaviour. The new function object has
+  //    internal slots whose names are the elements of internalSlotsList, and an
+  //    [[InitialName]] internal slot.
+  // 6. Set func.[[Prototype]] to prototype.
+  // 7. Set func.[[Extensible]] to true.
+  let internal_methods = if is_constructor {
+    &BUILTIN_CONSTRUCTOR_INTERNAL_METHODS
+  } else {
+    &BUILTIN_FUNCTION_INTERNAL_METHODS
+  };
+  let func = JsObject::new(prototype, true, internal_methods);
+  func.set_internal_slot("Behaviour", InternalSlot::Behaviour(behaviour));
+  // 8. Set func.[[Realm]] to realm.
+  func.set_internal_slot("Realm", InternalSlot::Realm(realm));
+  // 9. Set func.[[InitialName]] to null.
+  func.set_internal_slot("InitialName", InternalSlot::Value(Value::null()));
+  // 10. Perform SetFunctionLength(func, length).
+  set_function_length(&func, length);
+  // 11. If prefix is not present, then
+  //   a. Perform SetFunctionName(func, name).
+  set_function_name(&func, name.into(), None);
+  // 12. Else,
+  //   a. Perform SetFunctionName(func, name, prefix).
+  // 13. Return func.
+  func
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::{call, get},
+    agent::running_execution_context,
+    executable_code_and_execution_contexts::realm::create_realm,
+  };
+
+  fn sum(
+    _: &Value,
+    arguments: &[Value],
+    _: Option<&JsObject>,
+  ) -> Result<Value, Value> {
+    let mut sum = 0.0;
+    for argument in arguments {
+      sum += *argument.to_number()?;
+    }
+    Ok(Value::from(sum))
+  }
+
+  #[test]
+  fn call_builtin_function() {
+    let realm = create_realm();
+    let f = create_builtin_function(sum, 2.0, "sum", Some(&realm), None, false);
+    assert!(matches!(
+      f.get_prototype_of(),
+      Either::A(proto) if JsObject::equals(&proto, &realm.intrinsic("Function.prototype"))
+    ));
+    let length = get(&f, &"length".into()).unwrap();
+    assert_eq!(*length.to_number().unwrap(), 2.0);
+    let name = get(&f, &"name".into()).unwrap();
+    assert_eq!(name.to_string().unwrap(), "sum");
+
+    let f = Value::from(f);
+    let arguments = [Value::from(1.0), Value::from("2"), Value::from(true)];
+    let result = call(&f, &Value::undefined(), &arguments).unwrap();
+    assert_eq!(*result.to_number().unwrap(), 4.0);
+    assert!(call(
+      &f,
+      &Value::undefined(),
+      &[Value::BigInt(num_bigint::BigInt::from(1).into())]
+    )
+    .is_err());
+    assert!(running_execution_context().is_none());
+  }
+
+  #[test]
+  fn function_prototype() {
+    let realm = create_realm();
+    let function_prototype = Value::from(realm.intrinsic("Function.prototype"));
+    assert!(function_prototype.is_callable());
+    let result = call(
+      &function_prototype,
+      &Value::undefined(),
+      &[Value::from(1.0)],
+    )
+    .unwrap();
+    assert!(matches!(result, Value::Undefined(_)));
+    let name = get(&realm.intrinsic("Function.prototype"), &"name".into());
+    assert_eq!(name.unwrap().to_string().unwrap(), "");
+  }
+}
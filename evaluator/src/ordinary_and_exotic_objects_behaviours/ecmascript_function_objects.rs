@@ -0,0 +1,1297 @@
+//! https://tc39.es/ecma262/#sec-ecmascript-function-objects
+
+use std::rc::Rc;
+
+use ng262_parser::parser::nodes::{Node, NodeType};
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{create_array_from_list, define_property_or_throw},
+    ordinary_object_internal_methods_and_internal_slots::{
+      ordinary_create_from_constructor, ordinary_object_create,
+      ORDINARY_INTERNAL_METHODS,
+    },
+  },
+  agent::{
+    current_realm, pop_execution_context, push_execution_context,
+    running_execution_context, set_lexical_environment,
+    set_variable_environment,
+  },
+  executable_code_and_execution_contexts::{
+    environment_records::{
+      new_declarative_environment, new_function_environment, Environment,
+    },
+    execution_context::ExecutionContext,
+    realm::Realm,
+  },
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    object::{InternalMethods, InternalSlot, JsObject, PropertyKey},
+    string::JsString,
+    Value,
+  },
+  runtime_semantics::evaluation::{
+    evaluate_expression, evaluate_statement_list, unsupported,
+  },
+  specification_types::{
+    completion_record::{Completion, Type},
+    property_descriptor::PropertyDescriptor,
+  },
+  static_semantics::scope_analysis::{
+    bound_names, is_constant_declaration, top_level_lexically_declared_names,
+    top_level_lexically_scoped_declarations, top_level_var_declared_names,
+    top_level_var_scoped_declarations,
+  },
+};
+
+use super::arguments_exotic_objects::create_unmapped_arguments_object;
+
+/// [[ThisMode]]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThisMode {
+  /// `this` refers to the this value of a lexically enclosing function.
+  Lexical,
+  /// The this value is used exactly as provided by an invocation.
+  Strict,
+  /// An undefined or null this value is replaced by the global object.
+  Global,
+}
+
+pub static ECMASCRIPT_FUNCTION_INTERNAL_METHODS: InternalMethods =
+  InternalMethods {
+    call: Some(ecmascript_function_call),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+/// The internal methods of an ECMAScript function after MakeConstructor.
+pub static ECMASCRIPT_CONSTRUCTOR_INTERNAL_METHODS: InternalMethods =
+  InternalMethods {
+    call: Some(ecmascript_function_call),
+    construct: Some(ecmascript_function_construct),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+/// F.[[Environment]]
+pub(crate) fn function_environment(f: &JsObject) -> Environment {
+  match f.get_internal_slot("Environment") {
+    Some(InternalSlot::Environment(env)) => env,
+    _ => panic!("ECMAScript function without an environment"),
+  }
+}
+
+/// F.[[ThisMode]]
+pub(crate) fn function_this_mode(f: &JsObject) -> ThisMode {
+  match f.get_internal_slot("ThisMode") {
+    Some(InternalSlot::ThisMode(this_mode)) => this_mode,
+    _ => panic!("ECMAScript function without a this mode"),
+  }
+}
+
+/// F.[[ECMAScriptCode]], the function node.
+fn function_code(f: &JsObject) -> Rc<Node> {
+  match f.get_internal_slot("ECMAScriptCode") {
+    Some(InternalSlot::Code(code)) => code,
+    _ => panic!("ECMAScript function without code"),
+  }
+}
+
+/// F.[[Realm]]
+fn function_realm(f: &JsObject) -> Realm {
+  match f.get_internal_slot("Realm") {
+    Some(InternalSlot::Realm(realm)) => realm,
+    _ => panic!("ECMAScript function without a realm"),
+  }
+}
+
+/// F.[[Strict]]
+fn function_strict(f: &JsObject) -> bool {
+  matches!(
+    f.get_internal_slot("Strict"),
+    Some(InternalSlot::Value(Value::Boolean(JsBoolean::True)))
+  )
+}
+
+/// The [[FormalParameters]] and [[ECMAScriptCode]] of a function node.
+fn parameters_and_body(code: &Node) -> (&[Node], &[Node]) {
+  match code.node_type() {
+    NodeType::FunctionDeclaration { params, body, .. }
+    | NodeType::FunctionExpression { params, body, .. }
+    | NodeType::ArrowFunctionExpression { params, body, .. } => (params, body),
+    t => unreachable!("expect a function, got {:?}", t),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ecmascript-function-objects-call-thisargument-argumentslist
+fn ecmascript_function_call(
+  f: &JsObject,
+  this_argument: &Value,
+  arguments_list: &[Value],
+) -> Result<Value, Value> {
+  // 1. Let callerContext be the running execution context.
+  // 2. Let calleeContext be PrepareForOrdinaryCall(F, undefined).
+  let callee_context = prepare_for_ordinary_call(f, None);
+  // 3. Assert: calleeContext is now the running execution context.
+  // 4. If F.[[IsClassConstructor]] is true, then
+  //   ...
+  // 5. Perform OrdinaryCallBindThis(F, calleeContext, thisArgument).
+  ordinary_call_bind_this(f, &callee_context, this_argument);
+  // 6. Let result be Completion(OrdinaryCallEvaluateBody(F, argumentsList)).
+  let result = ordinary_call_evaluate_body(f, arguments_list);
+  // 7. Remove calleeContext from the execution context stack and restore
+  //    callerContext as the running execution context.
+  pop_execution_context();
+  match result? {
+    // 8. If result.[[Type]] is return, return result.[[Value]].
+    result if result.r#type() == Type::Return => Ok(
+      result
+        .into_value()
+        .expect("return completions have a value"),
+    ),
+    // 9. ReturnIfAbrupt(result).
+    // 10. Return undefined.
+    _ => Ok(Value::undefined()),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-ecmascript-function-objects-construct-argumentslist-newtarget,
+/// only for base constructors since there are no classes yet.
+fn ecmascript_function_construct(
+  f: &JsObject,
+  arguments_list: &[Value],
+  new_target: &JsObject,
+) -> Result<JsObject, Value> {
+  // 1. Let callerContext be the running execution context.
+  // 2. Let kind be F.[[ConstructorKind]].
+  // 3. If kind is base, then
+  //   a. Let thisArgument be ? OrdinaryCreateFromConstructor(newTarget,
+  //      "%Object.prototype%").
+  let this_argument =
+    ordinary_create_from_constructor(new_target, "Object.prototype", &[])?;
+  // 4. Let calleeContext be PrepareForOrdinaryCall(F, newTarget).
+  let callee_context = prepare_for_ordinary_call(f, Some(new_target.clone()));
+  // 5. Assert: calleeContext is now the running execution context.
+  // 6. If kind is base, then
+  //   a. Perform OrdinaryCallBindThis(F, calleeContext, thisArgument).
+  ordinary_call_bind_this(f, &callee_context, &this_argument.clone().into());
+  //   b. Let initializeResult be Completion(InitializeInstanceElements(
+  //      thisArgument, F)).
+  //   c. If initializeResult is an abrupt completion, then
+  //     i. Remove calleeContext from the execution context stack and restore
+  //        callerContext as the running execution context.
+  //     ii. Return ? initializeResult.
+  // 7. Let constructorEnv be the LexicalEnvironment of calleeContext.
+  let constructor_env = callee_context
+    .lexical_environment
+    .expect("the function environment of the callee");
+  // 8. Let result be Completion(OrdinaryCallEvaluateBody(F, argumentsList)).
+  let result = ordinary_call_evaluate_body(f, arguments_list);
+  // 9. Remove calleeContext from the execution context stack and restore
+  //    callerContext as the running execution context.
+  pop_execution_context();
+  let result = result?;
+  // 10. If result is a return completion, then
+  if result.r#type() == Type::Return {
+    // a. If result.[[Value]] is an Object, return result.[[Value]].
+    if let Some(Value::Object(result)) = result.into_value() {
+      return Ok(result);
+    }
+    // b. If kind is base, return thisArgument.
+    // c. If result.[[Value]] is not undefined, throw a TypeError exception.
+    return Ok(this_argument);
+  }
+  // 11. Else, ReturnIfAbrupt(result).
+  // 12. Let thisBinding be ? constructorEnv.GetThisBinding().
+  // 13. Assert: thisBinding is an Object.
+  // 14. Return thisBinding.
+  match constructor_env.get_this_binding()? {
+    Value::Object(this_binding) => Ok(this_binding),
+    _ => unreachable!("the this binding of a base constructor is an object"),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-prepareforordinarycall
+fn prepare_for_ordinary_call(
+  f: &JsObject,
+  new_target: Option<JsObject>,
+) -> ExecutionContext {
+  // 1. Let callerContext be the running execution context.
+  // 2. Let calleeContext be a new ECMAScript code execution context.
+  // 3. Set the Function of calleeContext to F.
+  // 4. Let calleeRealm be F.[[Realm]].
+  // 5. Set the Realm of calleeContext to calleeRealm.
+  // 6. Set the ScriptOrModule of calleeContext to F.[[ScriptOrModule]].
+  // 7. Let localEnv be NewFunctionEnvironment(F, newTarget).
+  let local_env = new_function_environment(f, new_target);
+  // 8. Set the LexicalEnvironment of calleeContext to localEnv.
+  // 9. Set the VariableEnvironment of calleeContext to localEnv.
+  // 10. Set the PrivateEnvironment of calleeContext to F.[[PrivateEnvironment]].
+  let callee_context = ExecutionContext {
+    function: Some(f.clone()),
+    realm: function_realm(f),
+    variable_environment: Some(local_env.clone()),
+    lexical_environment: Some(local_env),
+  };
+  // 11. If callerContext is not already suspended, suspend callerContext.
+  // 12. Push calleeContext onto the execution context stack; calleeContext is now the
+  //     running execution context.
+  push_execution_context(callee_context.clone());
+  // 13. NOTE: Any exception objects produced after this point are associated with
+  //     calleeRealm.
+  // 14. Return calleeContext.
+  callee_context
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarycallbindthis
+fn ordinary_call_bind_this(
+  f: &JsObject,
+  callee_context: &ExecutionContext,
+  this_argument: &Value,
+) {
+  // 1. Let thisMode be F.[[ThisMode]].
+  let this_mode = function_this_mode(f);
+  // 2. If thisMode is lexical, return unused.
+  if this_mode == ThisMode::Lexical {
+    return;
+  }
+  // 3. Let calleeRealm be F.[[Realm]].
+  let callee_realm = function_realm(f);
+  // 4. Let localEnv be the LexicalEnvironment of calleeContext.
+  let local_env = callee_context
+    .lexical_environment
+    .as_ref()
+    .expect("the function environment of the callee");
+  // 5. If thisMode is strict, let thisValue be thisArgument.
+  let this_value = if this_mode == ThisMode::Strict {
+    this_argument.clone()
+  // 6. Else,
+  } else {
+    match this_argument {
+      // a. If thisArgument is either undefined or null, then
+      Value::Undefined(_) | Value::Null(_) => {
+        // i. Let globalEnv be calleeRealm.[[GlobalEnv]].
+        // ii. Assert: globalEnv is a Global Environment Record.
+        // iii. Let thisValue be globalEnv.[[GlobalThisValue]].
+        // TODO: the global environment
+        Value::from(callee_realm.global_object())
+      }
+      // b. Else,
+      //   i. Let thisValue be ! ToObject(thisArgument).
+      //   ii. NOTE: ToObject produces wrapper objects using calleeRealm.
+      this_argument => Value::from(
+        this_argument
+          .to_object()
+          .expect("ToObject of neither undefined nor null"),
+      ),
+    }
+  };
+  // 7. Assert: localEnv is a Function Environment Record.
+  // 8. Assert: The next step never returns an abrupt completion because
+  //    localEnv.[[ThisBindingStatus]] is not initialized.
+  // 9. Perform ! localEnv.BindThisValue(thisValue).
+  local_env
+    .bind_this_value(this_value)
+    .expect("the this binding is uninitialized");
+  // 10. Return unused.
+}
+
+/// Whether a formal parameter binds an identifier, with or without a default
+/// value, or is a rest identifier, i.e. it isn't a binding pattern.
+pub(crate) fn is_identifier_parameter(formal: &Node) -> bool {
+  let identifier = match formal.node_type() {
+    NodeType::AssignmentPattern { left, .. } => left,
+    NodeType::RestElement { argument } => argument,
+    _ => formal,
+  };
+  matches!(identifier.node_type(), NodeType::BindingIdentifier { .. })
+}
+
+/// https://tc39.es/ecma262/#sec-ordinarycallevaluatebody
+fn ordinary_call_evaluate_body(
+  f: &JsObject,
+  arguments_list: &[Value],
+) -> Result<Completion, Value> {
+  // 1. Return ? EvaluateBody of F.[[ECMAScriptCode]] with arguments F and
+  //    argumentsList.
+  // https://tc39.es/ecma262/#sec-runtime-semantics-evaluatefunctionbody
+  // 1. Perform ? FunctionDeclarationInstantiation(functionObject, argumentsList).
+  function_declaration_instantiation(f, arguments_list)?;
+  let code = function_code(f);
+  match code.node_type() {
+    // https://tc39.es/ecma262/#sec-runtime-semantics-evaluateconcisebody
+    NodeType::ArrowFunctionExpression {
+      body,
+      expression: true,
+      ..
+    } => {
+      // 2. Return ? Evaluation of ExpressionBody.
+      // https://tc39.es/ecma262/#sec-arrow-function-definitions-runtime-semantics-evaluation
+      // ExpressionBody : AssignmentExpression
+      // 1. Let exprRef be ? Evaluation of AssignmentExpression.
+      // 2. Let exprValue be ? GetValue(exprRef).
+      // 3. Return Completion Record { [[Type]]: return, [[Value]]: exprValue,
+      //    [[Target]]: empty }.
+      Ok(Completion::r#return(evaluate_expression(&body[0])?))
+    }
+    _ => {
+      // 2. Return ? Evaluation of FunctionStatementList.
+      let (_, body) = parameters_and_body(&code);
+      evaluate_statement_list(body)
+    }
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-functiondeclarationinstantiation, the
+/// parameters are identifiers with or without a default value, or a rest
+/// identifier.
+fn function_declaration_instantiation(
+  func: &JsObject,
+  arguments_list: &[Value],
+) -> Result<(), Value> {
+  // 1. Let calleeContext be the running execution context.
+  let callee_context = running_execution_context().expect("the callee context");
+  // 2. Let code be func.[[ECMAScriptCode]].
+  let code = function_code(func);
+  let (formals, code) = parameters_and_body(&code);
+  // 3. Let strict be func.[[Strict]].
+  let strict = function_strict(func);
+  // 4. Let formals be func.[[FormalParameters]].
+  // 5. Let parameterNames be the BoundNames of formals.
+  let parameter_names: Vec<&str> =
+    formals.iter().flat_map(bound_names).collect();
+  // 6. If parameterNames has any duplicate entries, let hasDuplicates be true.
+  //    Otherwise, let hasDuplicates be false.
+  let has_duplicates = parameter_names
+    .iter()
+    .enumerate()
+    .any(|(i, name)| parameter_names[..i].contains(name));
+  // TODO: binding patterns
+  if let Some(pattern) = formals
+    .iter()
+    .find(|formal| !is_identifier_parameter(formal))
+  {
+    return Err(unsupported(pattern));
+  }
+  // 7. Let simpleParameterList be IsSimpleParameterList of formals.
+  let simple_parameter_list = formals.iter().all(|formal| {
+    matches!(formal.node_type(), NodeType::BindingIdentifier { .. })
+  });
+  // 8. Let hasParameterExpressions be ContainsExpression of formals.
+  let has_parameter_expressions = formals.iter().any(|formal| {
+    matches!(formal.node_type(), NodeType::AssignmentPattern { .. })
+  });
+  // 9. Let varNames be the VarDeclaredNames of code.
+  let var_names = top_level_var_declared_names(code);
+  // 10. Let varDeclarations be the VarScopedDeclarations of code.
+  let var_declarations = top_level_var_scoped_declarations(code);
+  // 11. Let lexicalNames be the LexicallyDeclaredNames of code.
+  let lexical_names = top_level_lexically_declared_names(code);
+  // 12. Let functionNames be a new empty List.
+  let mut function_names = Vec::new();
+  // 13. Let functionsToInitialize be a new empty List.
+  let mut functions_to_initialize = Vec::new();
+  // 14. For each element d of varDeclarations, in reverse List order, do
+  for d in var_declarations.into_iter().rev() {
+    // a. If d is neither a VariableDeclaration nor a ForBinding nor a
+    //    BindingIdentifier, then
+    if let NodeType::FunctionDeclaration { .. } = d.node_type() {
+      // i. Assert: d is either a FunctionDeclaration, a GeneratorDeclaration, an
+      //    AsyncFunctionDeclaration, or an AsyncGeneratorDeclaration.
+      // ii. Let fn be the sole element of the BoundNames of d.
+      let r#fn = bound_names(d)[0];
+      // iii. If functionNames does not contain fn, then
+      if !function_names.contains(&r#fn) {
+        // 1. Insert fn as the first element of functionNames.
+        function_names.insert(0, r#fn);
+        // 2. NOTE: If there are multiple function declarations for the same name,
+        //    the last declaration is used.
+        // 3. Insert d as the first element of functionsToInitialize.
+        functions_to_initialize.insert(0, d);
+      }
+    }
+  }
+  // 15. Let argumentsObjectNeeded be true.
+  // 16. If func.[[ThisMode]] is lexical, then
+  //   a. NOTE: Arrow functions never have an arguments object.
+  //   b. Set argumentsObjectNeeded to false.
+  // 17. Else if parameterNames contains "arguments", then
+  //   a. Set argumentsObjectNeeded to false.
+  // 18. Else if hasParameterExpressions is false, then
+  //   a. If functionNames contains "arguments" or lexicalNames contains
+  //      "arguments", then
+  //     i. Set argumentsObjectNeeded to false.
+  let arguments_object_needed = function_this_mode(func) != ThisMode::Lexical
+    && !parameter_names.contains(&"arguments")
+    && (has_parameter_expressions
+      || !function_names.contains(&"arguments")
+        && !lexical_names.contains(&"arguments"));
+  let callee_env = callee_context
+    .lexical_environment
+    .expect("the function environment of the callee");
+  // 19. If strict is true or hasParameterExpressions is false, then
+  let env = if strict || !has_parameter_expressions {
+    // a. NOTE: Only a single Environment Record is needed for the parameters, since
+    //    calls to eval in strict mode code cannot create new bindings which are
+    //    visible outside of the eval.
+    // b. Let env be the LexicalEnvironment of calleeContext.
+    callee_env
+  // 20. Else,
+  } else {
+    // a. NOTE: A separate Environment Record is needed to ensure that bindings
+    //    created by direct eval calls in the formal parameter list are outside
+    //    the environment where parameters are declared.
+    // b. Let calleeEnv be the LexicalEnvironment of calleeContext.
+    // c. Let env be NewDeclarativeEnvironment(calleeEnv).
+    let env = new_declarative_environment(Some(callee_env));
+    // d. Assert: The VariableEnvironment of calleeContext is calleeEnv.
+    // e. Set the LexicalEnvironment of calleeContext to env.
+    set_lexical_environment(env.clone());
+    env
+  };
+  // 21. For each String paramName of parameterNames, do
+  for param_name in &parameter_names {
+    // a. Let alreadyDeclared be ! env.HasBinding(paramName).
+    // b. NOTE: Early errors ensure that duplicate parameter names can only occur in
+    //    non-strict functions that do not have parameter default values or rest
+    //    parameters.
+    // c. If alreadyDeclared is false, then
+    if !env.has_binding(param_name)? {
+      // i. Perform ! env.CreateMutableBinding(paramName, false).
+      env.create_mutable_binding(param_name, false)?;
+      // ii. If hasDuplicates is true, then
+      if has_duplicates {
+        // 1. Perform ! env.InitializeBinding(paramName, undefined).
+        env.initialize_binding(param_name, Value::undefined())?;
+      }
+    }
+  }
+  // 22. If argumentsObjectNeeded is true, then
+  let parameter_bindings = if arguments_object_needed {
+    // a. If strict is true or simpleParameterList is false, then
+    //   i. Let ao be CreateUnmappedArgumentsObject(argumentsList).
+    // b. Else,
+    //   i. NOTE: A mapped argument object is only provided for non-strict functions
+    //      that don't have a rest parameter, any parameter default value
+    //      initializers, or any destructured parameters.
+    //   ii. Let ao be CreateMappedArgumentsObject(func, formals, argumentsList, env).
+    let ao = if strict || !simple_parameter_list {
+      create_unmapped_arguments_object(arguments_list)
+    } else {
+      // TODO: mapped arguments objects
+      create_unmapped_arguments_object(arguments_list)
+    };
+    // c. If strict is true, then
+    if strict {
+      // i. Perform ! env.CreateImmutableBinding("arguments", false).
+      // ii. NOTE: In strict mode code early errors prevent attempting to assign to
+      //     this binding, so its mutability is not observable.
+      env.create_immutable_binding("arguments", false);
+    // d. Else,
+    } else {
+      // i. Perform ! env.CreateMutableBinding("arguments", false).
+      env.create_mutable_binding("arguments", false)?;
+    }
+    // e. Perform ! env.InitializeBinding("arguments", ao).
+    env.initialize_binding("arguments", Value::from(ao))?;
+    // f. Let parameterBindings be the list-concatenation of parameterNames and
+    //    « "arguments" ».
+    let mut parameter_bindings = parameter_names.clone();
+    parameter_bindings.push("arguments");
+    parameter_bindings
+  // 23. Else,
+  } else {
+    // a. Let parameterBindings be parameterNames.
+    parameter_names.clone()
+  };
+  // 24. Let iteratorRecord be CreateListIteratorRecord(argumentsList).
+  // 25. If hasDuplicates is true, then
+  //   a. Perform ? IteratorBindingInitialization of formals with arguments
+  //      iteratorRecord and undefined.
+  // 26. Else,
+  //   a. Perform ? IteratorBindingInitialization of formals with arguments
+  //      iteratorRecord and env.
+  // the iterator of the arguments list is its index
+  for (i, formal) in formals.iter().enumerate() {
+    let (name, v) = match formal.node_type() {
+      // SingleNameBinding : BindingIdentifier Initializer(opt)
+      NodeType::BindingIdentifier { name } => (
+        name,
+        arguments_list
+          .get(i)
+          .cloned()
+          .unwrap_or_else(Value::undefined),
+      ),
+      NodeType::AssignmentPattern { left, right } => {
+        let name = match left.node_type() {
+          NodeType::BindingIdentifier { name } => name,
+          t => unreachable!("expect BindingIdentifier, got {:?}", t),
+        };
+        // 1. Let bindingId be StringValue of BindingIdentifier.
+        // 2. Let lhs be ? ResolveBinding(bindingId, environment).
+        // 3. Let v be undefined.
+        // 4. If iteratorRecord.[[Done]] is false, then
+        //   a. Let next be ? IteratorStepValue(iteratorRecord).
+        //   b. If next is not done, then
+        //     i. Set v to next.
+        // 5. If Initializer is present and v is undefined, then
+        //   a. If IsAnonymousFunctionDefinition(Initializer) is true, then
+        //     i. Set v to ? NamedEvaluation of Initializer with argument
+        //        bindingId.
+        //   b. Else,
+        //     i. Let defaultValue be ? Evaluation of Initializer.
+        //     ii. Set v to ? GetValue(defaultValue).
+        // TODO: NamedEvaluation
+        let v = match arguments_list.get(i) {
+          Some(v) if !matches!(v, Value::Undefined(_)) => v.clone(),
+          _ => evaluate_expression(right)?,
+        };
+        // 6. If environment is undefined, return ? PutValue(lhs, v).
+        // 7. Return ? InitializeReferencedBinding(lhs, v).
+        (name, v)
+      }
+      // BindingRestElement : `...` BindingIdentifier
+      NodeType::RestElement { argument } => {
+        let name = match argument.node_type() {
+          NodeType::BindingIdentifier { name } => name,
+          t => unreachable!("expect BindingIdentifier, got {:?}", t),
+        };
+        // 1. Let lhs be ? ResolveBinding(StringValue of BindingIdentifier,
+        //    environment).
+        // 2. Let A be ! ArrayCreate(0).
+        // 3. Let n be 0.
+        // 4. Repeat,
+        //   a. Let next be done.
+        //   b. If iteratorRecord.[[Done]] is false, then
+        //     i. Set next to ? IteratorStepValue(iteratorRecord).
+        //   c. If next is done, then
+        //     i. If environment is undefined, return ? PutValue(lhs, A).
+        //     ii. Return ? InitializeReferencedBinding(lhs, A).
+        //   d. Perform ! CreateDataPropertyOrThrow(A, ! ToString(𝔽(n)), next).
+        //   e. Set n to n + 1.
+        let rest = arguments_list.get(i..).unwrap_or_default();
+        (name, Value::from(create_array_from_list(rest)))
+      }
+      t => unreachable!("expect an identifier parameter, got {:?}", t),
+    };
+    if has_duplicates {
+      // the last parameter with the same name wins
+      env.set_mutable_binding(name, v, strict)?;
+    } else {
+      env.initialize_binding(name, v)?;
+    }
+  }
+  // 27. If hasParameterExpressions is false, then
+  let var_env = if !has_parameter_expressions {
+    // a. NOTE: Only a single Environment Record is needed for the parameters and
+    //    top-level vars.
+    // b. Let instantiatedVarNames be a copy of the List parameterBindings.
+    let mut instantiated_var_names = parameter_bindings;
+    // c. For each element n of varNames, do
+    for n in var_names {
+      // i. If instantiatedVarNames does not contain n, then
+      if !instantiated_var_names.contains(&n) {
+        // 1. Append n to instantiatedVarNames.
+        instantiated_var_names.push(n);
+        // 2. Perform ! env.CreateMutableBinding(n, false).
+        env.create_mutable_binding(n, false)?;
+        // 3. Perform ! env.InitializeBinding(n, undefined).
+        env.initialize_binding(n, Value::undefined())?;
+      }
+    }
+    // d. Let varEnv be env.
+    env
+  // 28. Else,
+  } else {
+    // a. NOTE: A separate Environment Record is needed to ensure that closures
+    //    created by expressions in the formal parameter list do not have
+    //    visibility of declarations in the function body.
+    // b. Let varEnv be NewDeclarativeEnvironment(env).
+    let var_env = new_declarative_environment(Some(env.clone()));
+    // c. Set the VariableEnvironment of calleeContext to varEnv.
+    set_variable_environment(var_env.clone());
+    // d. Let instantiatedVarNames be a new empty List.
+    let mut instantiated_var_names = Vec::new();
+    // e. For each element n of varNames, do
+    for n in var_names {
+      // i. If instantiatedVarNames does not contain n, then
+      if !instantiated_var_names.contains(&n) {
+        // 1. Append n to instantiatedVarNames.
+        instantiated_var_names.push(n);
+        // 2. Perform ! varEnv.CreateMutableBinding(n, false).
+        var_env.create_mutable_binding(n, false)?;
+        // 3. If parameterBindings does not contain n, or if functionNames
+        //    contains n, let initialValue be undefined.
+        // 4. Else,
+        //   a. Let initialValue be ! env.GetBindingValue(n, false).
+        let initial_value =
+          if !parameter_bindings.contains(&n) || function_names.contains(&n) {
+            Value::undefined()
+          } else {
+            env.get_binding_value(n, false)?
+          };
+        // 5. Perform ! varEnv.InitializeBinding(n, initialValue).
+        var_env.initialize_binding(n, initial_value)?;
+        // 6. NOTE: A var with the same name as a formal parameter initially
+        //    has the same value as the corresponding initialized parameter.
+      }
+    }
+    var_env
+  };
+  // 29. NOTE: Annex B.3.2.1 adds additional steps at this point.
+  // 30. If strict is false, then
+  let lex_env = if !strict {
+    // a. Let lexEnv be NewDeclarativeEnvironment(varEnv).
+    // b. NOTE: Non-strict functions use a separate Environment Record for top-level
+    //    lexical declarations so that a direct eval can determine whether any var
+    //    scoped declarations introduced by the eval code conflict with pre-existing
+    //    top-level lexically scoped declarations. This is not needed for strict
+    //    functions because a strict direct eval always places all declarations into
+    //    a new Environment Record.
+    new_declarative_environment(Some(var_env.clone()))
+  // 31. Else,
+  } else {
+    // a. Let lexEnv be varEnv.
+    var_env.clone()
+  };
+  // 32. Set the LexicalEnvironment of calleeContext to lexEnv.
+  set_lexical_environment(lex_env.clone());
+  // 33. Let lexDeclarations be the LexicallyScopedDeclarations of code.
+  // 34. For each element d of lexDeclarations, do
+  for d in top_level_lexically_scoped_declarations(code) {
+    // a. NOTE: A lexically declared name cannot be the same as a
+    //    function/generator declaration, formal parameter, or a var name. Lexically
+    //    declared names are only instantiated here but not initialized.
+    // b. For each element dn of the BoundNames of d, do
+    for dn in bound_names(d) {
+      // i. If IsConstantDeclaration of d is true, then
+      if is_constant_declaration(d) {
+        // 1. Perform ! lexEnv.CreateImmutableBinding(dn, true).
+        lex_env.create_immutable_binding(dn, true);
+      // ii. Else,
+      } else {
+        // 1. Perform ! lexEnv.CreateMutableBinding(dn, false).
+        lex_env.create_mutable_binding(dn, false)?;
+      }
+    }
+  }
+  // 35. Let privateEnv be the PrivateEnvironment of calleeContext.
+  // 36. For each Parse Node f of functionsToInitialize, do
+  for f in functions_to_initialize {
+    // a. Let fn be the sole element of the BoundNames of f.
+    let r#fn = bound_names(f)[0];
+    // b. Let fo be InstantiateFunctionObject of f with arguments lexEnv and
+    //    privateEnv.
+    let fo = instantiate_function_object(f, &lex_env);
+    // c. Perform ! varEnv.SetMutableBinding(fn, fo, false).
+    var_env
+      .set_mutable_binding(r#fn, Value::from(fo), false)
+      .expect("the var binding of a function");
+  }
+  // 37. Return unused.
+  Ok(())
+}
+
+/// https://tc39.es/ecma262/#sec-ordinaryfunctioncreate, the function node
+/// has the parameters and the body, `lexical_this` is for arrow functions.
+pub fn ordinary_function_create(
+  function_prototype: JsObject,
+  code: Rc<Node>,
+  lexical_this: bool,
+  env: Environment,
+) -> JsObject {
+  // 1. Let internalSlotsList be the internal slots listed in Table 30.
+  // 2. Let F be OrdinaryObjectCreate(functionPrototype, internalSlotsList).
+  // 3. Set F.[[Call]] to the definition specified in 10.2.1.
+  let f = JsObject::new(
+    Either::A(function_prototype),
+    true,
+    &ECMASCRIPT_FUNCTION_INTERNAL_METHODS,
+  );
+  // 4. Set F.[[SourceText]] to sourceText.
+  // 5. Set F.[[FormalParameters]] to ParameterList.
+  // 6. Set F.[[ECMAScriptCode]] to Body.
+  let (params, _) = parameters_and_body(&code);
+  // 21. Let len be the ExpectedArgumentCount of ParameterList.
+  // the parameters before the first one with an initializer or the rest one
+  let len = params
+    .iter()
+    .take_while(|param| {
+      !matches!(
+        param.node_type(),
+        NodeType::AssignmentPattern { .. } | NodeType::RestElement { .. }
+      )
+    })
+    .count();
+  // 7. If the source text matched by Body is strict mode code, let Strict be true;
+  //    else let Strict be false.
+  // a function with a Use Strict Directive is a strict node
+  let strict = code.is_strict();
+  f.set_internal_slot("ECMAScriptCode", InternalSlot::Code(code));
+  // 8. Set F.[[Strict]] to Strict.
+  f.set_internal_slot("Strict", InternalSlot::Value(Value::from(strict)));
+  // 9. If thisMode is lexical-this, set F.[[ThisMode]] to lexical.
+  // 10. Else if Strict is true, set F.[[ThisMode]] to strict.
+  // 11. Else, set F.[[ThisMode]] to global.
+  let this_mode = if lexical_this {
+    ThisMode::Lexical
+  } else if strict {
+    ThisMode::Strict
+  } else {
+    ThisMode::Global
+  };
+  f.set_internal_slot("ThisMode", InternalSlot::ThisMode(this_mode));
+  // 12. Set F.[[IsClassConstructor]] to false.
+  // 13. Set F.[[Environment]] to env.
+  f.set_internal_slot("Environment", InternalSlot::Environment(env));
+  // 14. Set F.[[PrivateEnvironment]] to privateEnv.
+  // 15. Set F.[[ScriptOrModule]] to GetActiveScriptOrModule().
+  // 16. Set F.[[Realm]] to the current Realm Record.
+  f.set_internal_slot("Realm", InternalSlot::Realm(current_realm()));
+  // 17. Set F.[[HomeObject]] to undefined.
+  // 18. Set F.[[Fields]] to a new empty List.
+  // 19. Set F.[[PrivateMethods]] to a new empty List.
+  // 20. Set F.[[ClassFieldInitializerName]] to empty.
+  // 22. Perform SetFunctionLength(F, len).
+  set_function_length(&f, len as f64);
+  // 23. Return F.
+  f
+}
+
+/// https://tc39.es/ecma262/#sec-runtime-semantics-instantiatefunctionobject
+pub fn instantiate_function_object(node: &Node, env: &Environment) -> JsObject {
+  // https://tc39.es/ecma262/#sec-runtime-semantics-instantiateordinaryfunctionobject
+  // 1. Let name be StringValue of BindingIdentifier.
+  let name = bound_names(node)[0];
+  // 2. Let sourceText be the source text matched by FunctionDeclaration.
+  // 3. Let F be OrdinaryFunctionCreate(%Function.prototype%, sourceText,
+  //    FormalParameters, FunctionBody, non-lexical-this, env, privateEnv).
+  let f = ordinary_function_create(
+    current_realm().intrinsic("Function.prototype"),
+    Rc::new(node.clone()),
+    false,
+    env.clone(),
+  );
+  // 4. Perform SetFunctionName(F, name).
+  set_function_name(&f, name.into(), None);
+  // 5. Perform MakeConstructor(F).
+  make_constructor(&f);
+  // 6. Return F.
+  f
+}
+
+/// https://tc39.es/ecma262/#sec-makeconstructor, with the default
+/// writablePrototype and prototype.
+pub fn make_constructor(f: &JsObject) {
+  // 1. If F is an ECMAScript function object, then
+  //   a. Assert: IsConstructor(F) is false.
+  //   b. Assert: F is an extensible object that does not have a "prototype"
+  //      own property.
+  //   c. Set F.[[Construct]] to the definition specified in 10.2.2.
+  // 2. Else,
+  //   a. Set F.[[Construct]] to the definition specified in 10.3.2.
+  debug_assert!(f.get_construct().is_none());
+  f.set_internal_methods(&ECMASCRIPT_CONSTRUCTOR_INTERNAL_METHODS);
+  // 3. Set F.[[ConstructorKind]] to base.
+  // every constructor is a base one until there are classes
+  // 4. If writablePrototype is not present, set writablePrototype to true.
+  // 5. If prototype is not present, then
+  //   a. Set prototype to OrdinaryObjectCreate(%Object.prototype%).
+  let prototype = ordinary_object_create(
+    Either::A(current_realm().intrinsic("Object.prototype")),
+    &[],
+  );
+  //   b. Perform ! DefinePropertyOrThrow(prototype, "constructor",
+  //      PropertyDescriptor { [[Value]]: F, [[Writable]]: writablePrototype,
+  //      [[Enumerable]]: false, [[Configurable]]: true }).
+  define_property_or_throw(
+    &prototype,
+    &"constructor".into(),
+    PropertyDescriptor {
+      value: Some(Value::from(f.clone())),
+      writable: Some(JsBoolean::True),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::True),
+    },
+  )
+  .expect("the prototype is a new ordinary object");
+  // 6. Perform ! DefinePropertyOrThrow(F, "prototype", PropertyDescriptor {
+  //    [[Value]]: prototype, [[Writable]]: writablePrototype, [[Enumerable]]:
+  //    false, [[Configurable]]: false }).
+  define_property_or_throw(
+    f,
+    &"prototype".into(),
+    PropertyDescriptor {
+      value: Some(Value::from(prototype)),
+      writable: Some(JsBoolean::True),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::False),
+    },
+  )
+  .expect("F is extensible without a prototype property");
+  // 7. Return unused.
+}
+
+/// https://tc39.es/ecma262/#sec-runtime-semantics-instantiateordinaryfunctionexpression
+pub fn instantiate_ordinary_function_expression(node: &Node) -> JsObject {
+  // 1. If name is not present, set name to "".
+  // TODO: NamedEvaluation
+  // 2. Let env be the LexicalEnvironment of the running execution context.
+  let env = running_execution_context()
+    .and_then(|context| context.lexical_environment)
+    .expect("the LexicalEnvironment of the running execution context");
+  // 3. Let privateEnv be the running execution context's PrivateEnvironment.
+  // 4. Let sourceText be the source text matched by FunctionExpression.
+  let id = match node.node_type() {
+    NodeType::FunctionExpression { id, .. } => id,
+    t => unreachable!("expect FunctionExpression, got {:?}", t),
+  };
+  let name = match id.as_deref().map(Node::node_type) {
+    Some(NodeType::BindingIdentifier { name }) => name,
+    // 5. Let closure be OrdinaryFunctionCreate(%Function.prototype%,
+    //    sourceText, FormalParameters, FunctionBody, non-lexical-this, env,
+    //    privateEnv).
+    // 6. Perform SetFunctionName(closure, name).
+    // 7. Perform MakeConstructor(closure).
+    // 8. Return closure.
+    _ => {
+      let closure = ordinary_function_create(
+        current_realm().intrinsic("Function.prototype"),
+        Rc::new(node.clone()),
+        false,
+        env,
+      );
+      set_function_name(&closure, "".into(), None);
+      make_constructor(&closure);
+      return closure;
+    }
+  };
+  // FunctionExpression : `function` BindingIdentifier `(` FormalParameters `)`
+  //   `{` FunctionBody `}`
+  // 1. Assert: name is not present.
+  // 2. Set name to the StringValue of BindingIdentifier.
+  // 3. Let outerEnv be the running execution context's LexicalEnvironment.
+  // 4. Let funcEnv be NewDeclarativeEnvironment(outerEnv).
+  let func_env = new_declarative_environment(Some(env));
+  // 5. Perform ! funcEnv.CreateImmutableBinding(name, false).
+  func_env.create_immutable_binding(name, false);
+  // 6. Let privateEnv be the running execution context's PrivateEnvironment.
+  // 7. Let sourceText be the source text matched by FunctionExpression.
+  // 8. Let closure be OrdinaryFunctionCreate(%Function.prototype%, sourceText,
+  //    FormalParameters, FunctionBody, non-lexical-this, funcEnv, privateEnv).
+  let closure = ordinary_function_create(
+    current_realm().intrinsic("Function.prototype"),
+    Rc::new(node.clone()),
+    false,
+    func_env.clone(),
+  );
+  // 9. Perform SetFunctionName(closure, name).
+  set_function_name(&closure, name.as_str().into(), None);
+  // 10. Perform MakeConstructor(closure).
+  make_constructor(&closure);
+  // 11. Perform ! funcEnv.InitializeBinding(name, closure).
+  func_env
+    .initialize_binding(name, Value::from(closure.clone()))
+    .expect("the binding of the function name");
+  // 12. Return closure.
+  closure
+}
+
+/// https://tc39.es/ecma262/#sec-runtime-semantics-instantiatearrowfunctionexpression
+pub fn instantiate_arrow_function_expression(node: &Node) -> JsObject {
+  // 1. If name is not present, set name to "".
+  // TODO: NamedEvaluation
+  // 2. Let env be the LexicalEnvironment of the running execution context.
+  let env = running_execution_context()
+    .and_then(|context| context.lexical_environment)
+    .expect("the LexicalEnvironment of the running execution context");
+  // 3. Let privateEnv be the running execution context's PrivateEnvironment.
+  // 4. Let sourceText be the source text matched by ArrowFunction.
+  // 5. Let closure be OrdinaryFunctionCreate(%Function.prototype%, sourceText,
+  //    ArrowParameters, ConciseBody, lexical-this, env, privateEnv).
+  let closure = ordinary_function_create(
+    current_realm().intrinsic("Function.prototype"),
+    Rc::new(node.clone()),
+    true,
+    env,
+  );
+  // 6. Perform SetFunctionName(closure, name).
+  set_function_name(&closure, "".into(), None);
+  // 7. Return closure.
+  closure
+}
+
+/// https://tc39.es/ecma262/#sec-setfunctionname
+pub fn set_function_name(
+  f: &JsObject,
+  name: PropertyKey,
+  prefix: Option<&str>,
+) {
+  // 1. Assert: F is an extensible object that does not have a "name" own property.
+  // 2. If name is a Symbol, then
+  //   a. Let description be name's [[Description]] value.
+  //   b. If description is undefined, set name to the empty String.
+  //   c. Else, set name to the string-concatenation of "[", description, and "]".
+  // 3. Else if name is a Private Name, then
+  //   a. Set name to name.[[Description]].
+  let mut name: JsString = match name {
+    PropertyKey::String(name) => name,
+    PropertyKey::Symbol(name) => match name.description() {
+      Some(description) => JsString::from("[") + description + "]",
+      None => JsString::default(),
+    },
+  };
+  // 4. If F has an [[InitialName]] internal slot, then
+  //   a. Set F.[[InitialName]] to name.
+  if f.has_internal_slot("InitialName") {
+    f.set_internal_slot(
+      "InitialName",
+      InternalSlot::Value(Value::from(name.clone())),
+    );
+  }
+  // 5. If prefix is present, then
+  if let Some(prefix) = prefix {
+    // a. Set name to the string-concatenation of prefix, the code unit 0x0020 (SPACE),
+    //    and name.
+    name = JsString::from(prefix) + " " + &name;
+    // b. If F has an [[InitialName]] internal slot, then
+    //   i. Optionally, set F.[[InitialName]] to name.
+  }
+  // 6. Perform ! DefinePropertyOrThrow(F, "name", PropertyDescriptor { [[Value]]: name,
+  //    [[Writable]]: false, [[Enumerable]]: false, [[Configurable]]: true }).
+  define_property_or_throw(
+    f,
+    &"name".into(),
+    PropertyDescriptor {
+      value: Some(Value::from(name)),
+      writable: Some(JsBoolean::False),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::True),
+    },
+  )
+  .expect("F is extensible without a name property");
+  // 7. Return unused.
+}
+
+/// https://tc39.es/ecma262/#sec-setfunctionlength
+pub fn set_function_length(f: &JsObject, length: f64) {
+  // 1. Assert: F is an extensible object that does not have a "length" own property.
+  // 2. Perform ! DefinePropertyOrThrow(F, "length", PropertyDescriptor { [[Value]]:
+  //    𝔽(length), [[Writable]]: false, [[Enumerable]]: false, [[Configurable]]: true }).
+  define_property_or_throw(
+    f,
+    &"length".into(),
+    PropertyDescriptor {
+      value: Some(Value::from(length)),
+      writable: Some(JsBoolean::False),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: Some(JsBoolean::True),
+    },
+  )
+  .expect("F is extensible without a length property");
+  // 3. Return unused.
+}
+
+#[cfg(test)]
+mod tests {
+  use ng262_parser::parser::Parser;
+
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::{
+      construct, create_data_property, get,
+    },
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    language_types::{null::JsNull, symbol::JsSymbol},
+    test_helpers::error_name,
+  };
+
+  /// Instantiates the function declarations of a script in a new
+  /// declarative environment, the first one is returned.
+  fn function(source: &'static str) -> JsObject {
+    let script = Parser::parse_script(source, None).unwrap();
+    let body = match script.node_type() {
+      NodeType::Script { body } => body,
+      _ => unreachable!(),
+    };
+    instantiate_function_object(&body[0], &new_declarative_environment(None))
+  }
+
+  fn number(value: Value) -> f64 {
+    match value {
+      Value::Number(n) => *n,
+      v => panic!("expect a number, got {:?}", v),
+    }
+  }
+
+  #[test]
+  fn symbol_function_names() {
+    let name = |key: JsSymbol, prefix| {
+      let f = ordinary_object_create(Either::B(JsNull), &[]);
+      set_function_name(&f, key.into(), prefix);
+      get(&f, &"name".into()).unwrap().to_string().unwrap()
+    };
+    assert_eq!(name(JsSymbol::iterator(), None), "[Symbol.iterator]");
+    assert_eq!(
+      name(JsSymbol::iterator(), Some("get")),
+      "get [Symbol.iterator]"
+    );
+    assert_eq!(name(JsSymbol::new(None), None), "");
+  }
+
+  #[test]
+  fn call_ecmascript_function() {
+    initialize_host_defined_realm().unwrap();
+    let f = function("function f(a) { return a; }");
+    assert_eq!(get(&f, &"name".into()).unwrap().to_string().unwrap(), "f");
+    assert_eq!(number(get(&f, &"length".into()).unwrap()), 1.0);
+    let g = function("function g(a, b = 1, c) {}");
+    assert_eq!(number(get(&g, &"length".into()).unwrap()), 1.0);
+    let result = f.call(&Value::undefined(), &[Value::from(1.0)]).unwrap();
+    assert_eq!(number(result), 1.0);
+    // missing arguments are undefined and the completion without a return
+    // is undefined too
+    let result = f.call(&Value::undefined(), &[]).unwrap();
+    assert!(matches!(result, Value::Undefined(_)));
+    let f = function("function f() { 1; }");
+    let result = f.call(&Value::undefined(), &[]).unwrap();
+    assert!(matches!(result, Value::Undefined(_)));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn function_declaration_instantiation() {
+    initialize_host_defined_realm().unwrap();
+    // vars and functions are hoisted, the last function declaration wins
+    let f = function(
+      "function f(a) {
+        var b = c;
+        return b;
+        function c() { return a; }
+        function c() { return 2; }
+      }",
+    );
+    let c = match f.call(&Value::undefined(), &[1.0.into()]) {
+      Ok(Value::Object(c)) => c,
+      _ => panic!("expect the hoisted function"),
+    };
+    assert_eq!(get(&c, &"name".into()).unwrap().to_string().unwrap(), "c");
+    assert_eq!(number(c.call(&Value::undefined(), &[]).unwrap()), 2.0);
+    // duplicate parameters, the last one wins
+    let f = function("function f(a, a) { const b = a; return b; }");
+    let result = f.call(&Value::undefined(), &[1.0.into(), 2.0.into()]);
+    assert_eq!(number(result.unwrap()), 2.0);
+    // the temporal dead zone
+    let f = function("function f() { let a = b; let b = 1; return a; }");
+    assert!(f.call(&Value::undefined(), &[]).is_err());
+    // the arguments object
+    let f = function("function f(a) { return arguments; }");
+    let arguments = match f.call(&Value::undefined(), &[1.0.into(), 2.0.into()])
+    {
+      Ok(Value::Object(arguments)) => arguments,
+      _ => panic!("expect the arguments object"),
+    };
+    assert_eq!(number(get(&arguments, &"length".into()).unwrap()), 2.0);
+    assert_eq!(number(get(&arguments, &"1".into()).unwrap()), 2.0);
+    let f = function("function f(a, arguments) { return arguments; }");
+    let result = f.call(&Value::undefined(), &[1.0.into()]).unwrap();
+    assert!(matches!(result, Value::Undefined(_)));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn construct_ecmascript_function() {
+    initialize_host_defined_realm().unwrap();
+    let f = function("function F(a) { this.a = a; }");
+    assert!(Value::from(f.clone()).is_constructor());
+    let prototype = match get(&f, &"prototype".into()).unwrap() {
+      Value::Object(prototype) => prototype,
+      _ => panic!("expect the prototype object"),
+    };
+    let constructor = get(&prototype, &"constructor".into()).unwrap();
+    assert!(
+      matches!(constructor, Value::Object(c) if JsObject::equals(&c, &f))
+    );
+    let o = construct(&f.clone().into(), &[1.0.into()], None).unwrap();
+    assert_eq!(number(get(&o, &"a".into()).unwrap()), 1.0);
+    assert!(matches!(
+      o.get_prototype_of(),
+      Either::A(p) if JsObject::equals(&p, &prototype)
+    ));
+    // an object return value replaces this, other values are ignored
+    let f = function("function F(o) { this.a = 1; return o; }");
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    let result = construct(&f.clone().into(), &[o.clone().into()], None);
+    assert!(JsObject::equals(&result.unwrap(), &o));
+    let result = construct(&f.into(), &[1.0.into()], None).unwrap();
+    assert_eq!(number(get(&result, &"a".into()).unwrap()), 1.0);
+    pop_execution_context();
+  }
+
+  #[test]
+  fn default_and_rest_parameters() {
+    initialize_host_defined_realm().unwrap();
+    let f = function("function f(a = 1, b = a + 1) { return b; }");
+    assert_eq!(number(f.call(&Value::undefined(), &[]).unwrap()), 2.0);
+    let result = f.call(&Value::undefined(), &[Value::undefined(), 3.0.into()]);
+    assert_eq!(number(result.unwrap()), 3.0);
+    // a var initially has the value of the parameter with the same name
+    let f = function("function f(a = 1) { var a; return a; }");
+    assert_eq!(number(f.call(&Value::undefined(), &[]).unwrap()), 1.0);
+    let f = function("function f(a, ...r) { return r; }");
+    let r = match f
+      .call(&Value::undefined(), &[1.0.into(), 2.0.into(), 3.0.into()])
+    {
+      Ok(Value::Object(r)) => r,
+      _ => panic!("expect the rest array"),
+    };
+    assert_eq!(number(get(&r, &"length".into()).unwrap()), 2.0);
+    assert_eq!(number(get(&r, &"0".into()).unwrap()), 2.0);
+    assert_eq!(number(get(&r, &"1".into()).unwrap()), 3.0);
+    let r = match f.call(&Value::undefined(), &[]) {
+      Ok(Value::Object(r)) => r,
+      _ => panic!("expect the rest array"),
+    };
+    assert_eq!(number(get(&r, &"length".into()).unwrap()), 0.0);
+    // binding patterns are not supported yet
+    let f = function("function f([a]) { return a; }");
+    let error = f.call(&Value::undefined(), &[]).unwrap_err();
+    assert_eq!(error_name(error), "SyntaxError");
+    let f = function("function f(...{ length }) { return length; }");
+    let error = f.call(&Value::undefined(), &[]).unwrap_err();
+    assert_eq!(error_name(error), "SyntaxError");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn update_properties() {
+    initialize_host_defined_realm().unwrap();
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    create_data_property(&o, &"a".into(), 1.0.into()).unwrap();
+    let f = function("function f(o, k) { o.a++; return --o[k]; }");
+    let result = f.call(&Value::undefined(), &[o.clone().into(), "a".into()]);
+    assert_eq!(number(result.unwrap()), 1.0);
+    let result = f.call(&Value::undefined(), &[o.clone().into(), "b".into()]);
+    assert!(number(result.unwrap()).is_nan());
+    assert_eq!(number(get(&o, &"a".into()).unwrap()), 2.0);
+    // a failed [[Set]] only throws in strict mode code
+    o.prevent_extensions().unwrap();
+    let f = function("function f(o) { return ++o.c; }");
+    assert!(
+      number(f.call(&Value::undefined(), &[o.clone().into()]).unwrap())
+        .is_nan()
+    );
+    let f = function("function f(o) { 'use strict'; return ++o.c; }");
+    assert!(f.call(&Value::undefined(), &[o.into()]).is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn closures_capture_by_reference() {
+    initialize_host_defined_realm().unwrap();
+    let make = function(
+      "function make() {
+        let n = 0;
+        function inc() { return ++n; }
+        return inc;
+      }",
+    );
+    let counter = || match make.call(&Value::undefined(), &[]) {
+      Ok(Value::Object(inc)) => inc,
+      _ => panic!("expect the inner function"),
+    };
+    let inc = counter();
+    for i in 1..=3 {
+      assert_eq!(
+        number(inc.call(&Value::undefined(), &[]).unwrap()),
+        i as f64
+      );
+    }
+    // every call of make has its own environment
+    let other = counter();
+    assert_eq!(number(other.call(&Value::undefined(), &[]).unwrap()), 1.0);
+    assert_eq!(number(inc.call(&Value::undefined(), &[]).unwrap()), 4.0);
+    // a later update in the outer function is seen by the inner one
+    let f = function(
+      "function f() {
+        let n = 1;
+        function get() { return n; }
+        n++;
+        return get;
+      }",
+    );
+    let get = match f.call(&Value::undefined(), &[]) {
+      Ok(Value::Object(get)) => get,
+      _ => panic!("expect the inner function"),
+    };
+    assert_eq!(number(get.call(&Value::undefined(), &[]).unwrap()), 2.0);
+    pop_execution_context();
+  }
+
+  #[test]
+  fn function_and_arrow_expressions() {
+    initialize_host_defined_realm().unwrap();
+    // the name of a function expression is bound in the function itself
+    let f = function("function f() { return function g() { return g; }; }");
+    let g = match f.call(&Value::undefined(), &[]) {
+      Ok(Value::Object(g)) => g,
+      _ => panic!("expect the function expression"),
+    };
+    assert_eq!(get(&g, &"name".into()).unwrap().to_string().unwrap(), "g");
+    assert!(Value::from(g.clone()).is_constructor());
+    let result = g.call(&Value::undefined(), &[]).unwrap();
+    assert!(
+      matches!(result, Value::Object(result) if JsObject::equals(&result, &g))
+    );
+    // arrow functions use the this value of the enclosing function and
+    // aren't constructors
+    let f = function("function f() { return (a = 1) => this.b + a; }");
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    create_data_property(&o, &"b".into(), 2.0.into()).unwrap();
+    let arrow = match f.call(&o.into(), &[]) {
+      Ok(Value::Object(arrow)) => arrow,
+      _ => panic!("expect the arrow function"),
+    };
+    assert_eq!(
+      get(&arrow, &"name".into()).unwrap().to_string().unwrap(),
+      ""
+    );
+    assert!(!Value::from(arrow.clone()).is_constructor());
+    let result = arrow.call(&Value::null(), &[]).unwrap();
+    assert_eq!(number(result), 3.0);
+    let result = arrow.call(&Value::null(), &[2.0.into()]).unwrap();
+    assert_eq!(number(result), 4.0);
+    let f = function("function f() { return () => { 1; }; }");
+    let arrow = match f.call(&Value::undefined(), &[]) {
+      Ok(Value::Object(arrow)) => arrow,
+      _ => panic!("expect the arrow function"),
+    };
+    let result = arrow.call(&Value::undefined(), &[]).unwrap();
+    assert!(matches!(result, Value::Undefined(_)));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn update_expressions() {
+    initialize_host_defined_realm().unwrap();
+    let call = |source| function(source).call(&Value::undefined(), &[]);
+    let f = "function f() { let a = 1; const b = a++; return a * 10 + b; }";
+    assert_eq!(number(call(f).unwrap()), 21.0);
+    let f = "function f() { let a = 1; const b = --a; return a * 10 + b; }";
+    assert_eq!(number(call(f).unwrap()), 0.0);
+    let f = "function f() { var a = '1'; a++; return a; }";
+    assert_eq!(number(call(f).unwrap()), 2.0);
+    let f = "function f() { let a = 1n; return ++a; }";
+    assert!(matches!(call(f).unwrap(), Value::BigInt(n) if *n == 2.into()));
+    assert!(call("function f() { const a = 1; a++; }").is_err());
+    assert!(call("function f() { 'use strict'; a++; }").is_err());
+    pop_execution_context();
+  }
+}
@@ -0,0 +1,6 @@
+//! https://tc39.es/ecma262/#sec-ordinary-and-exotic-objects-behaviours
+
+pub mod arguments_exotic_objects;
+pub mod array_exotic_objects;
+pub mod builtin_function;
+pub mod ecmascript_function_objects;
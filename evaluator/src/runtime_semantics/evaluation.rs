@@ -0,0 +1,872 @@
+//! https://tc39.es/ecma262/#sec-evaluation of the statements and
+//! expressions, throw completions are the `Err` of the result.
+
+use ng262_parser::parser::{
+  nodes::{Node, NodeType, VariableKind},
+  source::SourceText,
+  tokens::TokenType,
+};
+
+use crate::{
+  abstract_operations::{
+    testing_and_comparison_operations::{
+      is_less_than, is_loosely_equal, is_strictly_equal,
+    },
+    type_conversion::{type_of, Numeric, PreferredType},
+  },
+  executable_code_and_execution_contexts::execution_context::{
+    resolve_binding, resolve_this_binding,
+  },
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  language_types::{
+    big_int::JsBigInt, boolean::JsBoolean, number::JsNumber, string::JsString,
+    Value,
+  },
+  ordinary_and_exotic_objects_behaviours::ecmascript_function_objects::{
+    instantiate_arrow_function_expression,
+    instantiate_ordinary_function_expression,
+  },
+  specification_types::{
+    completion_record::Completion,
+    reference_record::{Base, Reference},
+  },
+};
+
+/// https://tc39.es/ecma262/#sec-block-runtime-semantics-evaluation, of a
+/// StatementList.
+pub fn evaluate_statement_list(
+  statements: &[Node],
+) -> Result<Completion, Value> {
+  let mut sl = Completion::empty();
+  for statement in statements {
+    // StatementList : StatementList StatementListItem
+    // 1. Let sl be ? Evaluation of StatementList.
+    // 2. Let s be Completion(Evaluation of StatementListItem).
+    // 3. Return ? UpdateEmpty(s, sl).
+    sl = evaluate_statement(statement)?.update_empty(sl.into_value());
+    if sl.is_abrupt() {
+      return Ok(sl);
+    }
+  }
+  Ok(sl)
+}
+
+pub fn evaluate_statement(node: &Node) -> Result<Completion, Value> {
+  match node.node_type() {
+    // https://tc39.es/ecma262/#sec-function-definitions-runtime-semantics-evaluation
+    // 1. Return empty.
+    NodeType::FunctionDeclaration { .. } => Ok(Completion::empty()),
+    // https://tc39.es/ecma262/#sec-empty-statement-runtime-semantics-evaluation
+    // 1. Return empty.
+    NodeType::EmptyStatement => Ok(Completion::empty()),
+    // https://tc39.es/ecma262/#sec-debugger-statement-runtime-semantics-evaluation
+    // 1. If an implementation-defined debugging facility is available and enabled, then
+    //   a. Perform an implementation-defined debugging action.
+    //   b. Return a new implementation-defined Completion Record.
+    // 2. Else,
+    //   a. Return empty.
+    NodeType::DebuggerStatement => Ok(Completion::empty()),
+    // https://tc39.es/ecma262/#sec-expression-statement-runtime-semantics-evaluation
+    NodeType::ExpressionStatement { expression } => {
+      // 1. Let exprRef be ? Evaluation of Expression.
+      // 2. Return ? GetValue(exprRef).
+      Ok(Completion::normal(evaluate_expression(expression)?))
+    }
+    NodeType::VariableDeclaration { kind, declarations } => {
+      for declaration in declarations {
+        evaluate_variable_declarator(declaration, *kind)?;
+      }
+      // 2. Return empty.
+      Ok(Completion::empty())
+    }
+    // https://tc39.es/ecma262/#sec-continue-statement-runtime-semantics-evaluation
+    // ContinueStatement : `continue` `;`
+    // 1. Return Completion Record { [[Type]]: continue, [[Value]]: empty,
+    //    [[Target]]: empty }.
+    // ContinueStatement : `continue` LabelIdentifier `;`
+    // 1. Let label be the StringValue of LabelIdentifier.
+    // 2. Return Completion Record { [[Type]]: continue, [[Value]]: empty,
+    //    [[Target]]: label }.
+    NodeType::ContinueStatement { label } => Ok(Completion::r#continue(
+      label.as_deref().map(label_string_value),
+    )),
+    // https://tc39.es/ecma262/#sec-break-statement-runtime-semantics-evaluation
+    // BreakStatement : `break` `;`
+    // 1. Return Completion Record { [[Type]]: break, [[Value]]: empty,
+    //    [[Target]]: empty }.
+    // BreakStatement : `break` LabelIdentifier `;`
+    // 1. Let label be the StringValue of LabelIdentifier.
+    // 2. Return Completion Record { [[Type]]: break, [[Value]]: empty,
+    //    [[Target]]: label }.
+    NodeType::BreakStatement { label } => Ok(Completion::r#break(
+      label.as_deref().map(label_string_value),
+    )),
+    // https://tc39.es/ecma262/#sec-return-statement-runtime-semantics-evaluation
+    NodeType::ReturnStatement { argument } => match argument {
+      // ReturnStatement : `return` `;`
+      // 1. Return Completion Record { [[Type]]: return, [[Value]]: undefined,
+      //    [[Target]]: empty }.
+      None => Ok(Completion::r#return(Value::undefined())),
+      // ReturnStatement : `return` Expression `;`
+      Some(argument) => {
+        // 1. Let exprRef be ? Evaluation of Expression.
+        // 2. Let exprValue be ? GetValue(exprRef).
+        // 3. If GetGeneratorKind() is async, set exprValue to ? Await(exprValue).
+        // 4. Return Completion Record { [[Type]]: return, [[Value]]: exprValue,
+        //    [[Target]]: empty }.
+        Ok(Completion::r#return(evaluate_expression(argument)?))
+      }
+    },
+    // https://tc39.es/ecma262/#sec-throw-statement-runtime-semantics-evaluation
+    NodeType::ThrowStatement { argument } => {
+      // 1. Let exprRef be ? Evaluation of Expression.
+      // 2. Let exprValue be ? GetValue(exprRef).
+      // 3. Return ThrowCompletion(exprValue).
+      Err(evaluate_expression(argument)?)
+    }
+    _ => Err(unsupported(node)),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-static-semantics-stringvalue of a
+/// LabelIdentifier.
+fn label_string_value(label: &Node) -> JsString {
+  match label.node_type() {
+    NodeType::LabelIdentifier { name, .. } => name.as_str().into(),
+    t => unreachable!("expect LabelIdentifier, got {:?}", t),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-let-and-const-declarations-runtime-semantics-evaluation
+/// and https://tc39.es/ecma262/#sec-variable-statement-runtime-semantics-evaluation
+fn evaluate_variable_declarator(
+  node: &Node,
+  kind: VariableKind,
+) -> Result<(), Value> {
+  let (id, init) = match node.node_type() {
+    NodeType::VariableDeclarator { id, init } => (id, init),
+    t => unreachable!("expect VariableDeclarator, got {:?}", t),
+  };
+  let name = match id.node_type() {
+    NodeType::BindingIdentifier { name } => name,
+    // TODO: BindingPattern
+    _ => return Err(unsupported(id)),
+  };
+  match (kind, init) {
+    // VariableDeclaration : BindingIdentifier
+    // 1. Return empty.
+    (VariableKind::Var, None) => Ok(()),
+    // LexicalBinding : BindingIdentifier
+    (_, None) => {
+      // 1. Let lhs be ! ResolveBinding(StringValue of BindingIdentifier).
+      let lhs = resolve_binding(name, None, node.is_strict())
+        .expect("the binding of a lexical declaration");
+      // 2. Perform ! InitializeReferencedBinding(lhs, undefined).
+      lhs
+        .initialize_referenced_binding(Value::undefined())
+        .expect("the binding of a lexical declaration");
+      // 3. Return empty.
+      Ok(())
+    }
+    (kind, Some(init)) => {
+      // 1. Let bindingId be StringValue of BindingIdentifier.
+      // 2. Let lhs be ? ResolveBinding(bindingId).
+      let lhs = resolve_binding(name, None, node.is_strict())?;
+      // 3. If IsAnonymousFunctionDefinition(Initializer) is true, then
+      //   a. Let value be ? NamedEvaluation of Initializer with argument bindingId.
+      // TODO: NamedEvaluation
+      // 4. Else,
+      //   a. Let rhs be ? Evaluation of Initializer.
+      //   b. Let value be ? GetValue(rhs).
+      let value = evaluate_expression(init)?;
+      if kind == VariableKind::Var {
+        // 5. Perform ? PutValue(lhs, value).
+        lhs.put_value(value)
+      } else {
+        // 5. Perform ! InitializeReferencedBinding(lhs, value).
+        lhs.initialize_referenced_binding(value)
+      }
+      // 6. Return empty.
+    }
+  }
+}
+
+/// The evaluation of an expression followed by GetValue.
+pub fn evaluate_expression(node: &Node) -> Result<Value, Value> {
+  match node.node_type() {
+    // https://tc39.es/ecma262/#sec-identifiers-runtime-semantics-evaluation
+    NodeType::IdentifierReference { name, .. } => {
+      // 1. Return ? ResolveBinding(StringValue of Identifier).
+      resolve_binding(name, None, node.is_strict())?.get_value()
+    }
+    // https://tc39.es/ecma262/#sec-this-keyword-runtime-semantics-evaluation
+    // 1. Return ? ResolveThisBinding().
+    NodeType::ThisExpression => resolve_this_binding(),
+    // https://tc39.es/ecma262/#sec-literals-runtime-semantics-evaluation
+    NodeType::NullLiteral => Ok(Value::null()),
+    NodeType::BooleanLiteral { value } => Ok(Value::from(*value)),
+    NodeType::NumericLiteral { value } => Ok(Value::from(*value)),
+    NodeType::BigIntLiteral { value } => {
+      Ok(Value::from(JsBigInt::from(value.clone())))
+    }
+    NodeType::StringLiteral { value } => {
+      Ok(Value::from(JsString::from(value.code_units())))
+    }
+    // https://tc39.es/ecma262/#sec-grouping-operator-runtime-semantics-evaluation
+    NodeType::ParenthesizedExpression { expression } => {
+      evaluate_expression(expression)
+    }
+    NodeType::MemberExpression { .. } => {
+      evaluate_member_expression(node)?.get_value()
+    }
+    // https://tc39.es/ecma262/#sec-function-definitions-runtime-semantics-evaluation
+    // 1. Return InstantiateOrdinaryFunctionExpression of FunctionExpression.
+    NodeType::FunctionExpression { .. } => {
+      Ok(Value::from(instantiate_ordinary_function_expression(node)))
+    }
+    // https://tc39.es/ecma262/#sec-arrow-function-definitions-runtime-semantics-evaluation
+    // 1. Return InstantiateArrowFunctionExpression of ArrowFunction.
+    NodeType::ArrowFunctionExpression { .. } => {
+      Ok(Value::from(instantiate_arrow_function_expression(node)))
+    }
+    NodeType::UpdateExpression {
+      operator,
+      argument,
+      prefix,
+    } => evaluate_update_expression(operator, argument, *prefix),
+    // https://tc39.es/ecma262/#sec-assignment-operators-runtime-semantics-evaluation
+    // TODO: compound assignments and destructuring assignments
+    NodeType::AssignmentExpression {
+      operator: TokenType::Assign,
+      left,
+      right,
+    } => {
+      // 1. If LeftHandSideExpression is neither an ObjectLiteral nor an
+      //    ArrayLiteral, then
+      //   a. Let lref be ? Evaluation of LeftHandSideExpression.
+      let lref = evaluate_reference(left)?;
+      //   b. If IsAnonymousFunctionDefinition(AssignmentExpression) and
+      //      IsIdentifierRef of LeftHandSideExpression are both true, then
+      //     i. Let lhs be the StringValue of LeftHandSideExpression.
+      //     ii. Let rval be ? NamedEvaluation of AssignmentExpression with
+      //         argument lhs.
+      //   c. Else,
+      //     i. Let rref be ? Evaluation of AssignmentExpression.
+      //     ii. Let rval be ? GetValue(rref).
+      // TODO: NamedEvaluation
+      let rval = evaluate_expression(right)?;
+      //   d. Perform ? PutValue(lref, rval).
+      lref.put_value(rval.clone())?;
+      //   e. Return rval.
+      Ok(rval)
+    }
+    NodeType::BinaryExpression {
+      operator:
+        operator @ (TokenType::Exp
+        | TokenType::Mul
+        | TokenType::Div
+        | TokenType::Mod
+        | TokenType::Add
+        | TokenType::Sub
+        | TokenType::Shl
+        | TokenType::Sar
+        | TokenType::Shr
+        | TokenType::BitAnd
+        | TokenType::BitXor
+        | TokenType::BitOr),
+      left,
+      right,
+    } => evaluate_string_or_numeric_binary_expression(left, operator, right),
+    // https://tc39.es/ecma262/#sec-relational-operators-runtime-semantics-evaluation
+    NodeType::BinaryExpression {
+      operator:
+        operator @ (TokenType::LessThan
+        | TokenType::GreaterThan
+        | TokenType::LessThanEqual
+        | TokenType::GreaterThanEqual),
+      left,
+      right,
+    } => {
+      // 1. Let lref be ? Evaluation of RelationalExpression.
+      // 2. Let lval be ? GetValue(lref).
+      let lval = evaluate_expression(left)?;
+      // 3. Let rref be ? Evaluation of ShiftExpression.
+      // 4. Let rval be ? GetValue(rref).
+      let rval = evaluate_expression(right)?;
+      let r = match operator {
+        // 5. Let r be ? IsLessThan(lval, rval, true).
+        // 6. If r is undefined, return false. Otherwise, return r.
+        TokenType::LessThan => is_less_than(&lval, &rval, true)?,
+        // 5. Let r be ? IsLessThan(rval, lval, false).
+        // 6. If r is undefined, return false. Otherwise, return r.
+        TokenType::GreaterThan => is_less_than(&rval, &lval, false)?,
+        // 5. Let r be ? IsLessThan(rval, lval, false).
+        // 6. If r is either true or undefined, return false. Otherwise,
+        //    return true.
+        TokenType::LessThanEqual => {
+          is_less_than(&rval, &lval, false)?.map(|r| !r)
+        }
+        // 5. Let r be ? IsLessThan(lval, rval, true).
+        // 6. If r is either true or undefined, return false. Otherwise,
+        //    return true.
+        _ => is_less_than(&lval, &rval, true)?.map(|r| !r),
+      };
+      Ok(Value::from(r.unwrap_or(false)))
+    }
+    // https://tc39.es/ecma262/#sec-equality-operators-runtime-semantics-evaluation
+    NodeType::BinaryExpression {
+      operator:
+        operator @ (TokenType::Equal
+        | TokenType::NotEqual
+        | TokenType::StrictEqual
+        | TokenType::StrictNotEqual),
+      left,
+      right,
+    } => {
+      // 1. Let lref be ? Evaluation of EqualityExpression.
+      // 2. Let lval be ? GetValue(lref).
+      let lval = evaluate_expression(left)?;
+      // 3. Let rref be ? Evaluation of RelationalExpression.
+      // 4. Let rval be ? GetValue(rref).
+      let rval = evaluate_expression(right)?;
+      Ok(Value::from(match operator {
+        // 5. Return ? IsLooselyEqual(rval, lval).
+        TokenType::Equal => is_loosely_equal(&rval, &lval)? == JsBoolean::True,
+        // 5. Let r be ? IsLooselyEqual(rval, lval).
+        // 6. If r is true, return false. Otherwise, return true.
+        TokenType::NotEqual => {
+          is_loosely_equal(&rval, &lval)? == JsBoolean::False
+        }
+        // 5. Return IsStrictlyEqual(rval, lval).
+        TokenType::StrictEqual => {
+          is_strictly_equal(&rval, &lval) == JsBoolean::True
+        }
+        // 5. Let r be IsStrictlyEqual(rval, lval).
+        // 6. If r is true, return false. Otherwise, return true.
+        _ => is_strictly_equal(&rval, &lval) == JsBoolean::False,
+      }))
+    }
+    // https://tc39.es/ecma262/#sec-binary-logical-operators-runtime-semantics-evaluation
+    NodeType::LogicalExpression {
+      operator,
+      left,
+      right,
+    } => {
+      // 1. Let lref be ? Evaluation of LogicalANDExpression.
+      // 2. Let lval be ? GetValue(lref).
+      let lval = evaluate_expression(left)?;
+      let short_circuit = match operator {
+        // 3. Let lbool be ToBoolean(lval).
+        // 4. If lbool is false, return lval.
+        TokenType::And => lval.to_boolean() == JsBoolean::False,
+        // 3. Let lbool be ToBoolean(lval).
+        // 4. If lbool is true, return lval.
+        TokenType::Or => lval.to_boolean() == JsBoolean::True,
+        // 3. If lval is either undefined or null, then
+        //   ...
+        // 4. Else,
+        //   a. Return lval.
+        TokenType::Nullish => {
+          !matches!(lval, Value::Undefined(_) | Value::Null(_))
+        }
+        t => unreachable!("expect a logical operator, got {:?}", t),
+      };
+      if short_circuit {
+        return Ok(lval);
+      }
+      // 5. Let rref be ? Evaluation of BitwiseORExpression.
+      // 6. Return ? GetValue(rref).
+      evaluate_expression(right)
+    }
+    // https://tc39.es/ecma262/#sec-typeof-operator-runtime-semantics-evaluation
+    NodeType::UnaryExpression {
+      operator: TokenType::Typeof,
+      argument,
+    } => {
+      // 1. Let val be ? Evaluation of UnaryExpression.
+      // 2. If val is a Reference Record, then
+      //   a. If IsUnresolvableReference(val) is true, return "undefined".
+      //   b. Set val to ? GetValue(val).
+      // the grouping operator returns the Reference Record of its expression
+      let mut argument = &**argument;
+      while let NodeType::ParenthesizedExpression { expression } =
+        argument.node_type()
+      {
+        argument = expression;
+      }
+      let val = match argument.node_type() {
+        NodeType::IdentifierReference { name, .. } => {
+          let val = resolve_binding(name, None, argument.is_strict())?;
+          if val.is_unresolvable_reference() {
+            return Ok(Value::from("undefined"));
+          }
+          val.get_value()?
+        }
+        _ => evaluate_expression(argument)?,
+      };
+      Ok(Value::from(type_of(&val)))
+    }
+    // https://tc39.es/ecma262/#sec-comma-operator-runtime-semantics-evaluation
+    NodeType::SequenceExpression { expressions } => {
+      let (last, rest) = expressions.split_last().expect("a, b");
+      for expression in rest {
+        // 1. Let lref be ? Evaluation of Expression.
+        // 2. Perform ? GetValue(lref).
+        evaluate_expression(expression)?;
+      }
+      // 3. Let rref be ? Evaluation of AssignmentExpression.
+      // 4. Return ? GetValue(rref).
+      evaluate_expression(last)
+    }
+    _ => Err(unsupported(node)),
+  }
+}
+
+/// A SyntaxError for the syntax the parser accepts but that can't be
+/// evaluated yet, instead of aborting the host.
+pub(crate) fn unsupported(node: &Node) -> Value {
+  let source = node.source_text().lines().next().unwrap_or_default();
+  throw_error(
+    ErrorKind::SyntaxError,
+    &format!("{} is not supported yet", source),
+  )
+}
+
+/// The evaluation of an expression that is a Reference Record, i.e. an
+/// identifier or a property access.
+fn evaluate_reference(node: &Node) -> Result<Reference, Value> {
+  match node.node_type() {
+    NodeType::IdentifierReference { name, .. } => {
+      resolve_binding(name, None, node.is_strict())
+    }
+    NodeType::MemberExpression { .. } => evaluate_member_expression(node),
+    _ => Err(unsupported(node)),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-property-accessors-runtime-semantics-evaluation
+fn evaluate_member_expression(node: &Node) -> Result<Reference, Value> {
+  let (object, property, computed) = match node.node_type() {
+    NodeType::MemberExpression {
+      object,
+      property,
+      computed,
+      optional: false,
+    } => (object, property, *computed),
+    _ => return Err(unsupported(node)),
+  };
+  // 1. Let baseReference be ? Evaluation of MemberExpression.
+  // 2. Let baseValue be ? GetValue(baseReference).
+  let base_value = evaluate_expression(object)?;
+  // 3. If the source text matched by this MemberExpression is strict mode
+  //    code, let strict be true; else let strict be false.
+  let strict = node.is_strict();
+  let referenced_name = if computed {
+    // 4. Return ? EvaluatePropertyAccessWithExpressionKey(baseValue,
+    //    Expression, strict).
+    //   1. Let propertyNameReference be ? Evaluation of expression.
+    //   2. Let propertyNameValue be ? GetValue(propertyNameReference).
+    //   3. Let propertyKey be ? ToPropertyKey(propertyNameValue).
+    evaluate_expression(property)?.to_property_key()?
+  } else {
+    // 4. Return EvaluatePropertyAccessWithIdentifierKey(baseValue,
+    //    IdentifierName, strict).
+    //   1. Let propertyNameString be the StringValue of identifierName.
+    match property.node_type() {
+      NodeType::IdentifierName { name } => name.as_str().into(),
+      _ => return Err(unsupported(node)),
+    }
+  };
+  //   4. Return the Reference Record { [[Base]]: baseValue,
+  //      [[ReferencedName]]: propertyKey, [[Strict]]: strict,
+  //      [[ThisValue]]: empty }.
+  Ok(Reference {
+    base: Base::Value(base_value),
+    referenced_name,
+    strict,
+  })
+}
+
+/// https://tc39.es/ecma262/#sec-postfix-increment-operator-runtime-semantics-evaluation
+/// and the other three update operators, they only differ in the operation
+/// and the returned value.
+fn evaluate_update_expression(
+  operator: &TokenType,
+  argument: &Node,
+  prefix: bool,
+) -> Result<Value, Value> {
+  // 1. Let expr be ? Evaluation of UnaryExpression.
+  let expr = evaluate_reference(argument)?;
+  // 2. Let oldValue be ? ToNumeric(? GetValue(expr)).
+  let old_value = expr.get_value()?.to_numeric()?;
+  // 3. If oldValue is a Number, then
+  //   a. Let newValue be Number::add(oldValue, 1𝔽).
+  // 4. Else,
+  //   a. Assert: oldValue is a BigInt.
+  //   b. Let newValue be BigInt::add(oldValue, 1ℤ).
+  // Number::subtract and BigInt::subtract for the decrement operators.
+  let delta = match operator {
+    TokenType::Inc => 1,
+    TokenType::Dec => -1,
+    t => unreachable!("expect an update operator, got {:?}", t),
+  };
+  let new_value = match &old_value {
+    Numeric::Number(n) => Value::from(JsNumber::add(*n, f64::from(delta))),
+    Numeric::BigInt(n) => Value::from(JsBigInt::from(&**n + delta)),
+  };
+  // 5. Perform ? PutValue(expr, newValue).
+  expr.put_value(new_value.clone())?;
+  // 6. Return newValue, or oldValue for the postfix operators.
+  if prefix {
+    Ok(new_value)
+  } else {
+    Ok(Value::from(old_value))
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-evaluatestringornumericbinaryexpression
+fn evaluate_string_or_numeric_binary_expression(
+  left_operand: &Node,
+  op_text: &TokenType,
+  right_operand: &Node,
+) -> Result<Value, Value> {
+  // 1. Let lref be ? Evaluation of leftOperand.
+  // 2. Let lval be ? GetValue(lref).
+  let lval = evaluate_expression(left_operand)?;
+  // 3. Let rref be ? Evaluation of rightOperand.
+  // 4. Let rval be ? GetValue(rref).
+  let rval = evaluate_expression(right_operand)?;
+  // 5. Return ? ApplyStringOrNumericBinaryOperator(lval, opText, rval).
+  apply_string_or_numeric_binary_operator(lval, op_text, rval)
+}
+
+/// https://tc39.es/ecma262/#sec-applystringornumericbinaryoperator
+pub fn apply_string_or_numeric_binary_operator(
+  lval: Value,
+  op_text: &TokenType,
+  rval: Value,
+) -> Result<Value, Value> {
+  // 1. If opText is +, then
+  let (lval, rval) = if *op_text == TokenType::Add {
+    // a. Let lprim be ? ToPrimitive(lval).
+    let lprim = lval.to_primitive(PreferredType::Default)?;
+    // b. Let rprim be ? ToPrimitive(rval).
+    let rprim = rval.to_primitive(PreferredType::Default)?;
+    // c. If lprim is a String or rprim is a String, then
+    if matches!(lprim, Value::String(_)) || matches!(rprim, Value::String(_)) {
+      // i. Let lstr be ? ToString(lprim).
+      // ii. Let rstr be ? ToString(rprim).
+      // iii. Return the string-concatenation of lstr and rstr.
+      return Ok(Value::from(lprim.to_string()? + &rprim.to_string()?));
+    }
+    // d. Set lval to lprim.
+    // e. Set rval to rprim.
+    (lprim, rprim)
+  } else {
+    (lval, rval)
+  };
+  // 2. NOTE: At this point, it must be a numeric operation.
+  // 3. Let lnum be ? ToNumeric(lval).
+  let lnum = lval.to_numeric()?;
+  // 4. Let rnum be ? ToNumeric(rval).
+  let rnum = rval.to_numeric()?;
+  match (lnum, rnum) {
+    // 7. Let operation be the abstract operation associated with opText and
+    //    Type(lnum) in Table 41.
+    // 8. Return operation(lnum, rnum).
+    (Numeric::Number(lnum), Numeric::Number(rnum)) => {
+      Ok(Value::from(number_operation(lnum, op_text, rnum)))
+    }
+    // 6. If lnum is a BigInt, then
+    //   a. If opText is **, return ? BigInt::exponentiate(lnum, rnum).
+    //   b. If opText is /, return ? BigInt::divide(lnum, rnum).
+    //   c. If opText is %, return ? BigInt::remainder(lnum, rnum).
+    //   d. If opText is >>>, return ? BigInt::unsignedRightShift(lnum, rnum).
+    (Numeric::BigInt(lnum), Numeric::BigInt(rnum)) => {
+      big_int_operation(&lnum, op_text, &rnum).map(Value::from)
+    }
+    // 5. If Type(lnum) is not Type(rnum), throw a TypeError exception.
+    _ => Err(throw_error(
+      ErrorKind::TypeError,
+      "Cannot mix BigInt and other types, use explicit conversions",
+    )),
+  }
+}
+
+/// The Number operations of Table 41.
+fn number_operation(x: f64, op_text: &TokenType, y: f64) -> f64 {
+  match op_text {
+    TokenType::Exp => JsNumber::exponentiate(x, y),
+    TokenType::Mul => JsNumber::multiply(x, y),
+    TokenType::Div => JsNumber::divide(x, y),
+    TokenType::Mod => JsNumber::remainder(x, y),
+    TokenType::Add => JsNumber::add(x, y),
+    TokenType::Sub => JsNumber::subtract(x, y),
+    TokenType::Shl => JsNumber::left_shift(x, y),
+    TokenType::Sar => JsNumber::signed_right_shift(x, y),
+    TokenType::Shr => JsNumber::unsigned_right_shift(x, y),
+    TokenType::BitAnd => JsNumber::bitwise_and(x, y),
+    TokenType::BitXor => JsNumber::bitwise_xor(x, y),
+    TokenType::BitOr => JsNumber::bitwise_or(x, y),
+    t => unreachable!("expect a numeric operator, got {:?}", t),
+  }
+}
+
+/// The BigInt operations of Table 41.
+fn big_int_operation(
+  x: &JsBigInt,
+  op_text: &TokenType,
+  y: &JsBigInt,
+) -> Result<JsBigInt, Value> {
+  match op_text {
+    TokenType::Exp => JsBigInt::exponentiate(x, y),
+    TokenType::Mul => Ok(JsBigInt::multiply(x, y)),
+    TokenType::Div => JsBigInt::divide(x, y),
+    TokenType::Mod => JsBigInt::remainder(x, y),
+    TokenType::Add => Ok(JsBigInt::add(x, y)),
+    TokenType::Sub => Ok(JsBigInt::subtract(x, y)),
+    TokenType::Shl => JsBigInt::left_shift(x, y),
+    TokenType::Sar => JsBigInt::signed_right_shift(x, y),
+    TokenType::Shr => JsBigInt::unsigned_right_shift(x, y),
+    TokenType::BitAnd => Ok(JsBigInt::bitwise_and(x, y)),
+    TokenType::BitXor => Ok(JsBigInt::bitwise_xor(x, y)),
+    TokenType::BitOr => Ok(JsBigInt::bitwise_or(x, y)),
+    t => unreachable!("expect a numeric operator, got {:?}", t),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ng262_parser::parser::Parser;
+  use num_bigint::BigInt;
+
+  use super::*;
+  use crate::{
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    specification_types::completion_record::Type, test_helpers,
+  };
+
+  /// The completion value of a script without declarations.
+  fn evaluate(source: &'static str) -> Result<Value, Value> {
+    initialize_host_defined_realm().unwrap();
+    let script = Parser::parse_script(source, None).unwrap();
+    let body = match script.node_type() {
+      NodeType::Script { body } => body,
+      _ => unreachable!(),
+    };
+    let result = evaluate_statement_list(body);
+    pop_execution_context();
+    result.map(|completion| completion.into_value().unwrap())
+  }
+
+  fn number(source: &'static str) -> f64 {
+    match evaluate(source) {
+      Ok(Value::Number(n)) => *n,
+      v => panic!("expect a number, got {:?}", v),
+    }
+  }
+
+  fn big_int(source: &'static str) -> BigInt {
+    match evaluate(source) {
+      Ok(Value::BigInt(n)) => (*n).clone(),
+      v => panic!("expect a BigInt, got {:?}", v),
+    }
+  }
+
+  /// The name of the error a script throws.
+  fn error_name(source: &'static str) -> JsString {
+    let error = match evaluate(source) {
+      Err(error) => error,
+      v => panic!("expect an error, got {:?}", v),
+    };
+    initialize_host_defined_realm().unwrap();
+    let name = test_helpers::error_name(error);
+    pop_execution_context();
+    name
+  }
+
+  #[test]
+  fn string_or_numeric_binary_operators() {
+    assert!(matches!(evaluate("1 + '2'"), Ok(Value::String(s)) if s == "12"));
+    assert!(
+      matches!(evaluate("'a' + null"), Ok(Value::String(s)) if s == "anull")
+    );
+    assert_eq!(number("1 + 2 * 3"), 7.0);
+    assert_eq!(number("true + 1"), 2.0);
+    assert_eq!(number("5 % 3"), 2.0);
+    assert_eq!(number("(0 - 5) % 3"), -2.0);
+    assert!(number("'a' - 1").is_nan());
+    assert_eq!(number("1 / 0"), f64::INFINITY);
+    assert_eq!(number("(0 - 1) >>> 0"), 4294967295.0);
+    assert_eq!(number("(0 - 8) >> 1"), -4.0);
+    assert_eq!(number("1 << 33"), 2.0);
+    assert_eq!(number("4294967297 | 0"), 1.0);
+    assert_eq!(number("6 & 3 ^ 1"), 3.0);
+    assert_eq!(number("2 ** 3 ** 2"), 512.0);
+    assert_eq!(number("(2 ** 3) ** 2"), 64.0);
+    assert_eq!(number("5 % (0 - 3)"), 2.0);
+  }
+
+  #[test]
+  fn big_int_binary_operators() {
+    assert_eq!(big_int("1n + 2n"), BigInt::from(3));
+    assert_eq!(big_int("10n / 3n"), BigInt::from(3));
+    assert_eq!(big_int("(0n - 5n) % 3n"), BigInt::from(-2));
+    assert_eq!(big_int("(0n - 5n) >> 1n"), BigInt::from(-3));
+    assert_eq!(big_int("1n << 64n"), BigInt::from(1u128 << 64));
+    assert_eq!(big_int("(0n - 6n) & 3n"), BigInt::from(2));
+    assert_eq!(error_name("1n + 2"), "TypeError");
+    assert_eq!(error_name("1n / 0n"), "RangeError");
+    assert_eq!(error_name("1n >>> 0n"), "TypeError");
+  }
+
+  fn boolean(source: &'static str) -> bool {
+    match evaluate(source) {
+      Ok(Value::Boolean(b)) => b == JsBoolean::True,
+      v => panic!("expect a boolean, got {:?}", v),
+    }
+  }
+
+  #[test]
+  fn relational_operators() {
+    assert!(boolean("'a' < 'b'"));
+    assert!(boolean("'10' < '9'"));
+    assert!(!boolean("'ab' < 'a'"));
+    assert!(boolean("10 > '9'"));
+    assert!(boolean("1n < 2"));
+    assert!(boolean("1n < 1.5"));
+    assert!(!boolean("2n <= 1.5"));
+    assert!(boolean("2 > 1n"));
+    assert!(boolean("1n >= '1'"));
+    assert!(!boolean("1n < 'x'"));
+    // IsLessThan is undefined for NaN
+    assert!(!boolean("0 / 0 < 1"));
+    assert!(!boolean("0 / 0 >= 1"));
+    assert!(!boolean("1n <= 0 / 0"));
+    assert!(boolean("1n < 1 / 0"));
+  }
+
+  #[test]
+  fn strict_equality_operators() {
+    assert!(!boolean("0 / 0 === 0 / 0"));
+    assert!(boolean("0 / 0 !== 0 / 0"));
+    assert!(boolean("0 === 0 * (0 - 1)"));
+    assert!(!boolean("'1' === 1"));
+    assert!(boolean("'1' !== 1"));
+    assert!(boolean("1n === 1n"));
+    assert!(!boolean("1n === 1"));
+    assert!(boolean("null === null"));
+    assert!(!boolean("null === 0"));
+  }
+
+  #[test]
+  fn unsupported_syntax() {
+    for source in ["{}", "if (1) 2;", "var [a] = 1;", "(x)++", "x += 1"] {
+      assert_eq!(error_name(source), "SyntaxError", "{}", source);
+    }
+  }
+
+  #[test]
+  fn break_and_continue() {
+    let script =
+      Parser::parse_script("a: while (1) { 1; break a; continue; }", None)
+        .unwrap();
+    let mut node = &script;
+    let body = loop {
+      node = match node.node_type() {
+        NodeType::Script { body } => &body[0],
+        NodeType::LabeledStatement { body, .. } => body,
+        NodeType::WhileStatement { body, .. } => body,
+        NodeType::BlockStatement { body } => break body,
+        t => unreachable!("{:?}", t),
+      }
+    };
+    // the break completion gets the value of the statements before it
+    let completion = evaluate_statement_list(body).unwrap();
+    assert_eq!(completion.r#type(), Type::Break);
+    assert_eq!(completion.target().unwrap(), "a");
+    assert!(matches!(completion.value(), Some(Value::Number(n)) if **n == 1.0));
+    let completion = evaluate_statement(&body[2]).unwrap();
+    assert_eq!(completion.r#type(), Type::Continue);
+    assert!(completion.target().is_none() && completion.value().is_none());
+  }
+
+  #[test]
+  fn property_references() {
+    assert!(matches!(evaluate("(1).a"), Ok(Value::Undefined(_))));
+    assert!(matches!(evaluate("'a'[0 + 1]"), Ok(Value::Undefined(_))));
+    assert_eq!(error_name("null.a"), "TypeError");
+    assert_eq!(error_name("null['a']++"), "TypeError");
+    assert_eq!(error_name("null.a = 1"), "TypeError");
+  }
+
+  #[test]
+  fn escaped_surrogates() {
+    assert!(boolean(r"'\uD83D\uDE00' === '😀'"));
+    assert!(boolean(r"'\uD83D' + '\uDE00' === '\u{1F600}'"));
+    assert!(!boolean(r"'\uD800' === '\uFFFD'"));
+    match evaluate(r"'a\uD800'") {
+      Ok(Value::String(s)) => assert_eq!(*s, [0x61, 0xD800]),
+      v => panic!("expect a string, got {:?}", v),
+    }
+  }
+
+  #[test]
+  fn equality_operators() {
+    assert!(boolean("null == null"));
+    assert!(boolean("1 == '1'"));
+    assert!(boolean("0 == false"));
+    assert!(boolean("1n == 1"));
+    assert!(boolean("'1' != 2"));
+    assert!(!boolean("0 / 0 == 0 / 0"));
+    assert!(!boolean("null == 0"));
+  }
+
+  #[test]
+  fn typeof_operator() {
+    let type_of = |source| match evaluate(source) {
+      Ok(Value::String(s)) => s.to_string_lossy(),
+      v => panic!("expect a string, got {:?}", v),
+    };
+    assert_eq!(type_of("typeof 1"), "number");
+    assert_eq!(type_of("typeof null"), "object");
+    assert_eq!(type_of("typeof (1n + 1n)"), "bigint");
+    assert_eq!(type_of("typeof typeof 1"), "string");
+    // an unresolvable reference isn't a ReferenceError
+    assert_eq!(type_of("typeof undeclared"), "undefined");
+    assert_eq!(type_of("typeof (undeclared)"), "undefined");
+    assert_eq!(error_name("typeof (0, undeclared)"), "ReferenceError");
+  }
+
+  #[test]
+  fn empty_statements() {
+    // the completion value of a script skips the empty ones
+    assert_eq!(number("1; ; debugger;"), 1.0);
+  }
+
+  #[test]
+  fn throw_statement() {
+    assert!(
+      matches!(evaluate("1; throw 2; 3;"), Err(Value::Number(n)) if *n == 2.0)
+    );
+  }
+
+  #[test]
+  fn comma_operator() {
+    assert_eq!(number("1, 2, 3"), 3.0);
+    assert_eq!(number("(1, 2) * 3"), 6.0);
+    assert_eq!(error_name("(1, undefinedName, 3)"), "ReferenceError");
+  }
+
+  #[test]
+  fn logical_operators() {
+    assert_eq!(number("0 || 2"), 2.0);
+    assert_eq!(number("1 && 2"), 2.0);
+    assert_eq!(number("0 ?? 2"), 0.0);
+    assert_eq!(number("null ?? 2"), 2.0);
+    // the right operand isn't evaluated
+    assert_eq!(number("1 || a"), 1.0);
+    assert!(evaluate("0 || a").is_err());
+  }
+}
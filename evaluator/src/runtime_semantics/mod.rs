@@ -1 +1,3 @@
 //! https://tc39.es/ecma262/#sec-runtime-semantics
+
+pub mod evaluation;
@@ -1,6 +1,10 @@
 use crate::language_types::{string::JsString, Value};
 
 /// https://tc39.es/ecma262/#sec-completion-record-specification-type
+///
+/// A throw completion is usually the `Err` of a `Result<Completion, Value>`,
+/// so `?` is ReturnIfAbrupt for it.
+#[derive(Debug)]
 pub struct Completion {
   r#type: Type,
   value: Option<Value>,
@@ -25,8 +29,88 @@ impl Completion {
       target: None,
     }
   }
+
+  /// A normal completion whose [[Value]] is empty, e.g. of a declaration.
+  pub fn empty() -> Self {
+    Self {
+      r#type: Type::Normal,
+      value: None,
+      target: None,
+    }
+  }
+
+  /// The completion of a `return` statement.
+  pub fn r#return(value: Value) -> Self {
+    Self {
+      r#type: Type::Return,
+      value: Some(value),
+      target: None,
+    }
+  }
+
+  /// The completion of a `break` statement, `target` is the label.
+  pub fn r#break(target: Option<JsString>) -> Self {
+    Self {
+      r#type: Type::Break,
+      value: None,
+      target,
+    }
+  }
+
+  /// The completion of a `continue` statement, `target` is the label.
+  pub fn r#continue(target: Option<JsString>) -> Self {
+    Self {
+      r#type: Type::Continue,
+      value: None,
+      target,
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-updateempty
+  pub fn update_empty(self, value: Option<Value>) -> Self {
+    // 1. Assert: If completionRecord.[[Type]] is either return or throw, then
+    //    completionRecord.[[Value]] is not empty.
+    debug_assert!(
+      !matches!(self.r#type, Type::Return | Type::Throw)
+        || self.value.is_some()
+    );
+    // 2. If completionRecord.[[Value]] is not empty, return
+    //    Completion(completionRecord).
+    if self.value.is_some() {
+      return self;
+    }
+    // 3. Return Completion Record { [[Type]]: completionRecord.[[Type]],
+    //    [[Value]]: value, [[Target]]: completionRecord.[[Target]] }.
+    Self { value, ..self }
+  }
+
+  /// https://tc39.es/ecma262/#sec-completion-record-specification-type, an
+  /// abrupt completion is any completion with a [[Type]] other than normal.
+  pub fn is_abrupt(&self) -> bool {
+    self.r#type != Type::Normal
+  }
+
+  /// [[Type]]
+  pub fn r#type(&self) -> Type {
+    self.r#type
+  }
+
+  /// [[Value]], `None` when it's empty.
+  pub fn value(&self) -> Option<&Value> {
+    self.value.as_ref()
+  }
+
+  pub fn into_value(self) -> Option<Value> {
+    self.value
+  }
+
+  /// [[Target]], the label of a break or continue completion.
+  pub fn target(&self) -> Option<&JsString> {
+    self.target.as_ref()
+  }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Type {
   Normal,
   Break,
@@ -34,3 +118,41 @@ pub enum Type {
   Return,
   Throw,
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn abrupt_completions() {
+    let completion = Completion::normal(Value::from(1.0));
+    assert!(!completion.is_abrupt());
+    assert!(matches!(completion.value(), Some(Value::Number(n)) if **n == 1.0));
+    assert!(completion.target().is_none());
+
+    let completion = Completion::r#break(Some("outer".into()));
+    assert!(completion.is_abrupt());
+    assert_eq!(completion.r#type(), Type::Break);
+    assert_eq!(completion.target().unwrap(), "outer");
+    assert!(completion.value().is_none());
+    let completion = Completion::r#continue(None);
+    assert_eq!(completion.r#type(), Type::Continue);
+    assert!(completion.target().is_none());
+    assert!(Completion::r#return(Value::undefined()).is_abrupt());
+  }
+
+  #[test]
+  fn update_empty() {
+    let completion =
+      Completion::r#break(Some("outer".into())).update_empty(Some("a".into()));
+    assert_eq!(completion.r#type(), Type::Break);
+    assert_eq!(completion.target().unwrap(), "outer");
+    assert!(matches!(completion.value(), Some(Value::String(s)) if s == "a"));
+    // a value isn't replaced
+    let completion =
+      Completion::normal(Value::null()).update_empty(Some("a".into()));
+    assert!(matches!(completion.value(), Some(Value::Null(_))));
+    let completion = Completion::empty().update_empty(None);
+    assert!(completion.value().is_none());
+  }
+}
@@ -2,3 +2,4 @@
 
 pub mod completion_record;
 pub mod property_descriptor;
+pub mod reference_record;
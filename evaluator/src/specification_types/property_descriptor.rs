@@ -1,4 +1,10 @@
 use crate::{
+  abstract_operations::{
+    operations_on_bjects::{create_data_property, get, has_property},
+    ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+  },
+  agent::current_realm,
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
   helpers::Either,
   language_types::{
     boolean::JsBoolean, object::JsObject, undefined::JsUndefined, Value,
@@ -6,13 +12,14 @@ use crate::{
 };
 
 /// https://tc39.es/ecma262/#sec-property-descriptor-specification-type
+#[derive(Debug, Clone)]
 pub struct PropertyDescriptor {
-  value: Option<Value>,
-  writable: Option<JsBoolean>,
-  get: Option<Either<JsObject, JsUndefined>>,
-  set: Option<Either<JsObject, JsUndefined>>,
-  enumerable: Option<JsBoolean>,
-  configurable: Option<JsBoolean>,
+  pub value: Option<Value>,
+  pub writable: Option<JsBoolean>,
+  pub get: Option<Either<JsObject, JsUndefined>>,
+  pub set: Option<Either<JsObject, JsUndefined>>,
+  pub enumerable: Option<JsBoolean>,
+  pub configurable: Option<JsBoolean>,
 }
 
 impl Default for PropertyDescriptor {
@@ -62,3 +69,258 @@ impl PropertyDescriptor {
     false
   }
 }
+
+/// https://tc39.es/ecma262/#sec-topropertydescriptor
+pub fn to_property_descriptor(
+  obj: &Value,
+) -> Result<PropertyDescriptor, Value> {
+  // 1. If Type(Obj) is not Object, throw a TypeError exception.
+  let obj = match obj {
+    Value::Object(obj) => obj,
+    _ => {
+      return Err(throw_error(
+        ErrorKind::TypeError,
+        "Property description must be an object",
+      ))
+    }
+  };
+  // the field of Obj named `name` if it has one
+  let field = |name: &str| -> Result<Option<Value>, Value> {
+    if has_property(obj, &name.into())? {
+      Ok(Some(get(obj, &name.into())?))
+    } else {
+      Ok(None)
+    }
+  };
+  // a getter or setter must be callable or undefined
+  let accessor = |name: &str| -> Result<_, Value> {
+    match field(name)? {
+      None => Ok(None),
+      Some(Value::Undefined(_)) => Ok(Some(Either::B(JsUndefined))),
+      Some(Value::Object(f)) if f.get_call().is_some() => {
+        Ok(Some(Either::A(f)))
+      }
+      Some(_) => Err(throw_error(
+        ErrorKind::TypeError,
+        &format!("Property description's {} must be a function", name),
+      )),
+    }
+  };
+  // 2. Let desc be a new Property Descriptor that initially has no fields.
+  // 3. Let hasEnumerable be ? HasProperty(Obj, "enumerable").
+  // 4. If hasEnumerable is true, then
+  //   a. Let enumerable be ToBoolean(? Get(Obj, "enumerable")).
+  //   b. Set desc.[[Enumerable]] to enumerable.
+  let enumerable = field("enumerable")?.map(|v| v.to_boolean());
+  // 5. Let hasConfigurable be ? HasProperty(Obj, "configurable").
+  // 6. If hasConfigurable is true, then
+  //   a. Let configurable be ToBoolean(? Get(Obj, "configurable")).
+  //   b. Set desc.[[Configurable]] to configurable.
+  let configurable = field("configurable")?.map(|v| v.to_boolean());
+  // 7. Let hasValue be ? HasProperty(Obj, "value").
+  // 8. If hasValue is true, then
+  //   a. Let value be ? Get(Obj, "value").
+  //   b. Set desc.[[Value]] to value.
+  let value = field("value")?;
+  // 9. Let hasWritable be ? HasProperty(Obj, "writable").
+  // 10. If hasWritable is true, then
+  //   a. Let writable be ToBoolean(? Get(Obj, "writable")).
+  //   b. Set desc.[[Writable]] to writable.
+  let writable = field("writable")?.map(|v| v.to_boolean());
+  // 11. Let hasGet be ? HasProperty(Obj, "get").
+  // 12. If hasGet is true, then
+  //   a. Let getter be ? Get(Obj, "get").
+  //   b. If IsCallable(getter) is false and getter is not undefined, throw a
+  //      TypeError exception.
+  //   c. Set desc.[[Get]] to getter.
+  let get = accessor("get")?;
+  // 13. Let hasSet be ? HasProperty(Obj, "set").
+  // 14. If hasSet is true, then
+  //   a. Let setter be ? Get(Obj, "set").
+  //   b. If IsCallable(setter) is false and setter is not undefined, throw a
+  //      TypeError exception.
+  //   c. Set desc.[[Set]] to setter.
+  let set = accessor("set")?;
+  let desc = PropertyDescriptor {
+    value,
+    writable,
+    get,
+    set,
+    enumerable,
+    configurable,
+  };
+  // 15. If desc has a [[Get]] field or desc has a [[Set]] field, then
+  //   a. If desc has a [[Value]] field or desc has a [[Writable]] field, throw a
+  //      TypeError exception.
+  if desc.is_accessor_descriptor() && desc.is_data_descriptor() {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      "Invalid property descriptor. Cannot both specify accessors and a value or writable attribute",
+    ));
+  }
+  // 16. Return desc.
+  Ok(desc)
+}
+
+/// https://tc39.es/ecma262/#sec-frompropertydescriptor
+pub fn from_property_descriptor(desc: Option<PropertyDescriptor>) -> Value {
+  // 1. If Desc is undefined, return undefined.
+  let desc = match desc {
+    Some(desc) => desc,
+    None => return Value::undefined(),
+  };
+  // 2. Let obj be OrdinaryObjectCreate(%Object.prototype%).
+  let obj = ordinary_object_create(
+    Either::A(current_realm().intrinsic("Object.prototype")),
+    &[],
+  );
+  // 3. Assert: obj is an extensible ordinary object with no own properties.
+  let function = |f: Either<JsObject, JsUndefined>| match f {
+    Either::A(f) => Value::from(f),
+    Either::B(_) => Value::undefined(),
+  };
+  // 4. If Desc has a [[Value]] field, then
+  //   a. Perform ! CreateDataPropertyOrThrow(obj, "value", Desc.[[Value]]).
+  // 5. If Desc has a [[Writable]] field, then
+  //   a. Perform ! CreateDataPropertyOrThrow(obj, "writable", Desc.[[Writable]]).
+  // 6. If Desc has a [[Get]] field, then
+  //   a. Perform ! CreateDataPropertyOrThrow(obj, "get", Desc.[[Get]]).
+  // 7. If Desc has a [[Set]] field, then
+  //   a. Perform ! CreateDataPropertyOrThrow(obj, "set", Desc.[[Set]]).
+  // 8. If Desc has an [[Enumerable]] field, then
+  //   a. Perform ! CreateDataPropertyOrThrow(obj, "enumerable",
+  //      Desc.[[Enumerable]]).
+  // 9. If Desc has a [[Configurable]] field, then
+  //   a. Perform ! CreateDataPropertyOrThrow(obj, "configurable",
+  //      Desc.[[Configurable]]).
+  let fields = [
+    ("value", desc.value),
+    ("writable", desc.writable.map(Value::from)),
+    ("get", desc.get.map(function)),
+    ("set", desc.set.map(function)),
+    ("enumerable", desc.enumerable.map(Value::from)),
+    ("configurable", desc.configurable.map(Value::from)),
+  ];
+  for (name, value) in fields {
+    if let Some(value) = value {
+      create_data_property(&obj, &name.into(), value)
+        .expect("obj is an extensible ordinary object");
+    }
+  }
+  // 10. Return obj.
+  obj.into()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    language_types::null::JsNull, test_helpers::error_name,
+  };
+
+  fn object(fields: &[(&str, Value)]) -> Value {
+    let obj = ordinary_object_create(Either::B(JsNull), &[]);
+    for (name, value) in fields {
+      create_data_property(&obj, &(*name).into(), value.clone()).unwrap();
+    }
+    obj.into()
+  }
+
+  #[test]
+  fn to_data_descriptor() {
+    initialize_host_defined_realm().unwrap();
+    let obj = object(&[("value", Value::from(1.0)), ("writable", true.into())]);
+    let desc = to_property_descriptor(&obj).unwrap();
+    assert!(matches!(desc.value, Some(Value::Number(n)) if *n == 1.0));
+    assert_eq!(desc.writable, Some(JsBoolean::True));
+    assert!(desc.get.is_none() && desc.set.is_none());
+    assert!(desc.enumerable.is_none() && desc.configurable.is_none());
+    assert!(desc.is_data_descriptor());
+
+    // the attributes are coerced to booleans
+    let obj = object(&[("enumerable", Value::from("yes"))]);
+    let desc = to_property_descriptor(&obj).unwrap();
+    assert_eq!(desc.enumerable, Some(JsBoolean::True));
+    assert!(desc.is_generic_descriptor());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn to_invalid_descriptor() {
+    initialize_host_defined_realm().unwrap();
+    let error = to_property_descriptor(&Value::from(1.0)).unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    let getter = object(&[]);
+    let error =
+      to_property_descriptor(&object(&[("get", getter)])).unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    let obj =
+      object(&[("value", Value::from(1.0)), ("get", Value::undefined())]);
+    let error = to_property_descriptor(&obj).unwrap_err();
+    assert_eq!(error_name(error), "TypeError");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn from_descriptor_round_trip() {
+    initialize_host_defined_realm().unwrap();
+    assert!(matches!(
+      from_property_descriptor(None),
+      Value::Undefined(_)
+    ));
+    let keys = |obj: &Value| match obj {
+      Value::Object(obj) => obj.own_property_keys().unwrap(),
+      obj => panic!("expect an object, got {:?}", obj),
+    };
+
+    let desc = PropertyDescriptor {
+      value: Some(Value::from(1.0)),
+      writable: Some(JsBoolean::True),
+      get: None,
+      set: None,
+      enumerable: Some(JsBoolean::False),
+      configurable: None,
+    };
+    let obj = from_property_descriptor(Some(desc));
+    assert_eq!(
+      keys(&obj),
+      ["value".into(), "writable".into(), "enumerable".into()]
+    );
+    let desc = to_property_descriptor(&obj).unwrap();
+    assert!(matches!(desc.value, Some(Value::Number(n)) if *n == 1.0));
+    assert_eq!(desc.writable, Some(JsBoolean::True));
+    assert_eq!(desc.enumerable, Some(JsBoolean::False));
+    assert!(desc.configurable.is_none() && !desc.is_accessor_descriptor());
+
+    let getter = current_realm().intrinsic("Object");
+    let desc = PropertyDescriptor {
+      value: None,
+      writable: None,
+      get: Some(Either::A(getter.clone())),
+      set: Some(Either::B(JsUndefined)),
+      enumerable: Some(JsBoolean::True),
+      configurable: Some(JsBoolean::True),
+    };
+    let obj = from_property_descriptor(Some(desc));
+    assert_eq!(
+      keys(&obj),
+      [
+        "get".into(),
+        "set".into(),
+        "enumerable".into(),
+        "configurable".into()
+      ]
+    );
+    let desc = to_property_descriptor(&obj).unwrap();
+    assert!(
+      matches!(&desc.get, Some(Either::A(f)) if JsObject::equals(f, &getter))
+    );
+    assert!(matches!(desc.set, Some(Either::B(_))));
+    assert_eq!(desc.enumerable, Some(JsBoolean::True));
+    assert_eq!(desc.configurable, Some(JsBoolean::True));
+    assert!(!desc.is_data_descriptor());
+    pop_execution_context();
+  }
+}
@@ -0,0 +1,149 @@
+use crate::{
+  abstract_operations::operations_on_bjects::create_data_property,
+  agent::current_realm,
+  executable_code_and_execution_contexts::environment_records::Environment,
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  language_types::{object::PropertyKey, string::JsString, Value},
+};
+
+/// [[Base]] of a Reference Record.
+pub enum Base {
+  Value(Value),
+  Environment(Environment),
+  Unresolvable,
+}
+
+/// https://tc39.es/ecma262/#sec-reference-record-specification-type
+pub struct Reference {
+  pub base: Base,
+  pub referenced_name: PropertyKey,
+  pub strict: bool,
+}
+
+impl Reference {
+  /// https://tc39.es/ecma262/#sec-ispropertyreference
+  pub fn is_property_reference(&self) -> bool {
+    // 1. If V.[[Base]] is unresolvable, return false.
+    // 2. If V.[[Base]] is an Environment Record, return false; otherwise
+    //    return true.
+    matches!(self.base, Base::Value(_))
+  }
+
+  /// https://tc39.es/ecma262/#sec-isunresolvablereference
+  pub fn is_unresolvable_reference(&self) -> bool {
+    // 1. If V.[[Base]] is unresolvable, return true; otherwise return false.
+    matches!(self.base, Base::Unresolvable)
+  }
+
+  /// https://tc39.es/ecma262/#sec-getvalue
+  pub fn get_value(&self) -> Result<Value, Value> {
+    // 1. If V is not a Reference Record, return V.
+    // 2. If IsUnresolvableReference(V) is true, throw a ReferenceError exception.
+    // 3. If IsPropertyReference(V) is true, then
+    //   a. Let baseObj be ? ToObject(V.[[Base]]).
+    //   b. If IsPrivateReference(V) is true, then
+    //     i. Return ? PrivateGet(baseObj, V.[[ReferencedName]]).
+    //   c. Return ? baseObj.[[Get]](V.[[ReferencedName]], GetThisValue(V)).
+    // 4. Else,
+    //   a. Let base be V.[[Base]].
+    //   b. Assert: base is an Environment Record.
+    //   c. Return ? base.GetBindingValue(V.[[ReferencedName]], V.[[Strict]]) (see 9.1).
+    match &self.base {
+      Base::Unresolvable => Err(throw_error(
+        ErrorKind::ReferenceError,
+        &format!("{} is not defined", self.binding_name()),
+      )),
+      Base::Value(base) => {
+        let base_obj = base.to_object()?;
+        base_obj.get(&self.referenced_name, base)
+      }
+      Base::Environment(base) => {
+        base.get_binding_value(&self.binding_name(), self.strict)
+      }
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-putvalue
+  pub fn put_value(&self, w: Value) -> Result<(), Value> {
+    // 1. If V is not a Reference Record, throw a ReferenceError exception.
+    match &self.base {
+      // 2. If IsUnresolvableReference(V) is true, then
+      Base::Unresolvable => {
+        // a. If V.[[Strict]] is true, throw a ReferenceError exception.
+        if self.strict {
+          return Err(throw_error(
+            ErrorKind::ReferenceError,
+            &format!("{} is not defined", self.binding_name()),
+          ));
+        }
+        // b. Let globalObj be GetGlobalObject().
+        // c. Perform ? Set(globalObj, V.[[ReferencedName]], W, false).
+        // d. Return unused.
+        // TODO: Set, this defines the property directly
+        let global_obj = current_realm().global_object();
+        create_data_property(&global_obj, &self.referenced_name, w)?;
+        Ok(())
+      }
+      // 3. If IsPropertyReference(V) is true, then
+      Base::Value(base) => {
+        // a. Let baseObj be ? ToObject(V.[[Base]]).
+        let base_obj = base.to_object()?;
+        // b. If IsPrivateReference(V) is true, then
+        //   i. Return ? PrivateSet(baseObj, V.[[ReferencedName]], W).
+        // c. Let succeeded be ? baseObj.[[Set]](V.[[ReferencedName]], W,
+        //    GetThisValue(V)).
+        let succeeded = base_obj.set(&self.referenced_name, w, base)?;
+        // d. If succeeded is false and V.[[Strict]] is true, throw a
+        //    TypeError exception.
+        if !succeeded && self.strict {
+          return Err(throw_error(
+            ErrorKind::TypeError,
+            &format!(
+              "Cannot assign to read only property {}",
+              self.binding_name()
+            ),
+          ));
+        }
+        // e. Return unused.
+        Ok(())
+      }
+      // 4. Else,
+      Base::Environment(base) => {
+        // a. Let base be V.[[Base]].
+        // b. Assert: base is an Environment Record.
+        // c. Return ? base.SetMutableBinding(V.[[ReferencedName]], W, V.[[Strict]])
+        //    (see 9.1).
+        base.set_mutable_binding(&self.binding_name(), w, self.strict)
+      }
+    }
+  }
+
+  /// https://tc39.es/ecma262/#sec-initializereferencedbinding
+  pub fn initialize_referenced_binding(&self, w: Value) -> Result<(), Value> {
+    // 1. Assert: IsUnresolvableReference(V) is false.
+    // 2. Let base be V.[[Base]].
+    // 3. Assert: base is an Environment Record.
+    // 4. Return ? base.InitializeBinding(V.[[ReferencedName]], W).
+    match &self.base {
+      Base::Environment(base) => {
+        base.initialize_binding(&self.binding_name(), w)
+      }
+      _ => panic!("initialize a reference that isn't a binding"),
+    }
+  }
+
+  /// The [[ReferencedName]] as the name of a binding, which is always a
+  /// String, or as the description of a property key in error messages.
+  fn binding_name(&self) -> String {
+    match &self.referenced_name {
+      PropertyKey::String(name) => name.to_string_lossy(),
+      PropertyKey::Symbol(symbol) => format!(
+        "Symbol({})",
+        symbol
+          .description()
+          .map(JsString::to_string_lossy)
+          .unwrap_or_default()
+      ),
+    }
+  }
+}
@@ -1 +1,3 @@
 //! https://tc39.es/ecma262/#sec-static-semantic-rules
+
+pub mod scope_analysis;
@@ -0,0 +1,157 @@
+//! https://tc39.es/ecma262/#sec-syntax-directed-operations-scope-analysis,
+//! the top level operations are of a function body, where function
+//! declarations are var scoped.
+
+use ng262_parser::parser::nodes::{Node, NodeType, VariableKind};
+
+/// https://tc39.es/ecma262/#sec-static-semantics-boundnames, the names
+/// of the `BindingIdentifier`s.
+pub fn bound_names(node: &Node) -> Vec<&str> {
+  node
+    .bound_names()
+    .into_iter()
+    .map(|node| match node.node_type() {
+      NodeType::BindingIdentifier { name } => name.as_str(),
+      t => unreachable!("expect BindingIdentifier, got {:?}", t),
+    })
+    .collect()
+}
+
+/// https://tc39.es/ecma262/#sec-static-semantics-isconstantdeclaration
+pub fn is_constant_declaration(node: &Node) -> bool {
+  matches!(
+    node.node_type(),
+    NodeType::VariableDeclaration {
+      kind: VariableKind::Const,
+      ..
+    }
+  )
+}
+
+/// https://tc39.es/ecma262/#sec-static-semantics-toplevellexicallyscopeddeclarations
+pub fn top_level_lexically_scoped_declarations(body: &[Node]) -> Vec<&Node> {
+  // TODO: ClassDeclaration
+  body
+    .iter()
+    .filter(|statement| {
+      matches!(
+        statement.node_type(),
+        NodeType::VariableDeclaration {
+          kind: VariableKind::Let | VariableKind::Const,
+          ..
+        }
+      )
+    })
+    .collect()
+}
+
+/// https://tc39.es/ecma262/#sec-static-semantics-toplevellexicallydeclarednames
+pub fn top_level_lexically_declared_names(body: &[Node]) -> Vec<&str> {
+  top_level_lexically_scoped_declarations(body)
+    .into_iter()
+    .flat_map(bound_names)
+    .collect()
+}
+
+/// https://tc39.es/ecma262/#sec-static-semantics-toplevelvarscopeddeclarations,
+/// the `VariableDeclarator`s of `var` declarations and the function
+/// declarations.
+pub fn top_level_var_scoped_declarations(body: &[Node]) -> Vec<&Node> {
+  let mut declarations = Vec::new();
+  for statement in body {
+    let mut item = statement;
+    // LabelledItem : FunctionDeclaration
+    while let NodeType::LabeledStatement { body, .. } = item.node_type() {
+      item = body;
+    }
+    match item.node_type() {
+      NodeType::FunctionDeclaration { .. } => declarations.push(item),
+      _ => collect_var_scoped_declarations(item, &mut declarations),
+    }
+  }
+  declarations
+}
+
+/// https://tc39.es/ecma262/#sec-static-semantics-toplevelvardeclarednames
+pub fn top_level_var_declared_names(body: &[Node]) -> Vec<&str> {
+  top_level_var_scoped_declarations(body)
+    .into_iter()
+    .flat_map(bound_names)
+    .collect()
+}
+
+/// https://tc39.es/ecma262/#sec-static-semantics-varscopeddeclarations
+fn collect_var_scoped_declarations<'a>(
+  node: &'a Node,
+  declarations: &mut Vec<&'a Node>,
+) {
+  match node.node_type() {
+    NodeType::VariableDeclaration {
+      kind: VariableKind::Var,
+      declarations: declarators,
+    } => declarations.extend(declarators),
+    NodeType::IfStatement {
+      consequent,
+      alternate,
+      ..
+    } => {
+      collect_var_scoped_declarations(consequent, declarations);
+      if let Some(alternate) = alternate {
+        collect_var_scoped_declarations(alternate, declarations);
+      }
+    }
+    NodeType::LabeledStatement { body, .. }
+    | NodeType::DoWhileStatement { body, .. }
+    | NodeType::WhileStatement { body, .. }
+    | NodeType::WithStatement { body, .. } => {
+      collect_var_scoped_declarations(body, declarations)
+    }
+    NodeType::BlockStatement { body } => {
+      for statement in body {
+        collect_var_scoped_declarations(statement, declarations);
+      }
+    }
+    NodeType::SwitchStatement { cases, .. } => {
+      for case in cases {
+        if let NodeType::SwitchCase { consequent, .. } = case.node_type() {
+          for statement in consequent {
+            collect_var_scoped_declarations(statement, declarations);
+          }
+        }
+      }
+    }
+    _ => {}
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ng262_parser::parser::Parser;
+
+  use super::*;
+
+  #[test]
+  fn top_level_declarations() {
+    let script = Parser::parse_script(
+      "var a, [b] = c; let d; const e = 1; function f() { var g; }
+      h: var i;
+      switch (j) { case 1: var k; let l; }
+      { var m; let n; function o() {} }
+      if (p) var q; else while (r) do var s; while (t);",
+      None,
+    )
+    .unwrap();
+    let body = match script.node_type() {
+      NodeType::Script { body } => body,
+      _ => unreachable!(),
+    };
+    assert_eq!(
+      top_level_var_declared_names(body),
+      ["a", "b", "f", "i", "k", "m", "q", "s"]
+    );
+    assert_eq!(top_level_lexically_declared_names(body), ["d", "e"]);
+    let lexical = top_level_lexically_scoped_declarations(body);
+    assert!(!is_constant_declaration(lexical[0]));
+    assert!(is_constant_declaration(lexical[1]));
+  }
+}
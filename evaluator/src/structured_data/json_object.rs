@@ -0,0 +1,560 @@
+//! https://tc39.es/ecma262/#sec-json-object
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{
+      call, create_data_property, enumerable_own_property_names, get, get_v,
+      length_of_array_like, PropertyKind,
+    },
+    ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+  },
+  fundamental_objects::error_objects::{throw_error, ErrorKind},
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    null::JsNull,
+    object::{InternalSlot, JsObject, PropertyKey},
+    string::JsString,
+    Value,
+  },
+};
+
+/// https://tc39.es/ecma262/#sec-json-serialization-record
+struct JsonSerializationRecord {
+  replacer_function: Option<Value>,
+  stack: Vec<JsObject>,
+  indent: String,
+  gap: String,
+  property_list: Option<Vec<PropertyKey>>,
+}
+
+/// https://tc39.es/ecma262/#sec-json.stringify
+pub fn json_stringify(
+  value: &Value,
+  replacer: &Value,
+  space: &Value,
+) -> Result<Option<JsString>, Value> {
+  // 1. Let stack be a new empty List.
+  // 2. Let indent be the empty String.
+  // 3. Let PropertyList and ReplacerFunction be undefined.
+  let mut property_list = None;
+  let mut replacer_function = None;
+  // 4. If Type(replacer) is Object, then
+  if let Value::Object(replacer_object) = replacer {
+    // a. If IsCallable(replacer) is true, then
+    if replacer.is_callable() {
+      // i. Set ReplacerFunction to replacer.
+      replacer_function = Some(replacer.clone());
+    } else if replacer.is_array()? {
+      // b. Else,
+      //   i. Let isArray be ? IsArray(replacer).
+      //   ii. If isArray is true, then
+      //     1. Set PropertyList to a new empty List.
+      let mut list = Vec::<PropertyKey>::new();
+      //     2. Let len be ? LengthOfArrayLike(replacer).
+      let len = length_of_array_like(replacer_object)?;
+      //     3. Let k be 0.
+      //     4. Repeat, while k < len,
+      let mut k = 0.0;
+      while k < len {
+        // a. Let prop be ! ToString(𝔽(k)).
+        let prop = Value::from(k).to_string()?;
+        // b. Let v be ? Get(replacer, prop).
+        let v = get(replacer_object, &prop.into())?;
+        // c. Let item be undefined.
+        // d. If Type(v) is String, set item to v.
+        // e. Else if Type(v) is Number, set item to ! ToString(v).
+        // f. Else if Type(v) is Object, then
+        //   i. If v has a [[StringData]] or [[NumberData]] internal slot, set item to
+        //      ? ToString(v).
+        let item = match &v {
+          Value::String(_) | Value::Number(_) => Some(v.to_string()?),
+          Value::Object(o)
+            if o.has_internal_slot("StringData")
+              || o.has_internal_slot("NumberData") =>
+          {
+            Some(v.to_string()?)
+          }
+          _ => None,
+        };
+        // g. If item is not undefined and item is not currently an element of
+        //    PropertyList, then
+        //   i. Append item to the end of PropertyList.
+        if let Some(item) = item.map(PropertyKey::from) {
+          if !list.contains(&item) {
+            list.push(item);
+          }
+        }
+        // h. Set k to k + 1.
+        k += 1.0;
+      }
+      property_list = Some(list);
+    }
+  }
+  // 5. If Type(space) is Object, then
+  //   a. If space has a [[NumberData]] internal slot, then
+  //     i. Set space to ? ToNumber(space).
+  //   b. Else if space has a [[StringData]] internal slot, then
+  //     i. Set space to ? ToString(space).
+  let space = match space {
+    Value::Object(o) if o.has_internal_slot("NumberData") => {
+      Value::Number(space.to_number()?)
+    }
+    Value::Object(o) if o.has_internal_slot("StringData") => {
+      Value::String(space.to_string()?)
+    }
+    _ => space.clone(),
+  };
+  let gap = match &space {
+    // 6. If Type(space) is Number, then
+    //   a. Let spaceMV be ! ToIntegerOrInfinity(space).
+    //   b. Set spaceMV to min(10, spaceMV).
+    //   c. If spaceMV < 1, let gap be the empty String; otherwise let gap be the String
+    //      value containing spaceMV occurrences of the code unit 0x0020 (SPACE).
+    Value::Number(_) => {
+      let space_mv = space.to_integer_or_infinity()?.min(10.0);
+      " ".repeat(space_mv.max(0.0) as usize)
+    }
+    // 7. Else if Type(space) is String, then
+    //   a. If the length of space is 10 or less, let gap be space; otherwise let gap be
+    //      the substring of space from 0 to 10.
+    Value::String(space) => {
+      String::from_utf16_lossy(&space[..space.len().min(10)])
+    }
+    // 8. Else,
+    //   a. Let gap be the empty String.
+    _ => String::new(),
+  };
+  // 9. Let wrapper be OrdinaryObjectCreate(%Object.prototype%).
+  // TODO: %Object.prototype%
+  let wrapper = ordinary_object_create(Either::B(JsNull), &[]);
+  // 10. Perform ! CreateDataPropertyOrThrow(wrapper, the empty String, value).
+  create_data_property(&wrapper, &"".into(), value.clone())?;
+  // 11. Let state be the JSON Serialization Record { [[ReplacerFunction]]:
+  //     ReplacerFunction, [[Stack]]: stack, [[Indent]]: indent, [[Gap]]: gap,
+  //     [[PropertyList]]: PropertyList }.
+  let mut state = JsonSerializationRecord {
+    replacer_function,
+    stack: Vec::new(),
+    indent: String::new(),
+    gap,
+    property_list,
+  };
+  // 12. Return ? SerializeJSONProperty(state, the empty String, wrapper).
+  let json = serialize_json_property(&mut state, &"".into(), &wrapper)?;
+  Ok(json.map(JsString::from))
+}
+
+/// https://tc39.es/ecma262/#sec-serializejsonproperty
+///
+/// Lone surrogates are escaped, so the JSON text is a Rust `String`.
+fn serialize_json_property(
+  state: &mut JsonSerializationRecord,
+  key: &PropertyKey,
+  holder: &JsObject,
+) -> Result<Option<String>, Value> {
+  // 1. Let value be ? Get(holder, key).
+  let mut value = get(holder, key)?;
+  // 2. If Type(value) is Object or BigInt, then
+  if let Value::Object(_) | Value::BigInt(_) = value {
+    // a. Let toJSON be ? GetV(value, "toJSON").
+    let to_json = get_v(&value, &"toJSON".into())?;
+    // b. If IsCallable(toJSON) is true, then
+    if to_json.is_callable() {
+      // i. Set value to ? Call(toJSON, value, « key »).
+      value = call(&to_json, &value, &[key.clone().into()])?;
+    }
+  }
+  // 3. If state.[[ReplacerFunction]] is not undefined, then
+  if let Some(replacer_function) = &state.replacer_function {
+    // a. Set value to ? Call(state.[[ReplacerFunction]], holder, « key, value »).
+    value = call(
+      replacer_function,
+      &holder.clone().into(),
+      &[key.clone().into(), value],
+    )?;
+  }
+  // 4. If Type(value) is Object, then
+  if let Value::Object(o) = &value {
+    // a. If value has a [[NumberData]] internal slot, then
+    //   i. Set value to ? ToNumber(value).
+    // b. Else if value has a [[StringData]] internal slot, then
+    //   i. Set value to ? ToString(value).
+    // c. Else if value has a [[BooleanData]] internal slot, then
+    //   i. Set value to value.[[BooleanData]].
+    // d. Else if value has a [[BigIntData]] internal slot, then
+    //   i. Set value to value.[[BigIntData]].
+    if o.has_internal_slot("NumberData") {
+      value = Value::Number(value.to_number()?);
+    } else if o.has_internal_slot("StringData") {
+      value = Value::String(value.to_string()?);
+    } else if let Some(InternalSlot::Value(data)) = o
+      .get_internal_slot("BooleanData")
+      .or_else(|| o.get_internal_slot("BigIntData"))
+    {
+      value = data;
+    }
+  }
+  match &value {
+    // 5. If value is null, return "null".
+    Value::Null(_) => Ok(Some("null".to_owned())),
+    // 6. If value is true, return "true".
+    Value::Boolean(JsBoolean::True) => Ok(Some("true".to_owned())),
+    // 7. If value is false, return "false".
+    Value::Boolean(JsBoolean::False) => Ok(Some("false".to_owned())),
+    // 8. If Type(value) is String, return QuoteJSONString(value).
+    Value::String(value) => Ok(Some(quote_json_string(value))),
+    // 9. If Type(value) is Number, then
+    //   a. If value is finite, return ! ToString(value).
+    //   b. Return "null".
+    Value::Number(n) if n.is_finite() => {
+      Ok(Some(value.to_string()?.to_string_lossy()))
+    }
+    Value::Number(_) => Ok(Some("null".to_owned())),
+    // 10. If Type(value) is BigInt, throw a TypeError exception.
+    Value::BigInt(_) => Err(throw_error(
+      ErrorKind::TypeError,
+      "Do not know how to serialize a BigInt",
+    )),
+    // 11. If Type(value) is Object and IsCallable(value) is false, then
+    //   a. Let isArray be ? IsArray(value).
+    //   b. If isArray is true, return ? SerializeJSONArray(state, value).
+    //   c. Return ? SerializeJSONObject(state, value).
+    Value::Object(o) if !value.is_callable() => {
+      if value.is_array()? {
+        serialize_json_array(state, o).map(Some)
+      } else {
+        serialize_json_object(state, o).map(Some)
+      }
+    }
+    // 12. Return undefined.
+    _ => Ok(None),
+  }
+}
+
+/// https://tc39.es/ecma262/#sec-quotejsonstring
+fn quote_json_string(value: &JsString) -> String {
+  // 1. Let product be the String value consisting solely of the code unit 0x0022
+  //    (QUOTATION MARK).
+  let mut product = String::from('"');
+  // 2. For each code point C of StringToCodePoints(value), do
+  for c in char::decode_utf16(value.iter().copied()) {
+    let c = match c {
+      Ok(c) => c,
+      // a lone surrogate is escaped as in step b
+      Err(e) => {
+        product.push_str(&format!("\\u{:04x}", e.unpaired_surrogate()));
+        continue;
+      }
+    };
+    match c {
+      // a. If C is listed in the “Code Point” column of Table 73, then
+      //   i. Set product to the string-concatenation of product and the escape sequence
+      //      for C as specified in the “Escape Sequence” column of the corresponding row.
+      '\u{0008}' => product.push_str("\\b"),
+      '\u{0009}' => product.push_str("\\t"),
+      '\u{000A}' => product.push_str("\\n"),
+      '\u{000C}' => product.push_str("\\f"),
+      '\u{000D}' => product.push_str("\\r"),
+      '"' => product.push_str("\\\""),
+      '\\' => product.push_str("\\\\"),
+      // b. Else if C has a numeric value less than 0x0020 (SPACE), or C has the same
+      //    numeric value as a leading surrogate or trailing surrogate, then
+      //   i. Let unit be the code unit whose numeric value is that of C.
+      //   ii. Set product to the string-concatenation of product and UnicodeEscape(unit).
+      c if (c as u32) < 0x20 => {
+        product.push_str(&format!("\\u{:04x}", c as u32))
+      }
+      // c. Else,
+      //   i. Set product to the string-concatenation of product and
+      //      UTF16EncodeCodePoint(C).
+      c => product.push(c),
+    }
+  }
+  // 3. Set product to the string-concatenation of product and the code unit 0x0022
+  //    (QUOTATION MARK).
+  product.push('"');
+  // 4. Return product.
+  product
+}
+
+/// Steps 1 and 2 of `SerializeJSONObject` and `SerializeJSONArray`.
+fn enter(
+  state: &mut JsonSerializationRecord,
+  value: &JsObject,
+) -> Result<String, Value> {
+  // 1. If state.[[Stack]] contains value, throw a TypeError exception because the
+  //    structure is cyclical.
+  if state.stack.iter().any(|o| JsObject::equals(o, value)) {
+    return Err(throw_error(
+      ErrorKind::TypeError,
+      "Converting circular structure to JSON",
+    ));
+  }
+  // 2. Append value to state.[[Stack]].
+  state.stack.push(value.clone());
+  // 3. Let stepback be state.[[Indent]].
+  let stepback = state.indent.clone();
+  // 4. Set state.[[Indent]] to the string-concatenation of state.[[Indent]] and
+  //    state.[[Gap]].
+  state.indent.push_str(&state.gap);
+  Ok(stepback)
+}
+
+/// The steps joining `partial` of `SerializeJSONObject` and
+/// `SerializeJSONArray`, also removing value from the stack.
+fn leave(
+  state: &mut JsonSerializationRecord,
+  partial: Vec<String>,
+  stepback: String,
+  (open, close): (char, char),
+) -> String {
+  // If partial is empty, then
+  //   Let final be "{}" or "[]".
+  let r#final = if partial.is_empty() {
+    format!("{}{}", open, close)
+  } else if state.gap.is_empty() {
+    // Else,
+    //   If state.[[Gap]] is the empty String, then
+    //     Let properties be the String value formed by concatenating all the element
+    //     Strings of partial with each adjacent pair of Strings separated with the code
+    //     unit 0x002C (COMMA). A comma is not inserted either before the first String
+    //     or after the last String.
+    //     Let final be the string-concatenation of the open bracket, properties, and
+    //     the close bracket.
+    format!("{}{}{}", open, partial.join(","), close)
+  } else {
+    //   Else,
+    //     Let separator be the string-concatenation of the code unit 0x002C (COMMA),
+    //     the code unit 0x000A (LINE FEED), and state.[[Indent]].
+    //     Let properties be the String value formed by concatenating all the element
+    //     Strings of partial with each adjacent pair of Strings separated with
+    //     separator. The separator String is not inserted either before the first
+    //     String or after the last String.
+    //     Let final be the string-concatenation of the open bracket, the code unit
+    //     0x000A (LINE FEED), state.[[Indent]], properties, the code unit 0x000A
+    //     (LINE FEED), stepback, and the close bracket.
+    let separator = format!(",\n{}", state.indent);
+    format!(
+      "{}\n{}{}\n{}{}",
+      open,
+      state.indent,
+      partial.join(&separator),
+      stepback,
+      close
+    )
+  };
+  // Remove the last element of state.[[Stack]].
+  state.stack.pop();
+  // Set state.[[Indent]] to stepback.
+  state.indent = stepback;
+  // Return final.
+  r#final
+}
+
+/// https://tc39.es/ecma262/#sec-serializejsonobject
+fn serialize_json_object(
+  state: &mut JsonSerializationRecord,
+  value: &JsObject,
+) -> Result<String, Value> {
+  // 1-4.
+  let stepback = enter(state, value)?;
+  // 5. If state.[[PropertyList]] is not undefined, then
+  //   a. Let K be state.[[PropertyList]].
+  // 6. Else,
+  //   a. Let K be ? EnumerableOwnPropertyNames(value, key).
+  let k = match &state.property_list {
+    Some(property_list) => property_list.clone(),
+    None => enumerable_own_property_names(value, PropertyKind::Key)?
+      .iter()
+      .map(|key| key.to_property_key().expect("a String key"))
+      .collect(),
+  };
+  // 7. Let partial be a new empty List.
+  let mut partial = Vec::new();
+  // 8. For each element P of K, do
+  for p in k {
+    // a. Let strP be ? SerializeJSONProperty(state, P, value).
+    // b. If strP is not undefined, then
+    if let Some(str_p) = serialize_json_property(state, &p, value)? {
+      // i. Let member be QuoteJSONString(P).
+      let mut member = match &p {
+        PropertyKey::String(p) => quote_json_string(p),
+        PropertyKey::Symbol(_) => unreachable!("JSON keys are strings"),
+      };
+      // ii. Set member to the string-concatenation of member and ":".
+      member.push(':');
+      // iii. If state.[[Gap]] is not the empty String, then
+      //   1. Set member to the string-concatenation of member and the code unit 0x0020
+      //      (SPACE).
+      if !state.gap.is_empty() {
+        member.push(' ');
+      }
+      // iv. Set member to the string-concatenation of member and strP.
+      member.push_str(&str_p);
+      // v. Append member to partial.
+      partial.push(member);
+    }
+  }
+  // 9-12.
+  Ok(leave(state, partial, stepback, ('{', '}')))
+}
+
+/// https://tc39.es/ecma262/#sec-serializejsonarray
+fn serialize_json_array(
+  state: &mut JsonSerializationRecord,
+  value: &JsObject,
+) -> Result<String, Value> {
+  // 1-4.
+  let stepback = enter(state, value)?;
+  // 5. Let partial be a new empty List.
+  let mut partial = Vec::new();
+  // 6. Let len be ? LengthOfArrayLike(value).
+  let len = length_of_array_like(value)?;
+  // 7. Let index be 0.
+  let mut index = 0.0;
+  // 8. Repeat, while index < len,
+  while index < len {
+    // a. Let strP be ? SerializeJSONProperty(state, ! ToString(𝔽(index)), value).
+    let key = Value::from(index).to_string()?.into();
+    let str_p = serialize_json_property(state, &key, value)?;
+    // b. If strP is undefined, then
+    //   i. Append "null" to partial.
+    // c. Else,
+    //   i. Append strP to partial.
+    partial.push(str_p.unwrap_or_else(|| "null".to_owned()));
+    // d. Set index to index + 1.
+    index += 1.0;
+  }
+  // 9-12.
+  Ok(leave(state, partial, stepback, ('[', ']')))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::{
+      operations_on_bjects::create_array_from_list,
+      ordinary_object_internal_methods_and_internal_slots::ORDINARY_INTERNAL_METHODS,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    language_types::object::InternalMethods,
+  };
+
+  static FUNCTION: InternalMethods = InternalMethods {
+    call: Some(|_, _, _| Ok(Value::undefined())),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+  /// A `toJSON` returning its key.
+  static TO_JSON: InternalMethods = InternalMethods {
+    call: Some(|_, _, arguments| Ok(arguments[0].clone())),
+    ..ORDINARY_INTERNAL_METHODS
+  };
+
+  fn object(properties: &[(&str, Value)]) -> Value {
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    for (key, value) in properties {
+      create_data_property(&o, &(*key).into(), value.clone()).unwrap();
+    }
+    o.into()
+  }
+
+  fn stringify(value: &Value) -> Option<JsString> {
+    json_stringify(value, &Value::undefined(), &Value::undefined()).unwrap()
+  }
+
+  #[test]
+  fn primitives() {
+    assert_eq!(stringify(&Value::null()).unwrap(), "null");
+    assert_eq!(stringify(&Value::from(true)).unwrap(), "true");
+    assert_eq!(
+      stringify(&Value::from("a\"\\\n\u{1}")).unwrap(),
+      r#""a\"\\\n\u0001""#
+    );
+    assert_eq!(stringify(&Value::undefined()), None);
+    let f = JsObject::new(Either::B(JsNull), true, &FUNCTION);
+    assert_eq!(stringify(&f.into()), None);
+  }
+
+  #[test]
+  fn numbers() {
+    initialize_host_defined_realm().unwrap();
+    assert_eq!(stringify(&Value::from(1.5)).unwrap(), "1.5");
+    assert_eq!(stringify(&Value::from(-0.0)).unwrap(), "0");
+    assert_eq!(stringify(&Value::from(1e21)).unwrap(), "1e+21");
+    assert_eq!(stringify(&Value::from(f64::NAN)).unwrap(), "null");
+    assert_eq!(stringify(&Value::from(f64::INFINITY)).unwrap(), "null");
+    let big = Value::BigInt(num_bigint::BigInt::from(1).into());
+    assert!(
+      json_stringify(&big, &Value::undefined(), &Value::undefined()).is_err()
+    );
+    pop_execution_context();
+  }
+
+  #[test]
+  fn nested() {
+    initialize_host_defined_realm().unwrap();
+    let f = JsObject::new(Either::B(JsNull), true, &FUNCTION);
+    let array = create_array_from_list(&[
+      Value::from(1.0),
+      Value::undefined(),
+      f.clone().into(),
+    ]);
+    let value = object(&[
+      ("a", object(&[("b", Value::from("c"))])),
+      ("array", array.into()),
+      ("undefined", Value::undefined()),
+      ("f", f.into()),
+      ("empty", object(&[])),
+    ]);
+    assert_eq!(
+      stringify(&value).unwrap(),
+      r#"{"a":{"b":"c"},"array":[1,null,null],"empty":{}}"#
+    );
+    pop_execution_context();
+  }
+
+  #[test]
+  fn cycle() {
+    initialize_host_defined_realm().unwrap();
+    let o = ordinary_object_create(Either::B(JsNull), &[]);
+    let inner = object(&[("o", o.clone().into())]);
+    create_data_property(&o, &"inner".into(), inner).unwrap();
+    assert!(json_stringify(
+      &o.into(),
+      &Value::undefined(),
+      &Value::undefined()
+    )
+    .is_err());
+    // the same object twice isn't a cycle
+    let shared = object(&[]);
+    let array = create_array_from_list(&[shared.clone(), shared]);
+    assert_eq!(stringify(&array.into()).unwrap(), "[{},{}]");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn to_json() {
+    let to_json = JsObject::new(Either::B(JsNull), true, &TO_JSON);
+    let value = object(&[("key", object(&[("toJSON", to_json.into())]))]);
+    assert_eq!(stringify(&value).unwrap(), r#"{"key":"key"}"#);
+  }
+
+  #[test]
+  fn replacer_and_space() {
+    initialize_host_defined_realm().unwrap();
+    let value = object(&[("a", Value::from(1.0))]);
+    let replacer =
+      create_array_from_list(&[Value::from("a"), Value::from("a")]).into();
+    let json = json_stringify(&value, &replacer, &Value::from(2.0)).unwrap();
+    assert_eq!(json.unwrap(), "{\n  \"a\": 1\n}");
+    let array = create_array_from_list(&[Value::from(1.0), object(&[])]);
+    let json =
+      json_stringify(&array.into(), &Value::undefined(), &"--".into()).unwrap();
+    assert_eq!(json.unwrap(), "[\n--1,\n--{}\n]");
+    pop_execution_context();
+  }
+}
@@ -0,0 +1,3 @@
+//! https://tc39.es/ecma262/#sec-structured-data
+
+pub mod json_object;
@@ -0,0 +1,16 @@
+//! Helpers shared by the unit tests.
+
+use crate::{
+  abstract_operations::operations_on_bjects::get,
+  language_types::{string::JsString, Value},
+};
+
+/// The `name` of a thrown error object, e.g. `TypeError`.
+pub fn error_name(error: Value) -> JsString {
+  match error {
+    Value::Object(error) => {
+      get(&error, &"name".into()).unwrap().to_string().unwrap()
+    }
+    error => panic!("expect an error, got {:?}", error),
+  }
+}
@@ -0,0 +1,3 @@
+//! https://tc39.es/ecma262/#sec-text-processing
+
+pub mod string_objects;
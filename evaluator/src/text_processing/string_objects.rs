@@ -0,0 +1,1034 @@
+//! https://tc39.es/ecma262/#sec-string-objects
+
+use crate::{
+  abstract_operations::{
+    operations_on_bjects::{create_array_from_list, get},
+    ordinary_object_internal_methods_and_internal_slots::ordinary_object_create,
+    testing_and_comparison_operations::require_object_coercible,
+    type_conversion::canonical_numeric_index_string,
+  },
+  executable_code_and_execution_contexts::realm::Realm,
+  fundamental_objects::{
+    error_objects::{throw_error, ErrorKind},
+    object_objects::{add_methods, argument},
+  },
+  helpers::Either,
+  language_types::{
+    boolean::JsBoolean,
+    object::{InternalSlot, JsObject, PropertyKey},
+    string::{
+      code_point_at, is_string_well_formed_unicode, string_index_of, JsString,
+    },
+    Value,
+  },
+  specification_types::property_descriptor::PropertyDescriptor,
+};
+
+/// https://tc39.es/ecma262/#sec-getsubstitution
+///
+/// `position` and all lengths are measured in UTF-16 code units.
+pub fn get_substitution(
+  matched: &JsString,
+  str: &JsString,
+  position: usize,
+  captures: &[Option<JsString>],
+  named_captures: &Value,
+  replacement_template: &JsString,
+) -> Result<JsString, Value> {
+  let template = replacement_template;
+  let is_digit = |c: Option<&u16>| {
+    c.is_some_and(|&c| (b'0' as u16..=b'9' as u16).contains(&c))
+  };
+  let digit = |c: u16| (c - b'0' as u16) as usize;
+  // 1. Let stringLength be the length of str.
+  let string_length = str.len();
+  // 2. Assert: position ≤ stringLength.
+  assert!(position <= string_length);
+  // 3. Let result be the empty String.
+  let mut result: Vec<u16> = Vec::new();
+  // 4. Let templateRemainder be replacementTemplate.
+  let mut template_remainder = &template[..];
+  // 5. Repeat, while templateRemainder is not the empty String,
+  while !template_remainder.is_empty() {
+    // a. NOTE: The following steps isolate ref (a prefix of templateRemainder), determine refReplacement (its replacement), and then append that replacement to result.
+    let (ref_length, ref_replacement): (usize, Vec<u16>) =
+      match template_remainder {
+        // b. If templateRemainder starts with "$$", then
+        [0x24, 0x24, ..] => {
+          // i. Let ref be "$$".
+          // ii. Let refReplacement be "$".
+          (2, vec![0x24])
+        }
+        // c. Else if templateRemainder starts with "$`", then
+        [0x24, 0x60, ..] => {
+          // i. Let ref be "$`".
+          // ii. Let refReplacement be the substring of str from 0 to position.
+          (2, str[..position].to_vec())
+        }
+        // d. Else if templateRemainder starts with "$&", then
+        [0x24, 0x26, ..] => {
+          // i. Let ref be "$&".
+          // ii. Let refReplacement be matched.
+          (2, matched.to_vec())
+        }
+        // e. Else if templateRemainder starts with "$'" (0x0024 (DOLLAR SIGN) followed by 0x0027 (APOSTROPHE)), then
+        [0x24, 0x27, ..] => {
+          // i. Let ref be "$'".
+          // ii. Let matchLength be the length of matched.
+          let match_length = matched.len();
+          // iii. Let tailPos be position + matchLength.
+          let tail_pos = position + match_length;
+          // iv. Let refReplacement be the substring of str from min(tailPos, stringLength).
+          // v. NOTE: tailPos can exceed stringLength only if this abstract operation was invoked by a call to the intrinsic @@replace method of %RegExp.prototype% on an object whose "exec" property is not the intrinsic %RegExp.prototype.exec%.
+          (2, str[tail_pos.min(string_length)..].to_vec())
+        }
+        // f. Else if templateRemainder starts with "$" followed by 1 or more decimal digits, then
+        [0x24, first, rest @ ..] if is_digit(Some(first)) => {
+          // i. If templateRemainder starts with "$" followed by 2 or more decimal digits, let digitCount be 2. Otherwise, let digitCount be 1.
+          let mut digit_count = if is_digit(rest.first()) { 2 } else { 1 };
+          // ii. Let digits be the substring of templateRemainder from 1 to 1 + digitCount.
+          // iii. Let index be ℝ(StringToNumber(digits)).
+          let mut index = if digit_count == 2 {
+            digit(*first) * 10 + digit(rest[0])
+          } else {
+            digit(*first)
+          };
+          // iv. Assert: 0 ≤ index ≤ 99.
+          // v. Let captureLen be the number of elements in captures.
+          let capture_len = captures.len();
+          // vi. If index > captureLen and digitCount = 2, then
+          if index > capture_len && digit_count == 2 {
+            // 1. NOTE: When a two-digit replacement pattern specifies an index exceeding the count of capturing groups, it is reinterpreted as a one-digit replacement pattern followed by a literal digit.
+            // 2. Set digitCount to 1.
+            digit_count = 1;
+            // 3. Set digits to the substring of digits from 0 to 1.
+            // 4. Set index to ℝ(StringToNumber(digits)).
+            index = digit(*first);
+          }
+          // vii. Let ref be the substring of templateRemainder from 0 to 1 + digitCount.
+          let r#ref = &template_remainder[..1 + digit_count];
+          // viii. If 1 ≤ index ≤ captureLen, then
+          let ref_replacement = if (1..=capture_len).contains(&index) {
+            // 1. Let capture be captures[index - 1].
+            match &captures[index - 1] {
+              // 2. If capture is undefined, then
+              //   a. Let refReplacement be the empty String.
+              None => vec![],
+              // 3. Else,
+              //   a. Let refReplacement be capture.
+              Some(capture) => capture.to_vec(),
+            }
+          } else {
+            // ix. Else,
+            //   1. Let refReplacement be ref.
+            r#ref.to_vec()
+          };
+          (r#ref.len(), ref_replacement)
+        }
+        // g. Else if templateRemainder starts with "$<", then
+        [0x24, 0x3c, ..] => {
+          // i. Let gtPos be StringIndexOf(templateRemainder, ">", 0).
+          let gt_pos = template_remainder.iter().position(|&c| c == 0x3e);
+          match (gt_pos, named_captures) {
+            // ii. If gtPos = -1 or namedCaptures is undefined, then
+            //   1. Let ref be "$<".
+            //   2. Let refReplacement be ref.
+            (None, _) | (_, Value::Undefined(_)) => (2, vec![0x24, 0x3c]),
+            // iii. Else,
+            (Some(gt_pos), Value::Object(named_captures)) => {
+              // 1. Let ref be the substring of templateRemainder from 0 to gtPos + 1.
+              // 2. Let groupName be the substring of templateRemainder from 2 to gtPos.
+              let group_name =
+                String::from_utf16_lossy(&template_remainder[2..gt_pos]);
+              // 3. Assert: namedCaptures is an Object.
+              // 4. Let capture be ? Get(namedCaptures, groupName).
+              let capture = get(named_captures, &group_name.into())?;
+              let ref_replacement = match capture {
+                // 5. If capture is undefined, then
+                //   a. Let refReplacement be the empty String.
+                Value::Undefined(_) => vec![],
+                // 6. Else,
+                //   a. Let refReplacement be ? ToString(capture).
+                capture => capture.to_string()?.to_vec(),
+              };
+              (gt_pos + 1, ref_replacement)
+            }
+            (Some(_), _) => {
+              unreachable!("namedCaptures is either undefined or an Object")
+            }
+          }
+        }
+        // h. Else,
+        //   i. Let ref be the substring of templateRemainder from 0 to 1.
+        //   ii. Let refReplacement be ref.
+        _ => (1, template_remainder[..1].to_vec()),
+      };
+    // i. Let refLength be the length of ref.
+    // j. Set templateRemainder to the substring of templateRemainder from refLength.
+    template_remainder = &template_remainder[ref_length..];
+    // k. Set result to the string-concatenation of result and refReplacement.
+    result.extend(ref_replacement);
+  }
+  // 6. Return result.
+  Ok(result.into())
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.towellformed, the steps
+/// after ToString on the UTF-16 code units of S.
+pub fn to_well_formed(s: &[u16]) -> Vec<u16> {
+  // 3. Let strLen be the length of S.
+  let str_len = s.len();
+  // 4. Let k be 0.
+  let mut k = 0;
+  // 5. Let result be the empty String.
+  let mut result = Vec::with_capacity(str_len);
+  // 6. Repeat, while k < strLen,
+  while k < str_len {
+    // a. Let cp be CodePointAt(S, k).
+    let cp = code_point_at(s, k);
+    // b. If cp.[[IsUnpairedSurrogate]] is true, then
+    if cp.is_unpaired_surrogate {
+      // i. Set result to the string-concatenation of result and 0xFFFD
+      //    (REPLACEMENT CHARACTER).
+      result.push(0xFFFD);
+    // c. Else,
+    } else {
+      // i. Set result to the string-concatenation of result and
+      //    UTF16EncodeCodePoint(cp.[[CodePoint]]).
+      result.extend_from_slice(&s[k..k + cp.code_unit_count]);
+    }
+    // d. Set k to k + cp.[[CodeUnitCount]].
+    k += cp.code_unit_count;
+  }
+  // 7. Return result.
+  result
+}
+
+/// https://tc39.es/ecma262/#sec-stringgetownproperty, `s` is the
+/// [[StringData]] of the String exotic object.
+pub fn string_get_own_property(
+  s: &JsString,
+  p: &PropertyKey,
+) -> Option<PropertyDescriptor> {
+  // 1. If P is not a String, return undefined.
+  let p = match p {
+    PropertyKey::String(p) => p,
+    PropertyKey::Symbol(_) => return None,
+  };
+  // 2. Let index be CanonicalNumericIndexString(P).
+  // 3. If index is undefined, return undefined.
+  let index = *canonical_numeric_index_string(&p.to_string_lossy())?;
+  // 4. If IsIntegralNumber(index) is false, return undefined.
+  // 5. If index is -0𝔽, return undefined.
+  if index.fract() != 0.0 || (index == 0.0 && index.is_sign_negative()) {
+    return None;
+  }
+  // 6. Let str be S.[[StringData]].
+  // 7. Assert: str is a String.
+  let str = s;
+  // 8. Let len be the length of str.
+  // 9. If ℝ(index) < 0 or len ≤ ℝ(index), return undefined.
+  if index < 0.0 || str.len() as f64 <= index {
+    return None;
+  }
+  // 10. Let resultStr be the substring of str from ℝ(index) to ℝ(index) + 1.
+  let index = index as usize;
+  let result_str = substring(str, index, index + 1);
+  // 11. Return the PropertyDescriptor { [[Value]]: resultStr, [[Writable]]:
+  //     false, [[Enumerable]]: true, [[Configurable]]: false }.
+  Some(PropertyDescriptor {
+    value: Some(result_str),
+    writable: Some(JsBoolean::False),
+    get: None,
+    set: None,
+    enumerable: Some(JsBoolean::True),
+    configurable: Some(JsBoolean::False),
+  })
+}
+
+/// `? RequireObjectCoercible(this value)` and `? ToString(O)`, the first
+/// two steps of most String.prototype methods, S is in UTF-16 code units.
+fn this_string(this: &Value, method: &str) -> Result<JsString, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  let o = require_object_coercible(this).map_err(|_| {
+    throw_error(
+      ErrorKind::TypeError,
+      &format!("String.prototype.{} called on null or undefined", method),
+    )
+  })?;
+  // 2. Let S be ? ToString(O).
+  o.to_string()
+}
+
+/// The substring of `s` from `from` to `to`.
+fn substring(s: &[u16], from: usize, to: usize) -> Value {
+  Value::from(JsString::from(&s[from..to]))
+}
+
+/// ToIntegerOrInfinity of `argument` clamped between 0 and `len`.
+fn clamped_position(argument: &Value, len: usize) -> Result<usize, Value> {
+  Ok(argument.to_integer_or_infinity()?.clamp(0.0, len as f64) as usize)
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.at
+fn at(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "at")?;
+  // 3. Let len be the length of S.
+  let len = s.len() as f64;
+  // 4. Let relativeIndex be ? ToIntegerOrInfinity(index).
+  let relative_index = argument(arguments, 0).to_integer_or_infinity()?;
+  // 5. If relativeIndex ≥ 0, then
+  //   a. Let k be relativeIndex.
+  // 6. Else,
+  //   a. Let k be len + relativeIndex.
+  let k = if relative_index >= 0.0 {
+    relative_index
+  } else {
+    len + relative_index
+  };
+  // 7. If k < 0 or k ≥ len, return undefined.
+  if k < 0.0 || k >= len {
+    return Ok(Value::undefined());
+  }
+  // 8. Return the substring of S from k to k + 1.
+  Ok(substring(&s, k as usize, k as usize + 1))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.charat
+fn char_at(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "charAt")?;
+  // 3. Let position be ? ToIntegerOrInfinity(pos).
+  let position = argument(arguments, 0).to_integer_or_infinity()?;
+  // 4. Let size be the length of S.
+  let size = s.len() as f64;
+  // 5. If position < 0 or position ≥ size, return the empty String.
+  if position < 0.0 || position >= size {
+    return Ok(Value::from(""));
+  }
+  // 6. Return the substring of S from position to position + 1.
+  Ok(substring(&s, position as usize, position as usize + 1))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.charcodeat
+fn char_code_at(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "charCodeAt")?;
+  // 3. Let position be ? ToIntegerOrInfinity(pos).
+  let position = argument(arguments, 0).to_integer_or_infinity()?;
+  // 4. Let size be the length of S.
+  let size = s.len() as f64;
+  // 5. If position < 0 or position ≥ size, return NaN.
+  if position < 0.0 || position >= size {
+    return Ok(Value::from(f64::NAN));
+  }
+  // 6. Return the Number value for the numeric value of the code unit at index
+  //    position within the String S.
+  Ok(Value::from(s[position as usize] as f64))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.codepointat
+fn code_point_at_method(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "codePointAt")?;
+  // 3. Let position be ? ToIntegerOrInfinity(pos).
+  let position = argument(arguments, 0).to_integer_or_infinity()?;
+  // 4. Let size be the length of S.
+  let size = s.len() as f64;
+  // 5. If position < 0 or position ≥ size, return undefined.
+  if position < 0.0 || position >= size {
+    return Ok(Value::undefined());
+  }
+  // 6. Let cp be CodePointAt(S, position).
+  let cp = code_point_at(&s, position as usize);
+  // 7. Return 𝔽(cp.[[CodePoint]]).
+  Ok(Value::from(cp.code_point as f64))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.endswith
+fn ends_with(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "endsWith")?;
+  // 3. Let isRegExp be ? IsRegExp(searchString).
+  // 4. If isRegExp is true, throw a TypeError exception.
+  // TODO: IsRegExp
+  // 5. Let searchStr be ? ToString(searchString).
+  let search_str: Vec<u16> = argument(arguments, 0).to_string()?.to_vec();
+  // 6. Let len be the length of S.
+  let len = s.len();
+  // 7. If endPosition is undefined, let pos be len; else let pos be ?
+  //    ToIntegerOrInfinity(endPosition).
+  // 8. Let end be the result of clamping pos between 0 and len.
+  let end = match argument(arguments, 1) {
+    Value::Undefined(_) => len,
+    end_position => clamped_position(&end_position, len)?,
+  };
+  // 9. Let searchLength be the length of searchStr.
+  let search_length = search_str.len();
+  // 10. If searchLength = 0, return true.
+  if search_length == 0 {
+    return Ok(true.into());
+  }
+  // 11. Let start be end - searchLength.
+  // 12. If start < 0, return false.
+  let start = match end.checked_sub(search_length) {
+    Some(start) => start,
+    None => return Ok(false.into()),
+  };
+  // 13. Let substring be the substring of S from start to end.
+  // 14. If substring is searchStr, return true.
+  // 15. Return false.
+  Ok((s[start..end] == search_str[..]).into())
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.includes
+fn includes(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "includes")?;
+  // 3. Let isRegExp be ? IsRegExp(searchString).
+  // 4. If isRegExp is true, throw a TypeError exception.
+  // TODO: IsRegExp
+  // 5. Let searchStr be ? ToString(searchString).
+  let search_str: Vec<u16> = argument(arguments, 0).to_string()?.to_vec();
+  // 6. Let pos be ? ToIntegerOrInfinity(position).
+  // 7. Assert: If position is undefined, then pos is 0.
+  // 8. Let len be the length of S.
+  // 9. Let start be the result of clamping pos between 0 and len.
+  let start = clamped_position(&argument(arguments, 1), s.len())?;
+  // 10. Let index be StringIndexOf(S, searchStr, start).
+  // 11. If index is not-found, return false.
+  // 12. Return true.
+  Ok(string_index_of(&s, &search_str, start).is_some().into())
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.indexof
+fn index_of(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "indexOf")?;
+  // 3. Let searchStr be ? ToString(searchString).
+  let search_str: Vec<u16> = argument(arguments, 0).to_string()?.to_vec();
+  // 4. Let pos be ? ToIntegerOrInfinity(position).
+  // 5. Assert: If position is undefined, then pos is 0.
+  // 6. Let len be the length of S.
+  // 7. Let start be the result of clamping pos between 0 and len.
+  let start = clamped_position(&argument(arguments, 1), s.len())?;
+  // 8. Let result be StringIndexOf(S, searchStr, start).
+  // 9. If result is not-found, return -1𝔽.
+  // 10. Return 𝔽(result).
+  Ok(match string_index_of(&s, &search_str, start) {
+    Some(result) => Value::from(result as f64),
+    None => Value::from(-1.0),
+  })
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.iswellformed
+fn is_well_formed(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "isWellFormed")?;
+  // 3. Return IsStringWellFormedUnicode(S).
+  Ok(is_string_well_formed_unicode(&s).into())
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.lastindexof
+fn last_index_of(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "lastIndexOf")?;
+  // 3. Let searchStr be ? ToString(searchString).
+  let search_str: Vec<u16> = argument(arguments, 0).to_string()?.to_vec();
+  // 4. Let numPos be ? ToNumber(position).
+  let num_pos = argument(arguments, 1).to_number()?;
+  // 5. Assert: If position is undefined, then numPos is NaN.
+  // 6. If numPos is NaN, let pos be +∞; otherwise, let pos be !
+  //    ToIntegerOrInfinity(numPos).
+  let pos = if num_pos.is_nan() {
+    f64::INFINITY
+  } else {
+    Value::from(num_pos).to_integer_or_infinity()?
+  };
+  // 7. Let len be the length of S.
+  // 8. Let searchLen be the length of searchStr.
+  // 9. Let start be the result of clamping pos between 0 and len - searchLen.
+  let start = match s.len().checked_sub(search_str.len()) {
+    Some(max) => pos.clamp(0.0, max as f64) as usize,
+    None => return Ok(Value::from(-1.0)),
+  };
+  // 10. If searchStr is the empty String, return 𝔽(start).
+  // 11. For each integer i such that 0 ≤ i ≤ start, in descending order, do
+  //   a. Let candidate be the substring of S from i to i + searchLen.
+  //   b. If candidate is searchStr, return 𝔽(i).
+  // 12. Return -1𝔽.
+  Ok(
+    match (0..=start)
+      .rev()
+      .find(|&i| s[i..i + search_str.len()] == search_str[..])
+    {
+      Some(i) => Value::from(i as f64),
+      None => Value::from(-1.0),
+    },
+  )
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.slice
+fn slice(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "slice")?;
+  // 3. Let len be the length of S.
+  let len = s.len() as f64;
+  // 4. Let intStart be ? ToIntegerOrInfinity(start).
+  let int_start = argument(arguments, 0).to_integer_or_infinity()?;
+  // 5. If intStart = -∞, let from be 0.
+  // 6. Else if intStart < 0, let from be max(len + intStart, 0).
+  // 7. Else, let from be min(intStart, len).
+  let from = if int_start < 0.0 {
+    (len + int_start).max(0.0)
+  } else {
+    int_start.min(len)
+  };
+  // 8. If end is undefined, let intEnd be len; else let intEnd be ?
+  //    ToIntegerOrInfinity(end).
+  let int_end = match argument(arguments, 1) {
+    Value::Undefined(_) => len,
+    end => end.to_integer_or_infinity()?,
+  };
+  // 9. If intEnd = -∞, let to be 0.
+  // 10. Else if intEnd < 0, let to be max(len + intEnd, 0).
+  // 11. Else, let to be min(intEnd, len).
+  let to = if int_end < 0.0 {
+    (len + int_end).max(0.0)
+  } else {
+    int_end.min(len)
+  };
+  // 12. If from ≥ to, return the empty String.
+  if from >= to {
+    return Ok(Value::from(""));
+  }
+  // 13. Return the substring of S from from to to.
+  Ok(substring(&s, from as usize, to as usize))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.split, without the
+/// @@split method of the separator.
+fn split(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  let separator = argument(arguments, 0);
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. If separator is neither undefined nor null, then
+  //   a. Let splitter be ? GetMethod(separator, @@split).
+  //   b. If splitter is not undefined, then
+  //     i. Return ? Call(splitter, separator, « O, limit »).
+  // TODO: @@split
+  // 3. Let S be ? ToString(O).
+  let s = this_string(this, "split")?;
+  // 4. If limit is undefined, let lim be 2^32 - 1; else let lim be ℝ(?
+  //    ToUint32(limit)).
+  let lim = match argument(arguments, 1) {
+    Value::Undefined(_) => u32::MAX,
+    limit => limit.to_uint32()?,
+  } as usize;
+  // 5. Let R be ? ToString(separator).
+  let r: Vec<u16> = separator.to_string()?.to_vec();
+  // 6. If lim = 0, then
+  if lim == 0 {
+    // a. Return CreateArrayFromList(« »).
+    return Ok(create_array_from_list(&[]).into());
+  }
+  // 7. If separator is undefined, then
+  if let Value::Undefined(_) = separator {
+    // a. Return CreateArrayFromList(« S »).
+    return Ok(create_array_from_list(&[substring(&s, 0, s.len())]).into());
+  }
+  // 8. Let separatorLength be the length of R.
+  let separator_length = r.len();
+  // 9. If separatorLength = 0, then
+  if separator_length == 0 {
+    // a. Let head be the substring of S from 0 to lim.
+    // b. Let codeUnits be a List consisting of the sequence of code units that
+    //    are the elements of head.
+    // c. Return CreateArrayFromList(codeUnits).
+    let code_units = (0..lim.min(s.len()))
+      .map(|i| substring(&s, i, i + 1))
+      .collect::<Vec<_>>();
+    return Ok(create_array_from_list(&code_units).into());
+  }
+  // 10. If S is the empty String, return CreateArrayFromList(« S »).
+  if s.is_empty() {
+    return Ok(create_array_from_list(&[Value::from("")]).into());
+  }
+  // 11. Let substrings be a new empty List.
+  let mut substrings = Vec::new();
+  // 12. Let i be 0.
+  let mut i = 0;
+  // 13. Let j be StringIndexOf(S, R, 0).
+  let mut j = string_index_of(&s, &r, 0);
+  // 14. Repeat, while j is not not-found,
+  while let Some(index) = j {
+    // a. Let T be the substring of S from i to j.
+    // b. Append T to substrings.
+    substrings.push(substring(&s, i, index));
+    // c. If the number of elements in substrings is lim, return
+    //    CreateArrayFromList(substrings).
+    if substrings.len() == lim {
+      return Ok(create_array_from_list(&substrings).into());
+    }
+    // d. Set i to j + separatorLength.
+    i = index + separator_length;
+    // e. Set j to StringIndexOf(S, R, i).
+    j = string_index_of(&s, &r, i);
+  }
+  // 15. Let T be the substring of S from i.
+  // 16. Append T to substrings.
+  substrings.push(substring(&s, i, s.len()));
+  // 17. Return CreateArrayFromList(substrings).
+  Ok(create_array_from_list(&substrings).into())
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.startswith
+fn starts_with(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "startsWith")?;
+  // 3. Let isRegExp be ? IsRegExp(searchString).
+  // 4. If isRegExp is true, throw a TypeError exception.
+  // TODO: IsRegExp
+  // 5. Let searchStr be ? ToString(searchString).
+  let search_str: Vec<u16> = argument(arguments, 0).to_string()?.to_vec();
+  // 6. Let len be the length of S.
+  let len = s.len();
+  // 7. If position is undefined, let pos be 0; else let pos be ?
+  //    ToIntegerOrInfinity(position).
+  // 8. Let start be the result of clamping pos between 0 and len.
+  let start = clamped_position(&argument(arguments, 1), len)?;
+  // 9. Let searchLength be the length of searchStr.
+  let search_length = search_str.len();
+  // 10. If searchLength = 0, return true.
+  if search_length == 0 {
+    return Ok(true.into());
+  }
+  // 11. Let end be start + searchLength.
+  let end = start + search_length;
+  // 12. If end > len, return false.
+  if end > len {
+    return Ok(false.into());
+  }
+  // 13. Let substring be the substring of S from start to end.
+  // 14. If substring is searchStr, return true.
+  // 15. Return false.
+  Ok((s[start..end] == search_str[..]).into())
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.substring
+fn substring_method(
+  this: &Value,
+  arguments: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "substring")?;
+  // 3. Let len be the length of S.
+  let len = s.len();
+  // 4. Let intStart be ? ToIntegerOrInfinity(start).
+  // 6. Let finalStart be the result of clamping intStart between 0 and len.
+  let final_start = clamped_position(&argument(arguments, 0), len)?;
+  // 5. If end is undefined, let intEnd be len; else let intEnd be ?
+  //    ToIntegerOrInfinity(end).
+  // 7. Let finalEnd be the result of clamping intEnd between 0 and len.
+  let final_end = match argument(arguments, 1) {
+    Value::Undefined(_) => len,
+    end => clamped_position(&end, len)?,
+  };
+  // 8. Let from be min(finalStart, finalEnd).
+  let from = final_start.min(final_end);
+  // 9. Let to be max(finalStart, finalEnd).
+  let to = final_start.max(final_end);
+  // 10. Return the substring of S from from to to.
+  Ok(substring(&s, from, to))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.tolowercase
+fn to_lower_case(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "toLowerCase")?;
+  // 3. Let sText be StringToCodePoints(S).
+  // 4. Let lowerText be toLowercase(sText) according to the Unicode Default
+  //    Case Conversion algorithm.
+  // 5. Let L be CodePointsToString(lowerText).
+  // 6. Return L.
+  Ok(Value::from(String::from_utf16_lossy(&s).to_lowercase()))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.touppercase
+fn to_upper_case(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // It behaves in exactly the same way as String.prototype.toLowerCase,
+  // except that the String is mapped using the toUppercase algorithm of the
+  // Unicode Default Case Conversion.
+  let s = this_string(this, "toUpperCase")?;
+  Ok(Value::from(String::from_utf16_lossy(&s).to_uppercase()))
+}
+
+/// https://tc39.es/ecma262/#sec-string.prototype.towellformed
+fn to_well_formed_method(
+  this: &Value,
+  _: &[Value],
+  _: Option<&JsObject>,
+) -> Result<Value, Value> {
+  // 1. Let O be ? RequireObjectCoercible(this value).
+  // 2. Let S be ? ToString(O).
+  let s = this_string(this, "toWellFormed")?;
+  Ok(Value::from(String::from_utf16_lossy(&to_well_formed(&s))))
+}
+
+/// https://tc39.es/ecma262/#sec-properties-of-the-string-prototype-object,
+/// the methods work on the UTF-16 code units of the string.
+pub fn create_string_prototype(realm: &Realm) {
+  // The String prototype object:
+  // - is a String exotic object and has the internal methods specified for
+  //   such objects.
+  // - has a [[StringData]] internal slot whose value is the empty String.
+  // - has a "length" property whose initial value is +0𝔽 and whose attributes
+  //   are { [[Writable]]: false, [[Enumerable]]: false, [[Configurable]]:
+  //   false }.
+  // - has a [[Prototype]] internal slot whose value is %Object.prototype%.
+  // TODO: String exotic objects
+  let prototype =
+    ordinary_object_create(Either::A(realm.intrinsic("Object.prototype")), &[]);
+  prototype
+    .set_internal_slot("StringData", InternalSlot::Value(Value::from("")));
+  add_methods(
+    realm,
+    &prototype,
+    &[
+      ("at", at, 1.0),
+      ("charAt", char_at, 1.0),
+      ("charCodeAt", char_code_at, 1.0),
+      ("codePointAt", code_point_at_method, 1.0),
+      ("endsWith", ends_with, 1.0),
+      ("includes", includes, 1.0),
+      ("indexOf", index_of, 1.0),
+      ("isWellFormed", is_well_formed, 0.0),
+      ("lastIndexOf", last_index_of, 1.0),
+      ("slice", slice, 2.0),
+      ("split", split, 2.0),
+      ("startsWith", starts_with, 1.0),
+      ("substring", substring_method, 2.0),
+      ("toLowerCase", to_lower_case, 0.0),
+      ("toUpperCase", to_upper_case, 0.0),
+      ("toWellFormed", to_well_formed_method, 0.0),
+    ],
+  );
+  realm.set_intrinsic("String.prototype", prototype);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    abstract_operations::operations_on_bjects::{
+      call, create_data_property, length_of_array_like,
+    },
+    agent::pop_execution_context,
+    executable_code_and_execution_contexts::realm::initialize_host_defined_realm,
+    language_types::null::JsNull,
+  };
+
+  fn substitute(
+    template: &str,
+    captures: &[Option<&str>],
+    named_captures: &Value,
+  ) -> JsString {
+    let captures: Vec<_> =
+      captures.iter().map(|c| c.map(JsString::from)).collect();
+    // "abcdef".replace(/cd/, template)
+    get_substitution(
+      &"cd".into(),
+      &"abcdef".into(),
+      2,
+      &captures,
+      named_captures,
+      &template.into(),
+    )
+    .unwrap()
+  }
+
+  #[test]
+  fn substitution_tokens() {
+    let undefined = Value::undefined();
+    assert_eq!(substitute("[$$]", &[], &undefined), "[$]");
+    assert_eq!(substitute("[$&]", &[], &undefined), "[cd]");
+    assert_eq!(substitute("[$`]", &[], &undefined), "[ab]");
+    assert_eq!(substitute("[$']", &[], &undefined), "[ef]");
+    assert_eq!(substitute("$", &[], &undefined), "$");
+    assert_eq!(substitute("$x$", &[], &undefined), "$x$");
+    assert_eq!(substitute("$<a>", &[], &undefined), "$<a>");
+  }
+
+  #[test]
+  fn numbered_captures() {
+    let undefined = Value::undefined();
+    let captures = [Some("c"), None, Some("d")];
+    assert_eq!(substitute("$1-$2-$3", &captures, &undefined), "c--d");
+    assert_eq!(substitute("$01$03", &captures, &undefined), "cd");
+    // Out-of-range indices are left literal.
+    assert_eq!(substitute("$0$4$00", &captures, &undefined), "$0$4$00");
+    // A two-digit index beyond the captures falls back to one digit.
+    assert_eq!(substitute("$10", &captures, &undefined), "c0");
+    let captures: Vec<_> = (1..=11).map(|i| Some(i.to_string())).collect();
+    let captures: Vec<_> = captures.iter().map(|c| c.as_deref()).collect();
+    assert_eq!(substitute("$11$12", &captures, &undefined), "1112");
+  }
+
+  #[test]
+  fn named_captures() {
+    let groups = ordinary_object_create(Either::B(JsNull), &[]);
+    create_data_property(&groups, &"first".into(), "c".into()).unwrap();
+    create_data_property(&groups, &"missing".into(), Value::undefined())
+      .unwrap();
+    let groups = Value::from(groups);
+    assert_eq!(substitute("[$<first>]", &[], &groups), "[c]");
+    assert_eq!(substitute("[$<missing>]", &[], &groups), "[]");
+    assert_eq!(substitute("[$<none>]", &[], &groups), "[]");
+    assert_eq!(substitute("[$<first]", &[], &groups), "[$<first]");
+  }
+
+  #[test]
+  fn utf16_positions() {
+    let undefined = Value::undefined();
+    // "😀ab".replace("a", "[$`|$']"): position is counted in code units.
+    let result = get_substitution(
+      &"a".into(),
+      &"😀ab".into(),
+      2,
+      &[],
+      &undefined,
+      &"[$`|$']".into(),
+    )
+    .unwrap();
+    assert_eq!(result, "[😀|b]");
+  }
+
+  #[test]
+  fn well_formed() {
+    let pair: Vec<u16> = "a😀".encode_utf16().collect();
+    assert_eq!(to_well_formed(&pair), pair);
+    assert_eq!(to_well_formed(&[0x61, 0xD83D]), [0x61, 0xFFFD]);
+    assert_eq!(
+      to_well_formed(&[0xD83D, 0xD83D, 0xDE00]),
+      [0xFFFD, 0xD83D, 0xDE00]
+    );
+    assert_eq!(to_well_formed(&[0xDE00, 0x61]), [0xFFFD, 0x61]);
+  }
+
+  fn invoke(
+    realm: &Realm,
+    this: &str,
+    name: &str,
+    arguments: &[Value],
+  ) -> Result<Value, Value> {
+    let method = get(&realm.intrinsic("String.prototype"), &name.into())?;
+    call(&method, &this.into(), arguments)
+  }
+
+  fn number(result: Result<Value, Value>) -> f64 {
+    match result {
+      Ok(Value::Number(n)) => *n,
+      v => panic!("expect a number, got {:?}", v),
+    }
+  }
+
+  fn string(result: Result<Value, Value>) -> JsString {
+    match result {
+      Ok(Value::String(s)) => s,
+      v => panic!("expect a string, got {:?}", v),
+    }
+  }
+
+  #[test]
+  fn code_units_and_code_points() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let smile = "a😀";
+    assert_eq!(
+      number(invoke(&realm, smile, "charCodeAt", &[1.0.into()])),
+      0xD83D as f64
+    );
+    assert_eq!(
+      number(invoke(&realm, smile, "charCodeAt", &[2.0.into()])),
+      0xDE00 as f64
+    );
+    assert!(number(invoke(&realm, smile, "charCodeAt", &[3.0.into()])).is_nan());
+    assert_eq!(
+      number(invoke(&realm, smile, "codePointAt", &[1.0.into()])),
+      0x1F600 as f64
+    );
+    // a trailing surrogate on its own
+    assert_eq!(
+      number(invoke(&realm, smile, "codePointAt", &[2.0.into()])),
+      0xDE00 as f64
+    );
+    assert!(matches!(
+      invoke(&realm, smile, "codePointAt", &[(-1.0).into()]),
+      Ok(Value::Undefined(_))
+    ));
+    assert_eq!(string(invoke(&realm, "abc", "charAt", &[1.0.into()])), "b");
+    assert_eq!(string(invoke(&realm, "abc", "charAt", &[3.0.into()])), "");
+    assert_eq!(string(invoke(&realm, "abc", "at", &[(-1.0).into()])), "c");
+    assert!(matches!(
+      invoke(&realm, "abc", "at", &[(-4.0).into()]),
+      Ok(Value::Undefined(_))
+    ));
+    assert!(matches!(
+      invoke(&realm, smile, "isWellFormed", &[]),
+      Ok(Value::Boolean(JsBoolean::True))
+    ));
+    assert!(invoke(&realm, "", "charAt", &[]).is_ok());
+    let char_at = get(&realm.intrinsic("String.prototype"), &"charAt".into());
+    assert!(call(&char_at.unwrap(), &Value::undefined(), &[]).is_err());
+    pop_execution_context();
+  }
+
+  #[test]
+  fn substrings() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let slice = |arguments: &[Value]| {
+      string(invoke(&realm, "abcdef", "slice", arguments))
+    };
+    assert_eq!(slice(&[1.0.into(), 3.0.into()]), "bc");
+    assert_eq!(slice(&[(-2.0).into()]), "ef");
+    assert_eq!(slice(&[(-3.0).into(), (-1.0).into()]), "de");
+    assert_eq!(slice(&[(-10.0).into(), 2.0.into()]), "ab");
+    assert_eq!(slice(&[3.0.into(), 1.0.into()]), "");
+    let substring = |arguments: &[Value]| {
+      string(invoke(&realm, "abcdef", "substring", arguments))
+    };
+    assert_eq!(substring(&[3.0.into(), 1.0.into()]), "bc");
+    assert_eq!(substring(&[(-2.0).into(), 2.0.into()]), "ab");
+    assert_eq!(substring(&[4.0.into()]), "ef");
+    assert_eq!(string(invoke(&realm, "aBc", "toUpperCase", &[])), "ABC");
+    assert_eq!(string(invoke(&realm, "aBc", "toLowerCase", &[])), "abc");
+    pop_execution_context();
+  }
+
+  #[test]
+  fn searching() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let index_of = |name: &str, arguments: &[Value]| {
+      number(invoke(&realm, "abcabc", name, arguments))
+    };
+    assert_eq!(index_of("indexOf", &["c".into()]), 2.0);
+    assert_eq!(index_of("indexOf", &["c".into(), 3.0.into()]), 5.0);
+    assert_eq!(index_of("indexOf", &["d".into()]), -1.0);
+    assert_eq!(index_of("indexOf", &["".into(), 10.0.into()]), 6.0);
+    assert_eq!(index_of("lastIndexOf", &["c".into()]), 5.0);
+    assert_eq!(index_of("lastIndexOf", &["c".into(), 4.0.into()]), 2.0);
+    assert_eq!(index_of("lastIndexOf", &["d".into()]), -1.0);
+    assert_eq!(index_of("lastIndexOf", &["abcabcabc".into()]), -1.0);
+    let test = |name: &str, arguments: &[Value]| {
+      matches!(
+        invoke(&realm, "abcabc", name, arguments),
+        Ok(Value::Boolean(JsBoolean::True))
+      )
+    };
+    assert!(test("includes", &["ca".into()]));
+    assert!(!test("includes", &["ab".into(), 4.0.into()]));
+    assert!(test("startsWith", &["abc".into()]));
+    assert!(test("startsWith", &["ca".into(), 2.0.into()]));
+    assert!(!test("startsWith", &["bc".into()]));
+    assert!(test("endsWith", &["bc".into()]));
+    assert!(test("endsWith", &["ab".into(), 5.0.into()]));
+    assert!(!test("endsWith", &["abcabca".into()]));
+    pop_execution_context();
+  }
+
+  #[test]
+  fn split_without_regexp() {
+    let realm = initialize_host_defined_realm().unwrap();
+    let split = |this: &str, arguments: &[Value]| {
+      let array = match invoke(&realm, this, "split", arguments) {
+        Ok(Value::Object(array)) => array,
+        v => panic!("expect an array, got {:?}", v),
+      };
+      let len = length_of_array_like(&array).unwrap() as usize;
+      (0..len)
+        .map(|k| {
+          get(&array, &k.to_string().into())
+            .unwrap()
+            .to_string()
+            .unwrap()
+        })
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(split("a,b,,c", &[",".into()]), ["a", "b", "", "c"]);
+    assert_eq!(split("a,b,c", &[",".into(), 2.0.into()]), ["a", "b"]);
+    assert_eq!(split("abc", &["".into()]), ["a", "b", "c"]);
+    assert_eq!(split("abc", &[]), ["abc"]);
+    assert_eq!(split("", &[",".into()]), [""]);
+    assert!(split("abc", &["".into(), 0.0.into()]).is_empty());
+    pop_execution_context();
+  }
+}
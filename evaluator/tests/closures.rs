@@ -0,0 +1,70 @@
+//! Parse and evaluate closures end to end.
+
+use ng262_evaluator::{
+  agent::pop_execution_context,
+  executable_code_and_execution_contexts::{
+    environment_records::new_declarative_environment,
+    realm::initialize_host_defined_realm,
+  },
+  language_types::{object::JsObject, Value},
+  ordinary_and_exotic_objects_behaviours::ecmascript_function_objects::instantiate_function_object,
+};
+use ng262_parser::parser::{nodes::NodeType, Parser};
+
+/// Instantiate the function declaration of `source`.
+fn function(source: &str) -> JsObject {
+  let script = Parser::parse_script(source, None).unwrap();
+  match script.node_type() {
+    NodeType::Script { body } => {
+      instantiate_function_object(&body[0], &new_declarative_environment(None))
+    }
+    _ => unreachable!(),
+  }
+}
+
+fn call(f: &JsObject) -> Value {
+  f.call(&Value::undefined(), &[]).unwrap()
+}
+
+fn object(value: Value) -> JsObject {
+  match value {
+    Value::Object(o) => o,
+    v => panic!("expect an object, got {:?}", v),
+  }
+}
+
+fn number(value: Value) -> f64 {
+  match value {
+    Value::Number(n) => *n,
+    v => panic!("expect a number, got {:?}", v),
+  }
+}
+
+#[test]
+fn arrow_function_counter() {
+  initialize_host_defined_realm().unwrap();
+  let make = function("function make() { let n = 0; return () => ++n; }");
+  let counter = object(call(&make));
+  assert_eq!(number(call(&counter)), 1.0);
+  assert_eq!(number(call(&counter)), 2.0);
+  // every call of make has its own n
+  let other = object(call(&make));
+  assert_eq!(number(call(&other)), 1.0);
+  assert_eq!(number(call(&counter)), 3.0);
+  pop_execution_context();
+}
+
+#[test]
+fn function_expression_counter() {
+  initialize_host_defined_realm().unwrap();
+  let make = function(
+    "function make() {
+      let n = 0;
+      return function () { n = n + 1; return n; };
+    }",
+  );
+  let counter = object(call(&make));
+  assert_eq!(number(call(&counter)), 1.0);
+  assert_eq!(number(call(&counter)), 2.0);
+  pop_execution_context();
+}
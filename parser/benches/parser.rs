@@ -0,0 +1,75 @@
+//! Run with `cargo bench -p ng262-parser`.
+//!
+//! Sources are generated rather than checked in, the sizes are kept small
+//! enough that a quadratic regression shows up as a jump in time instead of
+//! a bench that never finishes.
+
+use criterion::{
+  criterion_group, criterion_main, BenchmarkId, Criterion, Throughput,
+};
+use ng262_parser::parser::{lexer::Lexer, Parser};
+
+/// A mix of the expressions the parser supports so far.
+fn synthetic(lines: usize) -> String {
+  (0..lines)
+    .map(|i| {
+      format!(
+        "a{i} = b.c[{i}] + f(x, ...y) * -z{i} ?? (`t${{{i}}}` || 'str\\n');\n"
+      )
+    })
+    .collect()
+}
+
+fn nested_parens(depth: usize) -> String {
+  format!("{}a{};", "(".repeat(depth), ")".repeat(depth))
+}
+
+fn long_identifiers(count: usize) -> String {
+  (0..count)
+    .map(|i| format!("{}{};\n", "identifier".repeat(20), i))
+    .collect()
+}
+
+fn numeric_separators(count: usize) -> String {
+  (0..count).map(|_| "1_000_000_000.000_001;\n").collect()
+}
+
+fn count_tokens(source: &str) -> u64 {
+  Lexer::new(source, false).map(Result::unwrap).count() as u64
+}
+
+fn inputs() -> Vec<(&'static str, String)> {
+  vec![
+    ("synthetic", synthetic(100)),
+    ("nested_parens", nested_parens(200)),
+    ("long_identifiers", long_identifiers(50)),
+    ("numeric_separators", numeric_separators(200)),
+  ]
+}
+
+fn lexer(c: &mut Criterion) {
+  let mut group = c.benchmark_group("lexer");
+  group.sample_size(10);
+  for (name, source) in inputs() {
+    group.throughput(Throughput::Elements(count_tokens(&source)));
+    group.bench_function(BenchmarkId::from_parameter(name), |b| {
+      b.iter(|| count_tokens(&source))
+    });
+  }
+  group.finish();
+}
+
+fn parser(c: &mut Criterion) {
+  let mut group = c.benchmark_group("parser");
+  group.sample_size(10);
+  for (name, source) in inputs() {
+    group.throughput(Throughput::Bytes(source.len() as u64));
+    group.bench_function(BenchmarkId::from_parameter(name), |b| {
+      b.iter(|| Parser::parse_script(&source, None).unwrap())
+    });
+  }
+  group.finish();
+}
+
+criterion_group!(benches, lexer, parser);
+criterion_main!(benches);
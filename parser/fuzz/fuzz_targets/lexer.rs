@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use ng262_parser::parser::{lexer::Lexer, tokens::TokenType};
+
+// The lexer should never panic, any input either lexes to the end or stops
+// at the first `SyntaxError`.
+fuzz_target!(|source: &str| {
+  for is_strict in [false, true] {
+    let mut lexer = Lexer::new(source, is_strict);
+    while let Ok(token) = lexer.bump() {
+      if token.token_type == TokenType::EndOfSource {
+        break;
+      }
+    }
+  }
+});
@@ -0,0 +1,61 @@
+use std::{env, fs, process};
+
+use ng262_parser::parser::{error::SyntaxError, lexer::Lexer, Parser};
+
+const USAGE: &str = "\
+Usage:
+  ng262 lex <file>
+  ng262 parse <file> [--module]";
+
+fn main() {
+  let args = env::args().skip(1).collect::<Vec<_>>();
+  let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+  let result = match args.as_slice() {
+    ["lex", file] => lex(&read(file)),
+    ["parse", file] => parse(&read(file), file, false),
+    ["parse", file, "--module"] | ["parse", "--module", file] => {
+      parse(&read(file), file, true)
+    }
+    _ => {
+      eprintln!("{}", USAGE);
+      process::exit(2);
+    }
+  };
+  if let Err(e) = result {
+    eprintln!("{}\n{}", e.decoration(), e);
+    process::exit(1);
+  }
+}
+
+fn read(file: &str) -> String {
+  match fs::read_to_string(file) {
+    Ok(source) => source,
+    Err(e) => {
+      eprintln!("{}: {}", file, e);
+      process::exit(1);
+    }
+  }
+}
+
+fn lex(source: &str) -> Result<(), SyntaxError> {
+  for token in Lexer::new(source, false) {
+    let token = token?;
+    println!("{}:{} {:?}", token.line, token.column, token.token_type);
+  }
+  Ok(())
+}
+
+fn parse(source: &str, file: &str, is_module: bool) -> Result<(), SyntaxError> {
+  let node = if is_module {
+    Parser::parse_module(source, Some(file))
+  } else {
+    Parser::parse_script(source, Some(file))
+  };
+  match node {
+    Ok(node) => {
+      println!("{:#?}", node);
+      Ok(())
+    }
+    Err(e) => Err(e.syntax_error().clone()),
+  }
+}
@@ -0,0 +1,808 @@
+//! Generate JavaScript source text from a `Node`.
+//!
+//! The output doesn't preserve the original formatting, it only parses back
+//! to an equivalent tree, parentheses are inserted where precedence requires.
+
+use super::{
+  nodes::{Node, NodeType, TemplateElement, VariableKind},
+  tokens::{StringValue, TokenType},
+};
+
+const SEQUENCE: usize = 0;
+const ASSIGNMENT: usize = 1;
+const CONDITIONAL: usize = 2;
+/// Binary operators map to `BINARY + TokenType::precedence()`.
+const BINARY: usize = 2;
+const UNARY: usize = 15;
+const UPDATE: usize = 16;
+const NEW_WITHOUT_ARGUMENTS: usize = 17;
+const LEFT_HAND_SIDE: usize = 18;
+const PRIMARY: usize = 19;
+
+pub fn generate(node: &Node) -> String {
+  match node.node_type() {
+    NodeType::Script { body } | NodeType::Module { body } => {
+      body.iter().map(statement).collect::<Vec<_>>().join("\n")
+    }
+    NodeType::ExpressionStatement { .. }
+    | NodeType::BlockStatement { .. }
+    | NodeType::EmptyStatement
+    | NodeType::DebuggerStatement
+    | NodeType::IfStatement { .. }
+    | NodeType::DoWhileStatement { .. }
+    | NodeType::WhileStatement { .. }
+    | NodeType::LabeledStatement { .. }
+    | NodeType::BreakStatement { .. }
+    | NodeType::ContinueStatement { .. }
+    | NodeType::SwitchStatement { .. }
+    | NodeType::WithStatement { .. }
+    | NodeType::VariableDeclaration { .. }
+    | NodeType::ReturnStatement { .. }
+    | NodeType::ThrowStatement { .. }
+    | NodeType::FunctionDeclaration { .. }
+    | NodeType::ImportDeclaration { .. }
+    | NodeType::ExportNamedDeclaration { .. }
+    | NodeType::ExportDefaultDeclaration { .. }
+    | NodeType::ExportAllDeclaration { .. } => statement(node),
+    _ => expression(node, SEQUENCE),
+  }
+}
+
+fn statement(node: &Node) -> String {
+  match node.node_type() {
+    NodeType::ExpressionStatement { expression: e } => {
+      format!("{};", expression(e, 0))
+    }
+    NodeType::BlockStatement { body } => block(body),
+    NodeType::EmptyStatement => ";".to_owned(),
+    NodeType::DebuggerStatement => "debugger;".to_owned(),
+    NodeType::VariableDeclaration { kind, declarations } => {
+      let kind = match kind {
+        VariableKind::Var => "var",
+        VariableKind::Let => "let",
+        VariableKind::Const => "const",
+      };
+      let declarations = declarations.iter().map(|d| match d.node_type() {
+        NodeType::VariableDeclarator {
+          id,
+          init: Some(init),
+        } => format!(
+          "{} = {}",
+          expression(id, ASSIGNMENT),
+          expression(init, ASSIGNMENT)
+        ),
+        NodeType::VariableDeclarator { id, init: None } => {
+          expression(id, ASSIGNMENT)
+        }
+        t => unreachable!("unexpected declarator {:?}", t),
+      });
+      format!("{} {};", kind, declarations.collect::<Vec<_>>().join(", "))
+    }
+    NodeType::FunctionDeclaration { id, params, body } => {
+      format!(
+        "function {}({}) {}",
+        expression(id, 0),
+        list(params),
+        block(body)
+      )
+    }
+    NodeType::IfStatement {
+      test,
+      consequent,
+      alternate,
+    } => {
+      let code =
+        format!("if ({}) {}", expression(test, 0), statement(consequent));
+      match alternate {
+        Some(alternate) => format!("{} else {}", code, statement(alternate)),
+        None => code,
+      }
+    }
+    NodeType::DoWhileStatement { body, test } => {
+      format!("do {} while ({});", statement(body), expression(test, 0))
+    }
+    NodeType::WhileStatement { test, body } => {
+      format!("while ({}) {}", expression(test, 0), statement(body))
+    }
+    NodeType::LabeledStatement { label, body } => {
+      format!("{}: {}", expression(label, 0), statement(body))
+    }
+    NodeType::BreakStatement { label } => match label {
+      Some(label) => format!("break {};", expression(label, 0)),
+      None => "break;".to_owned(),
+    },
+    NodeType::ContinueStatement { label } => match label {
+      Some(label) => format!("continue {};", expression(label, 0)),
+      None => "continue;".to_owned(),
+    },
+    NodeType::ReturnStatement { argument } => match argument {
+      Some(argument) => format!("return {};", expression(argument, 0)),
+      None => "return;".to_owned(),
+    },
+    NodeType::ThrowStatement { argument } => {
+      format!("throw {};", expression(argument, 0))
+    }
+    NodeType::ImportDeclaration { specifiers, source } => {
+      let mut clauses = Vec::new();
+      let mut named = Vec::new();
+      for specifier in specifiers {
+        match specifier.node_type() {
+          NodeType::ImportDefaultSpecifier { local } => {
+            clauses.push(expression(local, 0))
+          }
+          NodeType::ImportNamespaceSpecifier { local } => {
+            clauses.push(format!("* as {}", expression(local, 0)))
+          }
+          NodeType::ImportSpecifier { imported, local } => {
+            named.push(match imported {
+              Some(imported) => format!(
+                "{} as {}",
+                expression(imported, 0),
+                expression(local, 0)
+              ),
+              None => expression(local, 0),
+            })
+          }
+          t => unreachable!("unexpected import specifier {:?}", t),
+        }
+      }
+      if !named.is_empty() {
+        clauses.push(format!("{{ {} }}", named.join(", ")));
+      }
+      if clauses.is_empty() {
+        format!("import {};", expression(source, 0))
+      } else {
+        format!(
+          "import {} from {};",
+          clauses.join(", "),
+          expression(source, 0)
+        )
+      }
+    }
+    NodeType::ExportNamedDeclaration {
+      declaration: Some(declaration),
+      ..
+    } => format!("export {}", statement(declaration)),
+    NodeType::ExportNamedDeclaration {
+      declaration: None,
+      specifiers,
+      source,
+    } => {
+      let specifiers = specifiers.iter().map(|s| match s.node_type() {
+        NodeType::ExportSpecifier { local, exported } => match exported {
+          Some(exported) => {
+            format!("{} as {}", expression(local, 0), expression(exported, 0))
+          }
+          None => expression(local, 0),
+        },
+        t => unreachable!("unexpected export specifier {:?}", t),
+      });
+      let code =
+        format!("export {{ {} }}", specifiers.collect::<Vec<_>>().join(", "));
+      match source {
+        Some(source) => format!("{} from {};", code, expression(source, 0)),
+        None => format!("{};", code),
+      }
+    }
+    NodeType::ExportDefaultDeclaration { declaration } => {
+      match declaration.node_type() {
+        NodeType::FunctionDeclaration { .. } => {
+          format!("export default {}", statement(declaration))
+        }
+        _ => format!("export default {};", expression(declaration, ASSIGNMENT)),
+      }
+    }
+    NodeType::ExportAllDeclaration { exported, source } => match exported {
+      Some(exported) => format!(
+        "export * as {} from {};",
+        expression(exported, 0),
+        expression(source, 0)
+      ),
+      None => format!("export * from {};", expression(source, 0)),
+    },
+    NodeType::SwitchStatement {
+      discriminant,
+      cases,
+    } => {
+      let cases = cases.iter().map(|case| match case.node_type() {
+        NodeType::SwitchCase { test, consequent } => {
+          let test = match test {
+            Some(test) => format!("case {}:", expression(test, 0)),
+            None => "default:".to_owned(),
+          };
+          std::iter::once(test)
+            .chain(consequent.iter().map(statement))
+            .collect::<Vec<_>>()
+            .join(" ")
+        }
+        t => unreachable!("unexpected switch case {:?}", t),
+      });
+      format!(
+        "switch ({}) {{ {} }}",
+        expression(discriminant, 0),
+        cases.collect::<Vec<_>>().join(" ")
+      )
+    }
+    NodeType::WithStatement { object, body } => {
+      format!("with ({}) {}", expression(object, 0), statement(body))
+    }
+    _ => unreachable!("unexpected statement {:?}", node.node_type()),
+  }
+}
+
+/// `{}` or `{ a; b; }`.
+fn block(body: &[Node]) -> String {
+  if body.is_empty() {
+    "{}".to_owned()
+  } else {
+    let body = body.iter().map(statement).collect::<Vec<_>>();
+    format!("{{ {} }}", body.join(" "))
+  }
+}
+
+fn precedence(node: &Node) -> usize {
+  match node.node_type() {
+    NodeType::SequenceExpression { .. } => SEQUENCE,
+    NodeType::AssignmentExpression { .. }
+    | NodeType::ArrowFunctionExpression { .. } => ASSIGNMENT,
+    NodeType::ConditionalExpression { .. } => CONDITIONAL,
+    NodeType::BinaryExpression { operator, .. }
+    | NodeType::LogicalExpression { operator, .. } => {
+      BINARY + operator.precedence()
+    }
+    NodeType::UnaryExpression { .. } => UNARY,
+    NodeType::UpdateExpression { .. } => UPDATE,
+    NodeType::NewExpression {
+      arguments: None, ..
+    } => NEW_WITHOUT_ARGUMENTS,
+    NodeType::NewExpression { .. }
+    | NodeType::MemberExpression { .. }
+    | NodeType::CallExpression { .. }
+    | NodeType::ImportCall { .. }
+    | NodeType::ChainExpression { .. }
+    | NodeType::TaggedTemplateExpression { .. } => LEFT_HAND_SIDE,
+    NodeType::SpreadElement { .. } => ASSIGNMENT,
+    _ => PRIMARY,
+  }
+}
+
+/// Generate `node`, wrapped in parentheses when it binds looser than
+/// `min_precedence`.
+fn expression(node: &Node, min_precedence: usize) -> String {
+  let code = match node.node_type() {
+    NodeType::IdentifierName { name }
+    | NodeType::BindingIdentifier { name }
+    | NodeType::IdentifierReference { name, .. }
+    | NodeType::LabelIdentifier { name, .. } => name.clone(),
+    NodeType::PrivateIdentifier { name } => format!("#{}", name),
+    NodeType::NullLiteral => "null".to_owned(),
+    NodeType::BooleanLiteral { value } => value.to_string(),
+    NodeType::NumericLiteral { value } => value.to_string(),
+    NodeType::BigIntLiteral { value } => format!("{}n", value),
+    NodeType::RegularExpressionLiteral { pattern, flags } => {
+      format!("/{}/{}", pattern, flags)
+    }
+    NodeType::StringLiteral { value } => string_literal(value),
+    NodeType::TemplateLiteral {
+      quasis,
+      expressions,
+    } => template_literal(quasis, expressions),
+    NodeType::ThisExpression => "this".to_owned(),
+    NodeType::ParenthesizedExpression { expression: e } => {
+      format!("({})", expression(e, SEQUENCE))
+    }
+    NodeType::ArrayLiteral { elements } => {
+      let mut code = elements
+        .iter()
+        .map(|e| match e {
+          Some(e) => expression(e, ASSIGNMENT),
+          None => String::new(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+      // a trailing hole needs an extra comma, `[a, ,]` has two elements
+      if let Some(None) = elements.last() {
+        code.push(',');
+      }
+      format!("[{}]", code)
+    }
+    NodeType::SpreadElement { argument } => {
+      format!("...{}", expression(argument, ASSIGNMENT))
+    }
+    NodeType::MemberExpression {
+      object,
+      property,
+      computed,
+      optional,
+    } => {
+      let mut code = expression(object, LEFT_HAND_SIDE);
+      // `1.toString` is a syntax error, `1..toString` is fine
+      if !*computed
+        && matches!(object.node_type(), NodeType::NumericLiteral { .. })
+        && code.chars().all(|c| c.is_ascii_digit())
+      {
+        code.push('.');
+      }
+      match (computed, optional) {
+        (true, true) => format!("{}?.[{}]", code, expression(property, 0)),
+        (true, false) => format!("{}[{}]", code, expression(property, 0)),
+        (false, true) => format!("{}?.{}", code, expression(property, 0)),
+        (false, false) => format!("{}.{}", code, expression(property, 0)),
+      }
+    }
+    NodeType::CallExpression {
+      callee,
+      arguments,
+      optional,
+    } => format!(
+      "{}{}({})",
+      expression(callee, LEFT_HAND_SIDE),
+      if *optional { "?." } else { "" },
+      list(arguments)
+    ),
+    NodeType::ChainExpression { expression: e } => expression(e, 0),
+    NodeType::ImportCall { source } => {
+      format!("import({})", expression(source, ASSIGNMENT))
+    }
+    NodeType::MetaProperty { meta, property } => {
+      format!("{}.{}", expression(meta, 0), expression(property, 0))
+    }
+    NodeType::NewExpression { callee, arguments } => {
+      // `new (f())()` is not the same as `new f()()`
+      let callee = if has_call(callee) {
+        format!("({})", expression(callee, 0))
+      } else {
+        expression(callee, NEW_WITHOUT_ARGUMENTS)
+      };
+      match arguments {
+        Some(arguments) => format!("new {}({})", callee, list(arguments)),
+        None => format!("new {}", callee),
+      }
+    }
+    NodeType::TaggedTemplateExpression { tag, quasi } => {
+      format!(
+        "{}{}",
+        expression(tag, LEFT_HAND_SIDE),
+        expression(quasi, 0)
+      )
+    }
+    NodeType::UnaryExpression { operator, argument } => {
+      let argument = expression(argument, UNARY);
+      let operator = self::operator(operator);
+      // `typeof a`, `- -a` rather than `--a`
+      if operator.chars().all(|c| c.is_ascii_alphabetic())
+        || argument.starts_with(operator)
+      {
+        format!("{} {}", operator, argument)
+      } else {
+        format!("{}{}", operator, argument)
+      }
+    }
+    NodeType::UpdateExpression {
+      operator,
+      argument,
+      prefix: true,
+    } => format!(
+      "{}{}",
+      self::operator(operator),
+      expression(argument, UNARY)
+    ),
+    NodeType::UpdateExpression {
+      operator, argument, ..
+    } => format!(
+      "{}{}",
+      expression(argument, LEFT_HAND_SIDE),
+      self::operator(operator)
+    ),
+    NodeType::BinaryExpression {
+      operator,
+      left,
+      right,
+    }
+    | NodeType::LogicalExpression {
+      operator,
+      left,
+      right,
+    } => {
+      let precedence = BINARY + operator.precedence();
+      // `**` is right associative and a unary expression can't be its left
+      // operand
+      let (left_precedence, right_precedence) = if *operator == TokenType::Exp {
+        (precedence + 1, precedence)
+      } else {
+        (precedence, precedence + 1)
+      };
+      format!(
+        "{} {} {}",
+        logical_operand(operator, left, left_precedence),
+        self::operator(operator),
+        logical_operand(operator, right, right_precedence),
+      )
+    }
+    NodeType::ConditionalExpression {
+      test,
+      consequent,
+      alternate,
+    } => format!(
+      "{} ? {} : {}",
+      expression(test, CONDITIONAL + 1),
+      expression(consequent, ASSIGNMENT),
+      expression(alternate, ASSIGNMENT),
+    ),
+    NodeType::AssignmentExpression {
+      operator,
+      left,
+      right,
+    } => format!(
+      "{} {} {}",
+      expression(left, LEFT_HAND_SIDE),
+      self::operator(operator),
+      expression(right, ASSIGNMENT),
+    ),
+    NodeType::SequenceExpression { expressions } => list(expressions),
+    NodeType::FunctionExpression { id, params, body } => match id {
+      Some(id) => format!(
+        "function {}({}) {}",
+        expression(id, 0),
+        list(params),
+        block(body)
+      ),
+      None => format!("function ({}) {}", list(params), block(body)),
+    },
+    NodeType::ArrowFunctionExpression {
+      params,
+      body,
+      expression: true,
+    } => {
+      let body = expression(&body[0], ASSIGNMENT);
+      // `{` would start a block body
+      if body.starts_with('{') {
+        format!("({}) => ({})", list(params), body)
+      } else {
+        format!("({}) => {}", list(params), body)
+      }
+    }
+    NodeType::ArrowFunctionExpression { params, body, .. } => {
+      format!("({}) => {}", list(params), block(body))
+    }
+    NodeType::ObjectLiteral { properties }
+    | NodeType::ObjectPattern { properties } => {
+      if properties.is_empty() {
+        "{}".to_owned()
+      } else {
+        format!("{{ {} }}", list(properties))
+      }
+    }
+    NodeType::ArrayPattern { elements } => {
+      let mut code = elements
+        .iter()
+        .map(|e| match e {
+          Some(e) => expression(e, ASSIGNMENT),
+          None => String::new(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+      if let Some(None) = elements.last() {
+        code.push(',');
+      }
+      format!("[{}]", code)
+    }
+    NodeType::AssignmentPattern { left, right } => format!(
+      "{} = {}",
+      expression(left, ASSIGNMENT),
+      expression(right, ASSIGNMENT)
+    ),
+    NodeType::RestElement { argument } => {
+      format!("...{}", expression(argument, ASSIGNMENT))
+    }
+    NodeType::Property {
+      key,
+      value,
+      computed,
+      shorthand,
+    } => {
+      if *shorthand {
+        expression(value, ASSIGNMENT)
+      } else if *computed {
+        format!(
+          "[{}]: {}",
+          expression(key, ASSIGNMENT),
+          expression(value, ASSIGNMENT)
+        )
+      } else {
+        format!("{}: {}", expression(key, 0), expression(value, ASSIGNMENT))
+      }
+    }
+    NodeType::ExpressionStatement { .. }
+    | NodeType::BlockStatement { .. }
+    | NodeType::EmptyStatement
+    | NodeType::DebuggerStatement
+    | NodeType::IfStatement { .. }
+    | NodeType::DoWhileStatement { .. }
+    | NodeType::WhileStatement { .. }
+    | NodeType::FunctionDeclaration { .. }
+    | NodeType::ReturnStatement { .. }
+    | NodeType::ThrowStatement { .. }
+    | NodeType::VariableDeclaration { .. }
+    | NodeType::VariableDeclarator { .. }
+    | NodeType::LabeledStatement { .. }
+    | NodeType::BreakStatement { .. }
+    | NodeType::ContinueStatement { .. }
+    | NodeType::SwitchStatement { .. }
+    | NodeType::SwitchCase { .. }
+    | NodeType::WithStatement { .. }
+    | NodeType::Script { .. }
+    | NodeType::Module { .. }
+    | NodeType::ImportDeclaration { .. }
+    | NodeType::ImportSpecifier { .. }
+    | NodeType::ImportDefaultSpecifier { .. }
+    | NodeType::ImportNamespaceSpecifier { .. }
+    | NodeType::ExportNamedDeclaration { .. }
+    | NodeType::ExportSpecifier { .. }
+    | NodeType::ExportDefaultDeclaration { .. }
+    | NodeType::ExportAllDeclaration { .. } => {
+      unreachable!("unexpected expression {:?}", node.node_type())
+    }
+  };
+  if precedence(node) < min_precedence {
+    format!("({})", code)
+  } else {
+    code
+  }
+}
+
+/// `??` can't be mixed with `||` and `&&` without parentheses.
+fn logical_operand(
+  operator: &TokenType,
+  operand: &Node,
+  min_precedence: usize,
+) -> String {
+  match operand.node_type() {
+    NodeType::LogicalExpression {
+      operator: operand_operator,
+      ..
+    } if operator.is_logical()
+      && (*operator == TokenType::Nullish)
+        != (*operand_operator == TokenType::Nullish) =>
+    {
+      format!("({})", expression(operand, 0))
+    }
+    _ => expression(operand, min_precedence),
+  }
+}
+
+fn has_call(node: &Node) -> bool {
+  match node.node_type() {
+    NodeType::CallExpression { .. } | NodeType::ImportCall { .. } => true,
+    NodeType::MemberExpression { object, .. } => has_call(object),
+    NodeType::TaggedTemplateExpression { tag, .. } => has_call(tag),
+    _ => false,
+  }
+}
+
+fn list(nodes: &[Node]) -> String {
+  nodes
+    .iter()
+    .map(|n| expression(n, ASSIGNMENT))
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+fn string_literal(value: &StringValue) -> String {
+  let mut code = String::from('"');
+  for c in char::decode_utf16(value.code_units().iter().copied()) {
+    match c {
+      // a lone surrogate is always 4 hex digits
+      Err(e) => code.push_str(&format!("\\u{:x}", e.unpaired_surrogate())),
+      Ok('"') => code.push_str("\\\""),
+      Ok('\\') => code.push_str("\\\\"),
+      Ok('\n') => code.push_str("\\n"),
+      Ok('\r') => code.push_str("\\r"),
+      Ok('\u{2028}') => code.push_str("\\u2028"),
+      Ok('\u{2029}') => code.push_str("\\u2029"),
+      Ok(c) if c.is_control() => {
+        code.push_str(&format!("\\u{{{:x}}}", c as u32))
+      }
+      Ok(c) => code.push(c),
+    }
+  }
+  code.push('"');
+  code
+}
+
+fn template_literal(
+  quasis: &[TemplateElement],
+  expressions: &[Node],
+) -> String {
+  let mut code = String::from('`');
+  for (i, quasi) in quasis.iter().enumerate() {
+    code.push_str(&quasi.raw);
+    if let Some(e) = expressions.get(i) {
+      code.push_str(&format!("${{{}}}", expression(e, 0)));
+    }
+  }
+  code.push('`');
+  code
+}
+
+fn operator(token_type: &TokenType) -> &'static str {
+  match token_type {
+    TokenType::Assign => "=",
+    TokenType::AssignNullish => "??=",
+    TokenType::AssignOr => "||=",
+    TokenType::AssignAnd => "&&=",
+    TokenType::AssignBitOr => "|=",
+    TokenType::AssignBitXor => "^=",
+    TokenType::AssignBitAnd => "&=",
+    TokenType::AssignShl => "<<=",
+    TokenType::AssignSar => ">>=",
+    TokenType::AssignShr => ">>>=",
+    TokenType::AssignMul => "*=",
+    TokenType::AssignDiv => "/=",
+    TokenType::AssignMod => "%=",
+    TokenType::AssignExp => "**=",
+    TokenType::AssignAdd => "+=",
+    TokenType::AssignSub => "-=",
+    TokenType::Nullish => "??",
+    TokenType::Or => "||",
+    TokenType::And => "&&",
+    TokenType::BitOr => "|",
+    TokenType::BitXor => "^",
+    TokenType::BitAnd => "&",
+    TokenType::Shl => "<<",
+    TokenType::Sar => ">>",
+    TokenType::Shr => ">>>",
+    TokenType::Mul => "*",
+    TokenType::Div => "/",
+    TokenType::Mod => "%",
+    TokenType::Exp => "**",
+    TokenType::Add => "+",
+    TokenType::Sub => "-",
+    TokenType::Not => "!",
+    TokenType::BitNot => "~",
+    TokenType::Delete => "delete",
+    TokenType::Typeof => "typeof",
+    TokenType::Void => "void",
+    TokenType::Inc => "++",
+    TokenType::Dec => "--",
+    TokenType::Equal => "==",
+    TokenType::StrictEqual => "===",
+    TokenType::NotEqual => "!=",
+    TokenType::StrictNotEqual => "!==",
+    TokenType::LessThan => "<",
+    TokenType::GreaterThan => ">",
+    TokenType::LessThanEqual => "<=",
+    TokenType::GreaterThanEqual => ">=",
+    TokenType::Instanceof => "instanceof",
+    TokenType::In => "in",
+    _ => unreachable!("unexpected operator {:?}", token_type),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{super::Parser, *};
+
+  fn parse(source: &str) -> Node {
+    Parser::new(source).parse_expression().unwrap()
+  }
+
+  fn assert_round_trip(source: &str) {
+    let node = parse(source);
+    let code = generate(&node);
+    assert_eq!(parse(&code), node, "{} generates {}", source, code);
+  }
+
+  #[test]
+  fn round_trip() {
+    for source in [
+      "a + b * c",
+      "(a + b) * c",
+      "a - (b - c)",
+      "a ?? (b || c)",
+      "x = y += a ? b : c",
+      "typeof - -a",
+      "new A.b(c)",
+      "new (f())()",
+      "new new A()()",
+      "a?.b.c()?.[d]",
+      "f(...a, [b, , c, ,])",
+      "1..toString()",
+      "'a\"b\\n'",
+      "`a${b}c${`d${e}`}`",
+      "tag`\\x${a}`",
+      "this.#x",
+      "- --a + b++",
+      "2 ** 3 ** 2",
+      "(2 ** 3) ** 2",
+      "(-2) ** -2",
+      "-(2 ** 2)",
+      "a++ ** 2",
+      "++a.b[c]",
+      "import(a).b",
+      "/[/]\\//g.test(a / b)",
+      "a, (b, c)",
+      "f((a, b), c)",
+      "[(a, b)]",
+      "new (import(a))()",
+      "a = (b, c = 1) => d = b",
+      "() => ({})",
+      "x => { return x; }",
+      "(function f(a) {})()",
+    ] {
+      assert_round_trip(source);
+    }
+  }
+
+  #[test]
+  fn generate_expression() {
+    assert_eq!(generate(&parse("a+b*c")), "a + b * c");
+    assert_eq!(generate(&parse("(a+b)*c")), "(a + b) * c");
+    assert_eq!(generate(&parse("f(a,b)")), "f(a, b)");
+    assert_eq!(generate(&parse("`a${ b }c`")), "`a${b}c`");
+  }
+
+  #[test]
+  fn generate_script() {
+    let node = Parser::parse_script("a\nb + c", None).unwrap();
+    assert_eq!(generate(&node), "a;\nb + c;");
+    let node = Parser::parse_script("a:b:break a", None).unwrap();
+    assert_eq!(generate(&node), "a: b: break a;");
+    let node =
+      Parser::parse_script("switch(a){case 1:b;default:}", None).unwrap();
+    assert_eq!(generate(&node), "switch (a) { case 1: b; default: }");
+    let node =
+      Parser::parse_script("let {a,b:[c,,...d],e=1}=f,g;", None).unwrap();
+    assert_eq!(generate(&node), "let { a, b: [c, , ...d], e = 1 } = f, g;");
+    let node = Parser::parse_script(
+      "function f(a,b){return a}function g(){return}",
+      None,
+    )
+    .unwrap();
+    assert_eq!(
+      generate(&node),
+      "function f(a, b) { return a; }\nfunction g() { return; }"
+    );
+    let node = Parser::parse_script("(function(){return a=>a})", None).unwrap();
+    assert_eq!(generate(&node), "(function () { return (a) => a; });");
+    let node = Parser::parse_script("throw a+b", None).unwrap();
+    assert_eq!(generate(&node), "throw a + b;");
+    let node =
+      Parser::parse_script("({a,'b c':1,[d]:e,...f,0.10:g})", None).unwrap();
+    assert_eq!(
+      generate(&node),
+      "({ a, \"b c\": 1, [d]: e, ...f, 0.1: g });"
+    );
+    let node = Parser::parse_script("with(a)b", None).unwrap();
+    assert_eq!(generate(&node), "with (a) b;");
+    let node = Parser::parse_script("{debugger;;{}}", None).unwrap();
+    assert_eq!(generate(&node), "{ debugger; ; {} }");
+    let node =
+      Parser::parse_script("if(a)b;else if(c)d\nwhile(e)do f;while(g)", None)
+        .unwrap();
+    assert_eq!(
+      generate(&node),
+      "if (a) b; else if (c) d;\nwhile (e) do f; while (g);"
+    );
+  }
+
+  #[test]
+  fn generate_module() {
+    let node = Parser::parse_module(
+      "import'a';import b,{c,d as e}from'f';import*as g from'h';",
+      None,
+    )
+    .unwrap();
+    assert_eq!(
+      generate(&node),
+      "import \"a\";\nimport b, { c, d as e } from \"f\";\nimport * as g from \"h\";"
+    );
+    let node = Parser::parse_module(
+      "export{i,j as 'k'};var i,j;export*from'l';export*as m from'n';export let o;export default p", None)
+    .unwrap();
+    assert_eq!(
+      generate(&node),
+      "export { i, j as \"k\" };\nvar i, j;\nexport * from \"l\";\nexport * as m from \"n\";\nexport let o;\nexport default p;"
+    );
+  }
+}
@@ -1,6 +1,10 @@
 use std::{error::Error, fmt};
 
-use super::{lexer::is_line_terminator, tokens::Token};
+use super::{
+  lexer::{is_line_terminator, ColumnMode},
+  nodes::Node,
+  tokens::Token,
+};
 
 #[derive(Debug)]
 pub enum ParseError {
@@ -19,6 +23,15 @@ impl fmt::Display for ParseError {
   }
 }
 
+impl ParseError {
+  pub fn syntax_error(&self) -> &SyntaxError {
+    match self {
+      ParseError::SyntaxError(e) => e,
+      ParseError::EarlyError(e) => &e.0,
+    }
+  }
+}
+
 impl From<SyntaxError> for ParseError {
   fn from(e: SyntaxError) -> Self {
     Self::SyntaxError(e)
@@ -67,7 +80,7 @@ impl From<SyntaxError> for EarlyError {
 ///          ^^^^^^^^^^^^^^^^^^^^^^
 ///  SyntaxError: unexpected token
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SyntaxError {
   message: String,
   decoration: String,
@@ -77,11 +90,20 @@ impl Error for SyntaxError {}
 
 impl fmt::Display for SyntaxError {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    write!(f, "SyntaxError")
+    write!(f, "SyntaxError: {}", self.message)
   }
 }
 
 impl SyntaxError {
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  /// The location and the source line with the error range underlined.
+  pub fn decoration(&self) -> &str {
+    &self.decoration
+  }
+
   #[allow(clippy::too_many_arguments)]
   fn new<S: SyntaxErrorInfo>(
     informer: &S,
@@ -93,10 +115,14 @@ impl SyntaxError {
     line: usize,
     column: usize,
   ) -> Self {
-    let message = format!("{}", template);
-    // TODO: specifier
+    let message = template.to_string();
+    let specifier = match informer.specifier() {
+      Some(specifier) => format!("{}:", specifier),
+      None => String::new(),
+    };
     let decoration = format!(
-      "\n{}:{}\n{}\n{}{}",
+      "\n{}{}:{}\n{}\n{}{}",
+      specifier,
       line,
       column,
       informer.slice(line_start, line_end),
@@ -114,9 +140,10 @@ impl SyntaxError {
     start_index: usize,
   ) -> usize {
     let mut line_start = start_index;
-    while let Some(c) = informer.get(line_start) {
-      if !is_line_terminator(c) {
-        line_start -= 1;
+    while line_start > 0 {
+      match informer.get(line_start - 1) {
+        Some(c) if !is_line_terminator(c) => line_start -= 1,
+        _ => break,
       }
     }
     line_start
@@ -128,9 +155,10 @@ impl SyntaxError {
   ) -> usize {
     let mut line_end = start_index;
     while let Some(c) = informer.get(line_end) {
-      if !is_line_terminator(c) {
-        line_end += 1;
+      if is_line_terminator(c) {
+        break;
       }
+      line_end += 1;
     }
     line_end
   }
@@ -146,11 +174,20 @@ impl SyntaxError {
       panic!("index is out of range, (index + offset) should not < 0");
     }
     let start_index = start_index as usize;
-    let end_index = index + 1;
+    let end_index = start_index + 1;
     let line_start = Self::line_start_index(informer, start_index);
     let line_end = Self::line_end_index(informer, start_index);
     let line = informer.line();
-    let column = start_index - line_start + 1;
+    let column = match informer.column_mode() {
+      ColumnMode::CodePoint => start_index - line_start + 1,
+      ColumnMode::Utf16 => {
+        (line_start..start_index)
+          .filter_map(|i| informer.get(i))
+          .map(char::len_utf16)
+          .sum::<usize>()
+          + 1
+      }
+    };
 
     Self::new(
       informer,
@@ -187,6 +224,28 @@ impl SyntaxError {
       column,
     )
   }
+
+  pub fn from_node<S: SyntaxErrorInfo>(
+    informer: &S,
+    node: &Node,
+    template: SyntaxErrorTemplate,
+  ) -> Self {
+    let start = node.start_location();
+    let end = node.end_location();
+    let line_start = Self::line_start_index(informer, start.index);
+    let line_end = Self::line_end_index(informer, start.index);
+
+    Self::new(
+      informer,
+      template,
+      start.index,
+      end.index,
+      line_start,
+      line_end,
+      start.line,
+      start.column,
+    )
+  }
 }
 
 pub trait SyntaxErrorInfo {
@@ -196,6 +255,16 @@ pub trait SyntaxErrorInfo {
 
   fn get(&self, index: usize) -> Option<char>;
 
+  /// The file name or URL of the source, shown in the decoration.
+  fn specifier(&self) -> Option<&str> {
+    None
+  }
+
+  /// How the column in the decoration is counted.
+  fn column_mode(&self) -> ColumnMode {
+    ColumnMode::CodePoint
+  }
+
   fn slice(&self, start_index: usize, end_index: usize) -> String {
     let mut s = String::new();
     for i in start_index..end_index {
@@ -208,25 +277,75 @@ pub trait SyntaxErrorInfo {
 #[derive(Debug)]
 pub enum SyntaxErrorTemplate {
   UnterminatedComment,
-  UnexpectedToken,
+  /// The text of the unexpected token or char, empty at the end of the
+  /// source.
+  UnexpectedToken(String),
   InvalidUnicodeEscape,
   InvalidCodePoint,
   UnterminatedString,
+  UnterminatedTemplate,
+  InvalidTemplateEscape,
+  UnterminatedRegExp,
+  InvalidRegExpFlags,
   IllegalOctalEscape,
   UnexpectedReservedWordStrict,
   UnexpectedEvalOrArguments,
+  InvalidAssignmentTarget,
+  NullishAndLogical,
+  UnaryBeforeExponentiation,
+  InvalidTaggedTemplateOnOptionalChain,
+  InvalidNumericLiteral,
+  UndefinedLabel,
+  LabelAlreadyDeclared,
+  IllegalBreak,
+  IllegalContinue,
+  MultipleDefaultsInSwitch,
+  AlreadyDeclared,
+  LetInLexicalBinding,
+  MissingInitializerInConst,
+  MissingInitializerInDestructuring,
+  DuplicateParameter,
+  IllegalReturn,
+  NewlineAfterThrow,
+  IllegalImportExport,
+  ImportMetaOutsideModule,
+  DuplicateExport,
+  ExportStringWithoutFrom,
+  MalformedExportName,
+  UndeclaredExport,
+  IllegalUseStrict,
+  RestParameterNotLast,
+  RestDefaultInitializer,
+  StrictWith,
+  StrictDelete,
+  DeletePrivateField,
 }
 
 impl fmt::Display for SyntaxErrorTemplate {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
       Self::UnterminatedComment => write!(f, "Missing */ after comment"),
-      Self::UnexpectedToken => write!(f, "Unexpected token"),
+      Self::UnexpectedToken(text) if text.is_empty() => {
+        write!(f, "Unexpected end of input")
+      }
+      Self::UnexpectedToken(text) => write!(f, "Unexpected token \"{}\"", text),
       Self::InvalidUnicodeEscape => write!(f, "Invalid unicode escape"),
       Self::InvalidCodePoint => write!(f, "Not a valid code point"),
       Self::UnterminatedString => {
         write!(f, "Missing \' or \" after string literal")
       }
+      Self::UnterminatedTemplate => {
+        write!(f, "Missing ` after template literal")
+      }
+      Self::InvalidTemplateEscape => {
+        write!(f, "Invalid escape sequence in template literal")
+      }
+      Self::UnterminatedRegExp => {
+        write!(f, "Invalid regular expression: missing /")
+      }
+      Self::InvalidRegExpFlags => {
+        write!(f, "Invalid regular expression flags")
+      }
       Self::IllegalOctalEscape => write!(f, "Illegal octal escape"),
       Self::UnexpectedReservedWordStrict => {
         write!(f, "Unexpected reserved word in strict mode")
@@ -234,6 +353,99 @@ impl fmt::Display for SyntaxErrorTemplate {
       Self::UnexpectedEvalOrArguments => {
         write!(f, "`arguments` and `eval` are not valid in this context")
       }
+      Self::InvalidAssignmentTarget => write!(f, "Invalid assignment target"),
+      Self::NullishAndLogical => {
+        write!(f, "Cannot mix ?? with || or && without parentheses")
+      }
+      Self::UnaryBeforeExponentiation => write!(
+        f,
+        "Unary operator used immediately before exponentiation expression, parentheses must be used to disambiguate operator precedence"
+      ),
+      Self::InvalidTaggedTemplateOnOptionalChain => {
+        write!(f, "Invalid tagged template on optional chain")
+      }
+      Self::InvalidNumericLiteral => write!(f, "Invalid numeric literal"),
+      Self::UndefinedLabel => write!(f, "Undefined label"),
+      Self::LabelAlreadyDeclared => {
+        write!(f, "Label has already been declared")
+      }
+      Self::IllegalBreak => write!(f, "Illegal break statement"),
+      Self::IllegalContinue => write!(
+        f,
+        "Illegal continue statement: no surrounding iteration statement"
+      ),
+      Self::MultipleDefaultsInSwitch => {
+        write!(f, "More than one default clause in switch statement")
+      }
+      Self::AlreadyDeclared => {
+        write!(f, "Identifier has already been declared")
+      }
+      Self::LetInLexicalBinding => {
+        write!(f, "let is disallowed as a lexically bound name")
+      }
+      Self::MissingInitializerInConst => {
+        write!(f, "Missing initializer in const declaration")
+      }
+      Self::MissingInitializerInDestructuring => {
+        write!(f, "Missing initializer in destructuring declaration")
+      }
+      Self::DuplicateParameter => {
+        write!(f, "Duplicate parameter name not allowed in this context")
+      }
+      Self::IllegalReturn => write!(f, "Illegal return statement"),
+      Self::NewlineAfterThrow => write!(f, "Illegal newline after throw"),
+      Self::IllegalImportExport => write!(
+        f,
+        "'import' and 'export' may only appear at the top level of a module"
+      ),
+      Self::DuplicateExport => write!(f, "Duplicate export"),
+      Self::ImportMetaOutsideModule => {
+        write!(f, "Cannot use 'import.meta' outside a module")
+      }
+      Self::ExportStringWithoutFrom => write!(
+        f,
+        "A string literal can't be exported without a 'from' clause"
+      ),
+      Self::MalformedExportName => write!(
+        f,
+        "Invalid module export name: contains unpaired surrogate"
+      ),
+      Self::UndeclaredExport => {
+        write!(f, "Export of a binding that isn't declared in the module")
+      }
+      Self::IllegalUseStrict => write!(
+        f,
+        "Illegal 'use strict' directive in function with non-simple parameter list"
+      ),
+      Self::RestParameterNotLast => {
+        write!(f, "Rest parameter must be last formal parameter")
+      }
+      Self::RestDefaultInitializer => {
+        write!(f, "Rest parameter may not have a default initializer")
+      }
+      Self::StrictWith => {
+        write!(f, "Strict mode code may not include a with statement")
+      }
+      Self::StrictDelete => {
+        write!(f, "Delete of an unqualified identifier in strict mode.")
+      }
+      Self::DeletePrivateField => write!(f, "Private fields can not be deleted"),
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use crate::parser::Parser;
+
+  #[test]
+  fn decoration() {
+    let source = "a;\r\n  b;\u{2028}foo bar;\nc;";
+    let error = Parser::parse_script(source, None).unwrap_err();
+    let error = error.syntax_error();
+    assert_eq!(error.decoration(), "\n3:5\nfoo bar;\n    ^^^");
+    // an error on the first line
+    let error = Parser::parse_script("a b", None).unwrap_err();
+    assert_eq!(error.syntax_error().decoration(), "\n1:3\na b\n  ^");
+  }
+}
@@ -0,0 +1,1412 @@
+use crate::{eat, expect, test, test_ahead};
+
+use super::{
+  error::{EarlyError, ParseError, SyntaxError, SyntaxErrorTemplate},
+  nodes::{Node, NodeBuilder, NodeType, TemplateElement},
+  resolver::Flag,
+  source::SourceText,
+  strict::IsStrict,
+  tokens::TokenType,
+  Parser,
+};
+
+impl Parser {
+  /// Expression :
+  ///   AssignmentExpression
+  ///   Expression `,` AssignmentExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Expression
+  pub(super) fn parse_expression(&mut self) -> Result<Node, ParseError> {
+    let first = self.parse_assignment_expression()?;
+    if !test!(self.lexer, TokenType::Comma)? {
+      return Ok(first);
+    }
+    let node = self.start_from(&first);
+    let mut expressions = vec![first];
+    while eat!(&mut self.lexer, TokenType::Comma)? {
+      expressions.push(self.parse_assignment_expression()?);
+    }
+    Ok(self.finish(node, NodeType::SequenceExpression { expressions }))
+  }
+
+  /// AssignmentExpression :
+  ///   ConditionalExpression
+  ///   ArrowFunction
+  ///   LeftHandSideExpression `=` AssignmentExpression
+  ///   LeftHandSideExpression AssignmentOperator AssignmentExpression
+  ///   LeftHandSideExpression `&&=` AssignmentExpression
+  ///   LeftHandSideExpression `||=` AssignmentExpression
+  ///   LeftHandSideExpression `??=` AssignmentExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-AssignmentExpression
+  pub(super) fn parse_assignment_expression(
+    &mut self,
+  ) -> Result<Node, ParseError> {
+    // `()` is only valid as the parameters of an arrow function
+    if test!(self.lexer, TokenType::LParen)?
+      && test_ahead!(self.lexer, TokenType::RParen)?
+    {
+      let node = self.start()?;
+      self.lexer.forward()?;
+      self.lexer.forward()?;
+      return self.parse_arrow_function(node, Vec::new());
+    }
+    let left = self.parse_conditional_expression()?;
+    if test!(self.lexer, TokenType::Arrow)? {
+      let params = self.arrow_parameters(&left)?;
+      let node = self.start_from(&left);
+      return self.parse_arrow_function(node, params);
+    }
+    if self.lexer.peek()?.token_type.is_assign() {
+      if !self.is_valid_simple_assignment_target(&left) {
+        return Err(
+          SyntaxError::from_node(
+            self,
+            &left,
+            SyntaxErrorTemplate::InvalidAssignmentTarget,
+          )
+          .into(),
+        );
+      }
+      let node = self.start_from(&left);
+      let operator = self.lexer.bump()?.token_type.clone();
+      let right = self.parse_assignment_expression()?;
+      return Ok(self.finish(
+        node,
+        NodeType::AssignmentExpression {
+          operator,
+          left: Box::new(left),
+          right: Box::new(right),
+        },
+      ));
+    }
+    Ok(left)
+  }
+
+  /// ArrowParameters :
+  ///   BindingIdentifier
+  ///   CoverParenthesizedExpressionAndArrowParameterList
+  ///
+  /// Reinterpret the already parsed `cover` as the parameters of an arrow
+  /// function.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ArrowParameters
+  fn arrow_parameters(&self, cover: &Node) -> Result<Vec<Node>, ParseError> {
+    let params = match cover.node_type() {
+      NodeType::IdentifierReference { .. } => std::slice::from_ref(cover),
+      NodeType::ParenthesizedExpression { expression } => {
+        match expression.node_type() {
+          NodeType::SequenceExpression { expressions } => &expressions[..],
+          _ => std::slice::from_ref(&**expression),
+        }
+      }
+      _ => {
+        return Err(
+          SyntaxError::from_node(
+            self,
+            cover,
+            SyntaxErrorTemplate::UnexpectedToken(
+              cover.source_text().to_owned(),
+            ),
+          )
+          .into(),
+        )
+      }
+    };
+    params
+      .iter()
+      .map(|param| self.arrow_parameter(param))
+      .collect()
+  }
+
+  /// An identifier with or without a default value as a FormalParameter.
+  // TODO: binding patterns and rest parameters
+  fn arrow_parameter(&self, param: &Node) -> Result<Node, ParseError> {
+    match param.node_type() {
+      NodeType::IdentifierReference { name, .. } => {
+        if self.is_strict() && (name == "eval" || name == "arguments") {
+          return Err(
+            EarlyError::from(SyntaxError::from_node(
+              self,
+              param,
+              SyntaxErrorTemplate::UnexpectedEvalOrArguments,
+            ))
+            .into(),
+          );
+        }
+        Ok(
+          param.reinterpret(NodeType::BindingIdentifier { name: name.clone() }),
+        )
+      }
+      NodeType::AssignmentExpression {
+        operator: TokenType::Assign,
+        left,
+        right,
+      } if matches!(left.node_type(), NodeType::IdentifierReference { .. }) => {
+        Ok(param.reinterpret(NodeType::AssignmentPattern {
+          left: Box::new(self.arrow_parameter(left)?),
+          right: right.clone(),
+        }))
+      }
+      _ => Err(
+        SyntaxError::from_node(
+          self,
+          param,
+          SyntaxErrorTemplate::UnexpectedToken(param.source_text().to_owned()),
+        )
+        .into(),
+      ),
+    }
+  }
+
+  /// See https://tc39.es/ecma262/#sec-static-semantics-assignmenttargettype
+  fn is_valid_simple_assignment_target(&self, node: &Node) -> bool {
+    match node.node_type() {
+      NodeType::IdentifierReference { name, .. } => {
+        !(self.is_strict() && (name == "eval" || name == "arguments"))
+      }
+      NodeType::MemberExpression { .. } => true,
+      NodeType::ParenthesizedExpression { expression } => {
+        self.is_valid_simple_assignment_target(expression)
+      }
+      _ => false,
+    }
+  }
+
+  /// ConditionalExpression :
+  ///   ShortCircuitExpression
+  ///   ShortCircuitExpression `?` AssignmentExpression `:` AssignmentExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ConditionalExpression
+  fn parse_conditional_expression(&mut self) -> Result<Node, ParseError> {
+    let test = self.parse_short_circuit_expression()?;
+    if eat!(&mut self.lexer, TokenType::Conditional)? {
+      let node = self.start_from(&test);
+      let consequent = self.parse_assignment_expression()?;
+      expect!(&mut self.lexer, TokenType::Colon)?;
+      let alternate = self.parse_assignment_expression()?;
+      return Ok(self.finish(
+        node,
+        NodeType::ConditionalExpression {
+          test: Box::new(test),
+          consequent: Box::new(consequent),
+          alternate: Box::new(alternate),
+        },
+      ));
+    }
+    Ok(test)
+  }
+
+  /// ShortCircuitExpression :
+  ///   LogicalORExpression
+  ///   CoalesceExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ShortCircuitExpression
+  fn parse_short_circuit_expression(&mut self) -> Result<Node, ParseError> {
+    let left = self.parse_unary_expression()?;
+    self.parse_binary_expression(0, left)
+  }
+
+  /// Parse binary operators by precedence climbing, `left` is the already
+  /// parsed left operand and only operators binding tighter than
+  /// `min_precedence` are consumed. `**` is right associative.
+  fn parse_binary_expression(
+    &mut self,
+    min_precedence: usize,
+    mut left: Node,
+  ) -> Result<Node, ParseError> {
+    loop {
+      let peek = self.lexer.peek()?;
+      let precedence = peek.token_type.precedence();
+      if precedence <= min_precedence
+        || (peek.token_type == TokenType::In
+          && !self.resolver.flags.has(Flag::In))
+      {
+        return Ok(left);
+      }
+      let node = self.start_from(&left);
+      let operator = self.lexer.bump()?.token_type.clone();
+      // ExponentiationExpression : UpdateExpression `**` ExponentiationExpression
+      let min_precedence = if operator == TokenType::Exp {
+        if let NodeType::UnaryExpression { .. } = left.node_type() {
+          return Err(
+            SyntaxError::from_node(
+              self,
+              &left,
+              SyntaxErrorTemplate::UnaryBeforeExponentiation,
+            )
+            .into(),
+          );
+        }
+        precedence - 1
+      } else {
+        precedence
+      };
+      let right = self.parse_unary_expression()?;
+      let right = self.parse_binary_expression(min_precedence, right)?;
+      if operator.is_logical() {
+        self.validate_coalesce_operand(&operator, &left)?;
+        self.validate_coalesce_operand(&operator, &right)?;
+        left = self.finish(
+          node,
+          NodeType::LogicalExpression {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+          },
+        );
+      } else {
+        left = self.finish(
+          node,
+          NodeType::BinaryExpression {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+          },
+        );
+      }
+    }
+  }
+
+  /// `??` can't be mixed with `||` or `&&` without parentheses.
+  ///
+  /// See https://tc39.es/ecma262/#prod-CoalesceExpressionHead
+  fn validate_coalesce_operand(
+    &self,
+    operator: &TokenType,
+    operand: &Node,
+  ) -> Result<(), ParseError> {
+    if let NodeType::LogicalExpression {
+      operator: operand_operator,
+      ..
+    } = operand.node_type()
+    {
+      if (*operator == TokenType::Nullish)
+        != (*operand_operator == TokenType::Nullish)
+      {
+        return Err(
+          SyntaxError::from_node(
+            self,
+            operand,
+            SyntaxErrorTemplate::NullishAndLogical,
+          )
+          .into(),
+        );
+      }
+    }
+    Ok(())
+  }
+
+  /// UnaryExpression :
+  ///   UpdateExpression
+  ///   `delete` UnaryExpression
+  ///   `void` UnaryExpression
+  ///   `typeof` UnaryExpression
+  ///   `+` UnaryExpression
+  ///   `-` UnaryExpression
+  ///   `~` UnaryExpression
+  ///   `!` UnaryExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-UnaryExpression
+  fn parse_unary_expression(&mut self) -> Result<Node, ParseError> {
+    if test!(
+      self.lexer,
+      TokenType::Delete
+        | TokenType::Void
+        | TokenType::Typeof
+        | TokenType::Add
+        | TokenType::Sub
+        | TokenType::BitNot
+        | TokenType::Not
+    )? {
+      let node = self.start()?;
+      let operator = self.lexer.bump()?.token_type.clone();
+      let argument = self.parse_unary_expression()?;
+      if operator == TokenType::Delete {
+        self.validate_delete_operand(&argument)?;
+      }
+      return Ok(self.finish(
+        node,
+        NodeType::UnaryExpression {
+          operator,
+          argument: Box::new(argument),
+        },
+      ));
+    }
+    self.parse_update_expression()
+  }
+
+  /// `delete` of an identifier reference in strict mode code and of a
+  /// private reference are early errors, parentheses are looked through.
+  ///
+  /// See https://tc39.es/ecma262/#sec-delete-operator-static-semantics-early-errors
+  fn validate_delete_operand(&self, argument: &Node) -> Result<(), ParseError> {
+    let template = match argument.node_type() {
+      NodeType::ParenthesizedExpression { expression } => {
+        return self.validate_delete_operand(expression)
+      }
+      NodeType::ChainExpression { expression } => {
+        return self.validate_delete_operand(expression)
+      }
+      NodeType::IdentifierReference { .. } if self.is_strict() => {
+        SyntaxErrorTemplate::StrictDelete
+      }
+      NodeType::MemberExpression { property, .. }
+        if matches!(
+          property.node_type(),
+          NodeType::PrivateIdentifier { .. }
+        ) =>
+      {
+        SyntaxErrorTemplate::DeletePrivateField
+      }
+      _ => return Ok(()),
+    };
+    Err(
+      EarlyError::from(SyntaxError::from_node(self, argument, template)).into(),
+    )
+  }
+
+  /// UpdateExpression :
+  ///   LeftHandSideExpression
+  ///   LeftHandSideExpression [no LineTerminator here] `++`
+  ///   LeftHandSideExpression [no LineTerminator here] `--`
+  ///   `++` UnaryExpression
+  ///   `--` UnaryExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-UpdateExpression
+  fn parse_update_expression(&mut self) -> Result<Node, ParseError> {
+    if test!(self.lexer, TokenType::Inc | TokenType::Dec)? {
+      let node = self.start()?;
+      let operator = self.lexer.bump()?.token_type.clone();
+      let argument = self.parse_unary_expression()?;
+      self.validate_update_operand(&argument)?;
+      return Ok(self.finish(
+        node,
+        NodeType::UpdateExpression {
+          operator,
+          argument: Box::new(argument),
+          prefix: true,
+        },
+      ));
+    }
+    let argument = self.parse_left_hand_side_expression()?;
+    let token = self.lexer.peek()?;
+    if matches!(token.token_type, TokenType::Inc | TokenType::Dec)
+      && !token.had_line_terminator_before
+    {
+      self.validate_update_operand(&argument)?;
+      let node = self.start_from(&argument);
+      let operator = self.lexer.bump()?.token_type.clone();
+      return Ok(self.finish(
+        node,
+        NodeType::UpdateExpression {
+          operator,
+          argument: Box::new(argument),
+          prefix: false,
+        },
+      ));
+    }
+    Ok(argument)
+  }
+
+  /// See https://tc39.es/ecma262/#sec-update-expressions-static-semantics-early-errors
+  fn validate_update_operand(&self, argument: &Node) -> Result<(), ParseError> {
+    if self.is_valid_simple_assignment_target(argument) {
+      return Ok(());
+    }
+    Err(
+      EarlyError::from(SyntaxError::from_node(
+        self,
+        argument,
+        SyntaxErrorTemplate::InvalidAssignmentTarget,
+      ))
+      .into(),
+    )
+  }
+
+  /// LeftHandSideExpression :
+  ///   NewExpression
+  ///   CallExpression
+  ///   OptionalExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-LeftHandSideExpression
+  fn parse_left_hand_side_expression(&mut self) -> Result<Node, ParseError> {
+    let result = if test!(self.lexer, TokenType::New)? {
+      self.parse_new_expression()?
+    } else {
+      self.parse_primary_expression()?
+    };
+    self.parse_member_or_call(result, true)
+  }
+
+  /// NewExpression :
+  ///   MemberExpression
+  ///   `new` NewExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-NewExpression
+  fn parse_new_expression(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::New)?;
+    let callee = if test!(self.lexer, TokenType::New)? {
+      self.parse_new_expression()?
+    } else if test!(self.lexer, TokenType::Import)?
+      && test_ahead!(self.lexer, TokenType::LParen)?
+    {
+      // an ImportCall isn't a MemberExpression, `new import(a)` is invalid
+      expect!(&mut self.lexer, TokenType::Import)?;
+      return Err(self.unexpected());
+    } else {
+      self.parse_primary_expression()?
+    };
+    let callee = self.parse_member_or_call(callee, false)?;
+    let arguments = if test!(self.lexer, TokenType::LParen)? {
+      Some(self.parse_arguments()?)
+    } else {
+      None
+    };
+    Ok(self.finish(
+      node,
+      NodeType::NewExpression {
+        callee: Box::new(callee),
+        arguments,
+      },
+    ))
+  }
+
+  /// Parse the member accesses, tagged templates, calls and optional chains
+  /// following `result`, calls and optional chains are only consumed when
+  /// `allow_calls` is set, e.g. not in the callee of `new`.
+  fn parse_member_or_call(
+    &mut self,
+    mut result: Node,
+    allow_calls: bool,
+  ) -> Result<Node, ParseError> {
+    let mut in_optional_chain = false;
+    loop {
+      let token_type = self.lexer.peek()?.token_type.clone();
+      let check = if allow_calls {
+        token_type.is_property_call()
+      } else {
+        token_type.is_member()
+      };
+      if !check {
+        break;
+      }
+      let node = self.start_from(&result);
+      match token_type {
+        TokenType::Period => {
+          self.lexer.forward()?;
+          let property = self.parse_member_property()?;
+          result = self.finish(
+            node,
+            NodeType::MemberExpression {
+              object: Box::new(result),
+              property: Box::new(property),
+              computed: false,
+              optional: false,
+            },
+          );
+        }
+        TokenType::LBrack => {
+          self.lexer.forward()?;
+          let property = self.parse_expression()?;
+          expect!(&mut self.lexer, TokenType::RBrack)?;
+          result = self.finish(
+            node,
+            NodeType::MemberExpression {
+              object: Box::new(result),
+              property: Box::new(property),
+              computed: true,
+              optional: false,
+            },
+          );
+        }
+        TokenType::LParen => {
+          let arguments = self.parse_arguments()?;
+          result = self.finish(
+            node,
+            NodeType::CallExpression {
+              callee: Box::new(result),
+              arguments,
+              optional: false,
+            },
+          );
+        }
+        TokenType::Optional => {
+          self.lexer.forward()?;
+          in_optional_chain = true;
+          result = self.parse_optional_chain_element(node, result)?;
+        }
+        _ => {
+          // tagged templates are not allowed in optional chains:
+          // `a?.b\`\`` and `a?.\`\``
+          if in_optional_chain {
+            let peek = self.lexer.peek()?.to_owned();
+            return Err(
+              SyntaxError::from_token(
+                self,
+                &peek,
+                SyntaxErrorTemplate::InvalidTaggedTemplateOnOptionalChain,
+              )
+              .into(),
+            );
+          }
+          let quasi = self.parse_template_literal(true)?;
+          result = self.finish(
+            node,
+            NodeType::TaggedTemplateExpression {
+              tag: Box::new(result),
+              quasi: Box::new(quasi),
+            },
+          );
+        }
+      }
+    }
+    if in_optional_chain {
+      let node = self.start_from(&result);
+      result = self.finish(
+        node,
+        NodeType::ChainExpression {
+          expression: Box::new(result),
+        },
+      );
+    }
+    Ok(result)
+  }
+
+  /// The element right after `?.`, which is a call, a computed member or a
+  /// member.
+  ///
+  /// See https://tc39.es/ecma262/#prod-OptionalChain
+  fn parse_optional_chain_element(
+    &mut self,
+    node: NodeBuilder,
+    object: Node,
+  ) -> Result<Node, ParseError> {
+    let token_type = self.lexer.peek()?.token_type.clone();
+    match token_type {
+      TokenType::LParen => {
+        let arguments = self.parse_arguments()?;
+        Ok(self.finish(
+          node,
+          NodeType::CallExpression {
+            callee: Box::new(object),
+            arguments,
+            optional: true,
+          },
+        ))
+      }
+      TokenType::LBrack => {
+        self.lexer.forward()?;
+        let property = self.parse_expression()?;
+        expect!(&mut self.lexer, TokenType::RBrack)?;
+        Ok(self.finish(
+          node,
+          NodeType::MemberExpression {
+            object: Box::new(object),
+            property: Box::new(property),
+            computed: true,
+            optional: true,
+          },
+        ))
+      }
+      ref t if t.is_template() => {
+        let peek = self.lexer.peek()?.to_owned();
+        Err(
+          SyntaxError::from_token(
+            self,
+            &peek,
+            SyntaxErrorTemplate::InvalidTaggedTemplateOnOptionalChain,
+          )
+          .into(),
+        )
+      }
+      _ => {
+        let property = self.parse_member_property()?;
+        Ok(self.finish(
+          node,
+          NodeType::MemberExpression {
+            object: Box::new(object),
+            property: Box::new(property),
+            computed: false,
+            optional: true,
+          },
+        ))
+      }
+    }
+  }
+
+  fn parse_member_property(&mut self) -> Result<Node, ParseError> {
+    if test!(self.lexer, TokenType::PrivateIdentifier(_))? {
+      self.parse_private_identifier()
+    } else {
+      self.parse_identifier_name()
+    }
+  }
+
+  /// Arguments :
+  ///   `(` `)`
+  ///   `(` ArgumentList `)`
+  ///   `(` ArgumentList `,` `)`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Arguments
+  fn parse_arguments(&mut self) -> Result<Vec<Node>, ParseError> {
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let mut arguments = Vec::new();
+    while !eat!(&mut self.lexer, TokenType::RParen)? {
+      let argument = if test!(self.lexer, TokenType::Ellipsis)? {
+        self.parse_spread_element()?
+      } else {
+        self.parse_assignment_expression()?
+      };
+      arguments.push(argument);
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RParen)?;
+        break;
+      }
+    }
+    Ok(arguments)
+  }
+
+  /// SpreadElement :
+  ///   `...` AssignmentExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-SpreadElement
+  fn parse_spread_element(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Ellipsis)?;
+    let argument = self.parse_assignment_expression()?;
+    Ok(self.finish(
+      node,
+      NodeType::SpreadElement {
+        argument: Box::new(argument),
+      },
+    ))
+  }
+
+  /// PrimaryExpression :
+  ///   `this`
+  ///   IdentifierReference
+  ///   Literal
+  ///   ArrayLiteral
+  ///   ObjectLiteral
+  ///   FunctionExpression
+  ///   TemplateLiteral
+  ///   CoverParenthesizedExpressionAndArrowParameterList
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-PrimaryExpression
+  fn parse_primary_expression(&mut self) -> Result<Node, ParseError> {
+    let token_type = self.lexer.peek()?.token_type.clone();
+    let literal = match token_type {
+      TokenType::Identifier(_)
+      | TokenType::EscapedKeyword(_)
+      | TokenType::Yield
+      | TokenType::Await => return self.parse_identifier_reference(),
+      ref t if t.is_template() => return self.parse_template_literal(false),
+      TokenType::LParen => return self.parse_parenthesized_expression(),
+      TokenType::LBrack => return self.parse_array_literal(),
+      TokenType::LBrace => return self.parse_object_literal(),
+      TokenType::Import => return self.parse_import_call_or_meta(),
+      TokenType::Function => return self.parse_function_expression(),
+      TokenType::This => NodeType::ThisExpression,
+      TokenType::Null => NodeType::NullLiteral,
+      TokenType::True => NodeType::BooleanLiteral { value: true },
+      TokenType::False => NodeType::BooleanLiteral { value: false },
+      TokenType::Number(value) => NodeType::NumericLiteral { value },
+      TokenType::BigInt(value) => NodeType::BigIntLiteral { value },
+      TokenType::String(value) => NodeType::StringLiteral { value },
+      // a `/` starting an expression starts a regular expression literal
+      TokenType::Div | TokenType::AssignDiv => {
+        match self.lexer.rescan_regular_expression()?.token_type.clone() {
+          TokenType::RegularExpression { pattern, flags } => {
+            NodeType::RegularExpressionLiteral { pattern, flags }
+          }
+          t => unreachable!("expect RegularExpression, got {:?}", t),
+        }
+      }
+      _ => return Err(self.unexpected()),
+    };
+    let node = self.start()?;
+    self.lexer.forward()?;
+    Ok(self.finish(node, literal))
+  }
+
+  /// ImportCall :
+  ///   `import` `(` AssignmentExpression `)`
+  ///
+  /// ImportMeta :
+  ///   `import` `.` `meta`
+  ///
+  /// `import.meta` is only allowed in module code.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ImportCall
+  fn parse_import_call_or_meta(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let meta = self.parse_identifier_name()?;
+    if eat!(&mut self.lexer, TokenType::Period)? {
+      let property = self.start()?;
+      expect!(&mut self.lexer, "meta")?;
+      let property = self.finish(
+        property,
+        NodeType::IdentifierName {
+          name: "meta".to_owned(),
+        },
+      );
+      let node = self.finish(
+        node,
+        NodeType::MetaProperty {
+          meta: Box::new(meta),
+          property: Box::new(property),
+        },
+      );
+      if !self.resolver.flags.has(Flag::ImportMeta) {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(
+            self,
+            &node,
+            SyntaxErrorTemplate::ImportMetaOutsideModule,
+          ))
+          .into(),
+        );
+      }
+      return Ok(node);
+    }
+    // TODO: the options argument of import attributes
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let source = self.parse_assignment_expression()?;
+    expect!(&mut self.lexer, TokenType::RParen)?;
+    Ok(self.finish(
+      node,
+      NodeType::ImportCall {
+        source: Box::new(source),
+      },
+    ))
+  }
+
+  /// ParenthesizedExpression :
+  ///   `(` Expression `)`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ParenthesizedExpression
+  fn parse_parenthesized_expression(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let expression = self.parse_expression()?;
+    expect!(&mut self.lexer, TokenType::RParen)?;
+    Ok(self.finish(
+      node,
+      NodeType::ParenthesizedExpression {
+        expression: Box::new(expression),
+      },
+    ))
+  }
+
+  /// ArrayLiteral :
+  ///   `[` Elision? `]`
+  ///   `[` ElementList `]`
+  ///   `[` ElementList `,` Elision? `]`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ArrayLiteral
+  fn parse_array_literal(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::LBrack)?;
+    let mut elements = Vec::new();
+    while !eat!(&mut self.lexer, TokenType::RBrack)? {
+      if eat!(&mut self.lexer, TokenType::Comma)? {
+        elements.push(None);
+        continue;
+      }
+      let element = if test!(self.lexer, TokenType::Ellipsis)? {
+        self.parse_spread_element()?
+      } else {
+        self.parse_assignment_expression()?
+      };
+      elements.push(Some(element));
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RBrack)?;
+        break;
+      }
+    }
+    Ok(self.finish(node, NodeType::ArrayLiteral { elements }))
+  }
+
+  /// ObjectLiteral :
+  ///   `{` `}`
+  ///   `{` PropertyDefinitionList `}`
+  ///   `{` PropertyDefinitionList `,` `}`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ObjectLiteral
+  fn parse_object_literal(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::LBrace)?;
+    let mut properties = Vec::new();
+    while !eat!(&mut self.lexer, TokenType::RBrace)? {
+      properties.push(self.parse_property_definition()?);
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RBrace)?;
+        break;
+      }
+    }
+    Ok(self.finish(node, NodeType::ObjectLiteral { properties }))
+  }
+
+  /// PropertyDefinition :
+  ///   IdentifierReference
+  ///   PropertyName `:` AssignmentExpression
+  ///   `...` AssignmentExpression
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-PropertyDefinition
+  fn parse_property_definition(&mut self) -> Result<Node, ParseError> {
+    // TODO: CoverInitializedName and MethodDefinition
+    if test!(self.lexer, TokenType::Ellipsis)? {
+      return self.parse_spread_element();
+    }
+    let node = self.start()?;
+    let is_shorthand =
+      test!(
+        self.lexer,
+        TokenType::Identifier(_)
+          | TokenType::EscapedKeyword(_)
+          | TokenType::Yield
+          | TokenType::Await
+      )? && test_ahead!(self.lexer, TokenType::Comma | TokenType::RBrace)?;
+    if is_shorthand {
+      let value = self.parse_identifier_reference()?;
+      let name = match value.node_type() {
+        NodeType::IdentifierReference { name, .. } => name.clone(),
+        _ => unreachable!("expect IdentifierReference"),
+      };
+      let key = self.start_from(&value);
+      let key = self.finish(key, NodeType::IdentifierName { name });
+      return Ok(self.finish(
+        node,
+        NodeType::Property {
+          key: Box::new(key),
+          value: Box::new(value),
+          computed: false,
+          shorthand: true,
+        },
+      ));
+    }
+    let (key, computed) = self.parse_property_name()?;
+    expect!(&mut self.lexer, TokenType::Colon)?;
+    let value = self.parse_assignment_expression()?;
+    Ok(self.finish(
+      node,
+      NodeType::Property {
+        key: Box::new(key),
+        value: Box::new(value),
+        computed,
+        shorthand: false,
+      },
+    ))
+  }
+
+  /// PropertyName :
+  ///   LiteralPropertyName
+  ///   ComputedPropertyName
+  ///
+  /// Returns the key and whether it's computed, a literal key's string value
+  /// is its `prop_name()`.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-PropertyName
+  pub(super) fn parse_property_name(
+    &mut self,
+  ) -> Result<(Node, bool), ParseError> {
+    let token_type = self.lexer.peek()?.token_type.clone();
+    let literal = match token_type {
+      TokenType::LBrack => {
+        self.lexer.forward()?;
+        let key = self.parse_assignment_expression()?;
+        expect!(&mut self.lexer, TokenType::RBrack)?;
+        return Ok((key, true));
+      }
+      TokenType::String(value) => NodeType::StringLiteral { value },
+      TokenType::Number(value) => NodeType::NumericLiteral { value },
+      TokenType::BigInt(value) => NodeType::BigIntLiteral { value },
+      _ => return Ok((self.parse_identifier_name()?, false)),
+    };
+    let node = self.start()?;
+    self.lexer.forward()?;
+    Ok((self.finish(node, literal), false))
+  }
+
+  /// TemplateLiteral :
+  ///   NoSubstitutionTemplate
+  ///   SubstitutionTemplate
+  ///
+  /// The cooked value of a chunk may only be undefined in a tagged template.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-TemplateLiteral
+  fn parse_template_literal(
+    &mut self,
+    tagged: bool,
+  ) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let mut quasis = Vec::new();
+    let mut expressions = Vec::new();
+    loop {
+      let token = self.lexer.bump()?.to_owned();
+      let (cooked, raw, is_tail) = match token.token_type.clone() {
+        TokenType::NoSubstitutionTemplate { cooked, raw }
+          if quasis.is_empty() =>
+        {
+          (cooked, raw, true)
+        }
+        TokenType::TemplateHead { cooked, raw } if quasis.is_empty() => {
+          (cooked, raw, false)
+        }
+        TokenType::TemplateMiddle { cooked, raw } if !quasis.is_empty() => {
+          (cooked, raw, false)
+        }
+        TokenType::TemplateTail { cooked, raw } if !quasis.is_empty() => {
+          (cooked, raw, true)
+        }
+        _ => {
+          return Err(
+            SyntaxError::from_token(
+              self,
+              &token,
+              SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
+            )
+            .into(),
+          )
+        }
+      };
+      if cooked.is_none() && !tagged {
+        return Err(
+          SyntaxError::from_token(
+            self,
+            &token,
+            SyntaxErrorTemplate::InvalidTemplateEscape,
+          )
+          .into(),
+        );
+      }
+      quasis.push(TemplateElement { cooked, raw });
+      if is_tail {
+        break;
+      }
+      expressions.push(self.parse_expression()?);
+    }
+    Ok(self.finish(
+      node,
+      NodeType::TemplateLiteral {
+        quasis,
+        expressions,
+      },
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parser::tokens::StringValue;
+
+  fn template_element(cooked: Option<&str>, raw: &str) -> TemplateElement {
+    TemplateElement {
+      cooked: cooked.map(StringValue::from),
+      raw: raw.to_owned(),
+    }
+  }
+
+  #[test]
+  fn template_literal_substitution() {
+    let mut parser = Parser::new("`a${x}b`");
+    let node = parser.parse_expression().unwrap();
+    match node.node_type() {
+      NodeType::TemplateLiteral {
+        quasis,
+        expressions,
+      } => {
+        assert_eq!(
+          quasis,
+          &vec![
+            template_element(Some("a"), "a"),
+            template_element(Some("b"), "b")
+          ]
+        );
+        assert_eq!(expressions.len(), 1);
+        assert!(matches!(
+          expressions[0].node_type(),
+          NodeType::IdentifierReference { name, .. } if name == "x"
+        ));
+      }
+      t => panic!("expect TemplateLiteral, got {:?}", t),
+    }
+  }
+
+  #[test]
+  fn tagged_template() {
+    let mut parser = Parser::new(r#"tag`\x${a}\n`"#);
+    let node = parser.parse_expression().unwrap();
+    match node.node_type() {
+      NodeType::TaggedTemplateExpression { tag, quasi } => {
+        assert!(matches!(
+          tag.node_type(),
+          NodeType::IdentifierReference { name, .. } if name == "tag"
+        ));
+        match quasi.node_type() {
+          NodeType::TemplateLiteral { quasis, .. } => assert_eq!(
+            quasis,
+            &vec![
+              template_element(None, r#"\x"#),
+              template_element(Some("\n"), r#"\n"#)
+            ]
+          ),
+          t => panic!("expect TemplateLiteral, got {:?}", t),
+        }
+      }
+      t => panic!("expect TaggedTemplateExpression, got {:?}", t),
+    }
+  }
+
+  #[test]
+  fn untagged_template_invalid_escape() {
+    let mut parser = Parser::new(r#"`\x`"#);
+    assert!(parser.parse_expression().is_err());
+  }
+
+  #[test]
+  fn template_in_optional_chain() {
+    let mut parser = Parser::new("a?.b`c`");
+    assert!(parser.parse_expression().is_err());
+    let mut parser = Parser::new("a?.`c`");
+    assert!(parser.parse_expression().is_err());
+  }
+
+  #[test]
+  fn nested_template() {
+    let mut parser = Parser::new("`a${`b${c}`}d`");
+    let node = parser.parse_expression().unwrap();
+    match node.node_type() {
+      NodeType::TemplateLiteral { expressions, .. } => assert!(matches!(
+        expressions[0].node_type(),
+        NodeType::TemplateLiteral { .. }
+      )),
+      t => panic!("expect TemplateLiteral, got {:?}", t),
+    }
+  }
+
+  #[test]
+  fn delete_early_errors() {
+    let error = |source| {
+      Parser::parse_script(source, None)
+        .unwrap_err()
+        .syntax_error()
+        .message()
+        .to_owned()
+    };
+    assert!(Parser::parse_script("delete x;", None).is_ok());
+    assert!(Parser::parse_script("'use strict'; delete o.x;", None).is_ok());
+    assert!(Parser::parse_script("'use strict'; delete o[x];", None).is_ok());
+    assert_eq!(
+      error("'use strict'; delete x;"),
+      SyntaxErrorTemplate::StrictDelete.to_string()
+    );
+    assert_eq!(
+      error("'use strict'; delete ((x));"),
+      SyntaxErrorTemplate::StrictDelete.to_string()
+    );
+    assert_eq!(
+      error("delete o.#x;"),
+      SyntaxErrorTemplate::DeletePrivateField.to_string()
+    );
+    assert_eq!(
+      error("delete o?.#x;"),
+      SyntaxErrorTemplate::DeletePrivateField.to_string()
+    );
+  }
+
+  /// The `prop_name()`s of an object literal, `None` for computed keys.
+  fn prop_names(source: &str) -> Vec<Option<String>> {
+    let node = Parser::new(source).parse_expression().unwrap();
+    match node.node_type() {
+      NodeType::ObjectLiteral { properties } => properties
+        .iter()
+        .map(|property| match property.node_type() {
+          NodeType::Property { key, computed, .. } => {
+            assert_eq!(*computed, key.prop_name().is_none());
+            key.prop_name()
+          }
+          t => panic!("expect Property, got {:?}", t),
+        })
+        .collect(),
+      t => panic!("expect ObjectLiteral, got {:?}", t),
+    }
+  }
+
+  #[test]
+  fn property_names() {
+    assert_eq!(prop_names("{}"), Vec::<Option<String>>::new());
+    assert_eq!(prop_names("{0: 1}"), [Some("0".to_owned())]);
+    assert_eq!(prop_names("{\"a b\": 1}"), [Some("a b".to_owned())]);
+    assert_eq!(prop_names("{[x]: 1}"), [None]);
+    assert_eq!(prop_names("{1.5: 1}"), [Some("1.5".to_owned())]);
+    assert_eq!(
+      prop_names("{0.10: 1, 1e21: 2, 0x10: 3, 1n: 4, if: 5, a,}"),
+      ["0.1", "1e+21", "16", "1", "if", "a"].map(|s| Some(s.to_owned()))
+    );
+    let node = Parser::new("{ ...a }").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ObjectLiteral { properties }
+        if matches!(properties[0].node_type(), NodeType::SpreadElement { .. })
+    ));
+    assert!(Parser::new("{ a b }").parse_expression().is_err());
+    // a statement starting with `{` is a block, not an object literal
+    let node = Parser::parse_script("{}", None).unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::Script { body }
+        if matches!(body[0].node_type(), NodeType::BlockStatement { .. })
+    ));
+  }
+
+  #[test]
+  fn regular_expression_literal() {
+    let node = Parser::new("/a/g.test(b)").parse_expression().unwrap();
+    let callee = match node.node_type() {
+      NodeType::CallExpression { callee, .. } => callee,
+      t => panic!("expect a call, got {:?}", t),
+    };
+    assert!(matches!(
+      callee.node_type(),
+      NodeType::MemberExpression { object, .. }
+        if matches!(
+          object.node_type(),
+          NodeType::RegularExpressionLiteral { pattern, flags }
+            if pattern == "a" && flags == "g"
+        )
+    ));
+    // a `/` after an operand divides
+    let node = Parser::new("a / b / c").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::BinaryExpression { .. }
+    ));
+    let node = Parser::new("x = /=/").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::AssignmentExpression { right, .. }
+        if matches!(
+          right.node_type(),
+          NodeType::RegularExpressionLiteral { pattern, .. } if pattern == "="
+        )
+    ));
+  }
+
+  #[test]
+  fn sequence_expression() {
+    let node = Parser::new("a, b = c, d").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::SequenceExpression { expressions }
+        if expressions.len() == 3
+          && matches!(
+            expressions[1].node_type(),
+            NodeType::AssignmentExpression { .. }
+          )
+    ));
+    let node = Parser::new("(a, b)").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ParenthesizedExpression { expression }
+        if matches!(expression.node_type(), NodeType::SequenceExpression { .. })
+    ));
+    // elements and arguments are AssignmentExpressions
+    let node = Parser::new("[a, b]").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ArrayLiteral { elements } if elements.len() == 2
+    ));
+    let node = Parser::new("f(a, b)").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::CallExpression { arguments, .. } if arguments.len() == 2
+    ));
+    assert!(Parser::new("a, ").parse_expression().is_err());
+  }
+
+  #[test]
+  fn update_expression() {
+    let node = Parser::new("a++").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::UpdateExpression {
+        operator: TokenType::Inc,
+        prefix: false,
+        ..
+      }
+    ));
+    let node = Parser::new("--a.b").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::UpdateExpression {
+        operator: TokenType::Dec,
+        prefix: true,
+        ..
+      }
+    ));
+    // a line terminator before `++` isn't allowed, it's `a; ++b;`
+    let node = Parser::parse_script("a\n++b", None).unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::Script { body } if body.len() == 2
+    ));
+    assert!(Parser::parse_script("a\n++", None).is_err());
+    assert!(Parser::new("++1").parse_expression().is_err());
+    assert!(Parser::new("a()--").parse_expression().is_err());
+    assert!(Parser::new("++a++").parse_expression().is_err());
+    assert!(Parser::parse_script("'use strict'; eval++", None).is_err());
+  }
+
+  #[test]
+  fn exponentiation() {
+    let node = Parser::new("2 ** 3 ** 2").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::BinaryExpression { left, right, .. }
+        if matches!(left.node_type(), NodeType::NumericLiteral { .. })
+          && matches!(right.node_type(), NodeType::BinaryExpression { .. })
+    ));
+    let node = Parser::new("2 * 3 ** 2").parse_expression().unwrap();
+    assert!(matches!(
+      node.node_type(),
+      NodeType::BinaryExpression {
+        operator: TokenType::Mul,
+        ..
+      }
+    ));
+    assert!(Parser::new("(-2) ** 2").parse_expression().is_ok());
+    assert!(Parser::new("-(2 ** 2)").parse_expression().is_ok());
+    assert!(Parser::new("2 ** -2").parse_expression().is_ok());
+    assert!(Parser::new("a++ ** 2").parse_expression().is_ok());
+    for source in ["-2 ** 2", "typeof a ** 2", "!a ** 2", "1 + -2 ** 2"] {
+      assert!(
+        Parser::new(source).parse_expression().is_err(),
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn import_call() {
+    let node = Parser::new("import('x').then(f)")
+      .parse_expression()
+      .unwrap();
+    match node.node_type() {
+      NodeType::CallExpression { callee, .. } => match callee.node_type() {
+        NodeType::MemberExpression { object, .. } => assert!(matches!(
+          object.node_type(),
+          NodeType::ImportCall { source }
+            if matches!(source.node_type(), NodeType::StringLiteral { value } if value == "x")
+        )),
+        _ => unreachable!(),
+      },
+      _ => unreachable!(),
+    }
+    for source in ["import('x');", "function f() { return import(a + b); }"] {
+      assert!(Parser::parse_script(source, None).is_ok(), "{}", source);
+      assert!(Parser::parse_module(source, None).is_ok(), "{}", source);
+    }
+    for source in ["import()", "import(a, b, c)", "new import(a)", "import"] {
+      assert!(
+        Parser::new(source).parse_expression().is_err(),
+        "{}",
+        source
+      );
+    }
+  }
+
+  #[test]
+  fn import_meta() {
+    let node = Parser::parse_module("import.meta.url", None).unwrap();
+    match node.node_type() {
+      NodeType::Module { body } => match body[0].node_type() {
+        NodeType::ExpressionStatement { expression } => {
+          match expression.node_type() {
+            NodeType::MemberExpression { object, .. } => assert!(matches!(
+              object.node_type(),
+              NodeType::MetaProperty { meta, property }
+                if matches!(meta.node_type(), NodeType::IdentifierName { name } if name == "import")
+                  && matches!(property.node_type(), NodeType::IdentifierName { name } if name == "meta")
+            )),
+            _ => unreachable!(),
+          }
+        }
+        _ => unreachable!(),
+      },
+      _ => unreachable!(),
+    }
+    assert!(Parser::parse_module("function f() { import.meta; }", None).is_ok());
+    assert_eq!(
+      Parser::parse_script("import.meta", None)
+        .unwrap_err()
+        .syntax_error()
+        .message(),
+      "Cannot use 'import.meta' outside a module"
+    );
+    for source in ["import.m\\u0065ta", "import.foo", "import.meta = 1"] {
+      assert!(Parser::parse_module(source, None).is_err(), "{}", source);
+    }
+  }
+}
@@ -0,0 +1,513 @@
+use crate::{eat, expect, test};
+
+use super::{
+  error::{EarlyError, ParseError, SyntaxError, SyntaxErrorTemplate},
+  nodes::{Node, NodeBuilder, NodeType},
+  strict::{IsStrict, SetStrict},
+  tokens::TokenType,
+  Parser,
+};
+
+impl Parser {
+  /// FunctionDeclaration :
+  ///   `function` BindingIdentifier `(` FormalParameters `)` `{` FunctionBody `}`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-FunctionDeclaration
+  pub(super) fn parse_function_declaration(
+    &mut self,
+  ) -> Result<Node, ParseError> {
+    // TODO: generators, async functions and `export default function () {}`
+    let mut node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Function)?;
+    let id = self.parse_binding_identifier()?;
+    if let NodeType::BindingIdentifier { name } = id.node_type() {
+      if !self.resolver.declare_function(name) {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(
+            self,
+            &id,
+            SyntaxErrorTemplate::AlreadyDeclared,
+          ))
+          .into(),
+        );
+      }
+    }
+    let is_strict = self.is_strict();
+    self.resolver.enter_function_scope();
+    let function = self.parse_parameters_and_body();
+    self.resolver.exit_function_scope();
+    // the function is strict if its body has a Use Strict Directive
+    node.is_strict = self.is_strict();
+    self.set_strict(is_strict);
+    let (params, body) = function?;
+    Ok(self.finish(
+      node,
+      NodeType::FunctionDeclaration {
+        id: Box::new(id),
+        params,
+        body,
+      },
+    ))
+  }
+
+  /// FunctionExpression :
+  ///   `function` BindingIdentifier? `(` FormalParameters `)` `{` FunctionBody `}`
+  ///
+  /// The name is only bound in the function itself.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-FunctionExpression
+  pub(super) fn parse_function_expression(
+    &mut self,
+  ) -> Result<Node, ParseError> {
+    // TODO: generators and async functions
+    let mut node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Function)?;
+    let id = if test!(self.lexer, TokenType::LParen)? {
+      None
+    } else {
+      Some(Box::new(self.parse_binding_identifier()?))
+    };
+    let is_strict = self.is_strict();
+    self.resolver.enter_function_scope();
+    let function = self.parse_parameters_and_body();
+    self.resolver.exit_function_scope();
+    node.is_strict = self.is_strict();
+    self.set_strict(is_strict);
+    let (params, body) = function?;
+    Ok(self.finish(node, NodeType::FunctionExpression { id, params, body }))
+  }
+
+  /// ArrowFunction :
+  ///   ArrowParameters `=>` ConciseBody
+  ///
+  /// `node` starts at the already parsed `params`.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ArrowFunction
+  pub(super) fn parse_arrow_function(
+    &mut self,
+    mut node: NodeBuilder,
+    params: Vec<Node>,
+  ) -> Result<Node, ParseError> {
+    if self.lexer.peek()?.had_line_terminator_before {
+      return Err(self.unexpected());
+    }
+    expect!(&mut self.lexer, TokenType::Arrow)?;
+    let is_strict = self.is_strict();
+    self.resolver.enter_function_scope();
+    let function = self.parse_arrow_parameters_and_body(params);
+    self.resolver.exit_function_scope();
+    node.is_strict = self.is_strict();
+    self.set_strict(is_strict);
+    let (params, body, expression) = function?;
+    Ok(self.finish(
+      node,
+      NodeType::ArrowFunctionExpression {
+        params,
+        body,
+        expression,
+      },
+    ))
+  }
+
+  /// ConciseBody :
+  ///   ExpressionBody
+  ///   `{` FunctionBody `}`
+  fn parse_arrow_parameters_and_body(
+    &mut self,
+    params: Vec<Node>,
+  ) -> Result<(Vec<Node>, Vec<Node>, bool), ParseError> {
+    let (is_simple, duplicate) = self.declare_parameters(&params);
+    // https://tc39.es/ecma262/#sec-arrow-function-definitions-static-semantics-early-errors
+    // It is a Syntax Error if ArrowParameters contains any duplicate elements.
+    if let Some(duplicate) = duplicate {
+      return Err(self.duplicate_parameter(&duplicate));
+    }
+    if test!(self.lexer, TokenType::LBrace)? {
+      let body = self.parse_function_body(is_simple, None)?;
+      Ok((params, body, false))
+    } else {
+      let body = self.parse_assignment_expression()?;
+      Ok((params, vec![body], true))
+    }
+  }
+
+  /// `(` FormalParameters `)` `{` FunctionBody `}`
+  fn parse_parameters_and_body(
+    &mut self,
+  ) -> Result<(Vec<Node>, Vec<Node>), ParseError> {
+    let params = self.parse_formal_parameters()?;
+    let (is_simple, duplicate) = self.declare_parameters(&params);
+    // duplicates are only allowed in sloppy simple parameter lists
+    if let Some(duplicate) = &duplicate {
+      if self.is_strict() || !is_simple {
+        return Err(self.duplicate_parameter(duplicate));
+      }
+    }
+    let body = self.parse_function_body(is_simple, duplicate.as_ref())?;
+    Ok((params, body))
+  }
+
+  /// Declare the parameters in the function scope, whether the list is
+  /// simple and the first repeated parameter name are returned.
+  fn declare_parameters(&mut self, params: &[Node]) -> (bool, Option<Node>) {
+    // https://tc39.es/ecma262/#sec-parameter-lists-static-semantics-early-errors
+    let is_simple = params.iter().all(|param| {
+      matches!(param.node_type(), NodeType::BindingIdentifier { .. })
+    });
+    let mut duplicate = None;
+    for id in params.iter().flat_map(Node::bound_names) {
+      if let NodeType::BindingIdentifier { name } = id.node_type() {
+        if !self.resolver.declare_parameter(name) && duplicate.is_none() {
+          duplicate = Some(id.clone());
+        }
+      }
+    }
+    (is_simple, duplicate)
+  }
+
+  fn duplicate_parameter(&self, id: &Node) -> ParseError {
+    EarlyError::from(SyntaxError::from_node(
+      self,
+      id,
+      SyntaxErrorTemplate::DuplicateParameter,
+    ))
+    .into()
+  }
+
+  /// FormalParameters :
+  ///   [empty]
+  ///   FunctionRestParameter
+  ///   FormalParameterList `,`?
+  ///   FormalParameterList `,` FunctionRestParameter
+  ///
+  /// FormalParameterList :
+  ///   FormalParameter
+  ///   FormalParameterList `,` FormalParameter
+  ///
+  /// FormalParameter :
+  ///   BindingElement
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-FormalParameters
+  fn parse_formal_parameters(&mut self) -> Result<Vec<Node>, ParseError> {
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let mut params = Vec::new();
+    while !eat!(&mut self.lexer, TokenType::RParen)? {
+      if test!(self.lexer, TokenType::Ellipsis)? {
+        params.push(self.parse_function_rest_parameter()?);
+        break;
+      }
+      params.push(self.parse_binding_element()?);
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RParen)?;
+        break;
+      }
+    }
+    Ok(params)
+  }
+
+  /// FunctionRestParameter :
+  ///   BindingRestElement
+  ///
+  /// BindingRestElement :
+  ///   `...` BindingIdentifier
+  ///   `...` BindingPattern
+  ///
+  /// The rest parameter closes the parameter list.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-FunctionRestParameter
+  fn parse_function_rest_parameter(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Ellipsis)?;
+    let argument = self.parse_binding_target()?;
+    let rest = self.finish(
+      node,
+      NodeType::RestElement {
+        argument: Box::new(argument),
+      },
+    );
+    let template = match self.lexer.peek()?.token_type {
+      TokenType::RParen => {
+        self.lexer.forward()?;
+        return Ok(rest);
+      }
+      TokenType::Assign => SyntaxErrorTemplate::RestDefaultInitializer,
+      _ => SyntaxErrorTemplate::RestParameterNotLast,
+    };
+    Err(SyntaxError::from_node(self, &rest, template).into())
+  }
+
+  /// FunctionBody :
+  ///   FunctionStatementList
+  ///
+  /// A Use Strict Directive makes the parameters strict too, they're
+  /// checked again with `duplicate`, the first repeated parameter name.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-FunctionBody
+  fn parse_function_body(
+    &mut self,
+    is_simple: bool,
+    duplicate: Option<&Node>,
+  ) -> Result<Vec<Node>, ParseError> {
+    expect!(&mut self.lexer, TokenType::LBrace)?;
+    let (mut body, use_strict) = self.parse_directive_prologue()?;
+    if let Some(use_strict) = use_strict {
+      // https://tc39.es/ecma262/#sec-function-definitions-static-semantics-early-errors
+      // It is a Syntax Error if FunctionBodyContainsUseStrict of FunctionBody is
+      // true and IsSimpleParameterList of FormalParameters is false.
+      if !is_simple {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(
+            self,
+            &use_strict,
+            SyntaxErrorTemplate::IllegalUseStrict,
+          ))
+          .into(),
+        );
+      }
+      if let Some(duplicate) = duplicate {
+        return Err(self.duplicate_parameter(duplicate));
+      }
+    }
+    body.extend(self.parse_statement_list(TokenType::RBrace)?);
+    expect!(&mut self.lexer, TokenType::RBrace)?;
+    Ok(body)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_error(source: &str) -> String {
+    Parser::parse_script(source, None)
+      .unwrap_err()
+      .syntax_error()
+      .message()
+      .to_owned()
+  }
+
+  #[test]
+  fn function_declaration() {
+    let node =
+      Parser::parse_script("function f(a, b,) { return a; }", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::FunctionDeclaration { params, body, .. } => {
+          assert_eq!(params.len(), 2);
+          assert_eq!(body.len(), 1);
+        }
+        t => panic!("expect FunctionDeclaration, got {:?}", t),
+      },
+      _ => unreachable!(),
+    }
+    // sloppy simple parameter lists may have duplicates
+    assert!(Parser::parse_script("function f(a, a) {}", None).is_ok());
+    assert!(Parser::parse_script("function f(a) { var a; }", None).is_ok());
+    assert!(Parser::parse_script("function f() {} var f;", None).is_ok());
+  }
+
+  #[test]
+  fn function_early_errors() {
+    assert_eq!(
+      parse_error("function f(a) { let a; }"),
+      SyntaxErrorTemplate::AlreadyDeclared.to_string()
+    );
+    assert_eq!(
+      parse_error("let f; function f() {}"),
+      SyntaxErrorTemplate::AlreadyDeclared.to_string()
+    );
+    assert_eq!(
+      Parser::parse_module("function f(a, a) {}", None)
+        .unwrap_err()
+        .syntax_error()
+        .message(),
+      SyntaxErrorTemplate::DuplicateParameter.to_string()
+    );
+    assert_eq!(
+      parse_error("function f(a, a) { 'use strict'; }"),
+      SyntaxErrorTemplate::DuplicateParameter.to_string()
+    );
+    // break targets don't cross function boundaries
+    assert_eq!(
+      parse_error("switch (a) { case 1: function f() { break; } }"),
+      SyntaxErrorTemplate::IllegalBreak.to_string()
+    );
+  }
+
+  #[test]
+  fn non_simple_parameters() {
+    let node =
+      Parser::parse_script("function f(a, b = 2, ...c) {}", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::FunctionDeclaration { params, .. } => {
+          assert!(matches!(
+            params[1].node_type(),
+            NodeType::AssignmentPattern { .. }
+          ));
+          assert!(matches!(
+            params[2].node_type(),
+            NodeType::RestElement { .. }
+          ));
+        }
+        t => panic!("expect FunctionDeclaration, got {:?}", t),
+      },
+      _ => unreachable!(),
+    }
+    assert!(
+      Parser::parse_script("function f({ a }, [b], ...[c]) {}", None).is_ok()
+    );
+    assert_eq!(
+      parse_error("function f(a = 1) { 'use strict'; }"),
+      SyntaxErrorTemplate::IllegalUseStrict.to_string()
+    );
+    assert_eq!(
+      parse_error("function f({ a }) { \"use strict\"; }"),
+      SyntaxErrorTemplate::IllegalUseStrict.to_string()
+    );
+    // duplicates aren't allowed once the list isn't simple, even sloppy
+    assert_eq!(
+      parse_error("function f(a, a = 1) {}"),
+      SyntaxErrorTemplate::DuplicateParameter.to_string()
+    );
+    assert_eq!(
+      parse_error("function f(a, ...a) {}"),
+      SyntaxErrorTemplate::DuplicateParameter.to_string()
+    );
+    assert_eq!(
+      parse_error("function f(...a, b) {}"),
+      SyntaxErrorTemplate::RestParameterNotLast.to_string()
+    );
+    assert_eq!(
+      parse_error("function f(...a,) {}"),
+      SyntaxErrorTemplate::RestParameterNotLast.to_string()
+    );
+    assert_eq!(
+      parse_error("function f(...a = []) {}"),
+      SyntaxErrorTemplate::RestDefaultInitializer.to_string()
+    );
+  }
+
+  #[test]
+  fn function_expression() {
+    let node =
+      Parser::parse_script("(function f(a) { return f; })", None).unwrap();
+    let expression = match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::ExpressionStatement { expression } => {
+          match expression.node_type() {
+            NodeType::ParenthesizedExpression { expression } => expression,
+            t => panic!("expect ParenthesizedExpression, got {:?}", t),
+          }
+        }
+        t => panic!("expect ExpressionStatement, got {:?}", t),
+      },
+      _ => unreachable!(),
+    };
+    assert!(matches!(
+      expression.node_type(),
+      NodeType::FunctionExpression { id: Some(_), .. }
+    ));
+    // the name isn't declared in the enclosing scope
+    assert!(Parser::parse_script("let f = function f() {};", None).is_ok());
+    assert!(Parser::parse_script("a = function () { return; };", None).is_ok());
+    assert_eq!(
+      parse_error("a = function (a, a) { 'use strict'; };"),
+      SyntaxErrorTemplate::DuplicateParameter.to_string()
+    );
+  }
+
+  #[test]
+  fn arrow_function() {
+    for source in [
+      "a => a",
+      "() => {}",
+      "(a, b = 1) => { return a + b; }",
+      "(a) => b => a + b",
+      "a = () => ({})",
+    ] {
+      assert!(Parser::parse_script(source, None).is_ok(), "{}", source);
+    }
+    let node = Parser::parse_script("(a, b = 1) => a", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::ExpressionStatement { expression } => {
+          match expression.node_type() {
+            NodeType::ArrowFunctionExpression {
+              params,
+              body,
+              expression: true,
+            } => {
+              assert!(matches!(
+                params[0].node_type(),
+                NodeType::BindingIdentifier { .. }
+              ));
+              assert!(matches!(
+                params[1].node_type(),
+                NodeType::AssignmentPattern { .. }
+              ));
+              assert!(matches!(
+                body[0].node_type(),
+                NodeType::IdentifierReference { .. }
+              ));
+            }
+            t => panic!("expect ArrowFunctionExpression, got {:?}", t),
+          }
+        }
+        t => panic!("expect ExpressionStatement, got {:?}", t),
+      },
+      _ => unreachable!(),
+    }
+    assert_eq!(
+      parse_error("(a, a) => 1"),
+      SyntaxErrorTemplate::DuplicateParameter.to_string()
+    );
+    assert_eq!(
+      parse_error("'use strict'; (eval) => 1"),
+      SyntaxErrorTemplate::UnexpectedEvalOrArguments.to_string()
+    );
+    for source in [
+      "a\n=> 1",
+      "(a + b) => 1",
+      "!(a) => 1",
+      "((a)) => 1",
+      "f(a) => 1",
+    ] {
+      assert!(Parser::parse_script(source, None).is_err(), "{}", source);
+    }
+  }
+
+  #[test]
+  fn use_strict_directive() {
+    let node = Parser::parse_script(
+      "function f() { 'a'; 'use strict'; } function g() { 'use\\x20strict'; } a;", None)
+    .unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => {
+        assert!(body[0].is_strict());
+        assert!(!body[1].is_strict());
+        // strict mode ends with the function
+        assert!(!body[2].is_strict());
+      }
+      _ => unreachable!(),
+    }
+  }
+}
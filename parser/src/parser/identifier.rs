@@ -18,27 +18,16 @@ impl Parser {
   ///  - [ECMAScript specification][spec]
   ///
   /// [spec]: https://tc39.es/ecma262/#prod-IdentifierName
-  fn parse_identifier_name(&mut self) -> Result<Node, ParseError> {
+  pub(super) fn parse_identifier_name(&mut self) -> Result<Node, ParseError> {
     let node = self.start()?;
-    let peek = self.lexer.peek()?;
-    if matches!(
-      peek.token_type,
-      TokenType::Identifier(_) | TokenType::EscapedKeyword(_)
-    ) || peek.token_type.is_keyword()
-    {
-      let name = self.lexer.bump()?.token_type.identifier_or_keyword_value();
-      Ok(self.finish(node, NodeType::IdentifierName { name }))
-    } else {
-      let peek = peek.to_owned();
-      Err(
-        SyntaxError::from_token(
-          self,
-          &peek,
-          SyntaxErrorTemplate::UnexpectedToken,
-        )
-        .into(),
-      )
-    }
+    let name = expect!(
+      &mut self.lexer,
+      t if matches!(t, TokenType::Identifier(_) | TokenType::EscapedKeyword(_))
+        || t.is_keyword()
+    )?
+    .token_type
+    .identifier_or_keyword_value();
+    Ok(self.finish(node, NodeType::IdentifierName { name }))
   }
 
   /// BindingIdentifier :
@@ -50,7 +39,9 @@ impl Parser {
   ///  - [ECMAScript specification][spec]
   ///
   /// [spec]: https://tc39.es/ecma262/#prod-BindingIdentifier
-  fn parse_binding_identifier(&mut self) -> Result<Node, ParseError> {
+  pub(super) fn parse_binding_identifier(
+    &mut self,
+  ) -> Result<Node, ParseError> {
     let node = self.start()?;
     let token = self.lexer.bump()?.to_owned();
     let name = match &token.token_type {
@@ -63,7 +54,7 @@ impl Parser {
           SyntaxError::from_token(
             self,
             &token,
-            SyntaxErrorTemplate::UnexpectedToken,
+            SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
           )
           .into(),
         )
@@ -109,7 +100,7 @@ impl Parser {
         EarlyError::from(SyntaxError::from_token(
           self,
           &token,
-          SyntaxErrorTemplate::UnexpectedToken,
+          SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
         ))
         .into(),
       );
@@ -126,7 +117,9 @@ impl Parser {
   ///  - [ECMAScript specification][spec]
   ///
   /// [spec]: https://tc39.es/ecma262/#prod-IdentifierReference
-  fn parse_identifier_reference(&mut self) -> Result<Node, ParseError> {
+  pub(super) fn parse_identifier_reference(
+    &mut self,
+  ) -> Result<Node, ParseError> {
     let node = self.start()?;
     let token = self.lexer.bump()?.to_owned();
     let had_escaped = token.had_escaped;
@@ -146,7 +139,7 @@ impl Parser {
             SyntaxError::from_token(
               self,
               token,
-              SyntaxErrorTemplate::UnexpectedToken,
+              SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
             )
             .into(),
           );
@@ -159,7 +152,7 @@ impl Parser {
             SyntaxError::from_token(
               self,
               token,
-              SyntaxErrorTemplate::UnexpectedToken,
+              SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
             )
             .into(),
           );
@@ -171,7 +164,7 @@ impl Parser {
           SyntaxError::from_token(
             self,
             token,
-            SyntaxErrorTemplate::UnexpectedToken,
+            SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
           )
           .into(),
         )
@@ -208,7 +201,7 @@ impl Parser {
         SyntaxError::from_token(
           self,
           token,
-          SyntaxErrorTemplate::UnexpectedToken,
+          SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
         )
         .into(),
       );
@@ -225,7 +218,7 @@ impl Parser {
   ///  - [ECMAScript specification][spec]
   ///
   /// [spec]: https://tc39.es/ecma262/#prod-LabelIdentifier
-  fn parse_label_identifier(&mut self) -> Result<Node, ParseError> {
+  pub(super) fn parse_label_identifier(&mut self) -> Result<Node, ParseError> {
     let node = self.start()?;
     let token = self.lexer.bump()?.to_owned();
     let had_escaped = token.had_escaped;
@@ -240,7 +233,9 @@ impl Parser {
   ///  - [ECMAScript specification][spec]
   ///
   /// [spec]: https://tc39.es/ecma262/#prod-PrivateIdentifier
-  fn parse_private_identifier(&mut self) -> Result<Node, ParseError> {
+  pub(super) fn parse_private_identifier(
+    &mut self,
+  ) -> Result<Node, ParseError> {
     let node = self.start()?;
     let name = expect!(&mut self.lexer, TokenType::PrivateIdentifier(_))?
       .token_type
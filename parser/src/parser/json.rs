@@ -0,0 +1,275 @@
+//! JSON text, parsed with the ECMAScript lexer and the restrictions of
+//! https://tc39.es/ecma262/#sec-json.parse applied on top.
+
+use super::{
+  error::{ParseError, SyntaxError, SyntaxErrorTemplate},
+  source::SourceText,
+  tokens::{StringValue, Token, TokenType},
+  Parser,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+  Null,
+  Boolean(bool),
+  Number(f64),
+  String(StringValue),
+  Array(Vec<JsonValue>),
+  /// Members in source order, a duplicate key is kept, the last one wins
+  /// when the object is created.
+  Object(Vec<(StringValue, JsonValue)>),
+}
+
+impl Parser {
+  /// https://tc39.es/ecma262/#sec-json.parse
+  pub fn parse_json(text: &str) -> Result<JsonValue, ParseError> {
+    let mut parser = Self::new(text);
+    let mut end = 0;
+    let value = parser.parse_json_value(&mut end)?;
+    let token = parser.next_json_token(&mut end)?;
+    if token.token_type != TokenType::EndOfSource {
+      return Err(parser.unexpected_json_token(&token));
+    }
+    Ok(value)
+  }
+
+  /// JSONValue :
+  ///   JSONNullLiteral
+  ///   JSONBooleanLiteral
+  ///   JSONObject
+  ///   JSONArray
+  ///   JSONString
+  ///   JSONNumber
+  fn parse_json_value(
+    &mut self,
+    end: &mut usize,
+  ) -> Result<JsonValue, ParseError> {
+    let token = self.next_json_token(end)?;
+    match &token.token_type {
+      TokenType::Null => Ok(JsonValue::Null),
+      TokenType::True => Ok(JsonValue::Boolean(true)),
+      TokenType::False => Ok(JsonValue::Boolean(false)),
+      TokenType::String(s) if is_json_string(token.source_text()) => {
+        Ok(JsonValue::String(s.clone()))
+      }
+      TokenType::Number(n) if is_json_number(token.source_text()) => {
+        Ok(JsonValue::Number(*n))
+      }
+      TokenType::Sub => {
+        // `-` is a separate token, the number must follow it directly
+        let number = self.next_json_token(end)?;
+        match number.token_type {
+          TokenType::Number(n)
+            if number.start_index == token.end_index
+              && is_json_number(number.source_text()) =>
+          {
+            Ok(JsonValue::Number(-n))
+          }
+          _ => Err(self.unexpected_json_token(&number)),
+        }
+      }
+      TokenType::LBrack => {
+        let mut elements = Vec::new();
+        if self.lexer.peek()?.token_type == TokenType::RBrack {
+          self.next_json_token(end)?;
+          return Ok(JsonValue::Array(elements));
+        }
+        loop {
+          elements.push(self.parse_json_value(end)?);
+          let token = self.next_json_token(end)?;
+          match token.token_type {
+            TokenType::Comma => {}
+            TokenType::RBrack => return Ok(JsonValue::Array(elements)),
+            _ => return Err(self.unexpected_json_token(&token)),
+          }
+        }
+      }
+      TokenType::LBrace => {
+        let mut members = Vec::new();
+        if self.lexer.peek()?.token_type == TokenType::RBrace {
+          self.next_json_token(end)?;
+          return Ok(JsonValue::Object(members));
+        }
+        loop {
+          let key = self.next_json_token(end)?;
+          let key = match &key.token_type {
+            TokenType::String(s) if is_json_string(key.source_text()) => {
+              s.clone()
+            }
+            _ => return Err(self.unexpected_json_token(&key)),
+          };
+          let colon = self.next_json_token(end)?;
+          if colon.token_type != TokenType::Colon {
+            return Err(self.unexpected_json_token(&colon));
+          }
+          members.push((key, self.parse_json_value(end)?));
+          let token = self.next_json_token(end)?;
+          match token.token_type {
+            TokenType::Comma => {}
+            TokenType::RBrace => return Ok(JsonValue::Object(members)),
+            _ => return Err(self.unexpected_json_token(&token)),
+          }
+        }
+      }
+      _ => Err(self.unexpected_json_token(&token)),
+    }
+  }
+
+  /// The next token, only JSON whitespace may separate it from the token
+  /// ending at `end`, so comments are rejected here.
+  fn next_json_token(&mut self, end: &mut usize) -> Result<Token, ParseError> {
+    let token = self.lexer.bump()?.to_owned();
+    let gap = self.lexer.get_source().slice(*end, token.start_index);
+    if let Some((i, c)) = gap
+      .chars()
+      .enumerate()
+      .find(|(_, c)| !matches!(c, '\t' | '\n' | '\r' | ' '))
+    {
+      return Err(
+        SyntaxError::from_index(
+          self,
+          (*end + i) as isize - self.lexer.get_source().index() as isize,
+          SyntaxErrorTemplate::UnexpectedToken(c.to_string()),
+        )
+        .into(),
+      );
+    }
+    *end = token.end_index;
+    Ok(token)
+  }
+
+  fn unexpected_json_token(&self, token: &Token) -> ParseError {
+    SyntaxError::from_token(
+      self,
+      token,
+      SyntaxErrorTemplate::UnexpectedToken(token.source_text.clone()),
+    )
+    .into()
+  }
+}
+
+/// JSONString : `"` JSONStringCharacters? `"`
+fn is_json_string(source_text: &str) -> bool {
+  let mut chars = source_text.chars();
+  if chars.next() != Some('"') {
+    return false;
+  }
+  while let Some(c) = chars.next() {
+    match c {
+      '\u{0}'..='\u{1f}' => return false,
+      '\\' => match chars.next() {
+        Some('"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't') => {}
+        Some('u') => {
+          if !(0..4)
+            .all(|_| matches!(chars.next(), Some(c) if c.is_ascii_hexdigit()))
+          {
+            return false;
+          }
+        }
+        _ => return false,
+      },
+      _ => {}
+    }
+  }
+  true
+}
+
+/// JSONNumber without the sign:
+///   `0` or a non-zero digit followed by digits, an optional fraction with at
+///   least one digit, an optional exponent with at least one digit.
+fn is_json_number(source_text: &str) -> bool {
+  let bytes = source_text.as_bytes();
+  let digits =
+    |i: usize| bytes[i..].iter().take_while(|b| b.is_ascii_digit()).count();
+  let mut i = match bytes.first() {
+    Some(b'0') => 1,
+    Some(b'1'..=b'9') => digits(0),
+    _ => return false,
+  };
+  if bytes.get(i) == Some(&b'.') {
+    let n = digits(i + 1);
+    if n == 0 {
+      return false;
+    }
+    i += 1 + n;
+  }
+  if matches!(bytes.get(i), Some(b'e' | b'E')) {
+    i += 1;
+    if matches!(bytes.get(i), Some(b'+' | b'-')) {
+      i += 1;
+    }
+    let n = digits(i);
+    if n == 0 {
+      return false;
+    }
+    i += n;
+  }
+  i == bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn parse_json() {
+    let value = Parser::parse_json(
+      r#" {"a": [1, -2.5e1, true, false, null], "b": {"c": "A\n"}, "a": {}} "#,
+    )
+    .unwrap();
+    assert_eq!(
+      value,
+      JsonValue::Object(vec![
+        (
+          "a".into(),
+          JsonValue::Array(vec![
+            JsonValue::Number(1.0),
+            JsonValue::Number(-25.0),
+            JsonValue::Boolean(true),
+            JsonValue::Boolean(false),
+            JsonValue::Null,
+          ])
+        ),
+        (
+          "b".into(),
+          JsonValue::Object(vec![(
+            "c".into(),
+            JsonValue::String("A\n".into())
+          )])
+        ),
+        ("a".into(), JsonValue::Object(vec![])),
+      ])
+    );
+    assert_eq!(Parser::parse_json("[]").unwrap(), JsonValue::Array(vec![]));
+    assert_eq!(Parser::parse_json("-0").unwrap(), JsonValue::Number(-0.0));
+  }
+
+  #[test]
+  fn reject_js_syntax() {
+    for text in [
+      "'a'",
+      "0x10",
+      "undefined",
+      "[1,]",
+      r#"{"a": 1,}"#,
+      "{a: 1}",
+      "// comment\n1",
+      "/* comment */ 1",
+      "01",
+      "1.",
+      ".5",
+      "+1",
+      "- 1",
+      "1_000",
+      r#""\x41""#,
+      r#""\u{41}""#,
+      "\"\t\"",
+      "\u{a0}1",
+      "[1] [2]",
+      "",
+      "`a`",
+    ] {
+      assert!(Parser::parse_json(text).is_err(), "{}", text);
+    }
+  }
+}
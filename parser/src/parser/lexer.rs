@@ -1,11 +1,15 @@
+use std::collections::VecDeque;
+
 use lexical::parse_float_options;
 use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 use unicode_xid::UnicodeXID;
 
 use super::{
   error::{SyntaxError, SyntaxErrorInfo, SyntaxErrorTemplate},
   source::Source,
-  tokens::{lookup_keyword, Token, TokenType},
+  strict::{IsStrict, SetStrict},
+  tokens::{lookup_keyword, StringValue, Token, TokenType},
 };
 
 pub fn is_line_terminator(c: char) -> bool {
@@ -22,11 +26,11 @@ fn is_whitespace(c: char) -> bool {
 }
 
 fn is_decimal_digit(c: char) -> bool {
-  c.is_digit(10)
+  c.is_ascii_digit()
 }
 
 fn is_hex_digit(c: char) -> bool {
-  c.is_digit(16)
+  c.is_ascii_hexdigit()
 }
 
 fn is_octal_digit(c: char) -> bool {
@@ -38,7 +42,7 @@ fn is_binary_digit(c: char) -> bool {
 }
 
 fn is_identifier_start(c: char) -> bool {
-  c.is_ascii_alphanumeric()
+  c.is_ascii_alphabetic()
     || c == '$'
     || c == '_'
     || c == '\\'
@@ -55,28 +59,53 @@ fn is_identifier_part(c: char) -> bool {
     || c.is_xid_continue()
 }
 
-fn is_lead_surrogate(cp: char) -> bool {
-  cp >= unsafe { char::from_u32_unchecked(0xD800) }
-    && cp <= unsafe { char::from_u32_unchecked(0xDBFF) }
-}
-
-fn is_trail_surrogate(cp: char) -> bool {
-  cp >= unsafe { char::from_u32_unchecked(0xDC00) }
-    && cp <= unsafe { char::from_u32_unchecked(0xDFFF) }
+/// How the column of a token is counted from the start of its line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColumnMode {
+  /// Every code point is a column.
+  #[default]
+  CodePoint,
+  /// Every UTF-16 code unit is a column, so a code point outside the BMP is
+  /// two columns, like in editors using UTF-16 positions.
+  Utf16,
 }
 
 pub struct Lexer {
   source: Source,
   // start
   line: usize,
+  /// The index where the current line starts.
   column_offset: usize,
+  column_mode: ColumnMode,
+  /// The index up to which the current line has been counted in UTF-16
+  /// code units, and the column there, so each token only counts the code
+  /// points since the previous one.
+  utf16_column: (usize, usize),
   line_terminator_before_next_token: bool,
   had_escaped: bool,
   is_strict: bool,
+  /// Brace depth inside each open template substitution, so a `}` that
+  /// closes a `${` resumes the template instead of producing `RBrace`.
+  template_stack: Vec<usize>,
+  /// Whether scanning a token has failed, the position in the source isn't
+  /// at a token boundary after that.
+  failed: bool,
   // iter
   current_token: Option<Token>,
-  peek_token: Option<Token>,
-  peek_ahead_token: Option<Token>,
+  /// The tokens scanned ahead of the current one, `peek_at(n)` is the `n`th.
+  lookahead: VecDeque<Token>,
+}
+
+impl IsStrict for Lexer {
+  fn is_strict(&self) -> bool {
+    self.is_strict
+  }
+}
+
+impl SetStrict for Lexer {
+  fn set_strict(&mut self, is_strict: bool) {
+    self.is_strict = is_strict;
+  }
 }
 
 impl SyntaxErrorInfo for Lexer {
@@ -95,21 +124,52 @@ impl SyntaxErrorInfo for Lexer {
   fn slice(&self, start_cursor: usize, end_cursor: usize) -> String {
     self.source.slice(start_cursor, end_cursor)
   }
+
+  fn column_mode(&self) -> ColumnMode {
+    self.column_mode
+  }
+}
+
+/// The tokens from the current position, the peeked tokens included, until
+/// `EndOfSource`. Nothing follows an error.
+impl Iterator for Lexer {
+  type Item = Result<Token, SyntaxError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let token = match self.lookahead.pop_front() {
+      Some(token) => Ok(token),
+      None if self.failed => return None,
+      None => self.advance(false),
+    };
+    match token {
+      Ok(token) if token.token_type == TokenType::EndOfSource => None,
+      token => Some(token),
+    }
+  }
 }
 
 impl Lexer {
-  pub fn new(s: &'static str, is_strict: bool) -> Self {
-    Self {
+  pub fn new(s: &str, is_strict: bool) -> Self {
+    let mut lexer = Self {
       source: Source::new(s),
       line: 1,
       column_offset: 0,
+      column_mode: ColumnMode::default(),
+      utf16_column: (0, 1),
       line_terminator_before_next_token: false,
       had_escaped: false,
       is_strict,
+      template_stack: Vec::new(),
+      failed: false,
       current_token: None,
-      peek_token: None,
-      peek_ahead_token: None,
-    }
+      lookahead: VecDeque::new(),
+    };
+    lexer.skip_hashbang_comment();
+    lexer
+  }
+
+  pub fn set_column_mode(&mut self, column_mode: ColumnMode) {
+    self.column_mode = column_mode;
   }
 
   pub fn get_source(&self) -> &Source {
@@ -117,9 +177,8 @@ impl Lexer {
   }
 
   pub fn forward(&mut self) -> Result<(), SyntaxError> {
-    self.current_token = Some(self.peek()?.to_owned());
-    self.peek_token = Some(self.peek_ahead()?.to_owned());
-    self.peek_ahead_token = None;
+    self.peek_ahead()?;
+    self.current_token = self.lookahead.pop_front();
     Ok(())
   }
 
@@ -136,26 +195,62 @@ impl Lexer {
   }
 
   pub fn peek(&mut self) -> Result<&Token, SyntaxError> {
-    if self.peek_token.is_none() {
-      self.peek_token = Some(self.advance()?);
-    }
-    Ok(self.peek_token.as_ref().unwrap())
+    self.peek_at(0)
   }
 
   pub fn peek_ahead(&mut self) -> Result<&Token, SyntaxError> {
-    if self.peek_token.is_none() {
-      self.peek_token = Some(self.advance()?);
+    self.peek_at(1)
+  }
+
+  /// The `n`th token after the current one, `peek_at(0)` is `peek()`.
+  pub fn peek_at(&mut self, n: usize) -> Result<&Token, SyntaxError> {
+    while self.lookahead.len() <= n {
+      let token = self.advance(false)?;
+      self.lookahead.push_back(token);
     }
-    if self.peek_ahead_token.is_none() {
-      self.peek_ahead_token = Some(self.advance()?);
+    Ok(&self.lookahead[n])
+  }
+
+  /// Whether a token failed to scan, the lexer can't go on after that.
+  pub fn has_failed(&self) -> bool {
+    self.failed
+  }
+
+  /// Scan the peeked `/` or `/=` again as the start of a regular expression
+  /// literal, the parser knows whether a `/` divides or starts one.
+  pub fn rescan_regular_expression(&mut self) -> Result<&Token, SyntaxError> {
+    let peek = self
+      .lookahead
+      .pop_front()
+      .expect("rescan_regular_expression() should call after peek()");
+    assert!(
+      matches!(peek.token_type, TokenType::Div | TokenType::AssignDiv),
+      "expect `/` or `/=`, got {:?}",
+      peek.token_type
+    );
+    // nothing after the `/` has been scanned unless it was peeked ahead,
+    // the source goes back to the `/` on its line
+    self.lookahead.clear();
+    while self.source.index() > peek.start_index {
+      self.source.backward();
     }
-    Ok(self.peek_ahead_token.as_ref().unwrap())
+    self.line = peek.line;
+    let (_, column) = self.source.position_of(peek.start_index);
+    self.column_offset = peek.start_index + 1 - column;
+    let mut token = self.advance(true)?;
+    token.had_line_terminator_before = peek.had_line_terminator_before;
+    self.lookahead.push_front(token);
+    Ok(&self.lookahead[0])
   }
 
-  fn advance(&mut self) -> Result<Token, SyntaxError> {
+  /// `scan_regexp` scans a `/` as the start of a regular expression literal
+  /// instead of `Div` or `AssignDiv`.
+  fn advance(&mut self, scan_regexp: bool) -> Result<Token, SyntaxError> {
     self.line_terminator_before_next_token = false;
     self.had_escaped = false;
-    self.next_token()
+    let token = self.next_token(scan_regexp);
+    self.failed |= token.is_err();
+    token
   }
 
   fn create_token(
@@ -178,22 +273,63 @@ impl Lexer {
     }
   }
 
-  fn next_token(&mut self) -> Result<Token, SyntaxError> {
+  fn next_token(&mut self, scan_regexp: bool) -> Result<Token, SyntaxError> {
     self.skip_space()?;
 
     // set token location info after skipping space
     let start_index = self.source.index();
     let line = self.line;
-    let column = start_index - self.column_offset + 1;
+    let column = match self.column_mode {
+      ColumnMode::CodePoint => start_index - self.column_offset + 1,
+      ColumnMode::Utf16 => {
+        // count from the start of the line again after a new line or after
+        // going back for a rescan
+        let (mut index, mut column) = self.utf16_column;
+        if index < self.column_offset || index > start_index {
+          (index, column) = (self.column_offset, 1);
+        }
+        column += (index..start_index)
+          .filter_map(|i| self.source.get(i))
+          .map(char::len_utf16)
+          .sum::<usize>();
+        self.utf16_column = (start_index, column);
+        column
+      }
+    };
 
     let token_type = if let Some(c) = self.source.current() {
       if c < char::from(127) {
         // fast path for usual case
         match c {
-          '(' | ')' | '{' | '}' | '[' | ']' | ':' | ';' | ',' | '~' | '`' => {
+          '(' | ')' | '[' | ']' | ':' | ';' | ',' | '~' => {
             self.source.forward();
             Some(TokenType::from_single(c))
           }
+          '{' => {
+            if let Some(depth) = self.template_stack.last_mut() {
+              *depth += 1;
+            }
+            self.source.forward();
+            Some(TokenType::LBrace)
+          }
+          '}' => {
+            self.source.forward();
+            match self.template_stack.last_mut() {
+              Some(0) => {
+                self.template_stack.pop();
+                Some(self.scan_template(false)?)
+              }
+              Some(depth) => {
+                *depth -= 1;
+                Some(TokenType::RBrace)
+              }
+              None => Some(TokenType::RBrace),
+            }
+          }
+          '`' => {
+            self.source.forward();
+            Some(self.scan_template(true)?)
+          }
           // ? ?. ?? ??=
           '?' => match self.source.bump() {
             Some('.') => {
@@ -323,8 +459,9 @@ impl Lexer {
             }
             _ => Some(TokenType::Mod),
           },
-          // / /=
+          // / /= /pattern/flags
           '/' => match self.source.bump() {
+            _ if scan_regexp => Some(self.scan_regular_expression()?),
             Some('=') => {
               self.source.forward();
               Some(TokenType::AssignDiv)
@@ -340,7 +477,10 @@ impl Lexer {
               }
               _ => Some(TokenType::And),
             },
-            Some('=') => Some(TokenType::AssignBitAnd),
+            Some('=') => {
+              self.source.forward();
+              Some(TokenType::AssignBitAnd)
+            }
             _ => Some(TokenType::BitAnd),
           },
           // | || |= ||=
@@ -390,13 +530,14 @@ impl Lexer {
           'a'..='z' | 'A'..='Z' | '$' | '_' | '\\' => {
             Some(self.scan_identifier_or_keyword(false)?)
           }
-          '#' => {
+          // a PrivateIdentifier is `#` followed by an IdentifierName
+          '#' if self.source.peek().is_some_and(is_identifier_start) => {
             self.source.forward();
             Some(self.scan_identifier_or_keyword(true)?)
           }
           _ => None,
         }
-      } else if is_lead_surrogate(c) || is_identifier_start(c) {
+      } else if is_identifier_start(c) {
         Some(self.scan_identifier_or_keyword(false)?)
       } else {
         None
@@ -407,11 +548,7 @@ impl Lexer {
 
     match token_type {
       Some(t) => Ok(self.create_token(t, start_index, line, column)),
-      None => Err(SyntaxError::from_index(
-        self,
-        0,
-        SyntaxErrorTemplate::UnexpectedToken,
-      )),
+      None => Err(self.unexpected(0)),
     }
   }
 
@@ -429,11 +566,20 @@ impl Lexer {
         Some('e' | 'E' | '.') => {}
         Some('n') => {
           self.source.forward();
-          return Ok(TokenType::BigInt(
-            BigInt::parse_bytes(b"0", 10)
-              .expect("failed to parse string as a bigint"),
+          return Ok(TokenType::BigInt(BigInt::from(0)));
+        }
+        // legacy octal literals and separators after a leading 0 are not
+        // allowed
+        Some(c) if c == '_' || is_decimal_digit(c) => {
+          return Err(SyntaxError::from_index(
+            self,
+            0,
+            SyntaxErrorTemplate::InvalidNumericLiteral,
           ));
         }
+        Some(c) if is_identifier_start(c) => {
+          return Err(self.unexpected(0));
+        }
         _ => return Ok(TokenType::Number(0.0)),
       }
       check = match base {
@@ -444,8 +590,13 @@ impl Lexer {
         _ => unreachable!("base is not correct when scan_number"),
       };
       if base != 10 {
-        if matches!(self.source.peek(), Some(c) if !check(c)) {
-          return Ok(TokenType::Number(0.0));
+        // `0x` must be followed by at least one digit
+        if !matches!(self.source.peek(), Some(c) if check(c)) {
+          return Err(SyntaxError::from_index(
+            self,
+            0,
+            SyntaxErrorTemplate::InvalidNumericLiteral,
+          ));
         }
         self.source.forward();
       }
@@ -457,13 +608,13 @@ impl Lexer {
           if check(c) {
             self.source.forward();
           } else if c == '_' {
-            if matches!(self.source.peek(), Some(p) if !check(p)) {
-              return Err(
-                SyntaxError::from_index(
-                  self,
-                  1,
-                  SyntaxErrorTemplate::UnexpectedToken,
-                ));
+            // a separator must be between two digits
+            if !matches!(self.source.peek(), Some(p) if check(p)) {
+              return Err(SyntaxError::from_index(
+                self,
+                0,
+                SyntaxErrorTemplate::InvalidNumericLiteral,
+              ));
             }
             self.source.forward();
           } else {
@@ -477,13 +628,22 @@ impl Lexer {
     if self.source.current() == Some('n') {
       let buffer = self
         .source
-        .slice(start, self.source.index())
+        .slice(
+          if base == 10 { start } else { start + 2 },
+          self.source.index(),
+        )
         .replace('_', "");
-      self.source.forward();
-      return Ok(TokenType::BigInt(
-        BigInt::parse_bytes(buffer.as_bytes(), 10)
-          .expect("failed to parse string as a bigint"),
-      ));
+      return match BigInt::parse_bytes(buffer.as_bytes(), base) {
+        Some(bigint) => {
+          self.source.forward();
+          Ok(TokenType::BigInt(bigint))
+        }
+        None => Err(SyntaxError::from_index(
+          self,
+          0,
+          SyntaxErrorTemplate::InvalidNumericLiteral,
+        )),
+      };
     }
     // .
     if base == 10 && self.source.current() == Some('.') {
@@ -491,7 +651,7 @@ impl Lexer {
         return Err(SyntaxError::from_index(
           self,
           0,
-          SyntaxErrorTemplate::UnexpectedToken,
+          SyntaxErrorTemplate::InvalidNumericLiteral,
         ));
       }
       scan!();
@@ -506,28 +666,24 @@ impl Lexer {
         return Err(SyntaxError::from_index(
           self,
           0,
-          SyntaxErrorTemplate::UnexpectedToken,
+          SyntaxErrorTemplate::InvalidNumericLiteral,
         ));
       }
       if let Some('-' | '+') = self.source.current() {
         self.source.forward();
       }
-      if let Some('_') = self.source.current() {
+      if !matches!(self.source.current(), Some(c) if is_decimal_digit(c)) {
         return Err(SyntaxError::from_index(
           self,
           0,
-          SyntaxErrorTemplate::UnexpectedToken,
+          SyntaxErrorTemplate::InvalidNumericLiteral,
         ));
       }
       scan!();
     }
 
     if matches!(self.source.current(), Some(c) if is_identifier_start(c)) {
-      return Err(SyntaxError::from_index(
-        self,
-        0,
-        SyntaxErrorTemplate::UnexpectedToken,
-      ));
+      return Err(self.unexpected(0));
     }
     // parse
     let buffer = self
@@ -537,18 +693,31 @@ impl Lexer {
         self.source.index(),
       )
       .replace('_', "");
-    const FORMAT: u128 = lexical::format::JAVASCRIPT_STRING;
-    let num = lexical::parse_with_options::<f64, _, FORMAT>(
-      buffer,
-      &parse_float_options::JAVASCRIPT_STRING,
-    )
-    .expect("failed to parse string as a js number");
-    Ok(TokenType::Number(num))
+    let num = if base == 10 {
+      const FORMAT: u128 = lexical::format::JAVASCRIPT_STRING;
+      lexical::parse_with_options::<f64, _, FORMAT>(
+        buffer,
+        &parse_float_options::JAVASCRIPT_STRING,
+      )
+      .ok()
+    } else {
+      BigInt::parse_bytes(buffer.as_bytes(), base).and_then(|n| n.to_f64())
+    };
+    match num {
+      Some(num) => Ok(TokenType::Number(num)),
+      None => Err(SyntaxError::from_index(
+        self,
+        0,
+        SyntaxErrorTemplate::InvalidNumericLiteral,
+      )),
+    }
   }
 
   /// See https://tc39.es/ecma262/#sec-literals-string-literals
   fn scan_string(&mut self, quote: char) -> Result<TokenType, SyntaxError> {
-    let mut buffer = String::new();
+    let mut buffer = StringValue::default();
+    // line continuations inside a string do not precede the next token
+    let line_terminator_before = self.line_terminator_before_next_token;
     loop {
       match self.source.current() {
         None => {
@@ -581,10 +750,12 @@ impl Lexer {
                 ))
               }
               Some(p) => {
+                // LineContinuation :: `\` LineTerminatorSequence, `\r\n` is
+                // a single sequence and the continuation is removed
                 if is_line_terminator(p) {
                   self.terminate_line(p)
                 } else {
-                  buffer.push(self.scan_escape_sequence()?)
+                  buffer.push_code_point(self.scan_escape_sequence()?)
                 }
               }
             }
@@ -594,10 +765,190 @@ impl Lexer {
         }
       }
     }
+    self.line_terminator_before_next_token = line_terminator_before;
 
     Ok(TokenType::String(buffer))
   }
 
+  /// Scan a regular expression literal, starting right after the opening
+  /// `/`. The pattern is kept as source text, only the flags are validated.
+  ///
+  /// See https://tc39.es/ecma262/#sec-literals-regular-expression-literals
+  fn scan_regular_expression(&mut self) -> Result<TokenType, SyntaxError> {
+    let mut pattern = String::new();
+    let mut in_class = false;
+    loop {
+      let c = match self.source.current() {
+        Some(c) if !is_line_terminator(c) => c,
+        _ => {
+          return Err(SyntaxError::from_index(
+            self,
+            0,
+            SyntaxErrorTemplate::UnterminatedRegExp,
+          ))
+        }
+      };
+      self.source.forward();
+      match c {
+        '/' if !in_class => break,
+        '[' => in_class = true,
+        ']' => in_class = false,
+        // RegularExpressionBackslashSequence :: `\` RegularExpressionNonTerminator
+        '\\' => {
+          pattern.push(c);
+          match self.source.current() {
+            Some(c) if !is_line_terminator(c) => {
+              self.source.forward();
+              pattern.push(c);
+              continue;
+            }
+            _ => {
+              return Err(SyntaxError::from_index(
+                self,
+                0,
+                SyntaxErrorTemplate::UnterminatedRegExp,
+              ))
+            }
+          }
+        }
+        _ => {}
+      }
+      pattern.push(c);
+    }
+    // RegularExpressionFlags :: RegularExpressionFlags IdentifierPartChar
+    let flags_start = self.source.index();
+    let mut flags = String::new();
+    while let Some(c) = self.source.current() {
+      if !is_identifier_part(c) {
+        break;
+      }
+      self.source.forward();
+      flags.push(c);
+    }
+    // It is a Syntax Error if flags contains any code points other than
+    // "d", "g", "i", "m", "s", "u", "v", or "y", or if flags contains any
+    // code point more than once, or both "u" and "v".
+    let is_valid = flags
+      .char_indices()
+      .all(|(i, c)| "dgimsuvy".contains(c) && !flags[..i].contains(c))
+      && !(flags.contains('u') && flags.contains('v'));
+    if !is_valid {
+      let offset = flags_start as isize - self.source.index() as isize;
+      return Err(SyntaxError::from_index(
+        self,
+        offset,
+        SyntaxErrorTemplate::InvalidRegExpFlags,
+      ));
+    }
+    Ok(TokenType::RegularExpression { pattern, flags })
+  }
+
+  /// Scan a template chunk, starting right after the opening `` ` `` (when
+  /// `is_head`) or after the `}` closing a substitution.
+  ///
+  /// The cooked value is `None` when the chunk contains an escape sequence
+  /// that is only allowed in tagged templates, e.g. `\x` or `\01`.
+  ///
+  /// See https://tc39.es/ecma262/#sec-template-literal-lexical-components
+  fn scan_template(&mut self, is_head: bool) -> Result<TokenType, SyntaxError> {
+    let mut cooked = Some(StringValue::default());
+    let mut raw = String::new();
+    // line terminators inside a template do not precede the next token
+    let line_terminator_before = self.line_terminator_before_next_token;
+    loop {
+      match self.source.current() {
+        None => {
+          return Err(SyntaxError::from_index(
+            self,
+            0,
+            SyntaxErrorTemplate::UnterminatedTemplate,
+          ))
+        }
+        Some('`') => {
+          self.source.forward();
+          self.line_terminator_before_next_token = line_terminator_before;
+          return Ok(if is_head {
+            TokenType::NoSubstitutionTemplate { cooked, raw }
+          } else {
+            TokenType::TemplateTail { cooked, raw }
+          });
+        }
+        Some('$') if self.source.peek() == Some('{') => {
+          self.source.forward();
+          self.source.forward();
+          self.template_stack.push(0);
+          self.line_terminator_before_next_token = line_terminator_before;
+          return Ok(if is_head {
+            TokenType::TemplateHead { cooked, raw }
+          } else {
+            TokenType::TemplateMiddle { cooked, raw }
+          });
+        }
+        Some('\\') => {
+          self.source.forward();
+          raw.push('\\');
+          match self.source.current() {
+            Some(p) if is_line_terminator(p) => {
+              // LineContinuation: the cooked value is the empty sequence
+              self.terminate_line(p);
+              raw.push(if p == '\r' { '\n' } else { p });
+            }
+            Some(_) => {
+              let start = self.source.index();
+              let c = self.scan_template_escape_sequence();
+              raw.push_str(&self.source.slice(start, self.source.index()));
+              cooked = cooked.zip(c).map(|(mut s, c)| {
+                s.push_code_point(c);
+                s
+              });
+            }
+            None => {}
+          }
+        }
+        Some(c) if is_line_terminator(c) => {
+          // <CR><LF> and <CR> are normalized to <LF> for both values
+          self.terminate_line(c);
+          let c = if c == '\r' { '\n' } else { c };
+          raw.push(c);
+          if let Some(s) = cooked.as_mut() {
+            s.push(c);
+          }
+        }
+        Some(c) => {
+          self.source.forward();
+          raw.push(c);
+          if let Some(s) = cooked.as_mut() {
+            s.push(c);
+          }
+        }
+      }
+    }
+  }
+
+  /// Template literals don't allow legacy octal escapes and `\8` `\9`, and
+  /// malformed escapes make the cooked value undefined instead of being an
+  /// error, which is reported by the parser for untagged templates.
+  ///
+  /// See https://tc39.es/ecma262/#prod-TemplateEscapeSequence
+  fn scan_template_escape_sequence(&mut self) -> Option<u32> {
+    match self.source.current() {
+      Some('0') if !matches!(self.source.peek(), Some(p) if is_decimal_digit(p)) =>
+      {
+        self.source.forward();
+        Some(0)
+      }
+      Some(c) if is_decimal_digit(c) => {
+        self.source.forward();
+        None
+      }
+      Some(_) => self.scan_escape_sequence().ok(),
+      None => None,
+    }
+  }
+
+  /// The source is decoded from UTF-8, so a code point outside the BMP is
+  /// already a single `char` rather than a surrogate pair to combine.
+  ///
   /// See https://tc39.es/ecma262/#sec-names-and-keywords
   fn scan_identifier_or_keyword(
     &mut self,
@@ -611,7 +962,7 @@ impl Lexer {
         if !had_escaped {
           had_escaped = true;
         }
-        if matches!(self.source.bump(), Some(c) if c != 'u') {
+        if self.source.bump() != Some('u') {
           return Err(SyntaxError::from_index(
             self,
             0,
@@ -619,17 +970,16 @@ impl Lexer {
           ));
         }
         self.source.forward();
-        let raw = char::from_u32(self.scan_code_point()?).unwrap();
-        if !check(c) {
-          return Err(SyntaxError::from_index(
-            self,
-            0,
-            SyntaxErrorTemplate::InvalidUnicodeEscape,
-          ));
+        match char::from_u32(self.scan_code_point()?) {
+          Some(raw) if raw != '\\' && check(raw) => buffer.push(raw),
+          _ => {
+            return Err(SyntaxError::from_index(
+              self,
+              0,
+              SyntaxErrorTemplate::InvalidUnicodeEscape,
+            ))
+          }
         }
-        buffer.push(raw)
-      } else if is_lead_surrogate(c) {
-        todo!("CombineSurrogatePair is not supported yet")
       } else if check(c) {
         buffer.push(c);
         self.source.forward();
@@ -696,73 +1046,56 @@ impl Lexer {
           self.source.forward();
           n = (n << 4) | c.to_digit(16).unwrap();
         }
-        _ => {
-          return Err(SyntaxError::from_index(
-            self,
-            0,
-            SyntaxErrorTemplate::UnexpectedToken,
-          ))
-        }
+        _ => return Err(self.unexpected(0)),
       }
     }
     Ok(n)
   }
 
-  fn scan_escape_sequence(&mut self) -> Result<char, SyntaxError> {
+  /// The code point of an escape sequence, an escaped surrogate such as
+  /// `\uD83D` is a code point of its own, so the two halves of an escaped
+  /// pair become a surrogate pair in the String value again.
+  fn scan_escape_sequence(&mut self) -> Result<u32, SyntaxError> {
     // unwrap: only used by scan_string when `self.source.current()` is not None
-    match self.source.current().unwrap() {
-      'b' => {
-        self.source.forward();
-        Ok('\u{0008}')
-      }
-      't' => {
-        self.source.forward();
-        Ok('\t')
-      }
-      'n' => {
-        self.source.forward();
-        Ok('\n')
-      }
-      'v' => {
-        self.source.forward();
-        Ok('\u{000b}')
-      }
-      'f' => {
-        self.source.forward();
-        Ok('\u{000c}')
-      }
-      'r' => {
-        self.source.forward();
-        Ok('\r')
-      }
+    let c = match self.source.current().unwrap() {
+      'b' => '\u{0008}',
+      't' => '\t',
+      'n' => '\n',
+      'v' => '\u{000b}',
+      'f' => '\u{000c}',
+      'r' => '\r',
       'x' => {
         self.source.forward();
-        Ok(char::from_u32(self.scan_hex(2)?).unwrap())
+        return self.scan_hex(2);
       }
       'u' => {
         self.source.forward();
-        Ok(char::from_u32(self.scan_code_point()?).unwrap())
+        return self.scan_code_point();
       }
       c => {
         if c == '0'
           && matches!(self.source.peek(), Some(p) if is_decimal_digit(p))
         {
-          self.source.forward();
-          Ok('\u{0000}')
+          '\u{0000}'
         } else if self.is_strict && is_decimal_digit(c) {
-          Err(SyntaxError::from_index(
+          return Err(SyntaxError::from_index(
             self,
             0,
             SyntaxErrorTemplate::IllegalOctalEscape,
-          ))
+          ));
         } else {
-          self.source.forward();
-          Ok(c)
+          c
         }
       }
-    }
+    };
+    self.source.forward();
+    Ok(c as u32)
   }
 
+  /// A `#!` comment is only allowed at the very start of the source, before
+  /// any whitespace.
+  ///
+  /// See https://tc39.es/ecma262/#sec-hashbang
   fn skip_hashbang_comment(&mut self) {
     if self.source.index() == 0
       && matches!(self.source.current(), Some('#'))
@@ -853,14 +1186,31 @@ impl Lexer {
     Ok(())
   }
 
-  // fn unexpected(&self) -> SyntaxError {
-  //   return self.create_syntax_error(
-  //     self.source.position(),
-  //     SyntaxErrorTemplate::UnexpectedToken,
-  //   );
-  // }
+  /// An UnexpectedToken error at the char `offset` after the current one.
+  fn unexpected(&self, offset: isize) -> SyntaxError {
+    let text = (self.index() as isize + offset)
+      .try_into()
+      .ok()
+      .and_then(|index| self.get(index))
+      .map(String::from)
+      .unwrap_or_default();
+    SyntaxError::from_index(
+      self,
+      offset,
+      SyntaxErrorTemplate::UnexpectedToken(text),
+    )
+  }
 }
 
+/// Lookahead helpers shared by the whole parser. Prefer these over matching on
+/// `lexer.peek()` by hand: a string literal matches an unescaped identifier
+/// with that name (for contextual keywords like `async` or `let`), anything
+/// else is a pattern over `TokenType` with an optional guard.
+///
+/// - `matches_token_type!(token, ..)` tests an already obtained token.
+/// - `test!` / `test_ahead!` test the next / second next token.
+/// - `eat!` consumes the next token if it matches.
+/// - `expect!` consumes the next token or reports `UnexpectedToken`.
 #[macro_export]
 macro_rules! matches_token_type {
   ($peek:expr, $id:literal) => {{
@@ -922,10 +1272,11 @@ macro_rules! expect {
       false => match lexer.peek() {
         Ok(peek) => {
           let peek = peek.to_owned();
+          let text = peek.source_text.clone();
           Err(SyntaxError::from_token(
             lexer,
             &peek,
-            SyntaxErrorTemplate::UnexpectedToken,
+            SyntaxErrorTemplate::UnexpectedToken(text),
           ))
         },
         Err(e) => Err(e),
@@ -940,10 +1291,11 @@ macro_rules! expect {
       false => match lexer.peek() {
         Ok(peek) => {
           let peek = peek.to_owned();
+          let text = peek.source_text.clone();
           Err(SyntaxError::from_token(
             lexer,
             &peek,
-            SyntaxErrorTemplate::UnexpectedToken,
+            SyntaxErrorTemplate::UnexpectedToken(text),
           ))
         },
         Err(e) => Err(e),
@@ -959,7 +1311,7 @@ mod tests {
   macro_rules! assert_token_type {
     ($l: ident, $t: expr) => {{
       let token_type = $t;
-      let expected = $l.advance().unwrap().token_type;
+      let expected = $l.advance(false).unwrap().token_type;
       assert_eq!(expected, token_type);
     }};
     ($l: ident, $($t: expr),* $(,)?) => {{
@@ -1057,6 +1409,19 @@ block comment
     );
   }
 
+  #[test]
+  fn contextual_keyword_escape() {
+    let source = r#"\u0061sync async"#;
+    let mut lexer = Lexer::new(source, false);
+    let escaped = lexer.bump().unwrap().to_owned();
+    let plain = lexer.bump().unwrap().to_owned();
+    assert_eq!(escaped.token_type, plain.token_type);
+    assert_eq!(escaped.source_text, r#"\u0061sync"#);
+    assert_eq!(plain.source_text, "async");
+    assert!(!matches_token_type!(escaped, "async"));
+    assert!(matches_token_type!(plain, "async"));
+  }
+
   #[test]
   fn private_identifier_escape() {
     let source = r#"#a\u{61}pple"#;
@@ -1074,7 +1439,7 @@ block comment
     let mut lexer = Lexer::new(source, false);
     assert_token_type!(
       lexer,
-      TokenType::String("\n".to_owned()),
+      TokenType::String("\n".into()),
       TokenType::EndOfSource,
     );
   }
@@ -1085,7 +1450,7 @@ block comment
     let mut lexer = Lexer::new(source, false);
     assert_token_type!(
       lexer,
-      TokenType::String("\\n".to_owned()),
+      TokenType::String("\\n".into()),
       TokenType::EndOfSource,
     );
   }
@@ -1096,7 +1461,7 @@ block comment
     let mut lexer = Lexer::new(source, false);
     assert_token_type!(
       lexer,
-      TokenType::String("a".to_owned()),
+      TokenType::String("a".into()),
       TokenType::EndOfSource,
     );
   }
@@ -1107,7 +1472,7 @@ block comment
     let mut lexer = Lexer::new(source, false);
     assert_token_type!(
       lexer,
-      TokenType::String("4".to_owned()),
+      TokenType::String("4".into()),
       TokenType::EndOfSource,
     );
   }
@@ -1118,11 +1483,43 @@ block comment
     let mut lexer = Lexer::new(source, false);
     assert_token_type!(
       lexer,
-      TokenType::String("ng262".to_owned()),
+      TokenType::String("ng262".into()),
       TokenType::EndOfSource,
     );
   }
 
+  #[test]
+  fn big_int_literal_bases() {
+    for (source, value) in [("0xFFn", 255), ("0o17n", 15), ("0b101n", 5)] {
+      let mut lexer = Lexer::new(source, false);
+      assert_token_type!(
+        lexer,
+        TokenType::BigInt(BigInt::from(value)),
+        TokenType::EndOfSource
+      );
+    }
+    // a BigInt literal is an integer
+    for source in ["1.5n", "1e3n"] {
+      let error = Lexer::new(source, false).advance(false).unwrap_err();
+      assert_eq!(error.message(), "Unexpected token \"n\"");
+    }
+  }
+
+  #[test]
+  fn hashbang_comment() {
+    let mut lexer = Lexer::new("#!/bin/sh\nlet x = 1;", false);
+    assert_token_type!(
+      lexer,
+      TokenType::Identifier("let".to_owned()),
+      TokenType::Identifier("x".to_owned()),
+    );
+    // only at the start, and `#` alone is a private name
+    let error = Lexer::new(" #!/bin/sh", false).advance(false).unwrap_err();
+    assert_eq!(error.message(), "Unexpected token \"#\"");
+    let mut lexer = Lexer::new("#x", false);
+    assert_token_type!(lexer, TokenType::PrivateIdentifier("x".to_owned()));
+  }
+
   #[test]
   fn number_literal() {
     let source = r#"123.0"#;
@@ -1162,6 +1559,56 @@ block comment
     assert_token_type!(lexer, TokenType::Number(0.0), TokenType::EndOfSource);
   }
 
+  #[test]
+  fn number_radix() {
+    let source = r#"0xff 0o17 0b101 0X1_0"#;
+    let mut lexer = Lexer::new(source, false);
+    assert_token_type!(
+      lexer,
+      TokenType::Number(255.0),
+      TokenType::Number(15.0),
+      TokenType::Number(5.0),
+      TokenType::Number(16.0),
+      TokenType::EndOfSource
+    );
+  }
+
+  #[test]
+  fn number_invalid() {
+    for source in ["0b", "0x;", "0o8", "1e", "1e+", "0xn"] {
+      let mut lexer = Lexer::new(source, false);
+      let error = lexer.advance(false).unwrap_err();
+      assert_eq!(error.message(), "Invalid numeric literal", "{}", source);
+    }
+  }
+
+  #[test]
+  fn number_malformed() {
+    for source in [
+      "1_", "1__0", "0x_1", "0b1_", "1._0", "1_.0", "1e_1", "1e1_", "1.5e",
+      "1.5n", "1e3n", "0b2", "0o1_8", "1_n", "08_1", "0_1", "00n", "01", "0a",
+    ] {
+      let mut lexer = Lexer::new(source, false);
+      assert!(lexer.advance(false).is_err(), "{}", source);
+    }
+    let source = "1e400 0x1fffffffffffffffffffffffffffffffffffffffffffffffffff";
+    let mut lexer = Lexer::new(source, false);
+    assert_token_type!(
+      lexer,
+      TokenType::Number(f64::INFINITY),
+      TokenType::Number(2f64.powi(205)),
+      TokenType::EndOfSource
+    );
+  }
+
+  #[test]
+  fn number_separator_error() {
+    for source in ["1__0", "1_.5", "1_", "1._0", "1e_1", "0_1"] {
+      let error = Lexer::new(source, false).advance(false).unwrap_err();
+      assert_eq!(error.message(), "Invalid numeric literal", "{}", source);
+    }
+  }
+
   #[test]
   fn number_point() {
     let source = r#"1.123"#;
@@ -1180,6 +1627,47 @@ block comment
     );
   }
 
+  #[test]
+  fn invalid_identifier_escape() {
+    for source in [r#"\u0030a"#, r#"a\u"#, r#"a\x41"#, r#"\uD800"#] {
+      let mut lexer = Lexer::new(source, false);
+      assert!(lexer.advance(false).is_err(), "{}", source);
+    }
+  }
+
+  #[test]
+  fn string_escape_surrogates() {
+    let string = |source: &str| match Lexer::new(source, false)
+      .advance(false)
+      .unwrap()
+      .token_type
+    {
+      TokenType::String(value) => value,
+      t => panic!("expect a string, got {:?}", t),
+    };
+    assert_eq!(string(r"'\uD800'").code_units(), [0xD800]);
+    assert_eq!(string(r"'a\u{DC00}'").code_units(), [0x61, 0xDC00]);
+    assert_eq!(string(r"'\uD83D\uDE00'"), "😀");
+    assert_eq!(string(r"'\uD83D\uDE00'"), string("'😀'"));
+    assert_eq!(string(r"'\u{1F600}'").code_units(), [0xD83D, 0xDE00]);
+    let mut lexer = Lexer::new(r"`\uD800`", false);
+    match lexer.advance(false).unwrap().token_type {
+      TokenType::NoSubstitutionTemplate { cooked, .. } => {
+        assert_eq!(cooked.unwrap().code_units(), [0xD800])
+      }
+      t => panic!("expect a template, got {:?}", t),
+    }
+  }
+
+  #[test]
+  fn source_text_non_ascii() {
+    let source = "'é' + ab";
+    let mut lexer = Lexer::new(source, false);
+    assert_eq!(lexer.advance(false).unwrap().source_text, "'é'");
+    assert_eq!(lexer.advance(false).unwrap().source_text, "+");
+    assert_eq!(lexer.advance(false).unwrap().source_text, "ab");
+  }
+
   #[test]
   fn lexer_forward() {
     let source = r#"let ng = 262;"#;
@@ -1298,6 +1786,18 @@ block comment
     assert!(expect!(&mut lexer, TokenType::EndOfSource).is_ok());
   }
 
+  #[test]
+  fn lexer_expect_with_guard() {
+    let source = r#"if x ;"#;
+    let mut lexer = Lexer::new(source, false);
+    assert!(test!(lexer, t if t.is_keyword()).unwrap());
+    assert!(!test_ahead!(lexer, t if t.is_keyword()).unwrap());
+    assert!(expect!(&mut lexer, t if t.is_keyword()).is_ok());
+    assert!(!eat!(&mut lexer, t if t.is_keyword()).unwrap());
+    assert!(expect!(&mut lexer, TokenType::Identifier(s) if s == "x").is_ok());
+    assert!(expect!(&mut lexer, TokenType::Identifier(_)).is_err());
+  }
+
   #[test]
   fn lexer_next() {
     let source = r#"async;"#;
@@ -1311,4 +1811,225 @@ block comment
     let next = lexer.bump().unwrap();
     assert!(matches_token_type!(next, TokenType::EndOfSource));
   }
+
+  #[test]
+  fn string_line_continuation() {
+    for source in [
+      "'a\\\nb' c",
+      "'a\\\r\nb' c",
+      "'a\\\rb' c",
+      "'a\\\u{2028}b' c",
+      "'a\\\u{2029}b' c",
+    ] {
+      let mut lexer = Lexer::new(source, false);
+      let string = lexer.advance(false).unwrap();
+      assert_eq!(string.token_type, TokenType::String("ab".into()));
+      assert!(!string.had_line_terminator_before);
+      let c = lexer.advance(false).unwrap();
+      // the continuation is on the string's line and `c` is on the next
+      assert_eq!((string.line, c.line), (1, 2));
+      assert!(!c.had_line_terminator_before);
+    }
+    // an unescaped \r\n is two lines of an unterminated string, but the
+    // separators are allowed in strings
+    assert!(Lexer::new("'a\r\nb'", false).advance(false).is_err());
+    let mut lexer = Lexer::new("'a\u{2028}b'", false);
+    assert_eq!(
+      lexer.advance(false).unwrap().token_type,
+      TokenType::String("a\u{2028}b".into())
+    );
+  }
+
+  #[test]
+  fn template() {
+    let template = |cooked: Option<&str>, raw: &str| {
+      (cooked.map(StringValue::from), raw.to_owned())
+    };
+    let (cooked, raw) = template(Some("a"), "a");
+    let mut lexer = Lexer::new("`a${b}c`", false);
+    assert_token_type!(lexer, TokenType::TemplateHead { cooked, raw });
+    assert_token_type!(lexer, TokenType::Identifier("b".to_owned()));
+    let (cooked, raw) = template(Some("c"), "c");
+    assert_token_type!(
+      lexer,
+      TokenType::TemplateTail { cooked, raw },
+      TokenType::EndOfSource,
+    );
+    let mut lexer = Lexer::new(r"`\u{41}`", false);
+    let (cooked, raw) = template(Some("A"), r"\u{41}");
+    assert_token_type!(
+      lexer,
+      TokenType::NoSubstitutionTemplate { cooked, raw }
+    );
+    // an invalid escape is only an error outside of a tagged template
+    let mut lexer = Lexer::new(r"`\x`", false);
+    let (cooked, raw) = template(None, r"\x");
+    assert_token_type!(
+      lexer,
+      TokenType::NoSubstitutionTemplate { cooked, raw }
+    );
+    assert!(Lexer::new("`unterminated", false).advance(false).is_err());
+  }
+
+  #[test]
+  fn regular_expression() {
+    let regexp = |pattern: &str, flags: &str| TokenType::RegularExpression {
+      pattern: pattern.to_owned(),
+      flags: flags.to_owned(),
+    };
+    for (source, expected) in [
+      (r"/a\/b/", regexp(r"a\/b", "")),
+      ("/[/]/", regexp("[/]", "")),
+      ("/=a/dgimsy", regexp("=a", "dgimsy")),
+      ("/[\\]/]+/v", regexp("[\\]/]+", "v")),
+    ] {
+      let mut lexer = Lexer::new(source, false);
+      assert_eq!(lexer.advance(true).unwrap().token_type, expected);
+      assert_token_type!(lexer, TokenType::EndOfSource);
+    }
+    for source in [
+      "/a/gg",
+      "/a/x",
+      "/a/uv",
+      r"/a/\u0067",
+      "/a\n/",
+      "/[/",
+      r"/a\",
+    ] {
+      let mut lexer = Lexer::new(source, false);
+      assert!(lexer.advance(true).is_err(), "{}", source);
+    }
+    let error = Lexer::new("/a/gig", false).advance(true).unwrap_err();
+    assert_eq!(error.message(), "Invalid regular expression flags");
+  }
+
+  #[test]
+  fn tokens() {
+    let tokens = Lexer::new("let x = 1;", false)
+      .map(|token| token.map(|token| token.token_type))
+      .collect::<Result<Vec<_>, _>>()
+      .unwrap();
+    assert_eq!(
+      tokens,
+      [
+        TokenType::Identifier("let".to_owned()),
+        TokenType::Identifier("x".to_owned()),
+        TokenType::Assign,
+        TokenType::Number(1.0),
+        TokenType::Semicolon,
+      ]
+    );
+    let mut lexer = Lexer::new("a 'b", false);
+    lexer.peek().unwrap();
+    assert_eq!(lexer.next().unwrap().unwrap().source_text, "a");
+    assert!(lexer.next().unwrap().is_err());
+    assert!(lexer.next().is_none());
+  }
+
+  #[test]
+  fn peek_at() {
+    let mut lexer = Lexer::new("a + b * c", false);
+    assert_eq!(lexer.peek_at(3).unwrap().source_text, "*");
+    assert_eq!(lexer.peek_at(4).unwrap().source_text, "c");
+    assert_eq!(lexer.peek().unwrap().source_text, "a");
+    for source_text in ["a", "+", "b", "*", "c", ""] {
+      assert_eq!(lexer.bump().unwrap().source_text, source_text);
+    }
+    assert_eq!(lexer.peek_at(2).unwrap().token_type, TokenType::EndOfSource);
+  }
+
+  #[test]
+  fn owned_source() {
+    let source = format!("let {} = {};", "x", 1);
+    let lexer = Lexer::new(&source, false);
+    drop(source);
+    let tokens = lexer.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(tokens.len(), 5);
+    assert_eq!(tokens[1].source_text, "x");
+  }
+
+  #[test]
+  fn large_source() {
+    // looking up a char used to walk the source from its start
+    let source = "a = 'x';\n".repeat(12_000);
+    let mut lexer = Lexer::new(&source, false);
+    let mut count = 0;
+    while lexer.advance(false).unwrap().token_type != TokenType::EndOfSource {
+      count += 1;
+    }
+    assert_eq!(count, 48_000);
+  }
+
+  #[test]
+  fn column_mode() {
+    let source = "\ta\n𝒜 = b/c";
+    for (column_mode, columns) in [
+      (ColumnMode::CodePoint, [2, 1, 3, 5, 6, 7]),
+      (ColumnMode::Utf16, [2, 1, 4, 6, 7, 8]),
+    ] {
+      let mut lexer = Lexer::new(source, false);
+      lexer.set_column_mode(column_mode);
+      for column in columns {
+        assert_eq!(lexer.advance(false).unwrap().column, column);
+      }
+    }
+    // a rescanned regular expression keeps its column
+    let mut lexer = Lexer::new("𝒜(\n𝒜, /a/)", false);
+    lexer.set_column_mode(ColumnMode::Utf16);
+    for _ in 0..4 {
+      lexer.forward().unwrap();
+    }
+    let token = lexer.rescan_regular_expression().unwrap();
+    assert_eq!((token.line, token.column), (2, 5));
+    // and so does an error
+    for (column_mode, location) in
+      [(ColumnMode::CodePoint, "1:7"), (ColumnMode::Utf16, "1:9")]
+    {
+      let mut lexer = Lexer::new("'𝒜' 𝒜 @", false);
+      lexer.set_column_mode(column_mode);
+      lexer.advance(false).unwrap();
+      lexer.advance(false).unwrap();
+      let error = lexer.advance(false).unwrap_err();
+      assert_eq!(error.decoration().lines().nth(1), Some(location));
+    }
+  }
+
+  #[test]
+  fn rescan_regular_expression() {
+    let mut lexer = Lexer::new("a\n/=b/g.c", false);
+    lexer.forward().unwrap();
+    assert_eq!(lexer.peek().unwrap().token_type, TokenType::AssignDiv);
+    let token = lexer.rescan_regular_expression().unwrap();
+    assert_eq!(
+      token.token_type,
+      TokenType::RegularExpression {
+        pattern: "=b".to_owned(),
+        flags: "g".to_owned(),
+      }
+    );
+    assert_eq!((token.line, token.column), (2, 1));
+    assert!(token.had_line_terminator_before);
+    assert_eq!(token.source_text, "/=b/g");
+    lexer.forward().unwrap();
+    assert_eq!(lexer.bump().unwrap().token_type, TokenType::Period);
+  }
+
+  #[test]
+  fn identifier_astral() {
+    for source in ["𝒜bc", r"\u{1D49C}bc", r"a\u{1D49C}"] {
+      let mut lexer = Lexer::new(source, false);
+      let expected = source.replace(r"\u{1D49C}", "𝒜");
+      assert_token_type!(
+        lexer,
+        TokenType::Identifier(expected),
+        TokenType::EndOfSource,
+      );
+    }
+    // each escape is a code point, the halves of a pair aren't identifiers
+    assert!(Lexer::new(r"\uD835\uDC9Cbc", false).advance(false).is_err());
+    // 😀 is neither IdentifierStart nor IdentifierPart
+    let mut lexer = Lexer::new("a😀", false);
+    assert_token_type!(lexer, TokenType::Identifier("a".to_owned()));
+    assert!(lexer.advance(false).is_err());
+  }
 }
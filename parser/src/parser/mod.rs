@@ -1,25 +1,39 @@
 use std::collections::HashSet;
 
+use crate::expect;
+
 use self::{
-  error::{SyntaxError, SyntaxErrorInfo},
-  lexer::Lexer,
+  error::{ParseError, SyntaxError, SyntaxErrorInfo, SyntaxErrorTemplate},
+  lexer::{ColumnMode, Lexer},
   nodes::{Location, Node, NodeBuilder, NodeType},
-  resolver::Resolver,
-  strict::IsStrict,
+  resolver::{Flag, Flags, Resolver},
+  strict::{IsStrict, SetStrict},
+  tokens::TokenType,
 };
 
+pub mod codegen;
 pub mod error;
+pub mod expression;
+pub mod function;
 pub mod identifier;
+pub mod json;
 pub mod lexer;
+pub mod module;
 pub mod nodes;
+pub mod pattern;
+pub mod recovery;
 pub mod resolver;
 pub mod source;
+pub mod statement;
 pub mod strict;
 pub mod tokens;
 
 struct State {
-  has_top_level_await: bool,
-  json: bool,
+  /// The ExportedNames of the module, for the duplicate export early error.
+  exported_names: HashSet<String>,
+  /// The local names of `export { a }`, they're checked to be declared once
+  /// the whole module is parsed.
+  exported_bindings: Vec<Node>,
 }
 
 pub struct Parser {
@@ -27,6 +41,11 @@ pub struct Parser {
   resolver: Resolver,
   specifier: Option<String>,
   early_errors: HashSet<SyntaxError>,
+  /// Record errors and skip to the next statement instead of returning the
+  /// first one, see `Parser::parse_script_with_recovery`.
+  error_recovery: bool,
+  /// The errors recorded in error recovery mode, in source order.
+  errors: Vec<SyntaxError>,
   state: State,
 }
 
@@ -36,6 +55,13 @@ impl IsStrict for Parser {
   }
 }
 
+impl SetStrict for Parser {
+  fn set_strict(&mut self, is_strict: bool) {
+    self.resolver.set_strict(is_strict);
+    self.lexer.set_strict(is_strict);
+  }
+}
+
 impl SyntaxErrorInfo for Parser {
   fn line(&self) -> usize {
     self.lexer.line()
@@ -52,9 +78,91 @@ impl SyntaxErrorInfo for Parser {
   fn slice(&self, start_index: usize, end_index: usize) -> String {
     self.lexer.slice(start_index, end_index)
   }
+
+  fn specifier(&self) -> Option<&str> {
+    self.specifier.as_deref()
+  }
+
+  fn column_mode(&self) -> ColumnMode {
+    self.lexer.column_mode()
+  }
 }
 
 impl Parser {
+  pub fn new(source: &str) -> Self {
+    let mut flags = Flags::default();
+    flags.add(Flag::In);
+    Self {
+      lexer: Lexer::new(source, false),
+      resolver: Resolver::new(false, flags),
+      specifier: None,
+      early_errors: HashSet::new(),
+      error_recovery: false,
+      errors: Vec::new(),
+      state: State {
+        exported_names: HashSet::new(),
+        exported_bindings: Vec::new(),
+      },
+    }
+  }
+
+  /// Script :
+  ///   ScriptBody?
+  ///
+  /// A Use Strict Directive makes the whole script strict mode code.
+  /// `specifier` names the source in the decoration of errors.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Script
+  pub fn parse_script(
+    source: &str,
+    specifier: Option<&str>,
+  ) -> Result<Node, ParseError> {
+    let mut parser = Self::new(source);
+    parser.specifier = specifier.map(String::from);
+    parser.script()
+  }
+
+  fn script(&mut self) -> Result<Node, ParseError> {
+    let mut node = self.start()?;
+    let (mut body, use_strict) = self.parse_directive_prologue()?;
+    node.is_strict = use_strict.is_some();
+    body.extend(self.parse_statement_list(TokenType::EndOfSource)?);
+    expect!(&mut self.lexer, TokenType::EndOfSource)?;
+    Ok(self.finish(node, NodeType::Script { body }))
+  }
+
+  /// Module :
+  ///   ModuleBody?
+  ///
+  /// Module code is always strict mode code. `specifier` names the source
+  /// in the decoration of errors.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Module
+  pub fn parse_module(
+    source: &str,
+    specifier: Option<&str>,
+  ) -> Result<Node, ParseError> {
+    let mut parser = Self::new(source);
+    parser.specifier = specifier.map(String::from);
+    parser.module()
+  }
+
+  fn module(&mut self) -> Result<Node, ParseError> {
+    self.resolver.flags.add(Flag::Module);
+    self.resolver.flags.add(Flag::ImportMeta);
+    self.strict_on();
+    let node = self.start()?;
+    let body = self.parse_module_item_list()?;
+    expect!(&mut self.lexer, TokenType::EndOfSource)?;
+    Ok(self.finish(node, NodeType::Module { body }))
+  }
+
   fn start(&mut self) -> Result<NodeBuilder, SyntaxError> {
     let peek = self.lexer.peek()?;
     let location = Location {
@@ -65,6 +173,11 @@ impl Parser {
     Ok(NodeBuilder::new(location, self.is_strict()))
   }
 
+  /// Start a node at an already parsed node, e.g. `a.b` starts at `a`.
+  fn start_from(&self, node: &Node) -> NodeBuilder {
+    Node::start(node.start_location(), self.is_strict())
+  }
+
   fn finish(&mut self, node: NodeBuilder, node_type: NodeType) -> Node {
     let current = self.lexer.current();
     let index = current.end_index;
@@ -76,4 +189,19 @@ impl Parser {
     let source_text = self.lexer.get_source().slice(node.start.index, index);
     node.build(location, node_type, source_text)
   }
+
+  fn unexpected(&mut self) -> ParseError {
+    match self.lexer.peek() {
+      Ok(peek) => {
+        let peek = peek.to_owned();
+        SyntaxError::from_token(
+          self,
+          &peek,
+          SyntaxErrorTemplate::UnexpectedToken(peek.source_text.clone()),
+        )
+        .into()
+      }
+      Err(e) => e.into(),
+    }
+  }
 }
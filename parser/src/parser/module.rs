@@ -0,0 +1,701 @@
+use crate::{eat, expect, test, test_ahead};
+
+use super::{
+  error::{EarlyError, ParseError, SyntaxError, SyntaxErrorTemplate},
+  nodes::{Node, NodeBuilder, NodeType, VariableKind},
+  tokens::TokenType,
+  Parser,
+};
+
+impl Parser {
+  /// ModuleItemList :
+  ///   ModuleItem
+  ///   ModuleItemList ModuleItem
+  ///
+  /// ModuleItem :
+  ///   ImportDeclaration
+  ///   ExportDeclaration
+  ///   StatementListItem
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ModuleItemList
+  pub(super) fn parse_module_item_list(
+    &mut self,
+  ) -> Result<Vec<Node>, ParseError> {
+    let mut items = Vec::new();
+    while !test!(self.lexer, TokenType::EndOfSource)? {
+      let item = self.parse_recoverable(|parser| {
+        if parser.is_import_declaration()? {
+          parser.parse_import_declaration()
+        } else if test!(parser.lexer, TokenType::Export)? {
+          parser.parse_export_declaration()
+        } else {
+          parser.parse_statement_list_item()
+        }
+      })?;
+      items.extend(item);
+    }
+    // It is a Syntax Error if any element of the ExportedBindings of
+    // ModuleItemList does not also occur in either the VarDeclaredNames of
+    // ModuleItemList, or the LexicallyDeclaredNames of ModuleItemList.
+    for local in std::mem::take(&mut self.state.exported_bindings) {
+      if let NodeType::IdentifierName { name } = local.node_type() {
+        if !self.resolver.is_declared_at_top_level(name) {
+          self.report(
+            EarlyError::from(SyntaxError::from_node(
+              self,
+              &local,
+              SyntaxErrorTemplate::UndeclaredExport,
+            ))
+            .into(),
+          )?;
+        }
+      }
+    }
+    Ok(items)
+  }
+
+  /// `import` starts an ImportDeclaration unless it's `import(` or
+  /// `import.meta`.
+  pub(super) fn is_import_declaration(&mut self) -> Result<bool, ParseError> {
+    Ok(
+      test!(self.lexer, TokenType::Import)?
+        && !test_ahead!(self.lexer, TokenType::LParen | TokenType::Period)?,
+    )
+  }
+
+  /// ImportDeclaration :
+  ///   `import` ImportClause FromClause `;`
+  ///   `import` ModuleSpecifier `;`
+  ///
+  /// ImportClause :
+  ///   ImportedDefaultBinding
+  ///   NameSpaceImport
+  ///   NamedImports
+  ///   ImportedDefaultBinding `,` NameSpaceImport
+  ///   ImportedDefaultBinding `,` NamedImports
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ImportDeclaration
+  fn parse_import_declaration(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Import)?;
+    let mut specifiers = Vec::new();
+    if !test!(self.lexer, TokenType::String(_))? {
+      if !test!(self.lexer, TokenType::Mul | TokenType::LBrace)? {
+        let specifier = self.start()?;
+        let local = self.parse_imported_binding()?;
+        specifiers.push(self.finish(
+          specifier,
+          NodeType::ImportDefaultSpecifier {
+            local: Box::new(local),
+          },
+        ));
+        if !eat!(&mut self.lexer, TokenType::Comma)? {
+          return self.finish_import_declaration(node, specifiers);
+        }
+      }
+      if test!(self.lexer, TokenType::Mul)? {
+        specifiers.push(self.parse_namespace_import()?);
+      } else {
+        self.parse_named_imports(&mut specifiers)?;
+      }
+      return self.finish_import_declaration(node, specifiers);
+    }
+    let source = self.parse_module_specifier()?;
+    self.semicolon()?;
+    Ok(self.finish(
+      node,
+      NodeType::ImportDeclaration {
+        specifiers,
+        source: Box::new(source),
+      },
+    ))
+  }
+
+  /// FromClause `;` at the end of an ImportDeclaration with an ImportClause.
+  fn finish_import_declaration(
+    &mut self,
+    node: NodeBuilder,
+    specifiers: Vec<Node>,
+  ) -> Result<Node, ParseError> {
+    let source = self.parse_from_clause()?;
+    self.semicolon()?;
+    Ok(self.finish(
+      node,
+      NodeType::ImportDeclaration {
+        specifiers,
+        source: Box::new(source),
+      },
+    ))
+  }
+
+  /// NameSpaceImport :
+  ///   `*` `as` ImportedBinding
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-NameSpaceImport
+  fn parse_namespace_import(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Mul)?;
+    expect!(&mut self.lexer, "as")?;
+    let local = self.parse_imported_binding()?;
+    Ok(self.finish(
+      node,
+      NodeType::ImportNamespaceSpecifier {
+        local: Box::new(local),
+      },
+    ))
+  }
+
+  /// NamedImports :
+  ///   `{` `}`
+  ///   `{` ImportsList `}`
+  ///   `{` ImportsList `,` `}`
+  ///
+  /// ImportSpecifier :
+  ///   ImportedBinding
+  ///   ModuleExportName `as` ImportedBinding
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-NamedImports
+  fn parse_named_imports(
+    &mut self,
+    specifiers: &mut Vec<Node>,
+  ) -> Result<(), ParseError> {
+    expect!(&mut self.lexer, TokenType::LBrace)?;
+    while !eat!(&mut self.lexer, TokenType::RBrace)? {
+      let node = self.start()?;
+      // a string or a reserved word can't be bound without `as`
+      let imported = if test!(
+        self.lexer,
+        TokenType::Identifier(_)
+          | TokenType::EscapedKeyword(_)
+          | TokenType::Yield
+          | TokenType::Await
+      )? && !test_ahead!(self.lexer, "as")?
+      {
+        None
+      } else {
+        let imported = self.parse_module_export_name()?;
+        expect!(&mut self.lexer, "as")?;
+        Some(Box::new(imported))
+      };
+      let local = self.parse_imported_binding()?;
+      specifiers.push(self.finish(
+        node,
+        NodeType::ImportSpecifier {
+          imported,
+          local: Box::new(local),
+        },
+      ));
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RBrace)?;
+        break;
+      }
+    }
+    Ok(())
+  }
+
+  /// ImportedBinding :
+  ///   BindingIdentifier
+  ///
+  /// Imports are immutable bindings in the module scope.
+  fn parse_imported_binding(&mut self) -> Result<Node, ParseError> {
+    let local = self.parse_binding_identifier()?;
+    self.declare(&local, VariableKind::Const)?;
+    Ok(local)
+  }
+
+  /// ExportDeclaration :
+  ///   `export` ExportFromClause FromClause `;`
+  ///   `export` NamedExports `;`
+  ///   `export` VariableStatement
+  ///   `export` Declaration
+  ///   `export` `default` HoistableDeclaration
+  ///   `export` `default` AssignmentExpression `;`
+  ///
+  /// ExportFromClause :
+  ///   `*`
+  ///   `*` `as` ModuleExportName
+  ///   NamedExports
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ExportDeclaration
+  fn parse_export_declaration(&mut self) -> Result<Node, ParseError> {
+    // TODO: ClassDeclaration and async functions
+    let node = self.start()?;
+    let export = expect!(&mut self.lexer, TokenType::Export)?.to_owned();
+    if eat!(&mut self.lexer, TokenType::Mul)? {
+      let exported = if eat!(&mut self.lexer, "as")? {
+        let exported = self.parse_module_export_name()?;
+        self.add_exported_name(&exported)?;
+        Some(Box::new(exported))
+      } else {
+        None
+      };
+      let source = self.parse_from_clause()?;
+      self.semicolon()?;
+      return Ok(self.finish(
+        node,
+        NodeType::ExportAllDeclaration {
+          exported,
+          source: Box::new(source),
+        },
+      ));
+    }
+    if test!(self.lexer, TokenType::LBrace)? {
+      let specifiers = self.parse_named_exports()?;
+      let source = if test!(self.lexer, "from")? {
+        Some(Box::new(self.parse_from_clause()?))
+      } else {
+        // only a re-export can name a string
+        for specifier in &specifiers {
+          if let NodeType::ExportSpecifier { local, .. } = specifier.node_type()
+          {
+            if matches!(local.node_type(), NodeType::StringLiteral { .. }) {
+              return Err(
+                EarlyError::from(SyntaxError::from_node(
+                  self,
+                  local,
+                  SyntaxErrorTemplate::ExportStringWithoutFrom,
+                ))
+                .into(),
+              );
+            }
+            self.state.exported_bindings.push(local.as_ref().clone());
+          }
+        }
+        None
+      };
+      self.semicolon()?;
+      return Ok(self.finish(
+        node,
+        NodeType::ExportNamedDeclaration {
+          declaration: None,
+          specifiers,
+          source,
+        },
+      ));
+    }
+    if eat!(&mut self.lexer, TokenType::Default)? {
+      if !self.state.exported_names.insert("default".to_owned()) {
+        return Err(
+          EarlyError::from(SyntaxError::from_token(
+            self,
+            &export,
+            SyntaxErrorTemplate::DuplicateExport,
+          ))
+          .into(),
+        );
+      }
+      // TODO: `export default function () {}`
+      let declaration = if test!(self.lexer, TokenType::Function)? {
+        self.parse_function_declaration()?
+      } else {
+        let expression = self.parse_assignment_expression()?;
+        self.semicolon()?;
+        expression
+      };
+      return Ok(self.finish(
+        node,
+        NodeType::ExportDefaultDeclaration {
+          declaration: Box::new(declaration),
+        },
+      ));
+    }
+    let declaration = if test!(self.lexer, TokenType::Var)? {
+      self.parse_variable_statement()?
+    } else if test!(self.lexer, TokenType::Function)? {
+      self.parse_function_declaration()?
+    } else if test!(self.lexer, TokenType::Const)?
+      || self.is_let_declaration()?
+    {
+      self.parse_lexical_declaration()?
+    } else {
+      return Err(self.unexpected());
+    };
+    for name in declaration.bound_names() {
+      let name = name.clone();
+      self.add_exported_name(&name)?;
+    }
+    Ok(self.finish(
+      node,
+      NodeType::ExportNamedDeclaration {
+        declaration: Some(Box::new(declaration)),
+        specifiers: Vec::new(),
+        source: None,
+      },
+    ))
+  }
+
+  /// NamedExports :
+  ///   `{` `}`
+  ///   `{` ExportsList `}`
+  ///   `{` ExportsList `,` `}`
+  ///
+  /// ExportSpecifier :
+  ///   ModuleExportName
+  ///   ModuleExportName `as` ModuleExportName
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-NamedExports
+  fn parse_named_exports(&mut self) -> Result<Vec<Node>, ParseError> {
+    let mut specifiers = Vec::new();
+    expect!(&mut self.lexer, TokenType::LBrace)?;
+    while !eat!(&mut self.lexer, TokenType::RBrace)? {
+      let node = self.start()?;
+      let local = self.parse_module_export_name()?;
+      let exported = if eat!(&mut self.lexer, "as")? {
+        let exported = self.parse_module_export_name()?;
+        self.add_exported_name(&exported)?;
+        Some(Box::new(exported))
+      } else {
+        self.add_exported_name(&local)?;
+        None
+      };
+      specifiers.push(self.finish(
+        node,
+        NodeType::ExportSpecifier {
+          local: Box::new(local),
+          exported,
+        },
+      ));
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RBrace)?;
+        break;
+      }
+    }
+    Ok(specifiers)
+  }
+
+  /// ModuleExportName :
+  ///   IdentifierName
+  ///   StringLiteral
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ModuleExportName
+  fn parse_module_export_name(&mut self) -> Result<Node, ParseError> {
+    if test!(self.lexer, TokenType::String(_))? {
+      let name = self.parse_module_specifier()?;
+      // It is a Syntax Error if IsStringWellFormedUnicode(the SV of
+      // StringLiteral) is false.
+      if matches!(name.node_type(), NodeType::StringLiteral { value } if value.to_well_formed().is_none())
+      {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(
+            self,
+            &name,
+            SyntaxErrorTemplate::MalformedExportName,
+          ))
+          .into(),
+        );
+      }
+      Ok(name)
+    } else {
+      self.parse_identifier_name()
+    }
+  }
+
+  /// FromClause :
+  ///   `from` ModuleSpecifier
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-FromClause
+  fn parse_from_clause(&mut self) -> Result<Node, ParseError> {
+    expect!(&mut self.lexer, "from")?;
+    self.parse_module_specifier()
+  }
+
+  /// ModuleSpecifier :
+  ///   StringLiteral
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ModuleSpecifier
+  fn parse_module_specifier(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let value = match expect!(&mut self.lexer, TokenType::String(_))?
+      .token_type
+      .clone()
+    {
+      TokenType::String(value) => value,
+      _ => unreachable!("expect String"),
+    };
+    Ok(self.finish(node, NodeType::StringLiteral { value }))
+  }
+
+  /// It is a Syntax Error if the ExportedNames of ModuleItemList contains any
+  /// duplicate entries.
+  ///
+  /// See https://tc39.es/ecma262/#sec-module-semantics-static-semantics-early-errors
+  fn add_exported_name(&mut self, exported: &Node) -> Result<(), ParseError> {
+    let name = match exported.node_type() {
+      NodeType::IdentifierName { name } => name.clone(),
+      NodeType::BindingIdentifier { name } => name.clone(),
+      // a string name is well-formed
+      NodeType::StringLiteral { value } => value.to_string_lossy(),
+      t => unreachable!("unexpected exported name {:?}", t),
+    };
+    if !self.state.exported_names.insert(name) {
+      return Err(
+        EarlyError::from(SyntaxError::from_node(
+          self,
+          exported,
+          SyntaxErrorTemplate::DuplicateExport,
+        ))
+        .into(),
+      );
+    }
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn parse_module_item(source: &str) -> Node {
+    match Parser::parse_module(source, None).unwrap().node_type() {
+      NodeType::Module { body } => body[0].clone(),
+      _ => unreachable!(),
+    }
+  }
+
+  fn parse_error(source: &str) -> String {
+    Parser::parse_module(source, None)
+      .unwrap_err()
+      .syntax_error()
+      .message()
+      .to_owned()
+  }
+
+  fn names(nodes: Vec<&Node>) -> Vec<&str> {
+    nodes
+      .into_iter()
+      .map(|node| match node.node_type() {
+        NodeType::BindingIdentifier { name } => name.as_str(),
+        _ => unreachable!(),
+      })
+      .collect()
+  }
+
+  #[test]
+  fn import_declaration() {
+    let node = parse_module_item("import 'a';");
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ImportDeclaration { specifiers, source }
+        if specifiers.is_empty()
+          && matches!(source.node_type(), NodeType::StringLiteral { value } if value == "a")
+    ));
+    let node = parse_module_item("import a from 'b';");
+    match node.node_type() {
+      NodeType::ImportDeclaration { specifiers, .. } => assert!(matches!(
+        specifiers[0].node_type(),
+        NodeType::ImportDefaultSpecifier { .. }
+      )),
+      _ => unreachable!(),
+    }
+    assert_eq!(names(node.bound_names()), ["a"]);
+    let node = parse_module_item("import a, * as ns from 'b';");
+    match node.node_type() {
+      NodeType::ImportDeclaration { specifiers, .. } => assert!(matches!(
+        specifiers[1].node_type(),
+        NodeType::ImportNamespaceSpecifier { .. }
+      )),
+      _ => unreachable!(),
+    }
+    assert_eq!(names(node.bound_names()), ["a", "ns"]);
+    let node = parse_module_item("import { a, b as c, 'd e' as f, } from 'g';");
+    match node.node_type() {
+      NodeType::ImportDeclaration { specifiers, .. } => {
+        assert!(matches!(
+          specifiers[0].node_type(),
+          NodeType::ImportSpecifier { imported: None, .. }
+        ));
+        assert!(matches!(
+          specifiers[1].node_type(),
+          NodeType::ImportSpecifier { imported: Some(imported), .. }
+            if matches!(imported.node_type(), NodeType::IdentifierName { name } if name == "b")
+        ));
+      }
+      _ => unreachable!(),
+    }
+    assert_eq!(names(node.bound_names()), ["a", "c", "f"]);
+    assert!(Parser::parse_module("import {} from 'a';", None).is_ok());
+    assert!(Parser::parse_module("import { if as a } from 'b';", None).is_ok());
+    assert!(Parser::parse_module("import { if } from 'a';", None).is_err());
+    assert!(Parser::parse_module("import { 'a' } from 'b';", None).is_err());
+    assert!(Parser::parse_module("import * from 'a';", None).is_err());
+    assert!(Parser::parse_module("import a, b from 'c';", None).is_err());
+    assert_eq!(
+      parse_error("import a from 'b'; let a;"),
+      "Identifier has already been declared"
+    );
+    assert_eq!(
+      parse_error("import { a, b as a } from 'c';"),
+      "Identifier has already been declared"
+    );
+  }
+
+  #[test]
+  fn export_declaration() {
+    let node =
+      parse_module_item("export { a, b as c, d as 'e f' }; let a, b, d;");
+    match node.node_type() {
+      NodeType::ExportNamedDeclaration {
+        declaration: None,
+        specifiers,
+        source: None,
+      } => {
+        assert_eq!(specifiers.len(), 3);
+        assert!(matches!(
+          specifiers[0].node_type(),
+          NodeType::ExportSpecifier { exported: None, .. }
+        ));
+      }
+      _ => unreachable!(),
+    }
+    let node = parse_module_item("export { a as 'b', 'c' } from 'd';");
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ExportNamedDeclaration {
+        source: Some(_),
+        ..
+      }
+    ));
+    let node = parse_module_item("export * from 'a';");
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ExportAllDeclaration { exported: None, .. }
+    ));
+    let node = parse_module_item("export * as ns from 'a';");
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ExportAllDeclaration {
+        exported: Some(_),
+        ..
+      }
+    ));
+    let node = parse_module_item("export const a = 1, { b } = c;");
+    assert_eq!(names(node.bound_names()), ["a", "b"]);
+    let node = parse_module_item("export function f() {}");
+    assert_eq!(names(node.bound_names()), ["f"]);
+    let node = parse_module_item("export default function f() {}");
+    assert_eq!(names(node.bound_names()), ["f"]);
+    let node = parse_module_item("export default a + b;");
+    assert!(matches!(
+      node.node_type(),
+      NodeType::ExportDefaultDeclaration { declaration }
+        if matches!(declaration.node_type(), NodeType::BinaryExpression { .. })
+    ));
+    assert!(Parser::parse_module("export var a; export let b;", None).is_ok());
+    assert!(Parser::parse_module("export a;", None).is_err());
+    assert_eq!(
+      parse_error("export { 'a' };"),
+      "A string literal can't be exported without a 'from' clause"
+    );
+    assert!(
+      Parser::parse_module(r"export { '\uD83D\uDE00' } from 'm';", None)
+        .is_ok()
+    );
+    assert_eq!(
+      parse_error(r"export { '\uD800' } from 'm';"),
+      "Invalid module export name: contains unpaired surrogate"
+    );
+  }
+
+  #[test]
+  fn duplicate_export() {
+    for source in [
+      "export var a; export { b as a };",
+      "export { a, a };",
+      "export { a as default }; export default 1;",
+      "export default 1; export default 2;",
+      "export * as a from 'b'; export { c as a } from 'd';",
+      "export { a as 'b' }; export { c as 'b' };",
+      "export function f() {} export { g as f };",
+    ] {
+      assert_eq!(parse_error(source), "Duplicate export", "{}", source);
+    }
+    assert!(Parser::parse_module(
+      "export * from 'a'; export * from 'b';",
+      None
+    )
+    .is_ok());
+  }
+
+  #[test]
+  fn illegal_import_export() {
+    for source in ["{ export var a; }", "if (a) import 'b';"] {
+      assert_eq!(
+        parse_error(source),
+        "'import' and 'export' may only appear at the top level of a module"
+      );
+    }
+    for source in ["export var a;", "import 'a';"] {
+      assert!(Parser::parse_script(source, None).is_err());
+    }
+    assert!(
+      Parser::parse_script("function f() { export var a; }", None).is_err()
+    );
+  }
+
+  #[test]
+  fn undeclared_export() {
+    for source in ["export { a };", "export { a }; { let a; }"] {
+      assert_eq!(
+        parse_error(source),
+        "Export of a binding that isn't declared in the module",
+        "{}",
+        source
+      );
+    }
+    for source in [
+      "export { a }; var a;",
+      "export { a as b }; function a() {}",
+      "import a from 'b'; export { a };",
+      "export { a } from 'b';",
+    ] {
+      assert!(Parser::parse_module(source, None).is_ok(), "{}", source);
+    }
+  }
+
+  #[test]
+  fn entry_points() {
+    let node = Parser::parse_script("var a = 1;", Some("a.js")).unwrap();
+    assert!(matches!(node.node_type(), NodeType::Script { .. }));
+    assert!(!node.is_strict());
+    let node =
+      Parser::parse_module("import a from 'b'; export { a };", None).unwrap();
+    assert!(
+      matches!(node.node_type(), NodeType::Module { body } if body.len() == 2)
+    );
+    assert!(node.is_strict());
+    // the specifier names the source of an error
+    let error =
+      Parser::parse_script("import a from 'b';", Some("a.js")).unwrap_err();
+    assert!(error
+      .syntax_error()
+      .decoration()
+      .starts_with("\na.js:1:1\n"));
+  }
+}
@@ -1,19 +1,304 @@
-use super::source::SourceText;
+use num_bigint::BigInt;
 
+use super::{
+  source::SourceText,
+  tokens::{StringValue, TokenType},
+};
+
+#[derive(Debug, Clone, Copy)]
 pub struct Location {
   pub index: usize,
   pub line: usize,
   pub column: usize,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VariableKind {
+  Var,
+  Let,
+  Const,
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub enum NodeType {
-  IdentifierName { name: String },
-  BindingIdentifier { name: String },
-  IdentifierReference { name: String, had_escaped: bool },
-  LabelIdentifier { name: String, had_escaped: bool },
-  PrivateIdentifier { name: String },
+  IdentifierName {
+    name: String,
+  },
+  BindingIdentifier {
+    name: String,
+  },
+  IdentifierReference {
+    name: String,
+    had_escaped: bool,
+  },
+  LabelIdentifier {
+    name: String,
+    had_escaped: bool,
+  },
+  PrivateIdentifier {
+    name: String,
+  },
+
+  // Literals
+  NullLiteral,
+  BooleanLiteral {
+    value: bool,
+  },
+  NumericLiteral {
+    value: f64,
+  },
+  BigIntLiteral {
+    value: BigInt,
+  },
+  StringLiteral {
+    value: StringValue,
+  },
+  RegularExpressionLiteral {
+    pattern: String,
+    flags: String,
+  },
+  TemplateLiteral {
+    quasis: Vec<TemplateElement>,
+    expressions: Vec<Node>,
+  },
+
+  // Expressions
+  ThisExpression,
+  ParenthesizedExpression {
+    expression: Box<Node>,
+  },
+  ArrayLiteral {
+    elements: Vec<Option<Node>>,
+  },
+  /// The properties are `Property` and `SpreadElement` nodes.
+  ObjectLiteral {
+    properties: Vec<Node>,
+  },
+  SpreadElement {
+    argument: Box<Node>,
+  },
+  MemberExpression {
+    object: Box<Node>,
+    property: Box<Node>,
+    computed: bool,
+    optional: bool,
+  },
+  CallExpression {
+    callee: Box<Node>,
+    arguments: Vec<Node>,
+    optional: bool,
+  },
+  /// Wraps the outermost node of an optional chain such as `a?.b.c`.
+  ChainExpression {
+    expression: Box<Node>,
+  },
+  NewExpression {
+    callee: Box<Node>,
+    arguments: Option<Vec<Node>>,
+  },
+  TaggedTemplateExpression {
+    tag: Box<Node>,
+    quasi: Box<Node>,
+  },
+  /// `import(source)`
+  ImportCall {
+    source: Box<Node>,
+  },
+  /// `import.meta`
+  MetaProperty {
+    meta: Box<Node>,
+    property: Box<Node>,
+  },
+  UnaryExpression {
+    operator: TokenType,
+    argument: Box<Node>,
+  },
+  /// `prefix` is false for `a++` and `a--`.
+  UpdateExpression {
+    operator: TokenType,
+    argument: Box<Node>,
+    prefix: bool,
+  },
+  BinaryExpression {
+    operator: TokenType,
+    left: Box<Node>,
+    right: Box<Node>,
+  },
+  LogicalExpression {
+    operator: TokenType,
+    left: Box<Node>,
+    right: Box<Node>,
+  },
+  ConditionalExpression {
+    test: Box<Node>,
+    consequent: Box<Node>,
+    alternate: Box<Node>,
+  },
+  AssignmentExpression {
+    operator: TokenType,
+    left: Box<Node>,
+    right: Box<Node>,
+  },
+  /// `a, b`, always has at least two expressions.
+  SequenceExpression {
+    expressions: Vec<Node>,
+  },
+
+  // Patterns
+  ObjectPattern {
+    properties: Vec<Node>,
+  },
+  ArrayPattern {
+    elements: Vec<Option<Node>>,
+  },
+  /// A pattern with a default value, e.g. `a = 1` in `[a = 1]`.
+  AssignmentPattern {
+    left: Box<Node>,
+    right: Box<Node>,
+  },
+  RestElement {
+    argument: Box<Node>,
+  },
+  /// `key: value` of an object literal or pattern, `{ a }` is shorthand for
+  /// `{ a: a }`.
+  Property {
+    key: Box<Node>,
+    value: Box<Node>,
+    computed: bool,
+    shorthand: bool,
+  },
+
+  // Functions
+  FunctionDeclaration {
+    id: Box<Node>,
+    params: Vec<Node>,
+    body: Vec<Node>,
+  },
+  FunctionExpression {
+    id: Option<Box<Node>>,
+    params: Vec<Node>,
+    body: Vec<Node>,
+  },
+  /// `expression` is true for a concise body, the expression is the sole
+  /// element of `body`.
+  ArrowFunctionExpression {
+    params: Vec<Node>,
+    body: Vec<Node>,
+    expression: bool,
+  },
+
+  // Statements
+  BlockStatement {
+    body: Vec<Node>,
+  },
+  EmptyStatement,
+  DebuggerStatement,
+  VariableDeclaration {
+    kind: VariableKind,
+    declarations: Vec<Node>,
+  },
+  VariableDeclarator {
+    id: Box<Node>,
+    init: Option<Box<Node>>,
+  },
+  ExpressionStatement {
+    expression: Box<Node>,
+  },
+  IfStatement {
+    test: Box<Node>,
+    consequent: Box<Node>,
+    alternate: Option<Box<Node>>,
+  },
+  DoWhileStatement {
+    body: Box<Node>,
+    test: Box<Node>,
+  },
+  WhileStatement {
+    test: Box<Node>,
+    body: Box<Node>,
+  },
+  LabeledStatement {
+    label: Box<Node>,
+    body: Box<Node>,
+  },
+  BreakStatement {
+    label: Option<Box<Node>>,
+  },
+  ContinueStatement {
+    label: Option<Box<Node>>,
+  },
+  ReturnStatement {
+    argument: Option<Box<Node>>,
+  },
+  ThrowStatement {
+    argument: Box<Node>,
+  },
+  SwitchStatement {
+    discriminant: Box<Node>,
+    cases: Vec<Node>,
+  },
+  /// `test` is `None` for the `default` clause.
+  SwitchCase {
+    test: Option<Box<Node>>,
+    consequent: Vec<Node>,
+  },
+  WithStatement {
+    object: Box<Node>,
+    body: Box<Node>,
+  },
+
+  // Scripts and Modules
+  Script {
+    body: Vec<Node>,
+  },
+  Module {
+    body: Vec<Node>,
+  },
+  ImportDeclaration {
+    specifiers: Vec<Node>,
+    source: Box<Node>,
+  },
+  /// `imported` is `None` for `import { a }`.
+  ImportSpecifier {
+    imported: Option<Box<Node>>,
+    local: Box<Node>,
+  },
+  ImportDefaultSpecifier {
+    local: Box<Node>,
+  },
+  ImportNamespaceSpecifier {
+    local: Box<Node>,
+  },
+  /// Either a `declaration` or `specifiers` with an optional `source`.
+  ExportNamedDeclaration {
+    declaration: Option<Box<Node>>,
+    specifiers: Vec<Node>,
+    source: Option<Box<Node>>,
+  },
+  /// `exported` is `None` for `export { a }`.
+  ExportSpecifier {
+    local: Box<Node>,
+    exported: Option<Box<Node>>,
+  },
+  ExportDefaultDeclaration {
+    declaration: Box<Node>,
+  },
+  /// `exported` is `None` for `export * from "m"`.
+  ExportAllDeclaration {
+    exported: Option<Box<Node>>,
+    source: Box<Node>,
+  },
 }
 
+/// A chunk of a template literal, `cooked` is `None` when the chunk contains
+/// an escape sequence only allowed in tagged templates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateElement {
+  pub cooked: Option<StringValue>,
+  pub raw: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct Node {
   node_type: NodeType,
   start: Location,
@@ -48,6 +333,56 @@ impl NodeBuilder {
   }
 }
 
+/// https://tc39.es/ecma262/#sec-numeric-types-number-tostring with radix 10,
+/// the same as `Number.prototype.toString()`.
+fn number_to_string(x: f64) -> String {
+  if x.is_nan() {
+    return "NaN".to_owned();
+  }
+  if x == 0.0 {
+    return "0".to_owned();
+  }
+  if x < 0.0 {
+    return format!("-{}", number_to_string(-x));
+  }
+  if x.is_infinite() {
+    return "Infinity".to_owned();
+  }
+  // the shortest digits that round trip, k digits with the decimal point
+  // after the n-th one
+  let exponential = format!("{:e}", x);
+  let (mantissa, exponent) = exponential
+    .split_once('e')
+    .expect("exponential format has an exponent");
+  let digits = mantissa.replace('.', "");
+  let k = digits.len() as i32;
+  let n = exponent.parse::<i32>().expect("exponent is an integer") + 1;
+  if k <= n && n <= 21 {
+    return format!("{}{}", digits, "0".repeat((n - k) as usize));
+  }
+  if 0 < n && n <= 21 {
+    let (int, frac) = digits.split_at(n as usize);
+    return format!("{}.{}", int, frac);
+  }
+  if -6 < n && n <= 0 {
+    return format!("0.{}{}", "0".repeat(-n as usize), digits);
+  }
+  let sign = if n - 1 < 0 { '-' } else { '+' };
+  let (first, rest) = digits.split_at(1);
+  if k == 1 {
+    format!("{}e{}{}", first, sign, (n - 1).abs())
+  } else {
+    format!("{}.{}e{}{}", first, rest, sign, (n - 1).abs())
+  }
+}
+
+/// Nodes are compared structurally, locations and source text are ignored.
+impl PartialEq for Node {
+  fn eq(&self, other: &Self) -> bool {
+    self.node_type == other.node_type
+  }
+}
+
 impl SourceText for Node {
   fn source_text(&self) -> &str {
     self.source_text.as_str()
@@ -58,4 +393,102 @@ impl Node {
   pub fn start(location: Location, is_strict: bool) -> NodeBuilder {
     NodeBuilder::new(location, is_strict)
   }
+
+  pub fn node_type(&self) -> &NodeType {
+    &self.node_type
+  }
+
+  pub fn start_location(&self) -> Location {
+    self.start
+  }
+
+  pub fn end_location(&self) -> Location {
+    self.end
+  }
+
+  pub fn is_strict(&self) -> bool {
+    self.is_strict
+  }
+
+  /// The same source text as another production, e.g. an
+  /// IdentifierReference of a cover grammar as a BindingIdentifier.
+  pub fn reinterpret(&self, node_type: NodeType) -> Node {
+    Node {
+      node_type,
+      start: self.start,
+      end: self.end,
+      is_strict: self.is_strict,
+      source_text: self.source_text.clone(),
+    }
+  }
+
+  /// The string value of a literal property name, numeric keys are
+  /// canonicalized, e.g. `0.10` is `"0.1"`, and a lone surrogate of a string
+  /// key is U+FFFD. `None` for other nodes.
+  ///
+  /// See https://tc39.es/ecma262/#sec-static-semantics-propname
+  pub fn prop_name(&self) -> Option<String> {
+    match &self.node_type {
+      NodeType::IdentifierName { name } => Some(name.clone()),
+      NodeType::StringLiteral { value } => Some(value.to_string_lossy()),
+      NodeType::NumericLiteral { value } => Some(number_to_string(*value)),
+      NodeType::BigIntLiteral { value } => Some(value.to_string()),
+      _ => None,
+    }
+  }
+
+  /// The `BindingIdentifier`s a binding pattern declares.
+  ///
+  /// See https://tc39.es/ecma262/#sec-static-semantics-boundnames
+  pub fn bound_names(&self) -> Vec<&Node> {
+    let mut names = Vec::new();
+    self.collect_bound_names(&mut names);
+    names
+  }
+
+  fn collect_bound_names<'a>(&'a self, names: &mut Vec<&'a Node>) {
+    match &self.node_type {
+      NodeType::BindingIdentifier { .. } => names.push(self),
+      NodeType::ObjectPattern { properties } => {
+        for property in properties {
+          property.collect_bound_names(names);
+        }
+      }
+      NodeType::ArrayPattern { elements } => {
+        for element in elements.iter().flatten() {
+          element.collect_bound_names(names);
+        }
+      }
+      NodeType::AssignmentPattern { left, .. } => {
+        left.collect_bound_names(names)
+      }
+      NodeType::RestElement { argument } => argument.collect_bound_names(names),
+      NodeType::Property { value, .. } => value.collect_bound_names(names),
+      NodeType::VariableDeclaration { declarations, .. } => {
+        for declaration in declarations {
+          declaration.collect_bound_names(names);
+        }
+      }
+      NodeType::VariableDeclarator { id, .. } => id.collect_bound_names(names),
+      NodeType::FunctionDeclaration { id, .. } => id.collect_bound_names(names),
+      NodeType::ImportDeclaration { specifiers, .. } => {
+        for specifier in specifiers {
+          specifier.collect_bound_names(names);
+        }
+      }
+      NodeType::ImportSpecifier { local, .. }
+      | NodeType::ImportDefaultSpecifier { local }
+      | NodeType::ImportNamespaceSpecifier { local } => {
+        local.collect_bound_names(names)
+      }
+      NodeType::ExportNamedDeclaration {
+        declaration: Some(declaration),
+        ..
+      } => declaration.collect_bound_names(names),
+      NodeType::ExportDefaultDeclaration { declaration } => {
+        declaration.collect_bound_names(names)
+      }
+      _ => {}
+    }
+  }
 }
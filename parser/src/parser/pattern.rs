@@ -0,0 +1,231 @@
+use crate::{eat, expect, test, test_ahead};
+
+use super::{
+  error::ParseError,
+  nodes::{Node, NodeType},
+  tokens::TokenType,
+  Parser,
+};
+
+impl Parser {
+  /// The target of a declaration, a BindingIdentifier or a BindingPattern.
+  ///
+  /// BindingPattern :
+  ///   ObjectBindingPattern
+  ///   ArrayBindingPattern
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-BindingPattern
+  pub(super) fn parse_binding_target(&mut self) -> Result<Node, ParseError> {
+    let token_type = self.lexer.peek()?.token_type.clone();
+    match token_type {
+      TokenType::LBrack => self.parse_array_binding_pattern(),
+      TokenType::LBrace => self.parse_object_binding_pattern(),
+      _ => self.parse_binding_identifier(),
+    }
+  }
+
+  /// BindingElement :
+  ///   SingleNameBinding
+  ///   BindingPattern Initializer?
+  ///
+  /// SingleNameBinding :
+  ///   BindingIdentifier Initializer?
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-BindingElement
+  pub(super) fn parse_binding_element(&mut self) -> Result<Node, ParseError> {
+    let target = self.parse_binding_target()?;
+    self.parse_binding_initializer(target)
+  }
+
+  /// `target = AssignmentExpression`, or `target` without an initializer.
+  fn parse_binding_initializer(
+    &mut self,
+    target: Node,
+  ) -> Result<Node, ParseError> {
+    if !eat!(&mut self.lexer, TokenType::Assign)? {
+      return Ok(target);
+    }
+    let node = self.start_from(&target);
+    let right = self.parse_assignment_expression()?;
+    Ok(self.finish(
+      node,
+      NodeType::AssignmentPattern {
+        left: Box::new(target),
+        right: Box::new(right),
+      },
+    ))
+  }
+
+  /// ArrayBindingPattern :
+  ///   `[` Elision? BindingRestElement? `]`
+  ///   `[` BindingElementList `]`
+  ///   `[` BindingElementList `,` Elision? BindingRestElement? `]`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ArrayBindingPattern
+  fn parse_array_binding_pattern(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::LBrack)?;
+    let mut elements = Vec::new();
+    while !eat!(&mut self.lexer, TokenType::RBrack)? {
+      if eat!(&mut self.lexer, TokenType::Comma)? {
+        elements.push(None);
+        continue;
+      }
+      if test!(self.lexer, TokenType::Ellipsis)? {
+        // BindingRestElement :
+        //   `...` BindingIdentifier
+        //   `...` BindingPattern
+        let rest = self.start()?;
+        self.lexer.forward()?;
+        let argument = self.parse_binding_target()?;
+        elements.push(Some(self.finish(
+          rest,
+          NodeType::RestElement {
+            argument: Box::new(argument),
+          },
+        )));
+        expect!(&mut self.lexer, TokenType::RBrack)?;
+        break;
+      }
+      elements.push(Some(self.parse_binding_element()?));
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RBrack)?;
+        break;
+      }
+    }
+    Ok(self.finish(node, NodeType::ArrayPattern { elements }))
+  }
+
+  /// ObjectBindingPattern :
+  ///   `{` `}`
+  ///   `{` BindingRestProperty `}`
+  ///   `{` BindingPropertyList `}`
+  ///   `{` BindingPropertyList `,` BindingRestProperty? `}`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ObjectBindingPattern
+  fn parse_object_binding_pattern(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::LBrace)?;
+    let mut properties = Vec::new();
+    while !eat!(&mut self.lexer, TokenType::RBrace)? {
+      if test!(self.lexer, TokenType::Ellipsis)? {
+        // BindingRestProperty :
+        //   `...` BindingIdentifier
+        let rest = self.start()?;
+        self.lexer.forward()?;
+        let argument = self.parse_binding_identifier()?;
+        properties.push(self.finish(
+          rest,
+          NodeType::RestElement {
+            argument: Box::new(argument),
+          },
+        ));
+        expect!(&mut self.lexer, TokenType::RBrace)?;
+        break;
+      }
+      properties.push(self.parse_binding_property()?);
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        expect!(&mut self.lexer, TokenType::RBrace)?;
+        break;
+      }
+    }
+    Ok(self.finish(node, NodeType::ObjectPattern { properties }))
+  }
+
+  /// BindingProperty :
+  ///   SingleNameBinding
+  ///   PropertyName `:` BindingElement
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-BindingProperty
+  fn parse_binding_property(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let is_single_name = test!(
+      self.lexer,
+      TokenType::Identifier(_)
+        | TokenType::EscapedKeyword(_)
+        | TokenType::Yield
+        | TokenType::Await
+    )? && !test_ahead!(self.lexer, TokenType::Colon)?;
+    if is_single_name {
+      let value = self.parse_binding_identifier()?;
+      let name = match value.node_type() {
+        NodeType::BindingIdentifier { name } => name.clone(),
+        _ => unreachable!("expect BindingIdentifier"),
+      };
+      let key = self.start_from(&value);
+      let key = self.finish(key, NodeType::IdentifierName { name });
+      let value = self.parse_binding_initializer(value)?;
+      return Ok(self.finish(
+        node,
+        NodeType::Property {
+          key: Box::new(key),
+          value: Box::new(value),
+          computed: false,
+          shorthand: true,
+        },
+      ));
+    }
+    let (key, computed) = self.parse_property_name()?;
+    expect!(&mut self.lexer, TokenType::Colon)?;
+    let value = self.parse_binding_element()?;
+    Ok(self.finish(
+      node,
+      NodeType::Property {
+        key: Box::new(key),
+        value: Box::new(value),
+        computed,
+        shorthand: false,
+      },
+    ))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn names(source: &str) -> Vec<String> {
+    let mut parser = Parser::new(source);
+    let node = parser.parse_binding_element().unwrap();
+    node
+      .bound_names()
+      .into_iter()
+      .map(|node| match node.node_type() {
+        NodeType::BindingIdentifier { name } => name.clone(),
+        t => panic!("expect BindingIdentifier, got {:?}", t),
+      })
+      .collect()
+  }
+
+  #[test]
+  fn binding_patterns() {
+    assert_eq!(names("a = 1"), ["a"]);
+    assert_eq!(
+      names("{ a, b: [c, , ...d], e = 1, 'f': g, [h]: i, ...j }"),
+      ["a", "c", "d", "e", "g", "i", "j"]
+    );
+    assert_eq!(names("[{ a }, [b] = [], ...[c]]"), ["a", "b", "c"]);
+  }
+
+  #[test]
+  fn rest_must_be_last() {
+    assert!(Parser::new("[...a, b]").parse_binding_element().is_err());
+    assert!(Parser::new("{ ...a, b }").parse_binding_element().is_err());
+    assert!(Parser::new("{ ...[a] }").parse_binding_element().is_err());
+  }
+}
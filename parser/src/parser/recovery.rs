@@ -0,0 +1,220 @@
+//! Error recovery for editors: the errors of a statement are recorded and
+//! the tokens are skipped to the next statement boundary, so several
+//! independent errors are reported in one pass.
+
+use super::{
+  error::{ParseError, SyntaxError},
+  nodes::Node,
+  strict::{IsStrict, SetStrict},
+  tokens::TokenType,
+  Parser,
+};
+
+/// The result of parsing in error recovery mode, `node` is `None` when the
+/// parser couldn't go on, e.g. after a token failed to scan. The skipped
+/// statements are left out of `node`.
+#[derive(Debug)]
+pub struct ParseOutput {
+  pub node: Option<Node>,
+  pub errors: Vec<SyntaxError>,
+}
+
+impl Parser {
+  /// Like `Parser::parse_script`, but reports every error it can recover
+  /// from instead of the first one.
+  pub fn parse_script_with_recovery(
+    source: &str,
+    specifier: Option<&str>,
+  ) -> ParseOutput {
+    let mut parser = Self::new(source);
+    parser.specifier = specifier.map(String::from);
+    parser.error_recovery = true;
+    let result = parser.script();
+    parser.output(result)
+  }
+
+  /// Like `Parser::parse_module`, but reports every error it can recover
+  /// from instead of the first one.
+  pub fn parse_module_with_recovery(
+    source: &str,
+    specifier: Option<&str>,
+  ) -> ParseOutput {
+    let mut parser = Self::new(source);
+    parser.specifier = specifier.map(String::from);
+    parser.error_recovery = true;
+    let result = parser.module();
+    parser.output(result)
+  }
+
+  fn output(mut self, result: Result<Node, ParseError>) -> ParseOutput {
+    let node = match result {
+      Ok(node) => Some(node),
+      Err(error) => {
+        self.record(error);
+        None
+      }
+    };
+    ParseOutput {
+      node,
+      errors: self.errors,
+    }
+  }
+
+  fn record(&mut self, error: ParseError) {
+    match error {
+      ParseError::SyntaxError(error) => self.errors.push(error),
+      ParseError::EarlyError(_) => {
+        let error = error.syntax_error().clone();
+        if self.early_errors.insert(error.clone()) {
+          self.errors.push(error);
+        }
+      }
+    }
+  }
+
+  /// Return `error`, unless in error recovery mode where it's recorded and
+  /// parsing goes on.
+  pub(super) fn report(&mut self, error: ParseError) -> Result<(), ParseError> {
+    if !self.error_recovery {
+      return Err(error);
+    }
+    self.record(error);
+    Ok(())
+  }
+
+  /// Parse a statement with `parse`. In error recovery mode an error is
+  /// recorded, the resolver is restored to the state before the statement
+  /// and the tokens are skipped to the next statement boundary, `None` then.
+  ///
+  /// The bindings the statement declared before the error stay declared. A
+  /// token that failed to scan leaves the lexer in the middle of the source,
+  /// so the error is returned as is.
+  pub(super) fn parse_recoverable(
+    &mut self,
+    parse: impl FnOnce(&mut Self) -> Result<Node, ParseError>,
+  ) -> Result<Option<Node>, ParseError> {
+    if !self.error_recovery {
+      return parse(self).map(Some);
+    }
+    let checkpoint = self.resolver.checkpoint();
+    let is_strict = self.is_strict();
+    let start = self.lexer.peek()?.start_index;
+    let error = match parse(self) {
+      Ok(node) => return Ok(Some(node)),
+      Err(error) => error,
+    };
+    let peek = self.lexer.peek()?;
+    // nothing to skip at the end of the source, the enclosing list reports
+    // the missing end
+    let is_stuck =
+      peek.token_type == TokenType::EndOfSource && peek.start_index == start;
+    if self.lexer.has_failed() || is_stuck {
+      return Err(error);
+    }
+    self.record(error);
+    self.resolver.restore(checkpoint);
+    self.set_strict(is_strict);
+    self.synchronize(start)?;
+    Ok(None)
+  }
+
+  /// Skip tokens to after the next `;`, or to a `}` or a token on a new line
+  /// outside of the braces opened meanwhile. At least one token is skipped
+  /// since the statement started at `start`, so the same error isn't raised
+  /// again.
+  fn synchronize(&mut self, start: usize) -> Result<(), ParseError> {
+    let mut depth = 0;
+    let mut skipped = self.lexer.peek()?.start_index != start;
+    loop {
+      let peek = self.lexer.peek()?;
+      if peek.token_type == TokenType::EndOfSource
+        || (depth == 0
+          && skipped
+          && (peek.token_type == TokenType::RBrace
+            || peek.had_line_terminator_before))
+      {
+        return Ok(());
+      }
+      skipped = true;
+      match self.lexer.bump()?.token_type {
+        TokenType::LBrace => depth += 1,
+        TokenType::RBrace if depth > 0 => depth -= 1,
+        TokenType::Semicolon if depth == 0 => return Ok(()),
+        _ => {}
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::parser::nodes::NodeType;
+
+  fn messages(output: &ParseOutput) -> Vec<&str> {
+    output.errors.iter().map(SyntaxError::message).collect()
+  }
+
+  fn body_len(output: &ParseOutput) -> usize {
+    match output.node.as_ref().map(Node::node_type) {
+      Some(NodeType::Script { body } | NodeType::Module { body }) => body.len(),
+      node_type => panic!("expect a program, got {:?}", node_type),
+    }
+  }
+
+  #[test]
+  fn independent_errors() {
+    let output = Parser::parse_script_with_recovery(
+      "let a = ;\nvar b = 1;\nfoo bar;\nlet c;",
+      Some("a.js"),
+    );
+    assert_eq!(output.errors.len(), 2, "{:?}", output.errors);
+    assert!(output.errors[0].decoration().starts_with("\na.js:1:9\n"));
+    assert!(output.errors[1].decoration().starts_with("\na.js:3:5\n"));
+    assert_eq!(body_len(&output), 2);
+  }
+
+  #[test]
+  fn errors_in_blocks_and_functions() {
+    let output = Parser::parse_script_with_recovery(
+      "function f() { return 1 2; }\n{ break; }\nf();",
+      None,
+    );
+    assert_eq!(messages(&output).len(), 2, "{:?}", output.errors);
+    assert_eq!(body_len(&output), 3);
+    // the `return` of the skipped statement doesn't leak out of `f`
+    let output = Parser::parse_script_with_recovery(
+      "function f() { if (a b) {} }\nreturn;",
+      None,
+    );
+    assert_eq!(output.errors.len(), 2, "{:?}", output.errors);
+    assert_eq!(messages(&output)[1], "Illegal return statement");
+  }
+
+  #[test]
+  fn early_errors() {
+    let output = Parser::parse_module_with_recovery(
+      "export { a }; let b; let b;\nexport default 1; export default 2;",
+      None,
+    );
+    assert_eq!(
+      messages(&output),
+      [
+        "Identifier has already been declared",
+        "Duplicate export",
+        "Export of a binding that isn't declared in the module",
+      ]
+    );
+    assert!(output.node.is_some());
+  }
+
+  #[test]
+  fn lexer_errors_abort() {
+    let output =
+      Parser::parse_script_with_recovery("a b;\n'unterminated\nc d;", None);
+    assert!(output.node.is_none());
+    assert_eq!(output.errors.len(), 2, "{:?}", output.errors);
+    // without recovery the first error is returned
+    assert!(Parser::parse_script("a b;\nc d;", None).is_err());
+  }
+}
@@ -22,7 +22,7 @@ impl From<Flag> for u16 {
   }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Copy)]
 pub struct Flags(u16);
 
 impl Flags {
@@ -39,8 +39,20 @@ impl Flags {
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+  /// A function body or the top level of a script or module, `var`
+  /// declarations hoist to the nearest one.
+  Var,
+  Block,
+}
+
 struct Scope {
+  kind: ScopeKind,
+  /// The flags of the enclosing code, restored when a function scope exits.
   flags: Flags,
+  /// The labels of the enclosing code, they aren't visible in a function.
+  labels: Vec<Label>,
   lexicals: HashSet<String>,
   variables: HashSet<String>,
   functions: HashSet<String>,
@@ -48,9 +60,11 @@ struct Scope {
 }
 
 impl Scope {
-  pub fn new(flags: Flags) -> Self {
+  pub fn new(kind: ScopeKind, flags: Flags) -> Self {
     Self {
+      kind,
       flags,
+      labels: Vec::new(),
       lexicals: HashSet::new(),
       variables: HashSet::new(),
       functions: HashSet::new(),
@@ -59,12 +73,185 @@ impl Scope {
   }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabelKind {
+  /// `continue` may only target iteration statements.
+  Iteration,
+  Switch,
+  Statement,
+}
+
+/// A target of `break` and `continue`, iteration and `switch` statements
+/// push an unnamed one for the unlabelled forms.
+pub struct Label {
+  pub name: Option<String>,
+  pub kind: LabelKind,
+}
+
+/// The resolver state before a statement, to restore it when error recovery
+/// skips the statement.
+pub struct Checkpoint {
+  depth: usize,
+  labels: usize,
+  flags: Flags,
+}
+
 pub struct Resolver {
   scope_stack: Vec<Scope>,
+  labels: Vec<Label>,
   strict: Strict,
   pub flags: Flags,
 }
 
+impl Resolver {
+  pub fn new(is_strict: bool, flags: Flags) -> Self {
+    Self {
+      scope_stack: vec![Scope::new(ScopeKind::Var, Flags::default())],
+      labels: Vec::new(),
+      strict: Strict::new(is_strict),
+      flags,
+    }
+  }
+
+  /// Enter a block scope, e.g. the case block of a `switch`.
+  pub fn enter_block_scope(&mut self) {
+    self
+      .scope_stack
+      .push(Scope::new(ScopeKind::Block, Flags::default()));
+  }
+
+  pub fn exit_scope(&mut self) {
+    self.scope_stack.pop();
+  }
+
+  /// Enter the scope of a function's parameters and body, `return` is
+  /// allowed in it and the enclosing labels aren't.
+  pub fn enter_function_scope(&mut self) {
+    let mut scope = Scope::new(ScopeKind::Var, self.flags);
+    scope.labels = std::mem::take(&mut self.labels);
+    self.scope_stack.push(scope);
+    // TODO: generators and async functions
+    self.flags.add(Flag::Return);
+    self.flags.delete(Flag::Yield);
+    self.flags.delete(Flag::Await);
+    self.flags.add(Flag::In);
+  }
+
+  pub fn exit_function_scope(&mut self) {
+    let scope = self.scope_stack.pop().expect("no function scope to exit");
+    self.flags = scope.flags;
+    self.labels = scope.labels;
+  }
+
+  fn current_scope(&mut self) -> &mut Scope {
+    self.scope_stack.last_mut().expect("no scope to declare in")
+  }
+
+  /// Declare a `let`/`const` binding in the current scope, `false` if the
+  /// name is already declared there.
+  pub fn declare_lexical(&mut self, name: &str) -> bool {
+    let scope = self.current_scope();
+    if scope.lexicals.contains(name)
+      || scope.variables.contains(name)
+      || scope.functions.contains(name)
+      || scope.parameters.contains(name)
+    {
+      return false;
+    }
+    scope.lexicals.insert(name.to_owned());
+    true
+  }
+
+  /// Declare a function declaration's name, it's var scoped at the top
+  /// level of a function or script and lexical in a block.
+  pub fn declare_function(&mut self, name: &str) -> bool {
+    let scope = self.current_scope();
+    if scope.kind == ScopeKind::Block {
+      return self.declare_lexical(name);
+    }
+    if scope.lexicals.contains(name) {
+      return false;
+    }
+    scope.functions.insert(name.to_owned());
+    true
+  }
+
+  /// Declare a parameter of the current function, `false` if there is
+  /// already one with the same name.
+  pub fn declare_parameter(&mut self, name: &str) -> bool {
+    self.current_scope().parameters.insert(name.to_owned())
+  }
+
+  /// Declare a `var` binding, it's visible in every scope up to the nearest
+  /// var scope, `false` if it collides with a lexical binding of them.
+  pub fn declare_var(&mut self, name: &str) -> bool {
+    for scope in self.scope_stack.iter_mut().rev() {
+      if scope.lexicals.contains(name) {
+        return false;
+      }
+      scope.variables.insert(name.to_owned());
+      if scope.kind == ScopeKind::Var {
+        break;
+      }
+    }
+    true
+  }
+
+  /// Whether `name` is declared at the top level of the script or module.
+  pub fn is_declared_at_top_level(&self, name: &str) -> bool {
+    let scope = &self.scope_stack[0];
+    scope.lexicals.contains(name)
+      || scope.variables.contains(name)
+      || scope.functions.contains(name)
+  }
+
+  pub fn checkpoint(&self) -> Checkpoint {
+    Checkpoint {
+      depth: self.scope_stack.len(),
+      labels: self.labels.len(),
+      flags: self.flags,
+    }
+  }
+
+  /// Exit the scopes and labels entered after `checkpoint`.
+  pub fn restore(&mut self, checkpoint: Checkpoint) {
+    while self.scope_stack.len() > checkpoint.depth {
+      match self.scope_stack.last().map(|scope| scope.kind) {
+        Some(ScopeKind::Var) => self.exit_function_scope(),
+        _ => self.exit_scope(),
+      }
+    }
+    self.labels.truncate(checkpoint.labels);
+    self.flags = checkpoint.flags;
+  }
+
+  pub fn enter_label(&mut self, name: Option<String>, kind: LabelKind) {
+    self.labels.push(Label { name, kind });
+  }
+
+  pub fn exit_label(&mut self) {
+    self.labels.pop();
+  }
+
+  /// The enclosing label named `name`.
+  pub fn find_label(&self, name: &str) -> Option<&Label> {
+    self
+      .labels
+      .iter()
+      .rev()
+      .find(|label| label.name.as_deref() == Some(name))
+  }
+
+  /// Whether an unlabelled `break` or `continue` has an enclosing target.
+  pub fn has_break_target(&self, is_continue: bool) -> bool {
+    self.labels.iter().any(|label| {
+      label.name.is_none()
+        && (label.kind == LabelKind::Iteration
+          || (!is_continue && label.kind == LabelKind::Switch))
+    })
+  }
+}
+
 impl IsStrict for Resolver {
   fn is_strict(&self) -> bool {
     self.strict.is_strict()
@@ -1,19 +1,54 @@
-use std::str::Chars;
+use super::lexer::is_line_terminator;
 
 #[derive(Debug)]
 pub struct Source {
-  iter: Chars<'static>,
+  /// The decoded source, so a char index is looked up in O(1).
+  chars: Box<[char]>,
   index: usize,
+  /// The char index where each line starts, `\r\n` ends a single line.
+  line_starts: Vec<usize>,
 }
 
 impl Source {
-  pub fn new(s: &'static str) -> Self {
+  pub fn new(s: &str) -> Self {
+    let chars: Box<[char]> = s.chars().collect();
+    let mut line_starts = vec![0];
+    for (i, &c) in chars.iter().enumerate() {
+      if c == '\r' && chars.get(i + 1) == Some(&'\n') {
+        continue;
+      }
+      if is_line_terminator(c) {
+        line_starts.push(i + 1);
+      }
+    }
     Self {
-      iter: s.chars(),
+      chars,
       index: 0, // TODO: read_index starts with -1?
+      line_starts,
     }
   }
 
+  /// The 1-based line and column of a char index, every char is a column.
+  pub fn position_of(&self, index: usize) -> (usize, usize) {
+    let line = match self.line_starts.binary_search(&index) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    };
+    (line + 1, index - self.line_starts[line] + 1)
+  }
+
+  /// The char index of a 1-based line and column, the column may be right
+  /// after the last char of the line.
+  pub fn index_of_position(&self, line: usize, column: usize) -> Option<usize> {
+    let line_start = *self.line_starts.get(line.checked_sub(1)?)?;
+    let index = line_start + column.checked_sub(1)?;
+    let len = self.chars.len();
+    let line_end = (line_start..len)
+      .find(|&i| self.get(i).is_some_and(is_line_terminator))
+      .unwrap_or(len);
+    (index <= line_end).then_some(index)
+  }
+
   pub fn index(&self) -> usize {
     self.index
   }
@@ -40,25 +75,58 @@ impl Source {
   }
 
   pub fn get(&self, i: usize) -> Option<char> {
-    self.iter.clone().nth(i)
+    self.chars.get(i).copied()
   }
 
   pub fn index_of(&self, c: char) -> Option<usize> {
-    for (i, ch) in self.iter.clone().skip(self.index).enumerate() {
-      if ch == c {
-        return Some(i + self.index);
-      }
-    }
-    None
+    let rest = self.chars.get(self.index..)?;
+    let i = rest.iter().position(|&ch| ch == c)?;
+    Some(i + self.index)
   }
 
+  /// `start` and `end` are char indices like `index`, not byte offsets.
   pub fn slice(&self, start: usize, end: usize) -> String {
-    let str = self.iter.as_str();
-    let str = &str[start..end];
-    str.to_owned()
+    let end = end.min(self.chars.len());
+    match self.chars.get(start..end) {
+      Some(chars) => chars.iter().collect(),
+      None => String::new(),
+    }
   }
 }
 
 pub trait SourceText {
   fn source_text(&self) -> &str;
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn positions() {
+    let source = Source::new("ab\n\tc\r\nd\u{2028}");
+    for (index, position) in [
+      (0, (1, 1)),
+      (1, (1, 2)),
+      (2, (1, 3)),
+      (3, (2, 1)),
+      (4, (2, 2)),
+      (5, (2, 3)),
+      (7, (3, 1)),
+      (8, (3, 2)),
+      (9, (4, 1)),
+    ] {
+      assert_eq!(source.position_of(index), position);
+      assert_eq!(
+        source.index_of_position(position.0, position.1),
+        Some(index)
+      );
+    }
+    // the `\n` of `\r\n` is still on the second line
+    assert_eq!(source.position_of(6), (2, 4));
+    assert_eq!(source.index_of_position(1, 4), None);
+    assert_eq!(source.index_of_position(5, 1), None);
+    assert_eq!(source.index_of_position(0, 1), None);
+    assert_eq!(source.index_of_position(1, 0), None);
+  }
+}
@@ -0,0 +1,1269 @@
+use crate::{eat, expect, test, test_ahead};
+
+use super::{
+  error::{EarlyError, ParseError, SyntaxError, SyntaxErrorTemplate},
+  nodes::{Node, NodeBuilder, NodeType, VariableKind},
+  resolver::{Flag, LabelKind},
+  source::SourceText,
+  strict::{IsStrict, SetStrict},
+  tokens::TokenType,
+  Parser,
+};
+
+impl Parser {
+  /// StatementList :
+  ///   StatementListItem
+  ///   StatementList StatementListItem
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-StatementList
+  pub(super) fn parse_statement_list(
+    &mut self,
+    end: TokenType,
+  ) -> Result<Vec<Node>, ParseError> {
+    let mut statements = Vec::new();
+    while self.lexer.peek()?.token_type != end {
+      if let Some(statement) =
+        self.parse_recoverable(Self::parse_statement_list_item)?
+      {
+        statements.push(statement);
+      }
+    }
+    Ok(statements)
+  }
+
+  /// The directive prologue at the start of a script or function body, the
+  /// directives are ordinary expression statements. Returns them with the
+  /// Use Strict Directive if there's one, strict mode is on after it.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#directive-prologue
+  pub(super) fn parse_directive_prologue(
+    &mut self,
+  ) -> Result<(Vec<Node>, Option<Node>), ParseError> {
+    let mut directives = Vec::new();
+    let mut use_strict = None;
+    while test!(self.lexer, TokenType::String(_))? {
+      let statement = self.parse_statement_list_item()?;
+      // the string literal must be the whole statement, its source text
+      // has no escape sequences or line continuations
+      let source_text = match statement.node_type() {
+        NodeType::ExpressionStatement { expression }
+          if matches!(
+            expression.node_type(),
+            NodeType::StringLiteral { .. }
+          ) =>
+        {
+          Some(expression.source_text())
+        }
+        _ => None,
+      };
+      let is_directive = source_text.is_some();
+      if matches!(source_text, Some("'use strict'" | "\"use strict\""))
+        && use_strict.is_none()
+      {
+        use_strict = Some(statement.clone());
+        self.strict_on();
+      }
+      directives.push(statement);
+      if !is_directive {
+        break;
+      }
+    }
+    Ok((directives, use_strict))
+  }
+
+  /// StatementListItem :
+  ///   Statement
+  ///   Declaration
+  ///
+  /// Declaration :
+  ///   HoistableDeclaration
+  ///   LexicalDeclaration
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-StatementListItem
+  pub(super) fn parse_statement_list_item(
+    &mut self,
+  ) -> Result<Node, ParseError> {
+    // TODO: ClassDeclaration
+    if test!(self.lexer, TokenType::Function)? {
+      return self.parse_function_declaration();
+    }
+    if test!(self.lexer, TokenType::Const)? || self.is_let_declaration()? {
+      return self.parse_lexical_declaration();
+    }
+    self.parse_statement()
+  }
+
+  /// `let` is an identifier unless it starts a LexicalDeclaration, e.g.
+  /// `let = 1` in sloppy mode.
+  pub(super) fn is_let_declaration(&mut self) -> Result<bool, ParseError> {
+    Ok(
+      test!(self.lexer, "let")?
+        && test_ahead!(
+          self.lexer,
+          TokenType::Identifier(_)
+            | TokenType::EscapedKeyword(_)
+            | TokenType::Yield
+            | TokenType::Await
+            | TokenType::LBrack
+            | TokenType::LBrace
+        )?,
+    )
+  }
+
+  /// Statement :
+  ///   BlockStatement
+  ///   VariableStatement
+  ///   EmptyStatement
+  ///   ExpressionStatement
+  ///   IfStatement
+  ///   BreakableStatement
+  ///   ContinueStatement
+  ///   BreakStatement
+  ///   ReturnStatement
+  ///   WithStatement
+  ///   LabelledStatement
+  ///   ThrowStatement
+  ///   DebuggerStatement
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-Statement
+  pub(super) fn parse_statement(&mut self) -> Result<Node, ParseError> {
+    // TODO: declarations and the other statements
+    let token_type = self.lexer.peek()?.token_type.clone();
+    match token_type {
+      TokenType::LBrace => self.parse_block_statement(),
+      TokenType::Semicolon => self.parse_empty_statement(),
+      TokenType::Debugger => self.parse_debugger_statement(),
+      TokenType::Break | TokenType::Continue => {
+        self.parse_break_continue_statement()
+      }
+      TokenType::If => self.parse_if_statement(),
+      TokenType::Do => self.parse_do_while_statement(),
+      TokenType::While => self.parse_while_statement(),
+      TokenType::Return => self.parse_return_statement(),
+      TokenType::Switch => self.parse_switch_statement(),
+      TokenType::Throw => self.parse_throw_statement(),
+      TokenType::Var => self.parse_variable_statement(),
+      TokenType::With => self.parse_with_statement(),
+      TokenType::Export => Err(self.illegal_import_export()),
+      TokenType::Import if self.is_import_declaration()? => {
+        Err(self.illegal_import_export())
+      }
+      _ if self.is_label_start()? => self.parse_labelled_statement(),
+      _ => self.parse_expression_statement(),
+    }
+  }
+
+  /// ImportDeclaration and ExportDeclaration are only ModuleItems, they can't
+  /// be nested in a statement or appear in a script.
+  fn illegal_import_export(&mut self) -> ParseError {
+    match self.lexer.peek() {
+      Ok(peek) => {
+        let peek = peek.to_owned();
+        SyntaxError::from_token(
+          self,
+          &peek,
+          SyntaxErrorTemplate::IllegalImportExport,
+        )
+        .into()
+      }
+      Err(e) => e.into(),
+    }
+  }
+
+  /// BlockStatement :
+  ///   Block
+  ///
+  /// Block :
+  ///   `{` StatementList? `}`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-BlockStatement
+  fn parse_block_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::LBrace)?;
+    self.resolver.enter_block_scope();
+    let body = self.parse_statement_list(TokenType::RBrace);
+    self.resolver.exit_scope();
+    let body = body?;
+    expect!(&mut self.lexer, TokenType::RBrace)?;
+    Ok(self.finish(node, NodeType::BlockStatement { body }))
+  }
+
+  /// EmptyStatement :
+  ///   `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-EmptyStatement
+  fn parse_empty_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Semicolon)?;
+    Ok(self.finish(node, NodeType::EmptyStatement))
+  }
+
+  /// DebuggerStatement :
+  ///   `debugger` `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-DebuggerStatement
+  fn parse_debugger_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Debugger)?;
+    self.semicolon()?;
+    Ok(self.finish(node, NodeType::DebuggerStatement))
+  }
+
+  /// VariableStatement :
+  ///   `var` VariableDeclarationList `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-VariableStatement
+  pub(super) fn parse_variable_statement(
+    &mut self,
+  ) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Var)?;
+    let declarations =
+      self.parse_variable_declaration_list(VariableKind::Var)?;
+    self.semicolon()?;
+    Ok(self.finish(
+      node,
+      NodeType::VariableDeclaration {
+        kind: VariableKind::Var,
+        declarations,
+      },
+    ))
+  }
+
+  /// LexicalDeclaration :
+  ///   LetOrConst BindingList `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-LexicalDeclaration
+  pub(super) fn parse_lexical_declaration(
+    &mut self,
+  ) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let kind = if eat!(&mut self.lexer, TokenType::Const)? {
+      VariableKind::Const
+    } else {
+      expect!(&mut self.lexer, "let")?;
+      VariableKind::Let
+    };
+    let declarations = self.parse_variable_declaration_list(kind)?;
+    self.semicolon()?;
+    Ok(self.finish(node, NodeType::VariableDeclaration { kind, declarations }))
+  }
+
+  /// VariableDeclarationList :
+  ///   VariableDeclaration
+  ///   VariableDeclarationList `,` VariableDeclaration
+  ///
+  /// BindingList :
+  ///   LexicalBinding
+  ///   BindingList `,` LexicalBinding
+  ///
+  /// Each bound name is declared in the resolver as it's parsed, `var`s
+  /// hoist to the nearest var scope and `let`/`const` stay in the current
+  /// scope.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-VariableDeclarationList
+  fn parse_variable_declaration_list(
+    &mut self,
+    kind: VariableKind,
+  ) -> Result<Vec<Node>, ParseError> {
+    // TODO: the declarations of `for-in`/`for-of` heads have no initializer
+    let mut declarations = Vec::new();
+    loop {
+      let node = self.start()?;
+      let id = self.parse_binding_target()?;
+      self.declare(&id, kind)?;
+      let init = if eat!(&mut self.lexer, TokenType::Assign)? {
+        Some(Box::new(self.parse_assignment_expression()?))
+      } else if !matches!(id.node_type(), NodeType::BindingIdentifier { .. }) {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(
+            self,
+            &id,
+            SyntaxErrorTemplate::MissingInitializerInDestructuring,
+          ))
+          .into(),
+        );
+      } else if kind == VariableKind::Const {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(
+            self,
+            &id,
+            SyntaxErrorTemplate::MissingInitializerInConst,
+          ))
+          .into(),
+        );
+      } else {
+        None
+      };
+      declarations.push(self.finish(
+        node,
+        NodeType::VariableDeclarator {
+          id: Box::new(id),
+          init,
+        },
+      ));
+      if !eat!(&mut self.lexer, TokenType::Comma)? {
+        return Ok(declarations);
+      }
+    }
+  }
+
+  /// Declare the bound names of `target` in the resolver.
+  pub(super) fn declare(
+    &mut self,
+    target: &Node,
+    kind: VariableKind,
+  ) -> Result<(), ParseError> {
+    for binding in target.bound_names() {
+      let name = match binding.node_type() {
+        NodeType::BindingIdentifier { name } => name,
+        _ => unreachable!("expect BindingIdentifier"),
+      };
+      let template = match kind {
+        // https://tc39.es/ecma262/#sec-let-and-const-declarations-static-semantics-early-errors
+        VariableKind::Let | VariableKind::Const if name == "let" => {
+          Some(SyntaxErrorTemplate::LetInLexicalBinding)
+        }
+        VariableKind::Let | VariableKind::Const
+          if !self.resolver.declare_lexical(name) =>
+        {
+          Some(SyntaxErrorTemplate::AlreadyDeclared)
+        }
+        VariableKind::Var if !self.resolver.declare_var(name) => {
+          Some(SyntaxErrorTemplate::AlreadyDeclared)
+        }
+        _ => None,
+      };
+      if let Some(template) = template {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(self, binding, template))
+            .into(),
+        );
+      }
+    }
+    Ok(())
+  }
+
+  /// `LabelIdentifier :`
+  fn is_label_start(&mut self) -> Result<bool, ParseError> {
+    Ok(
+      test!(
+        self.lexer,
+        TokenType::Identifier(_)
+          | TokenType::EscapedKeyword(_)
+          | TokenType::Yield
+          | TokenType::Await
+      )? && test_ahead!(self.lexer, TokenType::Colon)?,
+    )
+  }
+
+  /// LabelledStatement :
+  ///   LabelIdentifier `:` LabelledItem
+  ///
+  /// LabelledItem :
+  ///   Statement
+  ///   FunctionDeclaration
+  ///
+  /// The labels directly before an iteration statement are all its label
+  /// set, so `continue` may target each of them, e.g. `a: b: while (x)
+  /// continue a;`.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-LabelledStatement
+  fn parse_labelled_statement(&mut self) -> Result<Node, ParseError> {
+    let mut labels: Vec<(NodeBuilder, Node, String)> = Vec::new();
+    loop {
+      let node = self.start()?;
+      let label = self.parse_label_identifier()?;
+      let name = match label.node_type() {
+        NodeType::LabelIdentifier { name, .. } => name.clone(),
+        _ => unreachable!("expect LabelIdentifier"),
+      };
+      if self.resolver.find_label(&name).is_some()
+        || labels.iter().any(|(_, _, n)| *n == name)
+      {
+        return Err(
+          EarlyError::from(SyntaxError::from_node(
+            self,
+            &label,
+            SyntaxErrorTemplate::LabelAlreadyDeclared,
+          ))
+          .into(),
+        );
+      }
+      expect!(&mut self.lexer, TokenType::Colon)?;
+      labels.push((node, label, name));
+      if !self.is_label_start()? {
+        break;
+      }
+    }
+    // TODO: FunctionDeclaration in sloppy mode
+    let kind = if test!(
+      self.lexer,
+      TokenType::For | TokenType::While | TokenType::Do
+    )? {
+      LabelKind::Iteration
+    } else {
+      LabelKind::Statement
+    };
+    for (_, _, name) in &labels {
+      self.resolver.enter_label(Some(name.clone()), kind);
+    }
+    let body = self.parse_statement();
+    for _ in &labels {
+      self.resolver.exit_label();
+    }
+    let mut body = body?;
+    for (node, label, _) in labels.into_iter().rev() {
+      body = self.finish(
+        node,
+        NodeType::LabeledStatement {
+          label: Box::new(label),
+          body: Box::new(body),
+        },
+      );
+    }
+    Ok(body)
+  }
+
+  /// IfStatement :
+  ///   `if` `(` Expression `)` Statement `else` Statement
+  ///   `if` `(` Expression `)` Statement [lookahead ≠ `else`]
+  ///
+  /// An `else` belongs to the nearest `if`.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-IfStatement
+  fn parse_if_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::If)?;
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let test = Box::new(self.parse_expression()?);
+    expect!(&mut self.lexer, TokenType::RParen)?;
+    // TODO: FunctionDeclaration in sloppy mode
+    let consequent = Box::new(self.parse_statement()?);
+    let alternate = if eat!(&mut self.lexer, TokenType::Else)? {
+      Some(Box::new(self.parse_statement()?))
+    } else {
+      None
+    };
+    Ok(self.finish(
+      node,
+      NodeType::IfStatement {
+        test,
+        consequent,
+        alternate,
+      },
+    ))
+  }
+
+  /// The body of an iteration statement, an unlabelled `break` or
+  /// `continue` in it targets the statement.
+  fn parse_iteration_body(&mut self) -> Result<Node, ParseError> {
+    self.resolver.enter_label(None, LabelKind::Iteration);
+    let body = self.parse_statement();
+    self.resolver.exit_label();
+    body
+  }
+
+  /// DoWhileStatement :
+  ///   `do` Statement `while` `(` Expression `)` `;`
+  ///
+  /// The `;` is inserted after the `)` even without a LineTerminator, e.g.
+  /// `do a; while (b) c;`.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-DoWhileStatement
+  fn parse_do_while_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Do)?;
+    let body = Box::new(self.parse_iteration_body()?);
+    expect!(&mut self.lexer, TokenType::While)?;
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let test = Box::new(self.parse_expression()?);
+    expect!(&mut self.lexer, TokenType::RParen)?;
+    eat!(&mut self.lexer, TokenType::Semicolon)?;
+    Ok(self.finish(node, NodeType::DoWhileStatement { body, test }))
+  }
+
+  /// WhileStatement :
+  ///   `while` `(` Expression `)` Statement
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-WhileStatement
+  fn parse_while_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::While)?;
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let test = Box::new(self.parse_expression()?);
+    expect!(&mut self.lexer, TokenType::RParen)?;
+    let body = Box::new(self.parse_iteration_body()?);
+    Ok(self.finish(node, NodeType::WhileStatement { test, body }))
+  }
+
+  /// SwitchStatement :
+  ///   `switch` `(` Expression `)` CaseBlock
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-SwitchStatement
+  fn parse_switch_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    expect!(&mut self.lexer, TokenType::Switch)?;
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let discriminant = self.parse_expression()?;
+    expect!(&mut self.lexer, TokenType::RParen)?;
+    self.resolver.enter_label(None, LabelKind::Switch);
+    self.resolver.enter_block_scope();
+    let cases = self.parse_case_block();
+    self.resolver.exit_scope();
+    self.resolver.exit_label();
+    let cases = cases?;
+    Ok(self.finish(
+      node,
+      NodeType::SwitchStatement {
+        discriminant: Box::new(discriminant),
+        cases,
+      },
+    ))
+  }
+
+  /// WithStatement :
+  ///   `with` `(` Expression `)` Statement
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-WithStatement
+  fn parse_with_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let keyword = self.lexer.bump()?.to_owned();
+    // https://tc39.es/ecma262/#sec-with-statement-static-semantics-early-errors
+    // It is a Syntax Error if the source text matched by this production is
+    // contained in strict mode code.
+    if self.is_strict() {
+      return Err(
+        EarlyError::from(SyntaxError::from_token(
+          self,
+          &keyword,
+          SyntaxErrorTemplate::StrictWith,
+        ))
+        .into(),
+      );
+    }
+    expect!(&mut self.lexer, TokenType::LParen)?;
+    let object = self.parse_expression()?;
+    expect!(&mut self.lexer, TokenType::RParen)?;
+    let body = self.parse_statement()?;
+    Ok(self.finish(
+      node,
+      NodeType::WithStatement {
+        object: Box::new(object),
+        body: Box::new(body),
+      },
+    ))
+  }
+
+  /// CaseBlock :
+  ///   `{` CaseClauses? `}`
+  ///   `{` CaseClauses? DefaultClause CaseClauses? `}`
+  ///
+  /// The whole case block is a single scope, so lexical declarations in
+  /// different clauses collide.
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-CaseBlock
+  fn parse_case_block(&mut self) -> Result<Vec<Node>, ParseError> {
+    expect!(&mut self.lexer, TokenType::LBrace)?;
+    let mut cases = Vec::new();
+    let mut has_default = false;
+    while !eat!(&mut self.lexer, TokenType::RBrace)? {
+      let node = self.start()?;
+      let test = if eat!(&mut self.lexer, TokenType::Case)? {
+        Some(Box::new(self.parse_expression()?))
+      } else {
+        let default = expect!(&mut self.lexer, TokenType::Default)?.to_owned();
+        if has_default {
+          return Err(
+            EarlyError::from(SyntaxError::from_token(
+              self,
+              &default,
+              SyntaxErrorTemplate::MultipleDefaultsInSwitch,
+            ))
+            .into(),
+          );
+        }
+        has_default = true;
+        None
+      };
+      expect!(&mut self.lexer, TokenType::Colon)?;
+      let mut consequent = Vec::new();
+      while !test!(
+        self.lexer,
+        TokenType::Case | TokenType::Default | TokenType::RBrace
+      )? {
+        consequent.push(self.parse_statement_list_item()?);
+      }
+      cases.push(self.finish(node, NodeType::SwitchCase { test, consequent }));
+    }
+    Ok(cases)
+  }
+
+  /// BreakStatement :
+  ///   `break` `;`
+  ///   `break` [no LineTerminator here] LabelIdentifier `;`
+  ///
+  /// ContinueStatement :
+  ///   `continue` `;`
+  ///   `continue` [no LineTerminator here] LabelIdentifier `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-BreakStatement
+  fn parse_break_continue_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let keyword = self.lexer.bump()?.to_owned();
+    let is_continue = keyword.token_type == TokenType::Continue;
+    let label = if !self.lexer.peek()?.had_line_terminator_before
+      && test!(
+        self.lexer,
+        TokenType::Identifier(_)
+          | TokenType::EscapedKeyword(_)
+          | TokenType::Yield
+          | TokenType::Await
+      )? {
+      Some(self.parse_label_identifier()?)
+    } else {
+      None
+    };
+    self.semicolon()?;
+    let error = match &label {
+      None if !self.resolver.has_break_target(is_continue) => {
+        let template = if is_continue {
+          SyntaxErrorTemplate::IllegalContinue
+        } else {
+          SyntaxErrorTemplate::IllegalBreak
+        };
+        Some(SyntaxError::from_token(self, &keyword, template))
+      }
+      None => None,
+      Some(label) => {
+        let name = match label.node_type() {
+          NodeType::LabelIdentifier { name, .. } => name,
+          _ => unreachable!("expect LabelIdentifier"),
+        };
+        match self.resolver.find_label(name) {
+          None => Some(SyntaxError::from_node(
+            self,
+            label,
+            SyntaxErrorTemplate::UndefinedLabel,
+          )),
+          Some(target)
+            if is_continue && target.kind != LabelKind::Iteration =>
+          {
+            Some(SyntaxError::from_node(
+              self,
+              label,
+              SyntaxErrorTemplate::IllegalContinue,
+            ))
+          }
+          Some(_) => None,
+        }
+      }
+    };
+    if let Some(error) = error {
+      return Err(EarlyError::from(error).into());
+    }
+    let label = label.map(Box::new);
+    let node_type = if is_continue {
+      NodeType::ContinueStatement { label }
+    } else {
+      NodeType::BreakStatement { label }
+    };
+    Ok(self.finish(node, node_type))
+  }
+
+  /// ExpressionStatement :
+  ///   [lookahead ∉ { `{`, `function`, `async` [no LineTerminator here] `function`, `class`, `let` `[` }] Expression `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ExpressionStatement
+  fn parse_expression_statement(&mut self) -> Result<Node, ParseError> {
+    // [lookahead ∉ { `{`, ... }], a block isn't an object literal
+    if test!(self.lexer, TokenType::LBrace)? {
+      return Err(self.unexpected());
+    }
+    let node = self.start()?;
+    let expression = Box::new(self.parse_expression()?);
+    self.semicolon()?;
+    Ok(self.finish(node, NodeType::ExpressionStatement { expression }))
+  }
+
+  /// ReturnStatement :
+  ///   `return` `;`
+  ///   `return` [no LineTerminator here] Expression `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ReturnStatement
+  fn parse_return_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let keyword = self.lexer.bump()?.to_owned();
+    if !self.resolver.flags.has(Flag::Return) {
+      return Err(
+        EarlyError::from(SyntaxError::from_token(
+          self,
+          &keyword,
+          SyntaxErrorTemplate::IllegalReturn,
+        ))
+        .into(),
+      );
+    }
+    let argument = if self.lexer.peek()?.had_line_terminator_before
+      || test!(
+        self.lexer,
+        TokenType::Semicolon | TokenType::RBrace | TokenType::EndOfSource
+      )? {
+      None
+    } else {
+      Some(Box::new(self.parse_expression()?))
+    };
+    self.semicolon()?;
+    Ok(self.finish(node, NodeType::ReturnStatement { argument }))
+  }
+
+  /// ThrowStatement :
+  ///   `throw` [no LineTerminator here] Expression `;`
+  ///
+  /// More information:
+  ///  - [ECMAScript specification][spec]
+  ///
+  /// [spec]: https://tc39.es/ecma262/#prod-ThrowStatement
+  fn parse_throw_statement(&mut self) -> Result<Node, ParseError> {
+    let node = self.start()?;
+    let keyword = self.lexer.bump()?.to_owned();
+    // no semicolon is inserted, `throw` always needs an argument
+    if self.lexer.peek()?.had_line_terminator_before {
+      return Err(
+        SyntaxError::from_token(
+          self,
+          &keyword,
+          SyntaxErrorTemplate::NewlineAfterThrow,
+        )
+        .into(),
+      );
+    }
+    let argument = Box::new(self.parse_expression()?);
+    self.semicolon()?;
+    Ok(self.finish(node, NodeType::ThrowStatement { argument }))
+  }
+
+  /// See https://tc39.es/ecma262/#sec-automatic-semicolon-insertion
+  pub(super) fn semicolon(&mut self) -> Result<(), ParseError> {
+    if eat!(&mut self.lexer, TokenType::Semicolon)? {
+      return Ok(());
+    }
+    if self.lexer.peek()?.had_line_terminator_before
+      || test!(&mut self.lexer, TokenType::RBrace | TokenType::EndOfSource)?
+    {
+      return Ok(());
+    }
+    Err(self.unexpected())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{super::error::SyntaxErrorTemplate, *};
+
+  #[test]
+  fn automatic_semicolon_insertion() {
+    let node = Parser::parse_script("a\nb; c", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => assert_eq!(body.len(), 3),
+      _ => unreachable!(),
+    }
+    assert!(Parser::parse_script("a b", None).is_err());
+  }
+
+  fn parse_error(source: &str) -> String {
+    Parser::parse_script(source, None)
+      .unwrap_err()
+      .syntax_error()
+      .message()
+      .to_owned()
+  }
+
+  #[test]
+  fn unexpected_token() {
+    assert_eq!(parse_error("a }"), "Unexpected token \"}\"");
+    assert_eq!(parse_error("if (a) else"), "Unexpected token \"else\"");
+    assert_eq!(parse_error("f(a"), "Unexpected end of input");
+  }
+
+  #[test]
+  fn labelled_statement() {
+    let node = Parser::parse_script("a: b: break a;", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::LabeledStatement { label, body } => {
+          assert!(matches!(
+            label.node_type(),
+            NodeType::LabelIdentifier { name, .. } if name == "a"
+          ));
+          assert!(matches!(
+            body.node_type(),
+            NodeType::LabeledStatement { body, .. }
+              if matches!(body.node_type(), NodeType::BreakStatement { label: Some(_) })
+          ));
+        }
+        t => panic!("expect LabeledStatement, got {:?}", t),
+      },
+      _ => unreachable!(),
+    }
+    // a label is only in scope of its own statement
+    assert!(Parser::parse_script("a: x;\nb: break b;", None).is_ok());
+    assert!(Parser::parse_script("a: x;\na: y;", None).is_ok());
+  }
+
+  #[test]
+  fn undefined_label() {
+    assert_eq!(
+      parse_error("break undefinedLabel;"),
+      SyntaxErrorTemplate::UndefinedLabel.to_string()
+    );
+    assert_eq!(
+      parse_error("a: x;\nbreak a;"),
+      SyntaxErrorTemplate::UndefinedLabel.to_string()
+    );
+  }
+
+  #[test]
+  fn duplicate_label() {
+    assert_eq!(
+      parse_error("a: a: x;"),
+      SyntaxErrorTemplate::LabelAlreadyDeclared.to_string()
+    );
+    assert_eq!(
+      parse_error("a: b: a: x;"),
+      SyntaxErrorTemplate::LabelAlreadyDeclared.to_string()
+    );
+  }
+
+  #[test]
+  fn break_continue_target() {
+    assert_eq!(
+      parse_error("break;"),
+      SyntaxErrorTemplate::IllegalBreak.to_string()
+    );
+    assert_eq!(
+      parse_error("continue;"),
+      SyntaxErrorTemplate::IllegalContinue.to_string()
+    );
+    // `continue` may only target an iteration statement
+    assert_eq!(
+      parse_error("a: continue a;"),
+      SyntaxErrorTemplate::IllegalContinue.to_string()
+    );
+    // no LineTerminator between `break` and the label
+    assert_eq!(
+      parse_error("a: break\na;"),
+      SyntaxErrorTemplate::IllegalBreak.to_string()
+    );
+  }
+
+  #[test]
+  fn switch_statement() {
+    let node = Parser::parse_script(
+      "switch (a) { case 1: case 2: b; break; default: c; case 3: }",
+      None,
+    )
+    .unwrap();
+    let cases = match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::SwitchStatement { cases, .. } => cases,
+        t => panic!("expect SwitchStatement, got {:?}", t),
+      },
+      _ => unreachable!(),
+    };
+    let clauses = cases
+      .iter()
+      .map(|case| match case.node_type() {
+        NodeType::SwitchCase { test, consequent } => {
+          (test.is_some(), consequent.len())
+        }
+        t => panic!("expect SwitchCase, got {:?}", t),
+      })
+      .collect::<Vec<_>>();
+    // `case 1` falls through to `case 2`
+    assert_eq!(clauses, [(true, 0), (true, 2), (false, 1), (true, 0)]);
+    assert!(Parser::parse_script("switch (a) {}", None).is_ok());
+    // `continue` can't target a `switch`
+    assert_eq!(
+      parse_error("switch (a) { case 1: continue; }"),
+      SyntaxErrorTemplate::IllegalContinue.to_string()
+    );
+    assert_eq!(
+      parse_error("switch (a) { case 1: } break;"),
+      SyntaxErrorTemplate::IllegalBreak.to_string()
+    );
+  }
+
+  #[test]
+  fn switch_multiple_defaults() {
+    assert_eq!(
+      parse_error("switch (a) { default: case 1: default: }"),
+      SyntaxErrorTemplate::MultipleDefaultsInSwitch.to_string()
+    );
+  }
+
+  #[test]
+  fn variable_declarations() {
+    let node = Parser::parse_script("let x, y;", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::VariableDeclaration { kind, declarations } => {
+          assert_eq!(*kind, VariableKind::Let);
+          assert_eq!(declarations.len(), 2);
+          assert_eq!(body[0].bound_names().len(), 2);
+        }
+        t => panic!("expect VariableDeclaration, got {:?}", t),
+      },
+      _ => unreachable!(),
+    }
+    assert!(Parser::parse_script("var a = 1, [b, c] = d; var a;", None).is_ok());
+    assert!(
+      Parser::parse_script("const { a, b: [c] } = d, e = 1;", None).is_ok()
+    );
+    // `let` is an identifier when it doesn't start a declaration
+    assert!(Parser::parse_script("let = 1;", None).is_ok());
+  }
+
+  #[test]
+  fn missing_initializer() {
+    assert_eq!(
+      parse_error("const x;"),
+      SyntaxErrorTemplate::MissingInitializerInConst.to_string()
+    );
+    assert_eq!(
+      parse_error("var [a];"),
+      SyntaxErrorTemplate::MissingInitializerInDestructuring.to_string()
+    );
+  }
+
+  #[test]
+  fn redeclaration() {
+    for source in [
+      "var x; let x;",
+      "let x; var x;",
+      "let x, x;",
+      "const x = 1; let { x } = o;",
+      // the case block is a single scope
+      "switch (a) { case 1: let x; case 2: let x; }",
+      "switch (a) { case 1: let x; default: var x; }",
+    ] {
+      assert_eq!(
+        parse_error(source),
+        SyntaxErrorTemplate::AlreadyDeclared.to_string(),
+        "{}",
+        source
+      );
+    }
+    assert_eq!(
+      parse_error("let [let] = a;"),
+      SyntaxErrorTemplate::LetInLexicalBinding.to_string()
+    );
+    // `var` in a case block hoists out of it
+    assert!(
+      Parser::parse_script("switch (a) { case 1: var x; } var x;", None)
+        .is_ok()
+    );
+    assert!(
+      Parser::parse_script("switch (a) { case 1: let x; } let x;", None)
+        .is_ok()
+    );
+  }
+
+  #[test]
+  fn small_statements() {
+    let node = Parser::parse_script("debugger; ; { a; { } }", None).unwrap();
+    let body = match node.node_type() {
+      NodeType::Script { body } => body,
+      _ => unreachable!(),
+    };
+    assert!(matches!(body[0].node_type(), NodeType::DebuggerStatement));
+    assert!(matches!(body[1].node_type(), NodeType::EmptyStatement));
+    match body[2].node_type() {
+      NodeType::BlockStatement { body } => {
+        assert!(matches!(
+          body[0].node_type(),
+          NodeType::ExpressionStatement { .. }
+        ));
+        assert!(matches!(
+          body[1].node_type(),
+          NodeType::BlockStatement { body } if body.is_empty()
+        ));
+      }
+      t => panic!("expect BlockStatement, got {:?}", t),
+    }
+    assert!(Parser::parse_script(
+      "debugger
+a",
+      None
+    )
+    .is_ok());
+    assert!(Parser::parse_script("debugger a", None).is_err());
+    assert!(Parser::parse_script("{ a; ", None).is_err());
+  }
+
+  #[test]
+  fn block_scope() {
+    for source in [
+      "{ let x; let x; }",
+      "{ let x; var x; }",
+      "{ const x = 1; function x() {} }",
+      "let x; { var x; }",
+    ] {
+      assert_eq!(
+        parse_error(source),
+        SyntaxErrorTemplate::AlreadyDeclared.to_string(),
+        "{}",
+        source
+      );
+    }
+    // a block is a new scope for lexical declarations
+    assert!(
+      Parser::parse_script("let x; { let x; { const x = 1; } }", None).is_ok()
+    );
+    assert!(Parser::parse_script("{ let x; } { let x; } let x;", None).is_ok());
+    assert!(Parser::parse_script("{ var x; } var x;", None).is_ok());
+  }
+
+  #[test]
+  fn if_statement() {
+    let node =
+      Parser::parse_script("if (a) if (b) c; else if (d) e; else f;", None)
+        .unwrap();
+    let statement = match node.node_type() {
+      NodeType::Script { body } => &body[0],
+      _ => unreachable!(),
+    };
+    // the dangling `else` belongs to the nearest `if`
+    match statement.node_type() {
+      NodeType::IfStatement {
+        consequent,
+        alternate: None,
+        ..
+      } => match consequent.node_type() {
+        NodeType::IfStatement {
+          alternate: Some(alternate),
+          ..
+        } => assert!(matches!(
+          alternate.node_type(),
+          NodeType::IfStatement {
+            alternate: Some(_),
+            ..
+          }
+        )),
+        t => panic!("expect IfStatement, got {:?}", t),
+      },
+      t => panic!("expect IfStatement, got {:?}", t),
+    }
+    assert!(Parser::parse_script("if (a) b\nelse c", None).is_ok());
+    assert!(Parser::parse_script("if (a) b else c", None).is_err());
+    assert!(Parser::parse_script("if (a) let x = 1;", None).is_err());
+  }
+
+  #[test]
+  fn iteration_statements() {
+    let node =
+      Parser::parse_script("do a; while (b) c; while (d) e;", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => {
+        assert!(matches!(
+          body[0].node_type(),
+          NodeType::DoWhileStatement { .. }
+        ));
+        assert!(matches!(
+          body[1].node_type(),
+          NodeType::ExpressionStatement { .. }
+        ));
+        assert!(matches!(
+          body[2].node_type(),
+          NodeType::WhileStatement { .. }
+        ));
+      }
+      _ => unreachable!(),
+    }
+    assert!(Parser::parse_script("do ; while (a)", None).is_ok());
+    assert!(Parser::parse_script("do a while (b)", None).is_err());
+    for source in [
+      "while (a) break;",
+      "while (a) { if (b) continue; }",
+      "do { break; } while (a);",
+      "a: while (b) { c: { continue a; } }",
+      "a: b: do continue a; while (c);",
+    ] {
+      assert!(Parser::parse_script(source, None).is_ok(), "{}", source);
+    }
+    assert_eq!(
+      parse_error("if (a) break;"),
+      SyntaxErrorTemplate::IllegalBreak.to_string()
+    );
+    assert_eq!(
+      parse_error("switch (a) { case 1: continue; }"),
+      SyntaxErrorTemplate::IllegalContinue.to_string()
+    );
+  }
+
+  #[test]
+  fn return_statement() {
+    assert_eq!(
+      parse_error("return;"),
+      SyntaxErrorTemplate::IllegalReturn.to_string()
+    );
+    let node = Parser::parse_script("function f() { return a }", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => assert!(matches!(
+        body[0].node_type(),
+        NodeType::FunctionDeclaration { body, .. }
+          if matches!(body[0].node_type(), NodeType::ReturnStatement { argument: Some(_) })
+      )),
+      _ => unreachable!(),
+    }
+    let node =
+      Parser::parse_script("function f() { return\na; }", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => match body[0].node_type() {
+        NodeType::FunctionDeclaration { body, .. } => {
+          assert!(matches!(
+            body[0].node_type(),
+            NodeType::ReturnStatement { argument: None }
+          ));
+          assert_eq!(body.len(), 2);
+        }
+        t => panic!("expect FunctionDeclaration, got {:?}", t),
+      },
+      _ => unreachable!(),
+    }
+  }
+
+  #[test]
+  fn throw_statement() {
+    let node = Parser::parse_script("throw a\nb", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => {
+        assert!(matches!(
+          body[0].node_type(),
+          NodeType::ThrowStatement { argument }
+            if matches!(argument.node_type(), NodeType::IdentifierReference { .. })
+        ));
+        assert_eq!(body.len(), 2);
+      }
+      _ => unreachable!(),
+    }
+    assert_eq!(
+      parse_error("throw\na;"),
+      SyntaxErrorTemplate::NewlineAfterThrow.to_string()
+    );
+    assert!(Parser::parse_script("throw;", None).is_err());
+    assert!(Parser::parse_script("{ throw }", None).is_err());
+  }
+
+  #[test]
+  fn module_is_strict() {
+    assert!(Parser::parse_script("eval = 1", None).is_ok());
+    let error = Parser::parse_module("eval = 1", None).unwrap_err();
+    assert_eq!(
+      error.syntax_error().message(),
+      SyntaxErrorTemplate::InvalidAssignmentTarget.to_string()
+    );
+  }
+
+  #[test]
+  fn with_statement() {
+    let node = Parser::parse_script("with (o) a;", None).unwrap();
+    match node.node_type() {
+      NodeType::Script { body } => {
+        assert!(matches!(
+          body[0].node_type(),
+          NodeType::WithStatement { .. }
+        ))
+      }
+      _ => unreachable!(),
+    }
+    assert_eq!(
+      parse_error("'use strict'; with (o) a;"),
+      SyntaxErrorTemplate::StrictWith.to_string()
+    );
+    assert_eq!(
+      parse_error("function f() { 'use strict'; with (o) a; }"),
+      SyntaxErrorTemplate::StrictWith.to_string()
+    );
+    assert_eq!(
+      Parser::parse_module("with (o) a;", None)
+        .unwrap_err()
+        .syntax_error()
+        .message(),
+      SyntaxErrorTemplate::StrictWith.to_string()
+    );
+  }
+
+  #[test]
+  fn use_strict_script() {
+    assert_eq!(
+      parse_error("'use strict'; eval = 1"),
+      SyntaxErrorTemplate::InvalidAssignmentTarget.to_string()
+    );
+    // not a directive after another statement
+    assert!(Parser::parse_script("a; 'use strict'; eval = 1", None).is_ok());
+    assert!(Parser::parse_script("'use strict'", None)
+      .unwrap()
+      .is_strict());
+  }
+}
@@ -1,3 +1,5 @@
+use std::fmt::{self, Write};
+
 use num_bigint::BigInt;
 
 use super::source::SourceText;
@@ -7,8 +9,16 @@ pub enum TokenType {
   // BEGIN PropertyOrCall
   // BEGIN Member
   // BEGIN Template
-  /// `
-  Template,
+  /// `string`
+  NoSubstitutionTemplate {
+    cooked: Option<StringValue>,
+    raw: String,
+  },
+  /// `string${
+  TemplateHead {
+    cooked: Option<StringValue>,
+    raw: String,
+  },
   // END Template
 
   // BEGIN Property
@@ -219,9 +229,14 @@ pub enum TokenType {
   /// number
   Number(f64),
   /// string
-  String(String),
+  String(StringValue),
   /// bigint
   BigInt(BigInt),
+  /// /pattern/flags
+  RegularExpression {
+    pattern: String,
+    flags: String,
+  },
 
   // BEGIN Callable
   /// super
@@ -251,9 +266,103 @@ pub enum TokenType {
   /// enum
   Enum,
 
+  /// }string${
+  TemplateMiddle {
+    cooked: Option<StringValue>,
+    raw: String,
+  },
+  /// }string`
+  TemplateTail {
+    cooked: Option<StringValue>,
+    raw: String,
+  },
+
   EscapedKeyword(String),
 }
 
+/// Renders the token as it's spelled in the source.
+impl fmt::Display for TokenType {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let s = match self {
+      TokenType::Period => ".",
+      TokenType::LBrack => "[",
+      TokenType::Optional => "?.",
+      TokenType::LParen => "(",
+      TokenType::RParen => ")",
+      TokenType::RBrack => "]",
+      TokenType::LBrace => "{",
+      TokenType::Colon => ":",
+      TokenType::Ellipsis => "...",
+      TokenType::Conditional => "?",
+      TokenType::Semicolon => ";",
+      TokenType::RBrace => "}",
+      TokenType::Arrow => "=>",
+      TokenType::Assign => "=",
+      TokenType::AssignNullish => "??=",
+      TokenType::AssignOr => "||=",
+      TokenType::AssignAnd => "&&=",
+      TokenType::AssignBitOr => "|=",
+      TokenType::AssignBitXor => "^=",
+      TokenType::AssignBitAnd => "&=",
+      TokenType::AssignShl => "<<=",
+      TokenType::AssignSar => ">>=",
+      TokenType::AssignShr => ">>>=",
+      TokenType::AssignMul => "*=",
+      TokenType::AssignDiv => "/=",
+      TokenType::AssignMod => "%=",
+      TokenType::AssignExp => "**=",
+      TokenType::AssignAdd => "+=",
+      TokenType::AssignSub => "-=",
+      TokenType::Comma => ",",
+      TokenType::Nullish => "??",
+      TokenType::Or => "||",
+      TokenType::And => "&&",
+      TokenType::BitOr => "|",
+      TokenType::BitXor => "^",
+      TokenType::BitAnd => "&",
+      TokenType::Shl => "<<",
+      TokenType::Sar => ">>",
+      TokenType::Shr => ">>>",
+      TokenType::Mul => "*",
+      TokenType::Div => "/",
+      TokenType::Mod => "%",
+      TokenType::Exp => "**",
+      TokenType::Add => "+",
+      TokenType::Sub => "-",
+      TokenType::Not => "!",
+      TokenType::BitNot => "~",
+      TokenType::Inc => "++",
+      TokenType::Dec => "--",
+      TokenType::Equal => "==",
+      TokenType::StrictEqual => "===",
+      TokenType::NotEqual => "!=",
+      TokenType::StrictNotEqual => "!==",
+      TokenType::LessThan => "<",
+      TokenType::GreaterThan => ">",
+      TokenType::LessThanEqual => "<=",
+      TokenType::GreaterThanEqual => ">=",
+      TokenType::EndOfSource => "end of input",
+      TokenType::Number(n) => return write!(f, "{}", n),
+      TokenType::String(s) => return write!(f, "{}", s),
+      TokenType::BigInt(n) => return write!(f, "{}n", n),
+      TokenType::RegularExpression { pattern, flags } => {
+        return write!(f, "/{}/{}", pattern, flags)
+      }
+      TokenType::NoSubstitutionTemplate { raw, .. } => {
+        return write!(f, "`{}`", raw)
+      }
+      TokenType::TemplateHead { raw, .. } => return write!(f, "`{}${{", raw),
+      TokenType::TemplateMiddle { raw, .. } => {
+        return write!(f, "}}{}${{", raw)
+      }
+      TokenType::TemplateTail { raw, .. } => return write!(f, "}}{}`", raw),
+      TokenType::PrivateIdentifier(s) => return write!(f, "#{}", s),
+      _ => return write!(f, "{}", self.identifier_or_keyword_value()),
+    };
+    write!(f, "{}", s)
+  }
+}
+
 impl TokenType {
   pub fn from_single(c: char) -> Self {
     match c {
@@ -267,7 +376,6 @@ impl TokenType {
       ';' => TokenType::Semicolon,
       ',' => TokenType::Comma,
       '~' => TokenType::BitNot,
-      '`' => TokenType::Template,
       _ => unreachable!("unexpected char"),
     }
   }
@@ -279,24 +387,82 @@ impl TokenType {
     )
   }
 
-  pub fn is_member(&self) -> bool {
+  pub fn is_template(&self) -> bool {
     matches!(
       self,
-      TokenType::Template | TokenType::Period | TokenType::LBrack
+      TokenType::NoSubstitutionTemplate { .. } | TokenType::TemplateHead { .. }
     )
   }
 
+  pub fn is_member(&self) -> bool {
+    self.is_template() || matches!(self, TokenType::Period | TokenType::LBrack)
+  }
+
   pub fn is_property_call(&self) -> bool {
+    self.is_template()
+      || matches!(
+        self,
+        TokenType::Period
+          | TokenType::LBrack
+          | TokenType::Optional
+          | TokenType::LParen
+      )
+  }
+
+  pub fn is_assign(&self) -> bool {
     matches!(
       self,
-      TokenType::Template
-        | TokenType::Period
-        | TokenType::LBrack
-        | TokenType::Optional
-        | TokenType::LParen
+      TokenType::Assign
+        | TokenType::AssignNullish
+        | TokenType::AssignOr
+        | TokenType::AssignAnd
+        | TokenType::AssignBitOr
+        | TokenType::AssignBitXor
+        | TokenType::AssignBitAnd
+        | TokenType::AssignShl
+        | TokenType::AssignSar
+        | TokenType::AssignShr
+        | TokenType::AssignMul
+        | TokenType::AssignDiv
+        | TokenType::AssignMod
+        | TokenType::AssignExp
+        | TokenType::AssignAdd
+        | TokenType::AssignSub
     )
   }
 
+  pub fn is_logical(&self) -> bool {
+    matches!(self, TokenType::Nullish | TokenType::Or | TokenType::And)
+  }
+
+  /// Precedence of binary operators, higher binds tighter, 0 means the token
+  /// is not a binary operator.
+  pub fn precedence(&self) -> usize {
+    match self {
+      TokenType::Nullish => 1,
+      TokenType::Or => 2,
+      TokenType::And => 3,
+      TokenType::BitOr => 4,
+      TokenType::BitXor => 5,
+      TokenType::BitAnd => 6,
+      TokenType::Equal
+      | TokenType::NotEqual
+      | TokenType::StrictEqual
+      | TokenType::StrictNotEqual => 7,
+      TokenType::LessThan
+      | TokenType::GreaterThan
+      | TokenType::LessThanEqual
+      | TokenType::GreaterThanEqual
+      | TokenType::Instanceof
+      | TokenType::In => 8,
+      TokenType::Shl | TokenType::Sar | TokenType::Shr => 9,
+      TokenType::Add | TokenType::Sub => 10,
+      TokenType::Mul | TokenType::Div | TokenType::Mod => 11,
+      TokenType::Exp => 12,
+      _ => 0,
+    }
+  }
+
   pub fn is_keyword(&self) -> bool {
     matches!(
       self,
@@ -381,7 +547,9 @@ impl TokenType {
       TokenType::While => "while",
       TokenType::With => "with",
       TokenType::Yield => "yield",
-      TokenType::Identifier(s) | TokenType::EscapedKeyword(s) => s,
+      TokenType::Identifier(s)
+      | TokenType::EscapedKeyword(s)
+      | TokenType::PrivateIdentifier(s) => s,
       _ => panic!("unexpected token_type"),
     };
     s.to_owned()
@@ -406,6 +574,83 @@ impl SourceText for Token {
   }
 }
 
+/// The String value (SV) of a string literal or the cooked value of a template
+/// chunk. It's a sequence of UTF-16 code units like the ECMAScript String
+/// type, so an escaped lone surrogate such as `'\uD800'` is kept as is and
+/// `'\uD83D\uDE00'` is the same string as `'😀'`.
+///
+/// See https://tc39.es/ecma262/#sec-static-semantics-sv
+#[derive(Default, PartialEq, Eq, Hash, Clone)]
+pub struct StringValue(Vec<u16>);
+
+impl StringValue {
+  pub fn code_units(&self) -> &[u16] {
+    &self.0
+  }
+
+  pub fn push(&mut self, c: char) {
+    self.0.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+  }
+
+  /// Push the code point of an escape sequence, a surrogate is a single code
+  /// unit.
+  pub fn push_code_point(&mut self, code_point: u32) {
+    match char::from_u32(code_point) {
+      Some(c) => self.push(c),
+      None => self.0.push(code_point as u16),
+    }
+  }
+
+  /// `None` when it contains a lone surrogate.
+  ///
+  /// See https://tc39.es/ecma262/#sec-isstringwellformedunicode
+  pub fn to_well_formed(&self) -> Option<String> {
+    String::from_utf16(&self.0).ok()
+  }
+
+  /// A lone surrogate is replaced with U+FFFD.
+  pub fn to_string_lossy(&self) -> String {
+    String::from_utf16_lossy(&self.0)
+  }
+}
+
+impl From<&str> for StringValue {
+  fn from(s: &str) -> Self {
+    Self(s.encode_utf16().collect())
+  }
+}
+
+impl PartialEq<str> for StringValue {
+  fn eq(&self, other: &str) -> bool {
+    self.0.iter().copied().eq(other.encode_utf16())
+  }
+}
+
+impl PartialEq<&str> for StringValue {
+  fn eq(&self, other: &&str) -> bool {
+    self == *other
+  }
+}
+
+impl fmt::Display for StringValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.to_string_lossy())
+  }
+}
+
+impl fmt::Debug for StringValue {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_char('"')?;
+    for c in char::decode_utf16(self.0.iter().copied()) {
+      match c {
+        Ok(c) => write!(f, "{}", c.escape_debug())?,
+        Err(e) => write!(f, "\\u{{{:x}}}", e.unpaired_surrogate())?,
+      }
+    }
+    f.write_char('"')
+  }
+}
+
 pub fn is_reserved_word_strict(s: &str) -> bool {
   matches!(
     s,
@@ -474,3 +719,27 @@ pub fn lookup_keyword(s: &str, had_escaped: bool) -> Option<TokenType> {
     }
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn display() {
+    assert_eq!(TokenType::RBrace.to_string(), "}");
+    assert_eq!(TokenType::StrictEqual.to_string(), "===");
+    assert_eq!(TokenType::Arrow.to_string(), "=>");
+    assert_eq!(TokenType::Instanceof.to_string(), "instanceof");
+    assert_eq!(TokenType::Identifier("x".to_owned()).to_string(), "x");
+    assert_eq!(TokenType::Number(1.5).to_string(), "1.5");
+    assert_eq!(TokenType::BigInt(BigInt::from(1)).to_string(), "1n");
+    assert_eq!(
+      TokenType::TemplateHead {
+        cooked: None,
+        raw: "a".to_owned()
+      }
+      .to_string(),
+      "`a${"
+    );
+  }
+}
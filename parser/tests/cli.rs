@@ -0,0 +1,53 @@
+use std::process::{Command, Output};
+
+fn ng262(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_ng262"))
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+fn fixture(name: &str) -> String {
+  format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+}
+
+#[test]
+fn parse_fixture() {
+  let output = ng262(&["parse", &fixture("expression.js")]);
+  assert!(output.status.success());
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  assert!(stdout.contains("Script"));
+  assert!(stdout.contains("BinaryExpression"));
+  assert!(!stdout.contains("TaggedTemplateExpression"));
+  assert!(stdout.contains("TemplateLiteral"));
+}
+
+#[test]
+fn parse_module_fixture() {
+  let output = ng262(&["parse", &fixture("expression.js"), "--module"]);
+  assert!(output.status.success());
+  assert!(String::from_utf8(output.stdout).unwrap().contains("Module"));
+}
+
+#[test]
+fn parse_error() {
+  let output = ng262(&["parse", &fixture("unexpected_token.js")]);
+  assert_eq!(output.status.code(), Some(1));
+  let stderr = String::from_utf8(output.stderr).unwrap();
+  assert!(stderr.contains("SyntaxError: Unexpected token"));
+  assert!(stderr.contains("a b"));
+}
+
+#[test]
+fn lex_fixture() {
+  let output = ng262(&["lex", &fixture("expression.js")]);
+  assert!(output.status.success());
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  assert!(stdout.starts_with("1:"));
+  assert!(stdout.contains("TemplateHead"));
+}
+
+#[test]
+fn usage() {
+  assert_eq!(ng262(&[]).status.code(), Some(2));
+}
@@ -0,0 +1,191 @@
+//! Run the parser against test262 syntax tests.
+//!
+//! By default the curated subset in `tests/test262` is used, point
+//! `TEST262_DIR` at a test262 checkout's `test` directory to run more:
+//!
+//! ```sh
+//! cargo test -p ng262-parser --test test262 -- --ignored
+//! TEST262_DIR=../test262/test/language cargo test -p ng262-parser --test test262 -- --ignored
+//! ```
+
+use std::{
+  env, fs,
+  panic::{self, AssertUnwindSafe},
+  path::{Path, PathBuf},
+};
+
+use ng262_parser::parser::Parser;
+
+/// Features the parser doesn't handle yet, tests using them are skipped.
+const UNSUPPORTED_FEATURES: &[&str] = &[
+  "class-fields-private",
+  "regexp-named-groups",
+  "top-level-await",
+];
+
+#[derive(Debug, Default)]
+struct Metadata {
+  flags: Vec<String>,
+  features: Vec<String>,
+  /// The phase of a negative test, `parse`, `resolution` or `runtime`.
+  negative_phase: Option<String>,
+}
+
+impl Metadata {
+  /// A minimal reader for the YAML front-matter between `/*---` and `---*/`,
+  /// only the keys the runner looks at are supported.
+  fn parse(source: &str) -> Option<Self> {
+    let start = source.find("/*---")? + "/*---".len();
+    let end = start + source[start..].find("---*/")?;
+    let mut metadata = Metadata::default();
+    let mut in_negative = false;
+    for line in source[start..end].lines() {
+      let indented = line.starts_with(' ');
+      let line = line.trim();
+      if let Some(value) = line.strip_prefix("flags:") {
+        metadata.flags = list(value);
+      } else if let Some(value) = line.strip_prefix("features:") {
+        metadata.features = list(value);
+      } else if line.starts_with("negative:") {
+        in_negative = true;
+        continue;
+      } else if let Some(value) = line.strip_prefix("phase:") {
+        if in_negative {
+          metadata.negative_phase = Some(value.trim().to_owned());
+        }
+      }
+      if !indented {
+        in_negative = false;
+      }
+    }
+    Some(metadata)
+  }
+
+  fn has_flag(&self, flag: &str) -> bool {
+    self.flags.iter().any(|f| f == flag)
+  }
+}
+
+/// `[a, b]` -> `vec!["a", "b"]`
+fn list(value: &str) -> Vec<String> {
+  value
+    .trim()
+    .trim_start_matches('[')
+    .trim_end_matches(']')
+    .split(',')
+    .map(|s| s.trim().to_owned())
+    .filter(|s| !s.is_empty())
+    .collect()
+}
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) {
+  let mut entries = fs::read_dir(dir)
+    .unwrap_or_else(|e| panic!("{}: {}", dir.display(), e))
+    .map(|entry| entry.unwrap().path())
+    .collect::<Vec<_>>();
+  entries.sort();
+  for path in entries {
+    if path.is_dir() {
+      collect(&path, files);
+    } else if path.extension().is_some_and(|e| e == "js")
+      && !path.to_string_lossy().ends_with("_FIXTURE.js")
+    {
+      files.push(path);
+    }
+  }
+}
+
+enum Outcome {
+  Pass,
+  Fail(String),
+  Skip,
+}
+
+fn run(path: &Path) -> Outcome {
+  let source = match fs::read_to_string(path) {
+    Ok(source) => source,
+    Err(e) => return Outcome::Fail(e.to_string()),
+  };
+  let metadata = match Metadata::parse(&source) {
+    Some(metadata) => metadata,
+    None => return Outcome::Fail("missing front-matter".to_owned()),
+  };
+  if metadata
+    .features
+    .iter()
+    .any(|f| UNSUPPORTED_FEATURES.contains(&f.as_str()))
+  {
+    return Outcome::Skip;
+  }
+  let source = if metadata.has_flag("onlyStrict") {
+    format!("\"use strict\";\n{}", source)
+  } else {
+    source
+  };
+  let is_module = metadata.has_flag("module");
+  let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    if is_module {
+      Parser::parse_module(&source, None).map(|_| ())
+    } else {
+      Parser::parse_script(&source, None).map(|_| ())
+    }
+  }));
+  let expect_error = metadata.negative_phase.as_deref() == Some("parse");
+  match result {
+    Err(_) => Outcome::Fail("panicked".to_owned()),
+    Ok(Ok(())) if expect_error => {
+      Outcome::Fail("expected a SyntaxError".to_owned())
+    }
+    Ok(Err(e)) if !expect_error => {
+      Outcome::Fail(format!("{}{}", e, e.syntax_error().decoration()))
+    }
+    Ok(_) => Outcome::Pass,
+  }
+}
+
+#[test]
+#[ignore]
+fn test262() {
+  let dir = env::var("TEST262_DIR")
+    .map(PathBuf::from)
+    .unwrap_or_else(|_| {
+      PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/test262"))
+    });
+  let mut files = Vec::new();
+  collect(&dir, &mut files);
+
+  // keep the output readable, the panics are reported as failures
+  panic::set_hook(Box::new(|_| {}));
+  let (mut passed, mut skipped, mut failures) = (0, 0, Vec::new());
+  for path in &files {
+    match run(path) {
+      Outcome::Pass => passed += 1,
+      Outcome::Skip => skipped += 1,
+      Outcome::Fail(reason) => failures.push((path, reason)),
+    }
+  }
+  let _ = panic::take_hook();
+
+  for (path, reason) in &failures {
+    println!("FAIL {}: {}", path.display(), reason);
+  }
+  println!(
+    "test262: {} passed, {} failed, {} skipped",
+    passed,
+    failures.len(),
+    skipped
+  );
+  assert!(failures.is_empty());
+}
+
+#[test]
+fn metadata() {
+  let metadata = Metadata::parse(
+    "/*---\nflags: [module, raw]\nnegative:\n  phase: parse\n  type: SyntaxError\nfeatures: [template]\n---*/",
+  )
+  .unwrap();
+  assert_eq!(metadata.flags, ["module", "raw"]);
+  assert_eq!(metadata.features, ["template"]);
+  assert_eq!(metadata.negative_phase.as_deref(), Some("parse"));
+  assert!(Metadata::parse("a;").is_none());
+}
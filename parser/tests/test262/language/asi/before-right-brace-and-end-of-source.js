@@ -0,0 +1,5 @@
+/*---
+description: A semicolon is inserted before the end of the source
+---*/
+
+a = b + c
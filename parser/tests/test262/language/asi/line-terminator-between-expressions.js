@@ -0,0 +1,8 @@
+/*---
+description: A LineTerminator between two expressions inserts a semicolon
+info: |
+  https://tc39.es/ecma262/#sec-automatic-semicolon-insertion
+---*/
+
+a
+b
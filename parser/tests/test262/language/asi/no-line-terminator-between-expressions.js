@@ -0,0 +1,10 @@
+/*---
+description: Two expressions on the same line are not separated by ASI
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+$DONOTEVALUATE();
+
+a b
@@ -0,0 +1,7 @@
+/*---
+description: A template after a LineTerminator continues the expression as a tagged template
+features: [template]
+---*/
+
+a
+`b`
@@ -0,0 +1,6 @@
+/*---
+description: eval is a valid assignment target in non-strict code
+flags: [noStrict]
+---*/
+
+eval = 1;
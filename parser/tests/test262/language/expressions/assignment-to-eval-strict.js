@@ -0,0 +1,9 @@
+/*---
+description: eval is not a valid assignment target in strict mode code
+flags: [module]
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+eval = 1;
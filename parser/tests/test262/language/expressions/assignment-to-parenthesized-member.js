@@ -0,0 +1,6 @@
+/*---
+description: A parenthesized member expression is a valid assignment target
+---*/
+
+(a.b) = 1;
+((a[b])) += 1;
@@ -0,0 +1,11 @@
+/*---
+description: ?? can't be mixed with || without parentheses
+features: [coalesce-expression]
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+$DONOTEVALUATE();
+
+a ?? b || c;
@@ -0,0 +1,7 @@
+/*---
+description: ?? can be mixed with || when parenthesized
+features: [coalesce-expression]
+---*/
+
+a ?? (b || c);
+(a ?? b) || c;
@@ -0,0 +1,5 @@
+/*---
+description: The in operator is allowed in call arguments
+---*/
+
+f(a in b, ...c);
@@ -0,0 +1,11 @@
+/*---
+description: A tagged template can't appear in an optional chain
+features: [optional-chaining]
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+$DONOTEVALUATE();
+
+a?.b`c`;
@@ -0,0 +1,10 @@
+/*---
+description: A binary literal needs at least one digit
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+$DONOTEVALUATE();
+
+0b;
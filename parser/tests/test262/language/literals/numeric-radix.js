@@ -0,0 +1,5 @@
+/*---
+description: Hex, octal and binary integer literals
+---*/
+
+[0xff, 0o17, 0b101, 1_000, 1e-3, .5];
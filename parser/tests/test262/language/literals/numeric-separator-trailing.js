@@ -0,0 +1,11 @@
+/*---
+description: A numeric separator can't end a numeric literal
+features: [numeric-separator-literal]
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+$DONOTEVALUATE();
+
+1_;
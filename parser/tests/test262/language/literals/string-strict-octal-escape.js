@@ -0,0 +1,11 @@
+/*---
+description: Octal escapes are not allowed in strict mode code
+flags: [onlyStrict]
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+$DONOTEVALUATE();
+
+'\1';
@@ -0,0 +1,6 @@
+/*---
+description: An invalid escape is allowed in a tagged template, its cooked value is undefined
+features: [template, template-literal-revision]
+---*/
+
+tag`\x${a}\u{}`;
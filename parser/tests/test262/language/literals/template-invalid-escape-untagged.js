@@ -0,0 +1,11 @@
+/*---
+description: An invalid escape is an error in an untagged template
+features: [template]
+negative:
+  phase: parse
+  type: SyntaxError
+---*/
+
+$DONOTEVALUATE();
+
+`\x`;